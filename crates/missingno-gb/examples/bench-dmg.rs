@@ -1,22 +1,37 @@
-//! Max-speed throughput benchmark: emulate N frames unpaced, report fps.
+//! Max-speed throughput benchmark: emulate N frames unpaced, report fps,
+//! and how much of the run went to converting each frame to RGBA for display.
 //!
 //! ```sh
 //! cargo run --profile profiling -p missingno-gb --example bench-dmg -- <rom.gb> [frames]
 //! ```
 
-use missingno_gb::{GameBoy, cartridge::Cartridge};
+use std::time::Duration;
+
+use missingno_gb::{
+    GameBoy,
+    cartridge::Cartridge,
+    ppu::{screen::RGBA_LEN, types::palette::Palette},
+};
 
 const WARMUP_FRAMES: u32 = 100;
 const GB_FPS: f64 = 59.7275;
 
-fn run_frames(gb: &mut GameBoy, frames: u32) {
+/// Returns the time spent converting frames to RGBA.
+fn run_frames(gb: &mut GameBoy, frames: u32) -> Duration {
+    let mut rgba = Box::new([0; RGBA_LEN]);
+    let mut converting = Duration::ZERO;
     let mut seen = 0;
     while seen < frames {
-        if gb.step().new_screen {
+        if gb.run_cycles(u32::MAX).new_screen {
             seen += 1;
             std::hint::black_box(gb.drain_audio_samples());
+            let start = std::time::Instant::now();
+            gb.framebuffer_rgba(&Palette::CLASSIC, &mut rgba);
+            std::hint::black_box(&rgba);
+            converting += start.elapsed();
         }
     }
+    converting
 }
 
 fn main() {
@@ -28,11 +43,11 @@ fn main() {
         .unwrap_or(1000);
 
     let rom = std::fs::read(&rom_path).expect("failed to read ROM");
-    let mut gb = GameBoy::new(Cartridge::new(rom, None), None);
+    let mut gb = GameBoy::new(Cartridge::new(rom, None).expect("unsupported ROM"), None);
 
     run_frames(&mut gb, WARMUP_FRAMES);
     let start = std::time::Instant::now();
-    run_frames(&mut gb, frames);
+    let converting = run_frames(&mut gb, frames);
     let elapsed = start.elapsed().as_secs_f64();
 
     let fps = frames as f64 / elapsed;
@@ -40,4 +55,9 @@ fn main() {
         "{frames} frames in {elapsed:.3}s = {fps:.1} fps ({:.2}x realtime)",
         fps / GB_FPS
     );
+    println!(
+        "RGBA conversion: {:.1}µs/frame ({:.2}% of the run)",
+        converting.as_secs_f64() * 1e6 / frames as f64,
+        converting.as_secs_f64() * 100.0 / elapsed
+    );
 }
@@ -10,6 +10,7 @@ pub mod registers;
 pub mod wave;
 
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Channels {
     pub ch1: PulseSweepChannel,
     pub ch2: PulseChannel,
@@ -38,22 +39,48 @@ impl Channels {
         self.ch4.reset();
     }
 
-    /// Sum the four channels' digital outputs (0–15 each) into a
-    /// `(left, right)` pair, gated by each channel's panning bits.
-    pub fn mix_digital(&self) -> (u32, u32) {
-        let mut left = 0u32;
-        let mut right = 0u32;
-        for (enabled, sample) in [
-            (self.ch1.enabled, self.ch1.digital_sample()),
-            (self.ch2.enabled, self.ch2.digital_sample()),
-            (self.ch3.enabled, self.ch3.digital_sample()),
-            (self.ch4.enabled, self.ch4.digital_sample()),
-        ] {
+    /// Sum the four DACs' outputs into a `(left, right)` pair, gated by
+    /// each channel's panning bits. A DAC that is on maps its 0–15 input
+    /// to -15..=15 in steps of two, so even a silent channel pulls the line
+    /// to its low level; a DAC that is off floats at the midpoint, 0.
+    /// Channels with their bit set in `muted` are left out the same way.
+    pub fn mix_dacs(&self, muted: u8) -> (i32, i32) {
+        let mut left = 0i32;
+        let mut right = 0i32;
+        for (index, (enabled, dac_enabled, sample)) in [
+            (
+                self.ch1.enabled,
+                self.ch1.dac_enabled(),
+                self.ch1.digital_sample(),
+            ),
+            (
+                self.ch2.enabled,
+                self.ch2.dac_enabled(),
+                self.ch2.digital_sample(),
+            ),
+            (
+                self.ch3.enabled,
+                self.ch3.dac_enabled,
+                self.ch3.digital_sample(),
+            ),
+            (
+                self.ch4.enabled,
+                self.ch4.dac_enabled(),
+                self.ch4.digital_sample(),
+            ),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            if !dac_enabled || muted & (1 << index) != 0 {
+                continue;
+            }
+            let level = 2 * sample as i32 - 15;
             if enabled.output_left {
-                left += sample as u32;
+                left += level;
             }
             if enabled.output_right {
-                right += sample as u32;
+                right += level;
             }
         }
         (left, right)
@@ -61,6 +88,7 @@ impl Channels {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Enabled {
     pub enabled: bool,
     pub output_left: bool,
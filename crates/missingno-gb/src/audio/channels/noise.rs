@@ -12,6 +12,7 @@ pub enum Register {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoiseChannel {
     pub enabled: Enabled,
     pub volume_and_envelope: VolumeAndEnvelope,
@@ -206,7 +207,7 @@ impl NoiseChannel {
         self.kyvo = false;
 
         // DAC check
-        if self.volume_and_envelope.0 & 0xf8 == 0 {
+        if !self.volume_and_envelope.dac_enabled() {
             self.enabled.enabled = false;
         }
     }
@@ -214,7 +215,9 @@ impl NoiseChannel {
     pub fn tcycle(&mut self, apu_reset_n: bool, t_index: u8, double_speed: bool) {
         // ch4_1mhz↑ flips the hama half-phase (jeso); both free-run off the APU
         // clock and are cleared only by apu-off, never by a trigger.
-        let mhz_rise = self.mhz_prescaler.tcycle(apu_reset_n, t_index, double_speed);
+        let mhz_rise = self
+            .mhz_prescaler
+            .tcycle(apu_reset_n, t_index, double_speed);
         if !apu_reset_n {
             self.jeso = false;
             return;
@@ -319,6 +322,10 @@ impl NoiseChannel {
         }
     }
 
+    pub fn dac_enabled(&self) -> bool {
+        self.volume_and_envelope.dac_enabled()
+    }
+
     pub fn digital_sample(&self) -> u8 {
         if !self.enabled.enabled {
             return 0;
@@ -355,6 +362,7 @@ impl Control {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrequencyAndRandomness(pub u8);
 
 impl FrequencyAndRandomness {
@@ -22,6 +22,7 @@ pub enum Register {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct PulseChannel {
     pub enabled: Enabled,
     pub waveform_and_initial_length: WaveformAndInitialLength,
@@ -215,7 +216,7 @@ impl PulseChannel {
         self.kyvo = false;
 
         // DAC check: if upper 5 bits of volume register are 0, channel is disabled
-        if self.volume_and_envelope.0 & 0xf8 == 0 {
+        if !self.volume_and_envelope.dac_enabled() {
             self.enabled.enabled = false;
         }
     }
@@ -323,6 +324,10 @@ impl PulseChannel {
         }
     }
 
+    pub fn dac_enabled(&self) -> bool {
+        self.volume_and_envelope.dac_enabled()
+    }
+
     pub fn digital_sample(&self) -> u8 {
         if !self.enabled.enabled {
             return 0;
@@ -23,6 +23,7 @@ pub enum Register {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct PulseSweepChannel {
     pub enabled: Enabled,
     pub sweep: Sweep,
@@ -295,7 +296,7 @@ impl PulseSweepChannel {
         self.sweep_calc_restart = true;
 
         // DAC check
-        if self.volume_and_envelope.0 & 0xf8 == 0 {
+        if !self.volume_and_envelope.dac_enabled() {
             self.enabled.enabled = false;
         }
     }
@@ -518,6 +519,10 @@ impl PulseSweepChannel {
         }
     }
 
+    pub fn dac_enabled(&self) -> bool {
+        self.volume_and_envelope.dac_enabled()
+    }
+
     pub fn digital_sample(&self) -> u8 {
         if !self.enabled.enabled {
             return 0;
@@ -538,6 +543,7 @@ pub enum SweepDirection {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sweep(pub u8);
 
 impl Sweep {
@@ -5,6 +5,7 @@ pub enum EnvelopeDirection {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct WaveformAndInitialLength(pub u8);
 impl WaveformAndInitialLength {
     pub fn waveform(&self) -> u8 {
@@ -17,6 +18,7 @@ impl WaveformAndInitialLength {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct VolumeAndEnvelope(pub u8);
 
 impl VolumeAndEnvelope {
@@ -39,6 +41,11 @@ impl VolumeAndEnvelope {
     pub fn sweep_pace(&self) -> u8 {
         self.0 & 0b111
     }
+
+    /// The upper five bits power the channel's DAC.
+    pub fn dac_enabled(&self) -> bool {
+        self.0 & 0xf8 != 0
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -69,6 +76,7 @@ impl PeriodHighAndControl {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Signed11(pub u16);
 
 impl Signed11 {
@@ -114,6 +122,7 @@ impl From<i16> for Signed11 {
 /// to these stages — the silicon-level realisation of "low two bits
 /// of the frequency timer are NOT modified".
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prescaler {
     pub counter: u8,
 }
@@ -153,6 +162,7 @@ impl Prescaler {
 /// reload share the same load enable (`epyk` / `duju`) — there is no
 /// subset-of-stages distinction.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct PeriodDivider {
     pub counter: u16,
 }
@@ -31,6 +31,7 @@ pub enum Register {
 /// as a one-`ch3_2mhz`-cycle pulse on `restart` that drives the
 /// divider load network.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct TriggerSync {
     /// `gavu` — NR34 d7 captured at apu_wr ↑.
     pub bit_latch: bool,
@@ -49,6 +50,7 @@ pub struct TriggerSync {
 /// `wave_data_latch` strobe, plus the AZET extension that holds the
 /// prior T-cycle's latched value.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct WaveDataLatch {
     /// `busa` — captures `ch3_frst` on apu_4mhz ↑.
     pub sync_1: bool,
@@ -67,6 +69,7 @@ pub struct WaveDataLatch {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct WaveChannel {
     pub enabled: Enabled,
     pub dac_enabled: bool,
@@ -391,6 +394,7 @@ impl WaveChannel {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Volume(pub u8);
 impl Volume {
     pub fn volume(&self) -> f32 {
@@ -0,0 +1,42 @@
+use super::T_CYCLES_PER_SECOND;
+
+/// Fraction of the output coupling capacitor's charge left after one
+/// T-cycle on DMG hardware.
+const CHARGE_PER_TCYCLE: f32 = 0.999958;
+
+/// The capacitor between the mixer and the amplifier. It blocks the DC
+/// level the DACs put on the line, so turning a DAC on or off gives a pop
+/// that decays over a few milliseconds rather than a permanent offset.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub(super) struct HighPass {
+    /// Charge left after one host sample.
+    charge: f32,
+    left: f32,
+    right: f32,
+}
+
+impl HighPass {
+    pub(super) fn new(output_rate: u32) -> Self {
+        Self {
+            charge: Self::charge(output_rate),
+            left: 0.0,
+            right: 0.0,
+        }
+    }
+
+    pub(super) fn set_output_rate(&mut self, output_rate: u32) {
+        self.charge = Self::charge(output_rate);
+    }
+
+    fn charge(output_rate: u32) -> f32 {
+        CHARGE_PER_TCYCLE.powf(T_CYCLES_PER_SECOND / output_rate as f32)
+    }
+
+    pub(super) fn filter(&mut self, (left, right): (f32, f32)) -> (f32, f32) {
+        let out = (left - self.left, right - self.right);
+        self.left = left - out.0 * self.charge;
+        self.right = right - out.1 * self.charge;
+        out
+    }
+}
@@ -1,8 +1,12 @@
 use channels::{Channels, noise, pulse, pulse_sweep, wave};
+use high_pass::HighPass;
+use scope::Scope;
 use volume::Volume;
 
 pub mod channels;
+mod high_pass;
 pub mod registers;
+pub mod scope;
 pub mod volume;
 
 #[derive(PartialEq, Eq, Debug)]
@@ -16,15 +20,16 @@ pub enum Register {
     Channel4(noise::Register),
 }
 
-const SAMPLE_RATE: f32 = 44100.0;
+/// Host sample rate until the front-end picks one for its output device.
+pub const DEFAULT_OUTPUT_RATE: u32 = 44100;
 const T_CYCLES_PER_SECOND: f32 = 4_194_304.0;
-const T_CYCLES_PER_SAMPLE: f32 = T_CYCLES_PER_SECOND / SAMPLE_RATE;
 const DIV_APU_BIT: u16 = 1 << 10; // Bit 10 of M-cycle counter drives frame sequencer
 // In double speed the M-cycle counter runs at 2× the dot clock, so the tap
 // shifts up one bit to hold the frame sequencer at 512 Hz (DIV bit 6 vs bit 5).
 const DIV_APU_BIT_DOUBLE: u16 = 1 << 11;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Audio {
     pub(crate) enabled: bool,
     pub(crate) channels: Channels,
@@ -48,15 +53,44 @@ pub struct Audio {
     pub(crate) div_apu_switch_lag: bool,
     pub(crate) fs_edge_predelay: bool,
     sample_counter: f32,
-    // Digital channel sums accumulate as integers; fold_pending() applies
-    // the DAC scale and NR50 volume when either changes or a window closes.
-    pending_left: u32,
-    pending_right: u32,
+    /// Host-side: a restored state plays at the rate of the console it
+    /// replaces (see [`keep_host_side`](Self::keep_host_side)).
+    #[cfg_attr(feature = "save-state", serde(skip, default = "default_output_rate"))]
+    output_rate: u32,
+    /// Width of the box-filter window, in T-cycles per host sample.
+    #[cfg_attr(
+        feature = "save-state",
+        serde(skip, default = "default_tcycles_per_sample")
+    )]
+    tcycles_per_sample: f32,
+    // DAC output sums accumulate as integers; fold_pending() applies the
+    // scale and NR50 volume when either changes or a window closes.
+    pending_left: i32,
+    pending_right: i32,
     pending_count: u32,
     sample_accum_left: f32,
     sample_accum_right: f32,
     sample_accum_count: u32,
+    high_pass: HighPass,
+    #[cfg_attr(feature = "save-state", serde(skip))]
     sample_buffer: Vec<(f32, f32)>,
+    /// Sample history for debugger views; `None` keeps it off the hot path.
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    scope: Option<Box<Scope>>,
+    /// Channels muted from the debugger, bit 0 for CH1. Host-side like
+    /// `output_rate`: it survives resets and isn't part of a saved state.
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    pub(crate) channel_mask: u8,
+}
+
+#[cfg(feature = "save-state")]
+fn default_output_rate() -> u32 {
+    DEFAULT_OUTPUT_RATE
+}
+
+#[cfg(feature = "save-state")]
+fn default_tcycles_per_sample() -> f32 {
+    T_CYCLES_PER_SECOND / DEFAULT_OUTPUT_RATE as f32
 }
 
 impl Audio {
@@ -96,6 +130,8 @@ impl Audio {
             div_apu_switch_lag: false,
             fs_edge_predelay: false,
             sample_counter: 0.0,
+            output_rate: DEFAULT_OUTPUT_RATE,
+            tcycles_per_sample: T_CYCLES_PER_SECOND / DEFAULT_OUTPUT_RATE as f32,
             pending_left: 0,
             pending_right: 0,
             pending_count: 0,
@@ -103,7 +139,10 @@ impl Audio {
             sample_accum_right: 0.0,
             sample_accum_count: 0,
             wide_sweep_load_hold: false,
+            high_pass: HighPass::new(DEFAULT_OUTPUT_RATE),
             sample_buffer: Vec::new(),
+            scope: None,
+            channel_mask: 0,
         }
     }
 
@@ -133,6 +172,8 @@ impl Audio {
             div_apu_switch_lag: false,
             fs_edge_predelay: false,
             sample_counter: 0.0,
+            output_rate: DEFAULT_OUTPUT_RATE,
+            tcycles_per_sample: T_CYCLES_PER_SECOND / DEFAULT_OUTPUT_RATE as f32,
             pending_left: 0,
             pending_right: 0,
             pending_count: 0,
@@ -140,7 +181,10 @@ impl Audio {
             sample_accum_right: 0.0,
             sample_accum_count: 0,
             wide_sweep_load_hold: false,
+            high_pass: HighPass::new(DEFAULT_OUTPUT_RATE),
             sample_buffer: Vec::new(),
+            scope: None,
+            channel_mask: 0,
         }
     }
 
@@ -162,6 +206,17 @@ impl Audio {
         &self.channels
     }
 
+    /// Start or stop keeping per-channel sample history. Stopping drops it.
+    pub fn set_scope_enabled(&mut self, enabled: bool) {
+        if enabled != self.scope.is_some() {
+            self.scope = enabled.then(|| Box::new(Scope::new(self.output_rate)));
+        }
+    }
+
+    pub fn scope(&self) -> Option<&Scope> {
+        self.scope.as_deref()
+    }
+
     pub fn volume_left(&self) -> Volume {
         self.volume_left
     }
@@ -230,7 +285,7 @@ impl Audio {
             return;
         }
 
-        let (l, r) = self.channels.mix_digital();
+        let (l, r) = self.channels.mix_dacs(self.channel_mask);
         self.pending_left += l;
         self.pending_right += r;
         self.pending_count += 1;
@@ -263,14 +318,18 @@ impl Audio {
 
         // Push the box-filtered average when the host sample window closes.
         self.sample_counter += 1.0;
-        if self.sample_counter >= T_CYCLES_PER_SAMPLE {
-            self.sample_counter -= T_CYCLES_PER_SAMPLE;
+        if self.sample_counter >= self.tcycles_per_sample {
+            self.sample_counter -= self.tcycles_per_sample;
             self.fold_pending();
             let count = self.sample_accum_count as f32;
-            self.sample_buffer.push((
+            let sample = self.high_pass.filter((
                 self.sample_accum_left / count,
                 self.sample_accum_right / count,
             ));
+            self.sample_buffer.push(sample);
+            if let Some(scope) = &mut self.scope {
+                scope.record(&self.channels, sample);
+            }
             self.sample_accum_left = 0.0;
             self.sample_accum_right = 0.0;
             self.sample_accum_count = 0;
@@ -285,14 +344,14 @@ impl Audio {
         }
     }
 
-    /// Fold the pending digital sums into the f32 accumulators at the
-    /// current NR50 volume. Channels span 0–15 across four channels per
-    /// side, so full scale is 60.
+    /// Fold the pending DAC sums into the f32 accumulators at the current
+    /// NR50 volume. Each DAC spans ±15 across four channels per side, so
+    /// ±60 maps to ±0.5.
     pub(crate) fn fold_pending(&mut self) {
         if self.pending_count == 0 {
             return;
         }
-        const FULL_SCALE: f32 = 1.0 / 60.0;
+        const FULL_SCALE: f32 = 1.0 / 120.0;
         self.sample_accum_left +=
             self.pending_left as f32 * FULL_SCALE * self.volume_left.percentage();
         self.sample_accum_right +=
@@ -393,11 +452,83 @@ impl Audio {
         self.div_apu_switch_lag = self.div_apu_double_parity;
     }
 
+    /// Resample to `hz` host samples per emulated second, so a device that
+    /// isn't running at 44.1 kHz plays at the right pitch.
+    ///
+    /// Each host sample is the average of the DAC output over the T-cycles
+    /// since the last one, carrying the fractional remainder between
+    /// windows so the rate never drifts. Nothing is buffered beyond the
+    /// window being filled: a sample lags the emulation by at most one
+    /// host sample period (about 21 µs at 48 kHz). Any further latency is
+    /// the host's output buffer.
+    pub fn set_output_rate(&mut self, hz: u32) {
+        let hz = hz.max(1);
+        if hz == self.output_rate {
+            return;
+        }
+        self.output_rate = hz;
+        self.tcycles_per_sample = T_CYCLES_PER_SECOND / hz as f32;
+        self.high_pass.set_output_rate(hz);
+        if self.scope.is_some() {
+            self.scope = Some(Box::new(Scope::new(hz)));
+        }
+    }
+
+    pub fn output_rate(&self) -> u32 {
+        self.output_rate
+    }
+
+    /// Bytes the undrained samples and the scope take on the heap.
+    pub(crate) fn heap_size(&self) -> usize {
+        self.sample_buffer.capacity() * std::mem::size_of::<(f32, f32)>()
+            + self.scope.as_ref().map_or(0, |scope| scope.heap_size())
+    }
+
+    /// Take over `previous`'s host-side settings — output rate, scope and
+    /// channel mutes — when this audio replaces it mid-run, as a loaded state
+    /// does. The high-pass filter keeps its charge but is retuned to the rate.
+    pub(crate) fn keep_host_side(&mut self, previous: Audio) {
+        self.output_rate = previous.output_rate;
+        self.tcycles_per_sample = previous.tcycles_per_sample;
+        self.high_pass.set_output_rate(previous.output_rate);
+        self.scope = previous.scope;
+        self.channel_mask = previous.channel_mask;
+    }
+
+    /// Leave `channel` (0 for CH1) out of the mix. It keeps running, so its
+    /// length timer, envelope and sweep carry on while it's silent.
+    pub fn set_channel_muted(&mut self, channel: usize, muted: bool) {
+        if muted {
+            self.channel_mask |= 1 << channel;
+        } else {
+            self.channel_mask &= !(1 << channel);
+        }
+    }
+
+    pub fn channel_muted(&self, channel: usize) -> bool {
+        self.channel_mask & (1 << channel) != 0
+    }
+
+    /// Host samples mixed since the last [`drain_samples`](Self::drain_samples),
+    /// for a frontend to check before handing them to a full output buffer.
+    pub fn buffer_fill(&self) -> usize {
+        self.sample_buffer.len()
+    }
+
     pub fn drain_samples(&mut self) -> Vec<(f32, f32)> {
         std::mem::take(&mut self.sample_buffer)
     }
 
     /// Construct an Audio instance from a gbtrace snapshot.
+    ///
+    /// The snapshot carries the registers, the frame sequencer, and the
+    /// envelope and sweep timers, which is enough to pick a note back up at
+    /// the right pitch, volume and panning. It has no room for the period
+    /// dividers, duty and wave positions, length counters, envelope
+    /// volumes or the noise LFSR, so those restart as on a trigger (the
+    /// envelope from its initial volume), and the first few samples after a
+    /// load can differ from an uninterrupted run. A clone of the console
+    /// keeps everything.
     #[cfg(feature = "gbtrace")]
     pub fn from_snapshot(snap: &gbtrace::snapshot::ApuSnapshot, wave_ram: [u8; 16]) -> Self {
         use channels::noise::FrequencyAndRandomness;
@@ -413,13 +544,17 @@ impl Audio {
             wave::WaveChannel,
         };
 
+        // NR52 bits 0-3 are the channels' on flags; NR51 has each channel's
+        // right output in bits 0-3 and left in bits 4-7.
+        let enabled = |channel: u8| Enabled {
+            enabled: snap.sound_on & (0x01 << channel) != 0,
+            output_left: snap.sound_pan & (0x10 << channel) != 0,
+            output_right: snap.sound_pan & (0x01 << channel) != 0,
+        };
+
         let channels = Channels {
             ch1: PulseSweepChannel {
-                enabled: Enabled {
-                    enabled: true,
-                    output_left: true,
-                    output_right: true,
-                },
+                enabled: enabled(0),
                 sweep: Sweep(snap.ch1_sweep),
                 waveform_and_initial_length: WaveformAndInitialLength(snap.ch1_duty_len),
                 volume_and_envelope: VolumeAndEnvelope(snap.ch1_vol_env),
@@ -432,7 +567,7 @@ impl Audio {
                 pending_trigger_sync: 0,
                 divider_load_settle: false,
                 sweep_load_hold: 0,
-                current_volume: 0,
+                current_volume: VolumeAndEnvelope(snap.ch1_vol_env).initial_volume(),
                 envelope_timer: snap.ch1_envelope_timer,
                 length_counter: 0,
                 shadow_frequency: snap.ch1_period,
@@ -448,11 +583,7 @@ impl Audio {
                 ch1_frst: false,
             },
             ch2: PulseChannel {
-                enabled: Enabled {
-                    enabled: true,
-                    output_left: true,
-                    output_right: true,
-                },
+                enabled: enabled(1),
                 waveform_and_initial_length: WaveformAndInitialLength(snap.ch2_duty_len),
                 volume_and_envelope: VolumeAndEnvelope(snap.ch2_vol_env),
                 length_enabled: snap.ch2_length_enabled,
@@ -464,7 +595,7 @@ impl Audio {
                 ch2_frst: false,
                 pending_trigger_sync: 0,
                 divider_load_settle: false,
-                current_volume: 0,
+                current_volume: VolumeAndEnvelope(snap.ch2_vol_env).initial_volume(),
                 envelope_timer: snap.ch2_envelope_timer,
                 length_counter: 0,
                 kyvo: false,
@@ -472,11 +603,7 @@ impl Audio {
                 envelope_enable_tick_pending: false,
             },
             ch3: WaveChannel {
-                enabled: Enabled {
-                    enabled: true,
-                    output_left: true,
-                    output_right: true,
-                },
+                enabled: enabled(2),
                 dac_enabled: snap.ch3_dac & 0x80 != 0,
                 volume: WaveVolume(snap.ch3_vol),
                 length_enabled: snap.ch3_length_enabled,
@@ -494,11 +621,7 @@ impl Audio {
                 sample_byte: 0,
             },
             ch4: NoiseChannel {
-                enabled: Enabled {
-                    enabled: true,
-                    output_left: true,
-                    output_right: true,
-                },
+                enabled: enabled(3),
                 volume_and_envelope: VolumeAndEnvelope(snap.ch4_vol_env),
                 length_enabled: snap.ch4_length_enabled,
                 frequency_and_randomness: FrequencyAndRandomness(snap.ch4_freq),
@@ -510,7 +633,7 @@ impl Audio {
                 jeso: false,
                 skip_first_clock: false,
                 lfsr: 0x7FFF,
-                current_volume: 0,
+                current_volume: VolumeAndEnvelope(snap.ch4_vol_env).initial_volume(),
                 envelope_timer: snap.ch4_envelope_timer,
                 envelope_stopped: false,
                 kyvo: false,
@@ -521,8 +644,8 @@ impl Audio {
         Self {
             enabled: snap.sound_on & 0x80 != 0,
             channels,
-            volume_left: Volume(0),
-            volume_right: Volume(0),
+            volume_left: Volume((snap.master_vol >> 4) & 0b111),
+            volume_right: Volume(snap.master_vol & 0b111),
             nr50: snap.master_vol,
             prev_div_apu_bit: snap.prev_div_apu_bit,
             frame_sequencer_step: snap.frame_sequencer_step,
@@ -531,6 +654,8 @@ impl Audio {
             div_apu_switch_lag: false,
             fs_edge_predelay: false,
             sample_counter: 0.0,
+            output_rate: DEFAULT_OUTPUT_RATE,
+            tcycles_per_sample: T_CYCLES_PER_SECOND / DEFAULT_OUTPUT_RATE as f32,
             pending_left: 0,
             pending_right: 0,
             pending_count: 0,
@@ -538,7 +663,158 @@ impl Audio {
             sample_accum_right: 0.0,
             sample_accum_count: 0,
             wide_sweep_load_hold: false,
+            high_pass: HighPass::new(DEFAULT_OUTPUT_RATE),
             sample_buffer: Vec::new(),
+            scope: None,
+            channel_mask: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LENGTH_ENABLE: u8 = 0x40;
+    const TRIGGER: u8 = 0x80;
+
+    /// Each channel's (NRx1, NRx4, full length).
+    const CHANNELS: [(u16, u16, u16); 4] = [
+        (0xff11, 0xff14, 64),
+        (0xff16, 0xff19, 64),
+        (0xff1b, 0xff1e, 256),
+        (0xff20, 0xff23, 64),
+    ];
+
+    /// An APU whose frame sequencer last clocked `step`: even steps leave
+    /// caru low, so the next step won't clock length.
+    fn audio_at_step(step: u8) -> Audio {
+        Audio::post_boot_with_fs_step(0, step)
+    }
+
+    fn write(audio: &mut Audio, address: u16, value: u8) {
+        audio.write_register(Register::map(address), value, 0);
+    }
+
+    fn length_counter(audio: &Audio, channel: usize) -> u16 {
+        let channels = audio.channels();
+        match channel {
+            0 => channels.ch1.length_counter,
+            1 => channels.ch2.length_counter,
+            2 => channels.ch3.length_counter,
+            _ => channels.ch4.length_counter,
+        }
+    }
+
+    /// NRx1 value that loads a counter of `remaining`.
+    fn length_for(full: u16, remaining: u16) -> u8 {
+        (full - remaining) as u8
+    }
+
+    #[test]
+    fn enabling_length_clocks_once_when_next_step_skips_length() {
+        for (channel, &(nrx1, nrx4, full)) in CHANNELS.iter().enumerate() {
+            for (step, expected) in [(0, 4), (1, 5)] {
+                let mut audio = audio_at_step(step);
+                write(&mut audio, nrx1, length_for(full, 5));
+                write(&mut audio, nrx4, LENGTH_ENABLE);
+                assert_eq!(
+                    length_counter(&audio, channel),
+                    expected,
+                    "channel {} at step {step}",
+                    channel + 1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn enabling_length_when_already_enabled_does_not_clock() {
+        for (channel, &(nrx1, nrx4, full)) in CHANNELS.iter().enumerate() {
+            let mut audio = audio_at_step(0);
+            write(&mut audio, nrx1, length_for(full, 5));
+            write(&mut audio, nrx4, LENGTH_ENABLE);
+            write(&mut audio, nrx4, LENGTH_ENABLE);
+            assert_eq!(
+                length_counter(&audio, channel),
+                4,
+                "channel {}",
+                channel + 1
+            );
+        }
+    }
+
+    #[test]
+    fn trigger_reloading_an_expired_length_clocks_once_when_next_step_skips_length() {
+        for (channel, &(nrx1, nrx4, full)) in CHANNELS.iter().enumerate() {
+            for (step, expected) in [(0, full - 1), (1, full)] {
+                // Run the counter out on an odd step, so enabling it doesn't clock.
+                let mut audio = audio_at_step(1);
+                write(&mut audio, nrx1, length_for(full, 1));
+                write(&mut audio, nrx4, LENGTH_ENABLE);
+                audio.channels.tick_length_all();
+                assert_eq!(length_counter(&audio, channel), 0);
+
+                audio.frame_sequencer_step = step;
+                write(&mut audio, nrx4, LENGTH_ENABLE | TRIGGER);
+                assert_eq!(
+                    length_counter(&audio, channel),
+                    expected,
+                    "channel {} at step {step}",
+                    channel + 1
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn trigger_with_length_disabled_does_not_clock() {
+        for (channel, &(_, nrx4, full)) in CHANNELS.iter().enumerate() {
+            let mut audio = audio_at_step(0);
+            write(&mut audio, nrx4, TRIGGER);
+            assert_eq!(
+                length_counter(&audio, channel),
+                full,
+                "channel {}",
+                channel + 1
+            );
+        }
+    }
+
+    /// Each envelope channel's (NRx2, NRx4).
+    const ENVELOPE_CHANNELS: [(u16, u16); 3] =
+        [(0xff12, 0xff14), (0xff17, 0xff19), (0xff21, 0xff23)];
+
+    fn volume(audio: &Audio, channel: usize) -> u8 {
+        let channels = audio.channels();
+        match channel {
+            0 => channels.ch1.current_volume,
+            1 => channels.ch2.current_volume,
+            _ => channels.ch4.current_volume,
+        }
+    }
+
+    /// DMG "zombie mode": an NRx2 write to a playing channel bumps the volume
+    /// by one only when the old envelope pace was 0, wrapping at 4 bits.
+    #[test]
+    fn nrx2_write_bumps_volume_only_from_pace_0() {
+        for (channel, &(nrx2, nrx4)) in ENVELOPE_CHANNELS.iter().enumerate() {
+            let mut audio = audio_at_step(1);
+            write(&mut audio, nrx2, 0xe0);
+            write(&mut audio, nrx4, TRIGGER);
+            assert_eq!(volume(&audio, channel), 14);
+
+            write(&mut audio, nrx2, 0xe0);
+            assert_eq!(volume(&audio, channel), 15, "channel {}", channel + 1);
+            write(&mut audio, nrx2, 0xe1);
+            assert_eq!(volume(&audio, channel), 0, "channel {} wraps", channel + 1);
+            write(&mut audio, nrx2, 0xe0);
+            assert_eq!(
+                volume(&audio, channel),
+                0,
+                "channel {} old pace 1",
+                channel + 1
+            );
         }
     }
 }
@@ -0,0 +1,65 @@
+use std::collections::VecDeque;
+
+use super::{T_CYCLES_PER_SECOND, channels::Channels};
+
+/// One frame of samples at `output_rate`.
+pub fn scope_len(output_rate: u32) -> usize {
+    (output_rate as f32 * 70224.0 / T_CYCLES_PER_SECOND) as usize + 1
+}
+
+/// Recent per-channel and mixed output, for oscilloscope views. Each channel
+/// is its 0-15 DAC input sampled at the host rate; `mixed` is the mono
+/// average of what was pushed to the host, lifted from ±0.5 to 0.0-1.0.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scope {
+    channels: [VecDeque<u8>; 4],
+    mixed: VecDeque<f32>,
+    len: usize,
+}
+
+impl Scope {
+    pub(super) fn new(output_rate: u32) -> Self {
+        let len = scope_len(output_rate);
+        Self {
+            channels: std::array::from_fn(|_| VecDeque::with_capacity(len)),
+            mixed: VecDeque::with_capacity(len),
+            len,
+        }
+    }
+
+    pub(super) fn record(&mut self, channels: &Channels, (left, right): (f32, f32)) {
+        let samples = [
+            channels.ch1.digital_sample(),
+            channels.ch2.digital_sample(),
+            channels.ch3.digital_sample(),
+            channels.ch4.digital_sample(),
+        ];
+        for (history, sample) in self.channels.iter_mut().zip(samples) {
+            if history.len() == self.len {
+                history.pop_front();
+            }
+            history.push_back(sample);
+        }
+        if self.mixed.len() == self.len {
+            self.mixed.pop_front();
+        }
+        self.mixed.push_back((left + right) / 2.0 + 0.5);
+    }
+
+    /// Channel `index` (0 = CH1), oldest first.
+    pub fn channel(&self, index: usize) -> &VecDeque<u8> {
+        &self.channels[index]
+    }
+
+    /// Mixed output in 0.0-1.0, oldest first.
+    pub fn mixed(&self) -> &VecDeque<f32> {
+        &self.mixed
+    }
+
+    /// Bytes the scope takes on the heap, itself included.
+    pub(super) fn heap_size(&self) -> usize {
+        let channels: usize = self.channels.iter().map(VecDeque::capacity).sum();
+        std::mem::size_of::<Self>() + channels + self.mixed.capacity() * std::mem::size_of::<f32>()
+    }
+}
@@ -1,4 +1,5 @@
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Volume(pub u8);
 
 impl Volume {
@@ -0,0 +1,119 @@
+//! The program behind [`BootRom::Logo`](crate::BootRom::Logo): a stand-in
+//! for the DMG boot ROM that plays only the start-up animation. It draws the
+//! cartridge's header logo, scrolls it down from the top of the screen,
+//! sounds the two-note chime and then parks in a loop. Nothing here checks
+//! the logo or the header, and none of its timing matches the real boot ROM.
+
+/// Where the program loops once the chime has played.
+pub(crate) const PARK: u16 = 0x006C;
+
+const PROGRAM: [u8; 166] = [
+    // start: $00
+    0x31, 0xFE, 0xFF, // LD SP,$FFFE
+    0xAF, // XOR A
+    0x21, 0xFF, 0x9F, // LD HL,$9FFF
+    // clear: $07
+    0x32, // LD (HL-),A
+    0xCB, 0x7C, // BIT 7,H
+    0x20, 0xFB, // JR NZ,clear
+    0x3E, 0x80, // LD A,$80
+    0xE0, 0x26, // LDH ($26),A (NR52: sound on)
+    0xE0, 0x11, // LDH ($11),A (NR11: 50% duty)
+    0x3E, 0xF3, // LD A,$F3
+    0xE0, 0x12, // LDH ($12),A (NR12: full volume, fading)
+    0xE0, 0x25, // LDH ($25),A (NR51)
+    0x3E, 0x77, // LD A,$77
+    0xE0, 0x24, // LDH ($24),A (NR50)
+    0x3E, 0xFC, // LD A,$FC
+    0xE0, 0x47, // LDH ($47),A (BGP)
+    0x01, 0x04, 0x01, // LD BC,$0104 (cartridge logo)
+    0x21, 0x10, 0x80, // LD HL,$8010 (tile 1)
+    // tiles: $26
+    0x0A, // LD A,(BC)
+    0x57, // LD D,A
+    0x5F, // LD E,A
+    0xCD, 0x89, 0x00, // CALL expand
+    0xCD, 0x89, 0x00, // CALL expand
+    0x03, // INC BC
+    0x79, // LD A,C
+    0xFE, 0x34, // CP $34
+    0x20, 0xF1, // JR NZ,tiles
+    0x21, 0x04, 0x99, // LD HL,$9904
+    0x3E, 0x01, // LD A,1
+    // map: $3A
+    0x22, // LD (HL+),A
+    0x3C, // INC A
+    0xFE, 0x0D, // CP 13
+    0x20, 0x02, // JR NZ,map_next
+    0x2E, 0x24, // LD L,$24 (second row)
+    // map_next: $42
+    0xFE, 0x19, // CP 25
+    0x20, 0xF4, // JR NZ,map
+    0x3E, 0x64, // LD A,$64
+    0xE0, 0x42, // LDH ($42),A (SCY)
+    0x3E, 0x91, // LD A,$91
+    0xE0, 0x40, // LDH ($40),A (LCDC: LCD and BG on)
+    // scroll: $4E
+    0xCD, 0x7C, 0x00, // CALL frame
+    0xF0, 0x42, // LDH A,($42)
+    0x3D, // DEC A
+    0xE0, 0x42, // LDH ($42),A
+    0x20, 0xF6, // JR NZ,scroll
+    0x3E, 0x83, // LD A,$83
+    0xCD, 0x6E, 0x00, // CALL note
+    0x06, 0x08, // LD B,8
+    0xCD, 0x75, 0x00, // CALL frames
+    0x3E, 0xC1, // LD A,$C1
+    0xCD, 0x6E, 0x00, // CALL note
+    0x06, 0x5A, // LD B,90
+    0xCD, 0x75, 0x00, // CALL frames
+    // park: $6C
+    0x18, 0xFE, // JR park
+    // note: $6E
+    0xE0, 0x13, // LDH ($13),A (NR13)
+    0x3E, 0x87, // LD A,$87
+    0xE0, 0x14, // LDH ($14),A (NR14: trigger)
+    0xC9, // RET
+    // frames: $75
+    0xCD, 0x7C, 0x00, // CALL frame
+    0x05, // DEC B
+    0x20, 0xFA, // JR NZ,frames
+    0xC9, // RET
+    // frame: $7C
+    0xF0, 0x44, // LDH A,($44)
+    0xFE, 0x90, // CP 144
+    0x28, 0xFA, // JR Z,frame
+    // vblank: $82
+    0xF0, 0x44, // LDH A,($44)
+    0xFE, 0x90, // CP 144
+    0x20, 0xFA, // JR NZ,vblank
+    0xC9, // RET
+    // expand: $89
+    0xCB, 0x22, // SLA D
+    0x17, // RLA
+    0xCB, 0x23, // SLA E
+    0x17, // RLA
+    0xCB, 0x22, // SLA D
+    0x17, // RLA
+    0xCB, 0x23, // SLA E
+    0x17, // RLA
+    0xCB, 0x22, // SLA D
+    0x17, // RLA
+    0xCB, 0x23, // SLA E
+    0x17, // RLA
+    0xCB, 0x22, // SLA D
+    0x17, // RLA
+    0xCB, 0x23, // SLA E
+    0x17, // RLA
+    0x22, // LD (HL+),A
+    0x23, // INC HL
+    0x22, // LD (HL+),A
+    0x23, // INC HL
+    0xC9, // RET
+];
+
+/// The byte at `addr` in the 256-byte boot ROM window. Past the end of the
+/// program reads as zero.
+pub(crate) fn byte(addr: u16) -> u8 {
+    PROGRAM.get(addr as usize).copied().unwrap_or(0)
+}
@@ -1,4 +1,10 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Huc1 {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
     pub ram: Vec<[u8; 8 * 1024]>,
     pub rom_bank: u8,
     pub ram_bank: u8,
@@ -40,12 +46,16 @@ impl Huc1 {
         }
     }
 
+    /// The ROM bank mapped at $4000–$7FFF.
+    pub(super) fn rom_bank(&self) -> usize {
+        self.rom_bank.max(1) as usize
+    }
+
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
         match address {
             0x0000..=0x3fff => rom[address as usize],
             0x4000..=0x7fff => {
-                let bank = self.rom_bank.max(1) as usize;
-                let addr = bank * 0x4000 + (address - 0x4000) as usize;
+                let addr = self.rom_bank() * 0x4000 + (address - 0x4000) as usize;
                 rom[addr % rom.len()]
             }
             0xa000..=0xbfff => {
@@ -1,4 +1,7 @@
+use crate::warnings::{Warning, Warnings};
+
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mode {
     Rom,
     Ram,
@@ -8,16 +11,24 @@ pub enum Mode {
     Ir,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Huc3 {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
     pub ram: Vec<[u8; 8 * 1024]>,
     pub rom_bank: u8,
     pub ram_bank: u8,
     pub mode: Mode,
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub rtc_memory: [u8; 256],
     pub rtc_address: u8,
     pub rtc_last_command: u8,
     pub rtc_response: u8,
     pub rtc_semaphore: u8,
+    pub(crate) warnings: Warnings,
 }
 
 impl Huc3 {
@@ -49,6 +60,7 @@ impl Huc3 {
             rtc_last_command: 0,
             rtc_response: 0,
             rtc_semaphore: 1,
+            warnings: Warnings::new(),
         }
     }
 
@@ -109,12 +121,16 @@ impl Huc3 {
         }
     }
 
+    /// The ROM bank mapped at $4000–$7FFF.
+    pub(super) fn rom_bank(&self) -> usize {
+        self.rom_bank.max(1) as usize
+    }
+
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
         match address {
             0x0000..=0x3fff => rom[address as usize],
             0x4000..=0x7fff => {
-                let bank = self.rom_bank.max(1) as usize;
-                let addr = bank * 0x4000 + (address - 0x4000) as usize;
+                let addr = self.rom_bank() * 0x4000 + (address - 0x4000) as usize;
                 rom[addr % rom.len()]
             }
             0xa000..=0xbfff => match self.mode {
@@ -184,7 +200,7 @@ impl Huc3 {
                     false
                 }
                 Mode::Ir => {
-                    // IR transmitter control — ignored
+                    self.warnings.record(Warning::Huc3Infrared);
                     false
                 }
                 _ => false,
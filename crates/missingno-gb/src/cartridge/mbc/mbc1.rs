@@ -1,7 +1,18 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Ram {
     None,
-    Unbanked { data: [u8; 8 * 1024] },
-    Banked { data: [[u8; 8 * 1024]; 4] },
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
+    Unbanked {
+        data: [u8; 8 * 1024],
+    },
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
+    Banked {
+        data: [[u8; 8 * 1024]; 4],
+    },
 }
 
 impl Ram {
@@ -32,6 +43,8 @@ impl Ram {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbc1 {
     pub ram: Ram,
     pub ram_enabled: bool,
@@ -80,7 +93,7 @@ impl Mbc1 {
         }
     }
 
-    fn current_bank(&self, rom_len: usize) -> u8 {
+    pub(super) fn current_bank(&self, rom_len: usize) -> u8 {
         if self.multicart {
             // MBC1M: BANK2 applies to bits 4-5, only lower 4 bits of BANK1 used.
             // The 0→1 check uses the full 5-bit register, not the masked 4-bit value.
@@ -1,4 +1,7 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbc2 {
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub ram: [u8; 0x200],
     pub ram_enabled: bool,
     pub bank: u8,
@@ -19,7 +22,7 @@ impl Mbc2 {
         }
     }
 
-    fn current_bank(&self, rom_len: usize) -> u8 {
+    pub(super) fn current_bank(&self, rom_len: usize) -> u8 {
         let bank = if self.bank == 0 { 1 } else { self.bank };
         let mask = (rom_len / 0x4000) as u8 - 1;
         bank & mask
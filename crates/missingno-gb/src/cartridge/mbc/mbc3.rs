@@ -1,9 +1,16 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::warnings::{Warning, Warnings};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mapped {
     Ram(u8),
     Clock(ClockRegister),
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mbc3Chip {
     Mbc3,
     Mbc30,
@@ -19,6 +26,7 @@ impl Mbc3Chip {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum ClockRegister {
     Seconds,
     Minutes,
@@ -28,6 +36,7 @@ pub enum ClockRegister {
 }
 
 #[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClockRegisters {
     pub seconds: u8,
     pub minutes: u8,
@@ -41,6 +50,24 @@ const HALT_BIT: u8 = 0x40;
 /// Base master-clock dots per RTC second: the 32768 Hz crystal × 128 = the 2^22-dot base clock.
 const DOTS_PER_RTC_SECOND: u32 = 1 << 22;
 
+/// Length of the RTC footer BGB and VBA-M append to an MBC3 battery save,
+/// straight after the RAM. Every field is little-endian:
+///
+/// | Offset | Size | Field                         |
+/// |--------|------|-------------------------------|
+/// | 0      | 4    | RTCS, seconds                 |
+/// | 4      | 4    | RTCM, minutes                 |
+/// | 8      | 4    | RTCH, hours                   |
+/// | 12     | 4    | RTCDL, day low byte           |
+/// | 16     | 4    | RTCDH, day bit 8, halt, carry |
+/// | 20     | 20   | The same five, as latched     |
+/// | 40     | 8    | Unix time of the save         |
+///
+/// Only the low byte of each register field is meaningful.
+pub const RTC_FOOTER_LEN: usize = 48;
+/// Older VBA-M builds write the timestamp as 4 bytes instead of 8.
+const RTC_FOOTER_LEN_32BIT_TIME: usize = 44;
+
 impl ClockRegisters {
     /// Carry chain: each stage carries into the next ONLY on its true boundary
     /// value, so a written out-of-range value (e.g. seconds 63) wraps to 0
@@ -85,6 +112,52 @@ impl ClockRegisters {
         }
     }
 
+    /// Run forward `seconds` at once, for time that passed while the game
+    /// wasn't running.
+    fn advance(&mut self, mut seconds: u64) {
+        // Out-of-range values wrap without carrying, so step through them.
+        while seconds > 0 && (self.seconds > 59 || self.minutes > 59 || self.hours > 23) {
+            self.increment_second();
+            seconds -= 1;
+        }
+        let day = (((self.days_upper & 1) as u64) << 8) | self.days_lower as u64;
+        let total = ((day * 24 + self.hours as u64) * 60 + self.minutes as u64) * 60
+            + self.seconds as u64
+            + seconds;
+        self.seconds = (total % 60) as u8;
+        self.minutes = (total / 60 % 60) as u8;
+        self.hours = (total / 3600 % 24) as u8;
+        let days = total / 86400;
+        let overflow = if days > 0x1ff { 0x80 } else { 0 };
+        self.days_lower = days as u8;
+        self.days_upper = (self.days_upper & 0xc0) | overflow | ((days >> 8) as u8 & 1);
+    }
+
+    /// Five little-endian `u32` fields in register order.
+    fn from_footer(fields: &[u8]) -> Self {
+        let field = |index: usize| fields[index * 4];
+        let mut registers = Self::default();
+        registers.set(ClockRegister::Seconds, field(0));
+        registers.set(ClockRegister::Minutes, field(1));
+        registers.set(ClockRegister::Hours, field(2));
+        registers.set(ClockRegister::DayLower, field(3));
+        registers.set(ClockRegister::DayUpper, field(4));
+        registers
+    }
+
+    fn write_footer(&self, fields: &mut [u8]) {
+        let values = [
+            self.seconds,
+            self.minutes,
+            self.hours,
+            self.days_lower,
+            self.days_upper,
+        ];
+        for (field, value) in fields.chunks_exact_mut(4).zip(values) {
+            field.copy_from_slice(&(value as u32).to_le_bytes());
+        }
+    }
+
     fn get(&self, register: ClockRegister) -> u8 {
         match register {
             ClockRegister::Seconds => self.seconds,
@@ -106,6 +179,8 @@ impl ClockRegisters {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Clock {
     pub registers: ClockRegisters,
     pub latched: ClockRegisters,
@@ -115,6 +190,40 @@ pub struct Clock {
 }
 
 impl Clock {
+    fn new() -> Self {
+        Self {
+            registers: ClockRegisters::default(),
+            latched: ClockRegisters::default(),
+            latch_ready: false,
+            sub_second_dots: 0,
+        }
+    }
+
+    /// Restore a clock from an [`RTC_FOOTER_LEN`] (or 44-byte) footer, then
+    /// run it on to `now` from the saved timestamp unless it was halted.
+    fn from_footer(footer: &[u8], now: u64) -> Self {
+        let saved_at = match footer.len() {
+            RTC_FOOTER_LEN => u64::from_le_bytes(footer[40..48].try_into().unwrap()),
+            _ => u32::from_le_bytes(footer[40..44].try_into().unwrap()) as u64,
+        };
+        let mut clock = Self::new();
+        clock.registers = ClockRegisters::from_footer(&footer[0..20]);
+        clock.latched = ClockRegisters::from_footer(&footer[20..40]);
+        if clock.registers.days_upper & HALT_BIT == 0 {
+            clock.registers.advance(now.saturating_sub(saved_at));
+        }
+        clock
+    }
+
+    /// The [`RTC_FOOTER_LEN`]-byte footer, stamped with `now`.
+    fn footer(&self, now: u64) -> [u8; RTC_FOOTER_LEN] {
+        let mut footer = [0; RTC_FOOTER_LEN];
+        self.registers.write_footer(&mut footer[0..20]);
+        self.latched.write_footer(&mut footer[20..40]);
+        footer[40..48].copy_from_slice(&now.to_le_bytes());
+        footer
+    }
+
     pub fn get_register(&self, register: ClockRegister) -> u8 {
         self.latched.get(register)
     }
@@ -145,13 +254,20 @@ impl Clock {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbc3 {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
     pub ram: Vec<[u8; 8 * 1024]>,
     pub clock: Option<Clock>,
     pub ram_and_clock_enabled: bool,
     pub bank: u8,
     pub mapped: Mapped,
     pub chip: Mbc3Chip,
+    pub(crate) warnings: Warnings,
 }
 
 impl Mbc3 {
@@ -182,12 +298,19 @@ impl Mbc3 {
         };
 
         let clock = match rom[0x147] {
-            0x0f | 0x10 => Some(Clock {
-                registers: ClockRegisters::default(),
-                latched: ClockRegisters::default(),
-                latch_ready: false,
-                sub_second_dots: 0,
-            }),
+            0x0f | 0x10 => {
+                let ram_len = ram.len() * 8 * 1024;
+                let footer = save_data
+                    .as_deref()
+                    .and_then(|data| data.get(ram_len..))
+                    .filter(|footer| {
+                        matches!(footer.len(), RTC_FOOTER_LEN | RTC_FOOTER_LEN_32BIT_TIME)
+                    });
+                Some(match footer {
+                    Some(footer) => Clock::from_footer(footer, unix_now()),
+                    None => Clock::new(),
+                })
+            }
             _ => None,
         };
 
@@ -204,6 +327,7 @@ impl Mbc3 {
             bank: 1,
             mapped: Mapped::Ram(0),
             chip,
+            warnings: Warnings::new(),
         }
     }
 
@@ -215,18 +339,37 @@ impl Mbc3 {
         }
     }
 
+    /// What to write to the battery save: the RAM, then for a cartridge
+    /// with an RTC the BGB-format footer stamped with the current time.
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        let Some(clock) = &self.clock else {
+            return self.ram();
+        };
+        let mut data = self.ram().unwrap_or_default();
+        data.extend_from_slice(&clock.footer(unix_now()));
+        Some(data)
+    }
+
     pub fn tick_rtc(&mut self, dots: u32) {
         if let Some(clock) = &mut self.clock {
             clock.tick(dots);
         }
     }
 
+    /// The ROM bank mapped at $4000–$7FFF.
+    pub(super) fn rom_bank(&self) -> usize {
+        if self.bank == 0 {
+            1
+        } else {
+            self.bank as usize
+        }
+    }
+
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
         match address {
             0x0000..=0x3fff => rom[address as usize],
             0x4000..=0x7fff => {
-                let bank = if self.bank == 0 { 1 } else { self.bank } as usize;
-                let addr = (bank * 0x4000 + (address - 0x4000) as usize) % rom.len();
+                let addr = (self.rom_bank() * 0x4000 + (address - 0x4000) as usize) % rom.len();
                 rom[addr]
             }
             0xa000..=0xbfff if self.ram_and_clock_enabled => match self.mapped {
@@ -259,7 +402,10 @@ impl Mbc3 {
                     0x0a => Mapped::Clock(ClockRegister::Hours),
                     0x0b => Mapped::Clock(ClockRegister::DayLower),
                     0x0c => Mapped::Clock(ClockRegister::DayUpper),
-                    _ => panic!("Invalid bank select {:2x}", value),
+                    _ => {
+                        self.warnings.record(Warning::Mbc3BankSelect { value });
+                        self.mapped.clone()
+                    }
                 };
                 false
             }
@@ -280,15 +426,24 @@ impl Mbc3 {
                     self.ram[ram_bank as usize][(address - 0xa000) as usize] = value;
                     true
                 }
-                Mapped::Clock(register) => {
-                    if let Some(clock) = &mut self.clock {
+                // The clock is saved with the RAM, so setting it needs a save too.
+                Mapped::Clock(register) => match &mut self.clock {
+                    Some(clock) => {
                         clock.set_register(register, value);
+                        true
                     }
-                    false
-                }
+                    None => false,
+                },
                 _ => false,
             },
             _ => false,
         }
     }
 }
+
+/// Seconds since the Unix epoch, the RTC footer's timestamp.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
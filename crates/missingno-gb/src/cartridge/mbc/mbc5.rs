@@ -1,8 +1,18 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbc5 {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
     pub ram: Vec<[u8; 8 * 1024]>,
     pub ram_enabled: bool,
     pub rom_bank: u16,
     pub ram_bank: u8,
+    /// A rumble cartridge (types $1C–$1E), where bit 3 of the RAM bank
+    /// register drives the motor instead of selecting a bank.
+    pub has_rumble: bool,
+    /// The motor is on.
     pub rumble: bool,
 }
 
@@ -15,7 +25,7 @@ impl Mbc5 {
         Self::create(rom, save_data, true)
     }
 
-    fn create(rom: &[u8], save_data: Option<Vec<u8>>, rumble: bool) -> Self {
+    fn create(rom: &[u8], save_data: Option<Vec<u8>>, has_rumble: bool) -> Self {
         let num_ram_banks = match rom[0x149] {
             2 => 1,
             3 => 4,
@@ -40,7 +50,8 @@ impl Mbc5 {
             ram_enabled: false,
             rom_bank: 1,
             ram_bank: 0,
-            rumble,
+            has_rumble,
+            rumble: false,
         }
     }
 
@@ -52,11 +63,22 @@ impl Mbc5 {
         }
     }
 
+    /// Whether the game has the rumble motor on. Always false for a
+    /// cartridge without one.
+    pub fn rumble(&self) -> bool {
+        self.rumble
+    }
+
+    /// The ROM bank mapped at $4000–$7FFF.
+    pub(super) fn rom_bank(&self) -> usize {
+        self.rom_bank as usize
+    }
+
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
         match address {
             0x0000..=0x3fff => rom[address as usize],
             0x4000..=0x7fff => {
-                let addr = self.rom_bank as usize * 0x4000 + (address - 0x4000) as usize;
+                let addr = self.rom_bank() * 0x4000 + (address - 0x4000) as usize;
                 rom[addr % rom.len()]
             }
             0xa000..=0xbfff if self.ram_enabled => {
@@ -86,11 +108,12 @@ impl Mbc5 {
                 false
             }
             0x4000..=0x5fff => {
-                self.ram_bank = if self.rumble {
-                    value & 0x07
+                if self.has_rumble {
+                    self.rumble = value & 0x08 != 0;
+                    self.ram_bank = value & 0x07;
                 } else {
-                    value & 0x0f
-                };
+                    self.ram_bank = value & 0x0f;
+                }
                 false
             }
             0xa000..=0xbfff if self.ram_enabled => {
@@ -1,8 +1,53 @@
+//! MBC6 (Net de Get: Minigame @ 100): two independently switched 8 KiB
+//! halves at 0x4000-0x5FFF and 0x6000-0x7FFF, each mapping ROM or a 1 MiB
+//! Macronix flash, plus two 4 KiB RAM windows.
+//!
+//! Flash support is partial. Reads, byte programming, sector/chip erase and
+//! the ID query follow the usual AA/55 unlock protocol and complete instantly,
+//! so status polling never waits. Erase suspend, the boot-block sector layout
+//! and program/erase timing aren't modelled.
+
+const RAM_BANK_SIZE: usize = 4 * 1024;
+const RAM_BANKS: usize = 8;
+const FLASH_SIZE: usize = 1024 * 1024;
+/// Erase granularity. The chip's smaller boot-block sectors aren't modelled.
+const FLASH_SECTOR_SIZE: usize = 64 * 1024;
+
+/// Macronix manufacturer and MX29F008 device codes, read in ID mode.
+const FLASH_ID: [u8; 2] = [0xc2, 0x81];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlashState {
+    Idle,
+    /// AA written to 5555.
+    Unlock1,
+    /// 55 written to 2AAA; the next write to 5555 is the command.
+    Unlock2,
+    /// The next write programs one byte.
+    Program,
+    /// 80 received; waiting for the second AA/55 unlock.
+    EraseUnlock1,
+    EraseUnlock2,
+    /// Second unlock done; 10 erases the chip, 30 a sector.
+    EraseCommand,
+    /// Reads return the manufacturer and device codes until F0.
+    Id,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbc6 {
     pub flash: Vec<u8>,
-    pub ram: Vec<[u8; 4 * 1024]>,
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
+    pub ram: Vec<[u8; RAM_BANK_SIZE]>,
     pub ram_enabled: bool,
     pub flash_enabled: bool,
+    pub flash_write_enabled: bool,
+    pub flash_state: FlashState,
     pub rom_bank_a: u8,
     pub rom_bank_a_flash: bool,
     pub rom_bank_b: u8,
@@ -12,25 +57,32 @@ pub struct Mbc6 {
 }
 
 impl Mbc6 {
+    /// Save data is RAM followed by flash; a RAM-only save leaves the flash
+    /// erased.
     pub fn new(_rom: &[u8], save_data: Option<Vec<u8>>) -> Self {
-        let mut ram = vec![[0u8; 4 * 1024]; 8];
+        let mut ram = vec![[0u8; RAM_BANK_SIZE]; RAM_BANKS];
+        let mut flash = vec![0xff; FLASH_SIZE];
         if let Some(data) = &save_data {
             for (bank_idx, bank) in ram.iter_mut().enumerate() {
-                let offset = bank_idx * 4 * 1024;
+                let offset = bank_idx * RAM_BANK_SIZE;
                 if offset < data.len() {
                     let len = (data.len() - offset).min(bank.len());
                     bank[..len].copy_from_slice(&data[offset..offset + len]);
                 }
             }
+            if let Some(saved) = data.get(RAM_BANK_SIZE * RAM_BANKS..) {
+                let len = saved.len().min(FLASH_SIZE);
+                flash[..len].copy_from_slice(&saved[..len]);
+            }
         }
 
-        let flash = vec![0xff; 1024 * 1024];
-
         Self {
             flash,
             ram,
             ram_enabled: false,
             flash_enabled: false,
+            flash_write_enabled: false,
+            flash_state: FlashState::Idle,
             rom_bank_a: 0,
             rom_bank_a_flash: false,
             rom_bank_b: 0,
@@ -41,21 +93,75 @@ impl Mbc6 {
     }
 
     fn read_rom_or_flash(&self, rom: &[u8], bank: u8, is_flash: bool, offset: usize) -> u8 {
+        let addr = bank as usize * 0x2000 + offset;
         if is_flash && self.flash_enabled {
-            let addr = bank as usize * 0x2000 + offset;
-            if addr < self.flash.len() {
-                self.flash[addr]
+            if self.flash_state == FlashState::Id {
+                FLASH_ID.get(addr & 0xff).copied().unwrap_or(0)
             } else {
-                0xff
+                self.flash.get(addr).copied().unwrap_or(0xff)
             }
         } else {
-            let addr = bank as usize * 0x2000 + offset;
-            if addr < rom.len() { rom[addr] } else { 0xff }
+            rom.get(addr).copied().unwrap_or(0xff)
+        }
+    }
+
+    /// A write to a flash-mapped half: part of a command sequence, or the
+    /// data byte of a program command. Returns true if flash contents changed.
+    fn write_flash(&mut self, addr: usize, value: u8) -> bool {
+        // The chip decodes command addresses from A14-A0.
+        let command_addr = addr & 0x7fff;
+
+        if value == 0xf0 {
+            self.flash_state = FlashState::Idle;
+            return false;
         }
+
+        let (next, changed) = match (self.flash_state, command_addr, value) {
+            (FlashState::Idle | FlashState::Id, 0x5555, 0xaa) => (FlashState::Unlock1, false),
+            (FlashState::Unlock1, 0x2aaa, 0x55) => (FlashState::Unlock2, false),
+            (FlashState::Unlock2, 0x5555, 0xa0) => (FlashState::Program, false),
+            (FlashState::Unlock2, 0x5555, 0x80) => (FlashState::EraseUnlock1, false),
+            (FlashState::Unlock2, 0x5555, 0x90) => (FlashState::Id, false),
+            (FlashState::EraseUnlock1, 0x5555, 0xaa) => (FlashState::EraseUnlock2, false),
+            (FlashState::EraseUnlock2, 0x2aaa, 0x55) => (FlashState::EraseCommand, false),
+            (FlashState::EraseCommand, 0x5555, 0x10) if self.flash_write_enabled => {
+                self.flash.fill(0xff);
+                (FlashState::Idle, true)
+            }
+            (FlashState::EraseCommand, _, 0x30) if self.flash_write_enabled => {
+                let start = addr / FLASH_SECTOR_SIZE * FLASH_SECTOR_SIZE;
+                if let Some(sector) = self.flash.get_mut(start..start + FLASH_SECTOR_SIZE) {
+                    sector.fill(0xff);
+                }
+                (FlashState::Idle, true)
+            }
+            // Programming can only clear bits; erase is what sets them.
+            (FlashState::Program, _, _) if self.flash_write_enabled => {
+                match self.flash.get_mut(addr) {
+                    Some(byte) => {
+                        *byte &= value;
+                        (FlashState::Idle, true)
+                    }
+                    None => (FlashState::Idle, false),
+                }
+            }
+            (FlashState::Id, _, _) => (FlashState::Id, false),
+            _ => (FlashState::Idle, false),
+        };
+
+        self.flash_state = next;
+        changed
     }
 
     pub fn ram(&self) -> Option<Vec<u8>> {
-        Some(self.ram.iter().flatten().copied().collect())
+        Some(
+            self.ram
+                .iter()
+                .flatten()
+                .chain(self.flash.iter())
+                .copied()
+                .collect(),
+        )
     }
 
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
@@ -117,9 +223,12 @@ impl Mbc6 {
                 self.flash_enabled = value & 0x01 != 0;
                 false
             }
-            0x1000..=0x1fff => false, // Flash write enable — ignored
+            0x1000..=0x1fff => {
+                self.flash_write_enabled = value & 0x01 != 0;
+                false
+            }
             0x2000..=0x27ff => {
-                self.rom_bank_a = value;
+                self.rom_bank_a = value & 0x7f;
                 false
             }
             0x2800..=0x2fff => {
@@ -127,13 +236,21 @@ impl Mbc6 {
                 false
             }
             0x3000..=0x37ff => {
-                self.rom_bank_b = value;
+                self.rom_bank_b = value & 0x7f;
                 false
             }
             0x3800..=0x3fff => {
                 self.rom_bank_b_flash = value == 0x08;
                 false
             }
+            0x4000..=0x5fff if self.rom_bank_a_flash && self.flash_enabled => {
+                let addr = self.rom_bank_a as usize * 0x2000 + (address - 0x4000) as usize;
+                self.write_flash(addr, value)
+            }
+            0x6000..=0x7fff if self.rom_bank_b_flash && self.flash_enabled => {
+                let addr = self.rom_bank_b as usize * 0x2000 + (address - 0x6000) as usize;
+                self.write_flash(addr, value)
+            }
             0xa000..=0xafff if self.ram_enabled => {
                 let bank = self.ram_bank_a as usize;
                 if bank < self.ram.len() {
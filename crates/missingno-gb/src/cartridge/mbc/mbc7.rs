@@ -1,3 +1,5 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum EepromState {
     Idle,
     ReceivingCommand {
@@ -15,7 +17,10 @@ pub enum EepromState {
     },
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Eeprom {
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub data: [u16; 128],
     pub state: EepromState,
     pub write_enabled: bool,
@@ -50,7 +55,9 @@ impl Eeprom {
         self.data.iter().flat_map(|w| w.to_be_bytes()).collect()
     }
 
-    fn write(&mut self, value: u8) {
+    /// Clock in one bit. Returns true if it completed a command that
+    /// changed the stored data.
+    fn write(&mut self, value: u8) -> bool {
         let cs = value & 0x80 != 0;
         let clk = value & 0x40 != 0;
         let di = (value >> 1) & 1;
@@ -60,18 +67,19 @@ impl Eeprom {
             self.do_bit = 1;
             self.last_clk = clk;
             self.last_cs = cs;
-            return;
+            return false;
         }
 
         // Detect rising edge of clock
         if !clk || self.last_clk {
             self.last_clk = clk;
             self.last_cs = cs;
-            return;
+            return false;
         }
         self.last_clk = clk;
         self.last_cs = cs;
 
+        let mut stored = false;
         match &mut self.state {
             EepromState::Idle => {
                 if di == 1 {
@@ -109,6 +117,7 @@ impl Eeprom {
                             // ERASE
                             if self.write_enabled {
                                 self.data[address as usize] = 0xffff;
+                                stored = true;
                             }
                             self.do_bit = 1;
                             self.state = EepromState::Idle;
@@ -128,6 +137,7 @@ impl Eeprom {
                                     // ERAL - erase all
                                     if self.write_enabled {
                                         self.data = [0xffff; 128];
+                                        stored = true;
                                     }
                                 }
                                 0b01 => {
@@ -137,7 +147,7 @@ impl Eeprom {
                                         value: 0,
                                         bits_remaining: 16,
                                     };
-                                    return;
+                                    return false;
                                 }
                                 _ => unreachable!(),
                             }
@@ -180,12 +190,14 @@ impl Eeprom {
                         } else {
                             self.data[*address as usize] = *value;
                         }
+                        stored = true;
                     }
                     self.do_bit = 1;
                     self.state = EepromState::Idle;
                 }
             }
         }
+        stored
     }
 
     fn read(&self) -> u8 {
@@ -193,11 +205,15 @@ impl Eeprom {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum LatchState {
     Idle,
     WroteErase,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mbc7 {
     pub eeprom: Eeprom,
     pub ram_enabled_1: bool,
@@ -229,12 +245,16 @@ impl Mbc7 {
         Some(self.eeprom.to_vec())
     }
 
+    /// The ROM bank mapped at $4000–$7FFF.
+    pub(super) fn rom_bank(&self) -> usize {
+        self.rom_bank.max(1) as usize
+    }
+
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
         match address {
             0x0000..=0x3fff => rom[address as usize],
             0x4000..=0x7fff => {
-                let bank = self.rom_bank.max(1) as usize;
-                let addr = bank * 0x4000 + (address - 0x4000) as usize;
+                let addr = self.rom_bank() * 0x4000 + (address - 0x4000) as usize;
                 if addr < rom.len() { rom[addr] } else { 0xff }
             }
             0xa000..=0xafff if self.ram_accessible() => {
@@ -291,10 +311,7 @@ impl Mbc7 {
                         self.latch_state = LatchState::Idle;
                         false
                     }
-                    0x8 => {
-                        self.eeprom.write(value);
-                        false
-                    }
+                    0x8 => self.eeprom.write(value),
                     _ => false,
                 }
             }
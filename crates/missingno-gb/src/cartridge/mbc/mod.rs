@@ -8,6 +8,10 @@ pub mod mbc6;
 pub mod mbc7;
 pub mod no_mbc;
 
+use crate::warnings::Warning;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mbc {
     NoMbc(no_mbc::NoMbc),
     Mbc1(mbc1::Mbc1),
@@ -35,6 +39,14 @@ impl Mbc {
         }
     }
 
+    /// Battery save contents: [`ram`](Self::ram), plus MBC3's RTC footer.
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        match self {
+            Mbc::Mbc3(m) => m.save_data(),
+            _ => self.ram(),
+        }
+    }
+
     pub fn read(&self, rom: &[u8], address: u16) -> u8 {
         match self {
             Mbc::NoMbc(m) => m.read(rom, address),
@@ -49,7 +61,25 @@ impl Mbc {
         }
     }
 
-    /// Write to cartridge address space. Returns true if SRAM was written.
+    /// The 16 KiB ROM bank mapped at $4000–$7FFF, or `None` for MBC6, which
+    /// banks the two 8 KiB halves of that window separately.
+    pub fn rom_bank(&self, rom_len: usize) -> Option<usize> {
+        let bank = match self {
+            Mbc::NoMbc(_) => 1,
+            Mbc::Mbc1(m) => m.current_bank(rom_len).into(),
+            Mbc::Mbc2(m) => m.current_bank(rom_len).into(),
+            Mbc::Mbc3(m) => m.rom_bank(),
+            Mbc::Mbc5(m) => m.rom_bank(),
+            Mbc::Mbc6(_) => return None,
+            Mbc::Mbc7(m) => m.rom_bank(),
+            Mbc::Huc1(m) => m.rom_bank(),
+            Mbc::Huc3(m) => m.rom_bank(),
+        };
+        Some(bank % (rom_len / 0x4000).max(1))
+    }
+
+    /// Write to cartridge address space. Returns true if battery-backed
+    /// state was written: SRAM, or MBC3's clock registers.
     pub fn write(&mut self, address: u16, value: u8) -> bool {
         match self {
             Mbc::NoMbc(m) => m.write(address, value),
@@ -71,4 +101,13 @@ impl Mbc {
             m.tick_rtc(dots);
         }
     }
+
+    /// Warnings raised since the last call. Only MBC3 and HuC3 raise any.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        match self {
+            Mbc::Mbc3(m) => m.warnings.take(),
+            Mbc::Huc3(m) => m.warnings.take(),
+            _ => Vec::new(),
+        }
+    }
 }
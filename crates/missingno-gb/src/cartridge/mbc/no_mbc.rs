@@ -1,4 +1,7 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoMbc {
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub ram: Option<[u8; 8 * 1024]>,
 }
 
@@ -33,12 +36,14 @@ impl NoMbc {
         }
     }
 
+    /// Only RAM takes writes; with no MBC, writes to ROM go nowhere.
     pub fn write(&mut self, address: u16, value: u8) -> bool {
-        if let Some(ram) = &mut self.ram {
-            ram[(address - 0xa000) as usize] = value;
-            true
-        } else {
-            false
+        match (address, &mut self.ram) {
+            (0xa000..=0xbfff, Some(ram)) => {
+                ram[(address - 0xa000) as usize] = value;
+                true
+            }
+            _ => false,
         }
     }
 }
@@ -1,17 +1,124 @@
 pub mod mbc;
 
+use std::{fmt, sync::Arc};
+
 use mbc::{
     Mbc, huc1::Huc1, huc3::Huc3, mbc1::Mbc1, mbc2::Mbc2, mbc3::Mbc3, mbc5::Mbc5, mbc6::Mbc6,
     mbc7::Mbc7, no_mbc::NoMbc,
 };
 
+use crate::warnings::Warning;
+
+/// End of the cartridge header; anything shorter isn't a ROM.
+const HEADER_END: usize = 0x150;
+
+/// The two fixed 16 KiB banks every cartridge maps at $0000–$7FFF.
+const MIN_ROM_SIZE: usize = 0x8000;
+
+/// Why a ROM image can't be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// Shorter than the cartridge header.
+    TooShort { len: usize },
+    /// Shorter than 32 KiB, or than the ROM size in its header ($0148): a
+    /// cut-off dump.
+    Truncated { len: usize, expected: usize },
+    /// Header cartridge type ($0147) names a mapper that isn't emulated.
+    UnsupportedMbc(u8),
+    /// Header checksum ($014D) doesn't match the header bytes. The boot ROM
+    /// refuses these, but homebrew often leaves it unset.
+    HeaderChecksum { expected: u8, computed: u8 },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::TooShort { len } => {
+                write!(f, "file is {len} bytes, too short to be a Game Boy ROM")
+            }
+            LoadError::Truncated { len, expected } => {
+                write!(f, "file is {len} bytes but the ROM should be {expected}")
+            }
+            LoadError::UnsupportedMbc(kind) => {
+                write!(f, "unsupported cartridge type ${kind:02X}")
+            }
+            LoadError::HeaderChecksum { expected, computed } => write!(
+                f,
+                "header checksum is ${expected:02X} but the header sums to ${computed:02X}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// How a cartridge uses the CGB, from the header CGB flag ($0143).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum CgbSupport {
+    /// A DMG cartridge; the CGB runs it in DMG-compatibility mode.
+    None,
+    /// $80: uses CGB features but still runs on a DMG.
+    Enhanced,
+    /// $C0: refuses to run on a DMG.
+    Only,
+}
+
+/// Who published a cartridge, from the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum Licensee {
+    /// The old licensee code ($014B).
+    Old(u8),
+    /// The two ASCII characters at $0144–$0145, used when the old code is
+    /// $33.
+    New([u8; 2]),
+}
+
+/// Where a cartridge was sold, from the destination code ($014A).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum Destination {
+    Japan,
+    Overseas,
+}
+
+/// The cartridge header, decoded once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub struct Header {
+    pub title: String,
+    /// SGB flag ($0146) is $03.
+    pub sgb: bool,
+    pub cgb: CgbSupport,
+    pub has_battery: bool,
+    /// The cartridge type byte ($0147), naming the mapper and what's
+    /// fitted alongside it.
+    pub cartridge_type: u8,
+    /// ROM size in bytes from its code ($0148), or `None` for a code that
+    /// doesn't name one.
+    pub rom_size: Option<usize>,
+    /// Cartridge RAM in bytes from its code ($0149). MBC2's built-in 512
+    /// bytes count, though its code is 0.
+    pub ram_size: usize,
+    pub licensee: Licensee,
+    pub destination: Destination,
+    /// The header checksum ($014D) matches the header. The boot ROM locks
+    /// up on one that doesn't.
+    pub header_checksum_valid: bool,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cartridge {
-    title: String,
-    has_battery: bool,
-    sgb_flag: bool,
-    rom: Vec<u8>,
+    header: Header,
+    /// Shared so that cloning a console for a save state doesn't copy the ROM.
+    /// Left out of save state files; see [`attach_rom`](Self::attach_rom).
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    rom: Arc<[u8]>,
     mbc: Mbc,
-    pub(crate) sram_dirty: bool,
+    /// Bumped by every write to battery RAM.
+    ram_generation: u64,
 }
 
 pub fn parse_title(rom: &[u8]) -> String {
@@ -29,45 +136,133 @@ pub fn parse_title(rom: &[u8]) -> String {
     title
 }
 
-pub fn parse_header(rom: &[u8]) -> (String, bool, bool) {
-    let title = parse_title(rom);
-    let sgb_flag = rom[0x146] == 0x03;
+pub fn parse_header(rom: &[u8]) -> Header {
+    // Any CGB flag with bit 7 set enables CGB mode; only $C0 marks a
+    // CGB-only cartridge.
+    let cgb = match rom[0x143] {
+        0xC0 => CgbSupport::Only,
+        flag if flag & 0x80 != 0 => CgbSupport::Enhanced,
+        _ => CgbSupport::None,
+    };
     let cartridge_type = rom[0x147];
+    // MBC6's single header type covers battery-backed RAM and flash.
     let has_battery = matches!(
         cartridge_type,
-        0x03 | 0x06 | 0x09 | 0x10 | 0x13 | 0x1b | 0x1e | 0x22 | 0xfe | 0xff
+        0x03 | 0x06 | 0x09 | 0x0f | 0x10 | 0x13 | 0x1b | 0x1e | 0x20 | 0x22 | 0xfe | 0xff
     );
-    (title, sgb_flag, has_battery)
+    let rom_size = match rom[0x148] {
+        code @ 0..=8 => Some(0x8000 << code),
+        _ => None,
+    };
+    let ram_size = match (cartridge_type, rom[0x149]) {
+        (0x05 | 0x06, _) => 512,
+        (_, 1) => 2 * 1024,
+        (_, 2) => 8 * 1024,
+        (_, 3) => 32 * 1024,
+        (_, 4) => 128 * 1024,
+        (_, 5) => 64 * 1024,
+        _ => 0,
+    };
+    let licensee = match rom[0x14b] {
+        0x33 => Licensee::New([rom[0x144], rom[0x145]]),
+        code => Licensee::Old(code),
+    };
+    let destination = if rom[0x14a] == 0x00 {
+        Destination::Japan
+    } else {
+        Destination::Overseas
+    };
+    Header {
+        title: parse_title(rom),
+        sgb: rom[0x146] == 0x03,
+        cgb,
+        has_battery,
+        cartridge_type,
+        rom_size,
+        ram_size,
+        licensee,
+        destination,
+        header_checksum_valid: compute_header_checksum(rom) == rom[0x14d],
+    }
+}
+
+/// Refuse a ROM missing banks its mapper would read: shorter than 32 KiB or
+/// than the size its header gives.
+fn check_rom_size(rom: &[u8], header: &Header) -> Result<(), LoadError> {
+    let expected = header.rom_size.unwrap_or(0).max(MIN_ROM_SIZE);
+    if rom.len() < expected {
+        return Err(LoadError::Truncated {
+            len: rom.len(),
+            expected,
+        });
+    }
+    Ok(())
+}
+
+/// What the header checksum ($014D) should be for the bytes it covers.
+fn compute_header_checksum(rom: &[u8]) -> u8 {
+    rom[0x134..0x14d]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_sub(*byte).wrapping_sub(1))
 }
 
 impl Cartridge {
-    pub fn new(rom: Vec<u8>, save_data: Option<Vec<u8>>) -> Cartridge {
-        let (title, sgb_flag, has_battery) = parse_header(&rom);
-        let cartridge_type = rom[0x147];
-        let save = if has_battery { save_data } else { None };
-
-        let mbc = match cartridge_type {
-            0x00 | 0x08 | 0x09 => Mbc::NoMbc(NoMbc::new(&rom, save)),
-            0x01..=0x03 => Mbc::Mbc1(Mbc1::new(&rom, save)),
-            0x05 | 0x06 => Mbc::Mbc2(Mbc2::new(&rom, save)),
-            0x0f..=0x13 => Mbc::Mbc3(Mbc3::new(&rom, save)),
-            0x19..=0x1b => Mbc::Mbc5(Mbc5::new(&rom, save)),
-            0x1c..=0x1e => Mbc::Mbc5(Mbc5::new_rumble(&rom, save)),
-            0x20 => Mbc::Mbc6(Mbc6::new(&rom, save)),
-            0x22 => Mbc::Mbc7(Mbc7::new(&rom, save)),
-            0xfe => Mbc::Huc3(Huc3::new(&rom, save)),
-            0xff => Mbc::Huc1(Huc1::new(&rom, save)),
-
-            _ => panic!("nyi: mbc {:2x}", cartridge_type),
-        };
-
-        Cartridge {
-            title,
-            has_battery,
-            sgb_flag,
-            sram_dirty: false,
-            rom,
+    /// Fails on a ROM shorter than 32 KiB or than the size in its header, so
+    /// no bank can read past the end, or with a cartridge type that isn't
+    /// emulated. The header checksum isn't checked; see
+    /// [`try_new`](Self::try_new).
+    pub fn new(rom: Vec<u8>, save_data: Option<Vec<u8>>) -> Result<Cartridge, LoadError> {
+        if rom.len() < HEADER_END {
+            return Err(LoadError::TooShort { len: rom.len() });
+        }
+        let header = parse_header(&rom);
+        check_rom_size(&rom, &header)?;
+        let save = if header.has_battery { save_data } else { None };
+
+        let build_mbc = mbc_for(header.cartridge_type)
+            .ok_or(LoadError::UnsupportedMbc(header.cartridge_type))?;
+        let mbc = build_mbc(&rom, save);
+
+        Ok(Cartridge {
+            header,
+            ram_generation: 0,
+            rom: rom.into(),
             mbc,
+        })
+    }
+
+    /// Like [`new`](Self::new), but also rejects a bad header checksum.
+    pub fn try_new(rom: Vec<u8>, save_data: Option<Vec<u8>>) -> Result<Cartridge, LoadError> {
+        Self::validate(&rom)?;
+        Self::new(rom, save_data)
+    }
+
+    /// Check that `rom` has a full header, an emulated mapper, all the banks
+    /// its header claims and a matching header checksum.
+    pub fn validate(rom: &[u8]) -> Result<(), LoadError> {
+        if rom.len() < HEADER_END {
+            return Err(LoadError::TooShort { len: rom.len() });
+        }
+        let cartridge_type = rom[0x147];
+        if mbc_for(cartridge_type).is_none() {
+            return Err(LoadError::UnsupportedMbc(cartridge_type));
+        }
+        check_rom_size(rom, &parse_header(rom))?;
+        let computed = compute_header_checksum(rom);
+        if computed != rom[0x14d] {
+            return Err(LoadError::HeaderChecksum {
+                expected: rom[0x14d],
+                computed,
+            });
+        }
+        Ok(())
+    }
+
+    /// [`validate`](Self::validate) without the header checksum, for homebrew.
+    pub fn validate_lenient(rom: &[u8]) -> Result<(), LoadError> {
+        match Self::validate(rom) {
+            Err(LoadError::HeaderChecksum { .. }) => Ok(()),
+            result => result,
         }
     }
 
@@ -75,29 +270,50 @@ impl Cartridge {
         parse_title(rom)
     }
 
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
     pub fn title(&self) -> &str {
-        &self.title
+        &self.header.title
     }
 
     pub fn has_battery(&self) -> bool {
-        self.has_battery
+        self.header.has_battery
     }
 
     pub fn supports_sgb(&self) -> bool {
-        self.sgb_flag
+        self.header.sgb
     }
 
     /// CGB flag (header $0143): bit 7 set ($80 enhanced, $C0 CGB-only) marks a
     /// CGB-aware cartridge. Any other value is a DMG cartridge, which the CGB
     /// runs in DMG-compatibility mode.
-    pub fn is_cgb(&self) -> bool {
-        self.rom[0x143] & 0x80 != 0
+    pub fn supports_cgb(&self) -> bool {
+        self.header.cgb != CgbSupport::None
+    }
+
+    /// A CGB-only cartridge ($C0), which shows an error screen on a DMG.
+    pub fn cgb_only(&self) -> bool {
+        self.header.cgb == CgbSupport::Only
+    }
+
+    pub fn cgb_support(&self) -> CgbSupport {
+        self.header.cgb
     }
 
     pub fn ram(&self) -> Option<Vec<u8>> {
         self.mbc.ram()
     }
 
+    /// What to write to a `.sav`: the battery RAM, followed for MBC3 RTC
+    /// cartridges by the clock in the footer BGB and VBA-M use (see
+    /// [`RTC_FOOTER_LEN`](mbc::mbc3::RTC_FOOTER_LEN)). [`new`](Self::new)
+    /// reads the footer back, running the clock on for the time since.
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        self.mbc.save_data()
+    }
+
     pub fn rom(&self) -> &[u8] {
         &self.rom
     }
@@ -106,25 +322,59 @@ impl Cartridge {
         self.rom[0x14d]
     }
 
+    /// The sum of every ROM byte but the two at $014E–$014F, which is what
+    /// the header's global checksum there should hold. Worked out from the
+    /// ROM rather than trusted, since homebrew often leaves it zero.
+    pub fn rom_checksum(&self) -> u16 {
+        self.rom
+            .iter()
+            .enumerate()
+            .filter(|(address, _)| !matches!(address, 0x14e | 0x14f))
+            .fold(0u16, |sum, (_, byte)| sum.wrapping_add(*byte as u16))
+    }
+
+    /// Share `other`'s ROM, for a cartridge restored from a save state,
+    /// which doesn't carry one.
+    #[cfg(feature = "save-state")]
+    pub(crate) fn attach_rom(&mut self, other: &Cartridge) {
+        self.rom = other.rom.clone();
+    }
+
     pub fn read(&self, address: u16) -> u8 {
         self.mbc.read(&self.rom, address)
     }
 
+    /// The ROM bank mapped at $4000–$7FFF; see [`Mbc::rom_bank`].
+    pub fn rom_bank(&self) -> Option<usize> {
+        self.mbc.rom_bank(self.rom.len())
+    }
+
+    /// Whether an MBC5 rumble cartridge has its motor on, for a front-end
+    /// to pass on to a gamepad.
+    pub fn rumble(&self) -> bool {
+        matches!(&self.mbc, Mbc::Mbc5(mbc5) if mbc5.rumble())
+    }
+
     pub fn write(&mut self, address: u16, value: u8) {
         let ram_written = self.mbc.write(address, value);
-        if self.has_battery && ram_written {
-            self.sram_dirty = true;
+        if self.header.has_battery && ram_written {
+            self.ram_generation += 1;
         }
     }
 
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        self.mbc.take_warnings()
+    }
+
     /// Advance the cartridge RTC (if any) by `dots` of master-clock time.
     pub fn tick_rtc(&mut self, dots: u32) {
         self.mbc.tick_rtc(dots);
     }
 
-    /// Returns true if SRAM has been written to since the last call.
-    pub fn take_sram_dirty(&mut self) -> bool {
-        std::mem::replace(&mut self.sram_dirty, false)
+    /// Counts writes to battery RAM. A saver keeps the generation it last
+    /// wrote out; anything else means there is something new to save.
+    pub fn ram_generation(&self) -> u64 {
+        self.ram_generation
     }
 
     pub fn mbc(&self) -> &Mbc {
@@ -135,3 +385,24 @@ impl Cartridge {
         &mut self.mbc
     }
 }
+
+/// Builds a mapper from the ROM and any saved RAM.
+type MbcBuilder = fn(&[u8], Option<Vec<u8>>) -> Mbc;
+
+/// The mapper for a cartridge type, or `None` if it isn't emulated.
+fn mbc_for(cartridge_type: u8) -> Option<MbcBuilder> {
+    let build: MbcBuilder = match cartridge_type {
+        0x00 | 0x08 | 0x09 => |rom, save| Mbc::NoMbc(NoMbc::new(rom, save)),
+        0x01..=0x03 => |rom, save| Mbc::Mbc1(Mbc1::new(rom, save)),
+        0x05 | 0x06 => |rom, save| Mbc::Mbc2(Mbc2::new(rom, save)),
+        0x0f..=0x13 => |rom, save| Mbc::Mbc3(Mbc3::new(rom, save)),
+        0x19..=0x1b => |rom, save| Mbc::Mbc5(Mbc5::new(rom, save)),
+        0x1c..=0x1e => |rom, save| Mbc::Mbc5(Mbc5::new_rumble(rom, save)),
+        0x20 => |rom, save| Mbc::Mbc6(Mbc6::new(rom, save)),
+        0x22 => |rom, save| Mbc::Mbc7(Mbc7::new(rom, save)),
+        0xfe => |rom, save| Mbc::Huc3(Huc3::new(rom, save)),
+        0xff => |rom, save| Mbc::Huc1(Huc1::new(rom, save)),
+        _ => return None,
+    };
+    Some(build)
+}
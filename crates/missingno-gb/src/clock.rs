@@ -8,6 +8,7 @@
 /// phase layer: `Rise` ≡ `ClockPhase::Low` (master rise), `Fall` ≡
 /// `ClockPhase::High` (master fall).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edge {
     Rise,
     Fall,
@@ -25,6 +26,7 @@ impl Edge {
 /// The `÷1`-or-`÷2` divider cell — the one timing circuit the CGB adds to the
 /// DMG die. DMG is hard-wired `One`; KEY1 is the only thing that selects `Two`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum CpuDivider {
     One,
     Two,
@@ -90,6 +92,7 @@ pub struct Tick {
 /// (CPU edge) and `ppu_phase` (dot edge) fields with one object that owns the
 /// dispatch.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct MasterClock {
     /// Free-running master-edge counter. Monotone; one toggle per master
     /// half-cycle.
@@ -17,7 +17,8 @@ use super::registers::{Register8, Register16};
 /// the shape matches (e.g., `LD r,[HL]` final step emits `Commit::LoadR8`
 /// just as `LD r,d8` does).
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(super) enum Commit {
     // ── No register/flag change ──
     /// Retire edge with no architectural mutation. NOP, not-taken
@@ -26,7 +27,7 @@ pub(super) enum Commit {
     NoOperation,
     /// Invalid opcode — enters `HaltState::Locked` (hard-lock until
     /// power-off). Hardware continues to tick; the CPU never resumes.
-    Invalid,
+    Invalid(u8),
 
     // ── 8-bit register writes ──
     LoadR8 {
@@ -58,12 +59,6 @@ pub(super) enum Commit {
     AddHl {
         source: Register16,
     },
-    AddSpOffset {
-        offset: i8,
-    },
-    LdHlSpOffset {
-        offset: i8,
-    },
 
     // ── Flags / accumulator bit ops ──
     Daa,
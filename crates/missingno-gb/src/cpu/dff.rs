@@ -9,6 +9,8 @@
 ///
 /// The PPU has a u8-specific `DffLatch` (`ppu::dff`) with the same
 /// semantics; a future refactor can collapse it into `Dff<u8>`.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dff<T> {
     output: T,
     pending: Option<T>,
@@ -23,6 +23,8 @@
 use crate::cpu::dff::Dff;
 use crate::interrupts::{Interrupt, InterruptFlags};
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct DispatchChain {
     /// irq_latch_inst<i> outputs: per-bit post-latch IF.
     /// Bit i holds the (IE ∧ IF) bit i value sampled through the
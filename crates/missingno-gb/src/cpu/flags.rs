@@ -4,6 +4,7 @@ use bitflags::bitflags;
 
 #[derive(Clone)]
 #[allow(dead_code)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Flag {
     Zero,
     Negative,
@@ -39,6 +40,7 @@ impl fmt::Display for Flag {
 
 bitflags! {
     #[derive(Copy,Clone,Debug)]
+    #[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
     pub struct Flags: u8 {
         const ZERO = 0b10000000;
         const NEGATIVE = 0b01000000;
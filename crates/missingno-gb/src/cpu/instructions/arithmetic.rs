@@ -6,12 +6,14 @@ use crate::cpu::{
 };
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Arithmetic {
     Arithmetic8(Arithmetic8),
     Arithmetic16(Arithmetic16),
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Arithmetic8 {
     Increment(Target8),
     Decrement(Target8),
@@ -59,6 +61,7 @@ impl Arithmetic8 {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Arithmetic16 {
     Increment(Register16),
     Decrement(Register16),
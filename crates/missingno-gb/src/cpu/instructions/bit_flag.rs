@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::instructions::{Source8, Target8};
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum BitFlag {
     Check(u8, Source8),
     Set(u8, Target8),
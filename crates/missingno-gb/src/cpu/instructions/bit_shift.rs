@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::instructions::Target8;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum BitShift {
     RotateA(Direction, Carry), // Register A optimised variants, not within cb prefix
     Rotate(Direction, Carry, Target8),
@@ -12,6 +13,7 @@ pub enum BitShift {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Left,
     Right,
@@ -27,6 +29,7 @@ impl fmt::Display for Direction {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Carry {
     Through,
     SetOnly,
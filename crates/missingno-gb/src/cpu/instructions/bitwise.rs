@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::instructions::Source8;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bitwise {
     AndA(Source8),
     OrA(Source8),
@@ -1,6 +1,7 @@
 use core::fmt;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum CarryFlag {
     Complement,
     Set,
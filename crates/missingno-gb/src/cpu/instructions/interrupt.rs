@@ -1,6 +1,7 @@
 use core::fmt;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Interrupt {
     Enable,
     Disable,
@@ -6,6 +6,7 @@ use crate::cpu::{
 };
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Jump {
     Jump(Option<Condition>, Location),
     Call(Option<Condition>, Location),
@@ -15,6 +16,7 @@ pub enum Jump {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Location {
     Address(Address),
     RegisterHl,
@@ -48,6 +50,7 @@ impl fmt::Display for Location {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Condition(pub Flag, pub bool);
 
 impl Condition {
@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::instructions::{Source8, Source16, Target8, Target16};
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Load {
     Load8(Target8, Source8),
     Load16(Target16, Source16),
@@ -23,6 +23,7 @@ mod parameters;
 mod stack;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Instruction {
     Load(Load),
     Arithmetic(Arithmetic),
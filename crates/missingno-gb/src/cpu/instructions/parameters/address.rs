@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::Register16;
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Address {
     Fixed(u16),
     Relative(i8),
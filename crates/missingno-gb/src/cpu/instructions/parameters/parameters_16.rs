@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::{Register16, instructions::Address};
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Target16 {
     Register(Register16),
     Memory(Address),
@@ -44,6 +45,7 @@ impl fmt::Display for Target16 {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Source16 {
     Constant(u16),
     Register(Register16),
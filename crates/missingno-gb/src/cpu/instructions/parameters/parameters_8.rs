@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::{Register8, instructions::Address};
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Target8 {
     Register(Register8),
     Memory(Address),
@@ -87,6 +88,7 @@ impl fmt::Display for Target8 {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Source8 {
     Constant(u8),
     Register(Register8),
@@ -3,6 +3,7 @@ use core::fmt;
 use crate::cpu::registers::Register16;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stack {
     Adjust(i8),
     Push(Register16),
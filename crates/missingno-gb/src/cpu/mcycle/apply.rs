@@ -7,6 +7,7 @@ use super::super::{
     registers::Register16,
 };
 use super::{AluOp, PopAction, ReadAction, RmwOp};
+use crate::warnings::Warning;
 
 impl Cpu {
     /// Route a `Commit` variant to the corresponding mutation. Mirrors the
@@ -17,27 +18,23 @@ impl Cpu {
     pub(super) fn apply_commit(cpu: &mut Cpu, commit: Commit) {
         match commit {
             Commit::NoOperation => {}
-            Commit::Invalid => {
+            Commit::Invalid(opcode) => {
                 cpu.halt.state = HaltState::Locked;
+                cpu.warnings.record(Warning::IllegalOpcode {
+                    opcode,
+                    address: cpu.ir_address,
+                });
             }
 
             Commit::LoadR8 { reg, value } => cpu.set_register8(reg, value),
             Commit::LoadR16 { reg, value } => cpu.set_register16(reg, value),
 
             Commit::IncR8 { reg } => {
-                let val = cpu.get_register8(reg);
-                let result = val.wrapping_add(1);
-                cpu.flags.set(Flags::ZERO, result == 0);
-                cpu.flags.remove(Flags::NEGATIVE);
-                cpu.flags.set(Flags::HALF_CARRY, result & 0b1111 == 0);
+                let result = Self::increment(cpu, cpu.get_register8(reg));
                 cpu.set_register8(reg, result);
             }
             Commit::DecR8 { reg } => {
-                let val = cpu.get_register8(reg);
-                let result = val.wrapping_sub(1);
-                cpu.flags.set(Flags::ZERO, result == 0);
-                cpu.flags.insert(Flags::NEGATIVE);
-                cpu.flags.set(Flags::HALF_CARRY, result & 0b1111 == 0b1111);
+                let result = Self::decrement(cpu, cpu.get_register8(reg));
                 cpu.set_register8(reg, result);
             }
             Commit::AluA { op, value } => Self::apply_alu(cpu, &op, value),
@@ -62,34 +59,6 @@ impl Cpu {
                     .set(Flags::CARRY, hl as u32 + value as u32 > 0xffff);
                 cpu.set_register16(Register16::Hl, hl.wrapping_add(value));
             }
-            Commit::AddSpOffset { offset } => {
-                let sp = cpu.stack_pointer;
-                let offset_u8 = offset as u8;
-                cpu.stack_pointer = sp.wrapping_add(offset as i16 as u16);
-                cpu.flags.remove(Flags::ZERO);
-                cpu.flags.remove(Flags::NEGATIVE);
-                cpu.flags.set(
-                    Flags::HALF_CARRY,
-                    (sp & 0xf) + (offset_u8 as u16 & 0xf) > 0xf,
-                );
-                cpu.flags
-                    .set(Flags::CARRY, (sp & 0xff) + (offset_u8 as u16 & 0xff) > 0xff);
-            }
-            Commit::LdHlSpOffset { offset } => {
-                let sp = cpu.stack_pointer;
-                let offset_u8 = offset as u8;
-                let result = sp.wrapping_add(offset as i16 as u16);
-                cpu.flags.remove(Flags::ZERO);
-                cpu.flags.remove(Flags::NEGATIVE);
-                cpu.flags.set(
-                    Flags::HALF_CARRY,
-                    (sp & 0xf) + (offset_u8 as u16 & 0xf) > 0xf,
-                );
-                cpu.flags
-                    .set(Flags::CARRY, (sp & 0xff) + (offset_u8 as u16 & 0xff) > 0xff);
-                cpu.set_register16(Register16::Hl, result);
-            }
-
             Commit::Daa => Self::apply_daa(cpu),
             Commit::CarryFlag(cf) => Self::apply_carry_flag(cpu, &cf),
             Commit::ComplementA => {
@@ -334,20 +303,8 @@ impl Cpu {
 
     pub(super) fn apply_rmw(cpu: &mut Cpu, op: &RmwOp, value: u8) -> u8 {
         match op {
-            RmwOp::Increment => {
-                let result = value.wrapping_add(1);
-                cpu.flags.set(Flags::ZERO, result == 0);
-                cpu.flags.remove(Flags::NEGATIVE);
-                cpu.flags.set(Flags::HALF_CARRY, result & 0b1111 == 0b0000);
-                result
-            }
-            RmwOp::Decrement => {
-                let result = value.wrapping_sub(1);
-                cpu.flags.set(Flags::ZERO, result == 0);
-                cpu.flags.insert(Flags::NEGATIVE);
-                cpu.flags.set(Flags::HALF_CARRY, result & 0b1111 == 0b1111);
-                result
-            }
+            RmwOp::Increment => Self::increment(cpu, value),
+            RmwOp::Decrement => Self::decrement(cpu, value),
             RmwOp::Rotate(direction, carry) => {
                 let (new_value, new_carry) = Self::rotate(cpu, value, direction, carry);
                 cpu.flags.set(Flags::ZERO, new_value == 0);
@@ -394,6 +351,24 @@ impl Cpu {
         }
     }
 
+    /// `INC r` and `INC [hl]`: H on a carry out of bit 3, C untouched.
+    fn increment(cpu: &mut Cpu, value: u8) -> u8 {
+        let result = value.wrapping_add(1);
+        cpu.flags.set(Flags::ZERO, result == 0);
+        cpu.flags.remove(Flags::NEGATIVE);
+        cpu.flags.set(Flags::HALF_CARRY, result & 0b1111 == 0b0000);
+        result
+    }
+
+    /// `DEC r` and `DEC [hl]`: H on a borrow from bit 4, C untouched.
+    fn decrement(cpu: &mut Cpu, value: u8) -> u8 {
+        let result = value.wrapping_sub(1);
+        cpu.flags.set(Flags::ZERO, result == 0);
+        cpu.flags.insert(Flags::NEGATIVE);
+        cpu.flags.set(Flags::HALF_CARRY, result & 0b1111 == 0b1111);
+        result
+    }
+
     pub(super) fn rotate(cpu: &Cpu, value: u8, direction: &Direction, carry: &Carry) -> (u8, bool) {
         let old_carry = cpu.flags.contains(Flags::CARRY);
         match (direction, carry) {
@@ -45,6 +45,22 @@ impl Cpu {
         }
     }
 
+    /// SP + e8 for `ADD SP,e8` and `LD HL,SP+e8`. The ALU adds the offset
+    /// byte to SP's low byte unsigned, so H and C are the carries out of
+    /// bits 3 and 7 of that byte add whatever the offset's sign; Z and N
+    /// are cleared.
+    fn sp_plus_offset(cpu: &mut Cpu, offset: i8) -> u16 {
+        let sp = cpu.stack_pointer;
+        let (low, offset_u8) = (sp as u8, offset as u8);
+        cpu.flags.remove(Flags::ZERO);
+        cpu.flags.remove(Flags::NEGATIVE);
+        cpu.flags
+            .set(Flags::HALF_CARRY, (low & 0xf) + (offset_u8 & 0xf) > 0xf);
+        cpu.flags
+            .set(Flags::CARRY, low.checked_add(offset_u8).is_none());
+        sp.wrapping_add(offset as i16 as u16)
+    }
+
     fn check_condition(cpu: &Cpu, condition: &Option<jump::Condition>) -> bool {
         if let Some(jump::Condition(flag, value)) = condition {
             cpu.flags.contains(flag.clone().into()) == *value
@@ -122,17 +138,7 @@ impl Cpu {
                 (Target16::Register(reg), Source16::StackPointerWithOffset(offset)) => {
                     // LD HL,SP+e8: decode-edge register + flags write
                     // (multi-cycle — Phase::InternalOp follows).
-                    let sp = cpu.stack_pointer;
-                    let offset_u8 = *offset as u8;
-                    let result = sp.wrapping_add(*offset as i16 as u16);
-                    cpu.flags.remove(Flags::ZERO);
-                    cpu.flags.remove(Flags::NEGATIVE);
-                    cpu.flags.set(
-                        Flags::HALF_CARRY,
-                        (sp & 0xf) + (offset_u8 as u16 & 0xf) > 0xf,
-                    );
-                    cpu.flags
-                        .set(Flags::CARRY, (sp & 0xff) + (offset_u8 as u16 & 0xff) > 0xff);
+                    let result = Self::sp_plus_offset(cpu, *offset);
                     cpu.set_register16(*reg, result);
                     (Phase::InternalOp { count: 1 }, Commit::NoOperation)
                 }
@@ -536,17 +542,7 @@ impl Cpu {
             Stack::Adjust(offset) => {
                 // ADD SP,e8: decode-edge SP + flags write (multi-cycle —
                 // two internal M-cycles follow).
-                let sp = cpu.stack_pointer;
-                let offset_u8 = *offset as u8;
-                cpu.stack_pointer = sp.wrapping_add(*offset as i16 as u16);
-                cpu.flags.remove(Flags::ZERO);
-                cpu.flags.remove(Flags::NEGATIVE);
-                cpu.flags.set(
-                    Flags::HALF_CARRY,
-                    (sp & 0xf) + (offset_u8 as u16 & 0xf) > 0xf,
-                );
-                cpu.flags
-                    .set(Flags::CARRY, (sp & 0xff) + (offset_u8 as u16 & 0xff) > 0xff);
+                cpu.stack_pointer = Self::sp_plus_offset(cpu, *offset);
                 (Phase::InternalOp { count: 2 }, Commit::NoOperation)
             }
         }
@@ -54,6 +54,14 @@ impl Cpu {
             return self.next_mcycle();
         }
 
+        if let Some(profile) = &mut self.profile {
+            profile.record(fetch_addr, opcode);
+        }
+        if let Some(mut entries) = self.trace_entries.take() {
+            entries.push(crate::trace_log::Entry::capture(self, fetch_addr));
+            self.trace_entries = Some(entries);
+        }
+
         if self.halt.bug {
             self.halt.bug = false;
         } else {
@@ -128,7 +128,7 @@ impl Cpu {
                 (Phase::Empty, Commit::EnterHalt)
             }
             Instruction::Stop => (Phase::Empty, Commit::EnterStop),
-            Instruction::Invalid(_) => (Phase::Empty, Commit::Invalid),
+            Instruction::Invalid(opcode) => (Phase::Empty, Commit::Invalid(*opcode)),
             Instruction::NoOperation => (Phase::Empty, Commit::NoOperation),
             Instruction::DecimalAdjustAccumulator => (Phase::Empty, Commit::Daa),
             Instruction::CarryFlag(cf) => (Phase::Empty, Commit::CarryFlag(cf.clone())),
@@ -7,7 +7,8 @@ use super::super::registers::{Register8, Register16};
 // ── Bus action ──────────────────────────────────────────────────────────
 
 /// What happens on the memory bus during one M-cycle.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum MCycleAction {
     /// Read a byte at the given address.
     Read { address: u16 },
@@ -28,6 +29,7 @@ pub(crate) enum MCycleAction {
 /// T-cycles per M-cycle). Driven by the master clock; ticked by the
 /// SM83's internal AFUR/ALEF/APUK/ADYK DFFs.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct TCycle(u8);
 
 impl TCycle {
@@ -57,6 +59,7 @@ impl TCycle {
 /// - **Internal**: `[Idle, Idle, Idle, Idle]`
 /// - **OamBug**:   `[InternalOamBug, Idle, Idle, Idle]`
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum BusAction {
     /// No bus transfer this T-cycle.
     Idle,
@@ -75,6 +78,7 @@ pub enum BusAction {
 
 /// ALU operation applied to A with a read value.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum AluOp {
     Add,
     Sub,
@@ -87,7 +91,8 @@ pub(crate) enum AluOp {
 }
 
 /// What to do after reading one byte from memory.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum ReadAction {
     /// Load into register.
     LoadRegister(Register8),
@@ -100,7 +105,8 @@ pub(crate) enum ReadAction {
 }
 
 /// What to do after popping 2 bytes from the stack.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum PopAction {
     /// Set a 16-bit register pair.
     SetRegister(Register16),
@@ -111,7 +117,8 @@ pub(crate) enum PopAction {
 }
 
 /// Read-modify-write operation on a memory byte.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum RmwOp {
     Increment,
     Decrement,
@@ -128,8 +135,9 @@ pub(crate) enum RmwOp {
 /// The behavior of the current instruction's post-decode M-cycles,
 /// expressed as a sequence of bus actions. The CPU walks through the
 /// phase yielding one `MCycleAction` per M-cycle via `next_mcycle()`.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[allow(private_interfaces)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum Phase {
     /// Read operand bytes, then decode and transition to the execution
     /// phase. The opcode has already been read in the Fetch CpuPhase.
@@ -207,7 +215,8 @@ pub(crate) enum Phase {
 /// The CPU's top-level execution phase. The CPU is a persistent state
 /// machine that continuously cycles through these phases, yielding one
 /// `BusAction` per T-cycle.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum CpuPhase {
     /// Generic fetch: reading opcode at [PC]. First M-cycle of every
     /// instruction, and the last M-cycle of the previous instruction
@@ -240,6 +249,7 @@ pub(crate) enum CpuPhase {
 /// the `set_mcyc7_n` force-path; halt release is combinational on
 /// `irq_latched.q↑` via `ykua → ynkw`.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum HaltPhase {
     /// Steady-state spin. Boundary captures `irq_latched`; on
     /// capture-true the next M-cycle is the m7-driven post-halt fetch
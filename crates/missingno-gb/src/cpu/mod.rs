@@ -15,6 +15,7 @@ pub mod mcycle;
 pub mod registers;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum InterruptMasterEnable {
     Disabled,
     Enabled,
@@ -25,6 +26,7 @@ pub enum InterruptMasterEnable {
 /// `irq_latched` (yoii) captures `(IF & IE) != 0`; lockup has no
 /// release path.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum HaltState {
     /// Normal execution — CPU fetches and executes instructions.
     Running,
@@ -47,6 +49,8 @@ pub enum HaltState {
 /// plus the four hardware-level flags that together drive HALT-bug
 /// behaviour, the data_phase_n gating during halt-spin, and the
 /// PPU's post-HALT-wake timing offset.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct HaltContext {
     pub state: HaltState,
     /// HALT-bug flag: set when HALT is decoded with IME=0 and an
@@ -91,6 +95,8 @@ impl Default for HaltContext {
 /// All CPU-side interrupt state apart from the dispatch chain itself.
 /// The IF/IE register file lives on `interrupts::Registers` (bus-side);
 /// this struct holds the latches inside the SM83 that gate it.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct IrqContext {
     /// IME flip-flop. Promoted from `ime_delay` at every M-cycle
     /// boundary — that staging produces EI's one-instruction delay.
@@ -110,6 +116,9 @@ pub struct IrqContext {
     /// LALU.r_n target). The reset is held across the dispatch window, so a
     /// PC-push that writes FF0F cannot re-set this bit.
     pub(super) irq_ack_held: Option<Interrupt>,
+    /// Where the most recent dispatch jumped: the serviced interrupt's
+    /// vector, or $0000 if the IE push bug cancelled it.
+    pub(super) last_vector: Option<u16>,
     /// Combinational `(IF & IE) != 0`. Coarse signal kept for the
     /// gbtrace adapter; dispatch reads the data-phase-gated
     /// `dispatch.latched()` instead.
@@ -132,6 +141,7 @@ impl IrqContext {
             pending_vector_resolve: false,
             cpu_irq_ack1_pulse: false,
             irq_ack_held: None,
+            last_vector: None,
             irq_pending: false,
             irq_latched: Dff::new(false),
             halt_wake_presample: false,
@@ -147,6 +157,8 @@ impl Default for IrqContext {
 
 /// The SM83 CPU. Owns register file, IME, halt state, and the
 /// state-machine fields that sequence each instruction's M-cycles.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cpu {
     pub a: u8,
     pub b: u8,
@@ -246,6 +258,13 @@ pub struct Cpu {
     /// priority chain → int_take → zaij → zkog/zloz → zfex → zacw.
     /// Owns the `data_phase_n` latch and the EI/DI block.
     pub dispatch: dispatch_chain::DispatchChain,
+    pub(crate) warnings: crate::warnings::Warnings,
+    /// Execution counts, while profiling is on.
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    pub(crate) profile: Option<Box<crate::profiler::Profile>>,
+    /// Instructions started since the console last wrote them to its trace
+    /// log, while tracing is on.
+    pub(crate) trace_entries: Option<Vec<crate::trace_log::Entry>>,
 }
 
 impl Cpu {
@@ -269,6 +288,27 @@ impl Cpu {
         Self::post_boot_with(0x01, 0x00, 0x13, 0x00, 0xd8, 0x01, 0x4d, flags)
     }
 
+    /// DMG0 (early DMG revision) post-boot register file. Its boot ROM
+    /// leaves the flags clear and B=$FF regardless of the header.
+    pub fn post_boot_dmg0() -> Cpu {
+        Self::post_boot_with(0x01, 0xff, 0x13, 0x00, 0xc1, 0x84, 0x03, Flags::empty())
+    }
+
+    /// MGB (Game Boy Pocket) post-boot register file: the DMG's, but A=$FF
+    /// so software can tell the two apart.
+    pub fn post_boot_mgb(checksum: u8) -> Cpu {
+        Cpu {
+            a: 0xff,
+            ..Self::post_boot(checksum)
+        }
+    }
+
+    /// SGB post-boot register file. The SGB boot ROM hands the header off
+    /// to the SNES, so nothing here depends on the checksum.
+    pub fn post_boot_sgb() -> Cpu {
+        Self::post_boot_with(0x01, 0x00, 0x14, 0x00, 0x00, 0xc0, 0x60, Flags::empty())
+    }
+
     /// CGB (CPU-CGB-C) post-boot register file. A=$11 signals CGB hardware
     /// to the cartridge; unlike DMG, the flags don't depend on the header
     /// checksum.
@@ -332,7 +372,8 @@ impl Cpu {
             stack_pointer: snap.sp,
             pc: snap.pc,
             ir_address: snap.pc,
-            flags: Flags::from_bits_retain(snap.f),
+            // Only the top nibble of F exists; a trace may not have masked it.
+            flags: Flags::from_bits_retain(snap.f & 0xF0),
             irq: IrqContext {
                 ime: Dff::new(if snap.ime {
                     InterruptMasterEnable::Enabled
@@ -394,6 +435,9 @@ impl Cpu {
             last_bus_action: BusAction::Idle,
             boundary_flag: true,
             dispatch: dispatch_chain::DispatchChain::new(),
+            warnings: crate::warnings::Warnings::new(),
+            profile: None,
+            trace_entries: None,
         }
     }
 
@@ -526,6 +570,13 @@ impl Cpu {
         self.irq.pending_vector_resolve
     }
 
+    /// Vector taken by the most recent interrupt dispatch, `None` if there
+    /// hasn't been one since reset. $0000 means the IE push bug cancelled
+    /// the dispatch after the PC push.
+    pub fn last_isr_vector(&self) -> Option<u16> {
+        self.irq.last_vector
+    }
+
     /// HALT-bug flag (gbtrace extension). See `HaltContext::bug`.
     pub fn halt_bug_flag(&self) -> bool {
         self.halt.bug
@@ -540,6 +591,12 @@ impl Cpu {
         self.halt.state == HaltState::Stopped
     }
 
+    /// Whether an illegal opcode has hard-locked the CPU. Only a reset
+    /// brings it back.
+    pub fn is_locked(&self) -> bool {
+        self.halt.state == HaltState::Locked
+    }
+
     /// The CPU is inside its interrupt-dispatch sequence — one indivisible
     /// bus tenure that a DMA grant waits behind.
     pub(crate) fn in_dispatch(&self) -> bool {
@@ -592,6 +649,39 @@ impl Cpu {
         self.boundary_flag = true;
     }
 
+    /// Continue execution at `address` instead, as a debugger setting PC.
+    /// Only possible at an instruction boundary, where the next opcode's
+    /// fetch has been issued but not yet latched (that happens at T3): the
+    /// fetch is re-aimed at `address`. Returns false, changing nothing,
+    /// mid-instruction, while halted or stopped, or while dispatching an
+    /// interrupt. [`Console::jump_to`](crate::Console::jump_to) also moves
+    /// the fetch on the bus.
+    pub(crate) fn jump_to(&mut self, address: u16) -> bool {
+        if !self.boundary_flag || self.halt.state != HaltState::Running {
+            return false;
+        }
+        let fetch_in_flight = match self.phase {
+            CpuPhase::Fetch if !self.mcycle_active => false,
+            CpuPhase::Execute {
+                phase: Phase::FetchOverlap { .. },
+                ..
+            } => true,
+            _ => return false,
+        };
+        if fetch_in_flight {
+            for action in [&mut self.current_action, &mut self.parked_action] {
+                if let Some(MCycleAction::Read { address: fetch }) = action {
+                    *fetch = address;
+                }
+            }
+        }
+        self.pc = address;
+        self.ir_address = address;
+        // The halt bug repeats the byte after HALT, which is no longer next.
+        self.halt.bug = false;
+        true
+    }
+
     /// Mark an instruction boundary so the step driver returns. Used by the
     /// held speed-switch blackout, which advances the master clock without
     /// stepping the SM83's own M-cycle state machine.
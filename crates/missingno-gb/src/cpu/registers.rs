@@ -1,6 +1,7 @@
 use core::fmt;
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Register8 {
     A,
     B,
@@ -30,6 +31,7 @@ impl fmt::Display for Register8 {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Register16 {
     Bc,
     De,
@@ -5,6 +5,8 @@
 /// driven by whichever peripheral's tri-state driver is enabled.
 /// The CPU latches the bus at `data_phase_n↑` near the end of
 /// T-cycle 3 of a read M-cycle.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct CpuBus {
     /// Current `cpu_port_d[7:0]` value. Driven at T-cycle 2; latched
     /// by the CPU at end of M-cycle.
@@ -14,6 +16,8 @@ pub struct CpuBus {
 
 /// What the CPU is doing on the bus this M-cycle. The CPU asserts
 /// either `cpu_rd` or `cpu_wr` per M-cycle, never both.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 enum Activity {
     Idle,
     /// Peripheral drives the bus at T-cycle 2; CPU latches at end of
@@ -173,6 +177,7 @@ pub struct BusAccess {
 
 /// Optional recording of every CPU/DMA bus access during a step.
 /// Allocation-free unless `enable` is called.
+#[derive(Clone)]
 pub struct BusTrace {
     entries: Option<Vec<BusAccess>>,
 }
@@ -0,0 +1,44 @@
+//! Opcodes that do nothing on hardware but that test ROMs and homebrew use
+//! to signal an emulator: `LD B,B` as a breakpoint (the Mealybug and BGB
+//! convention) and `LD D,D` ahead of a BGB-style debug message.
+
+use crate::{Console, Model};
+
+/// `LD B,B`.
+pub const BREAKPOINT: u8 = 0x40;
+/// `LD D,D`.
+pub const MESSAGE: u8 = 0x52;
+
+/// Which debug opcodes the debugger acts on. Both are off by default, since
+/// ordinary games execute these opcodes too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DebugOpcodes {
+    /// Halt on `LD B,B`, as if a breakpoint were set there.
+    pub breakpoint: bool,
+    /// Log the message following `LD D,D`.
+    pub messages: bool,
+}
+
+/// The BGB message at `address`, if one starts there:
+///
+/// ```text
+///     ld d,d
+///     jr .end
+///     dw $6464
+///     dw $0000
+///     db "message"
+/// .end:
+/// ```
+pub(super) fn message_at<M: Model>(console: &Console<M>, address: u16) -> Option<String> {
+    let byte = |offset: u16| console.peek(address.wrapping_add(offset));
+    let skip = byte(2);
+    if byte(0) != MESSAGE
+        || byte(1) != 0x18
+        || !(4..0x80).contains(&skip)
+        || (3..7).map(byte).ne([0x64, 0x64, 0x00, 0x00])
+    {
+        return None;
+    }
+    let text: Vec<u8> = (7..3 + u16::from(skip)).map(byte).collect();
+    Some(String::from_utf8_lossy(&text).into_owned())
+}
@@ -0,0 +1,383 @@
+use std::fmt;
+
+use crate::{Console, Model};
+
+/// A register a watch expression can name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Register {
+    A,
+    F,
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+    Af,
+    Bc,
+    De,
+    Hl,
+    Sp,
+    /// The address of the instruction being executed, as the debugger
+    /// shows it, rather than the prefetch address in `pc`.
+    Pc,
+}
+
+impl Register {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_uppercase().as_str() {
+            "A" => Self::A,
+            "F" => Self::F,
+            "B" => Self::B,
+            "C" => Self::C,
+            "D" => Self::D,
+            "E" => Self::E,
+            "H" => Self::H,
+            "L" => Self::L,
+            "AF" => Self::Af,
+            "BC" => Self::Bc,
+            "DE" => Self::De,
+            "HL" => Self::Hl,
+            "SP" => Self::Sp,
+            "PC" => Self::Pc,
+            _ => return None,
+        })
+    }
+
+    fn is_byte(self) -> bool {
+        matches!(
+            self,
+            Self::A | Self::F | Self::B | Self::C | Self::D | Self::E | Self::H | Self::L
+        )
+    }
+}
+
+/// A value to watch in the debugger: a register, a memory byte or a sum of
+/// them, e.g. `A`, `HL`, `[HL]`, `[$C000]` or `[SP+2]`.
+///
+/// Numbers are decimal unless written `$C000` or `0xC000`. Arithmetic wraps
+/// at 8 bits when both sides are bytes and at 16 bits otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expression {
+    Register(Register),
+    Number(u16),
+    /// The byte at an address, read without side effects.
+    Memory(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+    Subtract(Box<Expression>, Box<Expression>),
+}
+
+/// Why a watch expression can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    /// A token that doesn't belong where it is, or the end of the input.
+    Unexpected(String),
+    UnknownRegister(String),
+    /// A number that doesn't fit in 16 bits, or hex with no digits.
+    BadNumber(String),
+    /// A breakpoint condition with no `==`, `!=`, `<`, `<=`, `>` or `>=`.
+    MissingComparison,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty expression"),
+            ParseError::Unexpected(token) if token.is_empty() => {
+                write!(f, "unexpected end of expression")
+            }
+            ParseError::Unexpected(token) => write!(f, "unexpected \"{token}\""),
+            ParseError::UnknownRegister(name) => write!(f, "unknown register \"{name}\""),
+            ParseError::BadNumber(number) => write!(f, "bad number \"{number}\""),
+            ParseError::MissingComparison => write!(f, "expected a comparison such as =="),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Register(Register),
+    Number(u16),
+    Open,
+    Close,
+    Plus,
+    Minus,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '[' => Token::Open,
+            ']' => Token::Close,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '$' | '0'..='9' | 'a'..='z' | 'A'..='Z' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || (i == start && c == '$')) {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(word(&source[start..end])?);
+                continue;
+            }
+            c => return Err(ParseError::Unexpected(c.to_string())),
+        };
+        chars.next();
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn word(word: &str) -> Result<Token, ParseError> {
+    let bad_number = || ParseError::BadNumber(word.to_string());
+    let hex = word
+        .strip_prefix('$')
+        .or_else(|| word.strip_prefix("0x"))
+        .or_else(|| word.strip_prefix("0X"));
+    if let Some(digits) = hex {
+        u16::from_str_radix(digits, 16)
+            .map(Token::Number)
+            .map_err(|_| bad_number())
+    } else if word.starts_with(|c: char| c.is_ascii_digit()) {
+        word.parse().map(Token::Number).map_err(|_| bad_number())
+    } else {
+        Register::from_name(word)
+            .map(Token::Register)
+            .ok_or_else(|| ParseError::UnknownRegister(word.to_string()))
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn unexpected(&self) -> ParseError {
+        let token = match self.tokens.get(self.position.saturating_sub(1)) {
+            Some(Token::Register(register)) => format!("{register:?}").to_uppercase(),
+            Some(Token::Number(number)) => number.to_string(),
+            Some(Token::Open) => "[".to_string(),
+            Some(Token::Close) => "]".to_string(),
+            Some(Token::Plus) => "+".to_string(),
+            Some(Token::Minus) => "-".to_string(),
+            None => String::new(),
+        };
+        ParseError::Unexpected(token)
+    }
+
+    /// term (('+' | '-') term)*
+    fn sum(&mut self) -> Result<Expression, ParseError> {
+        let mut expression = self.term()?;
+        loop {
+            match self.tokens.get(self.position) {
+                Some(Token::Plus) => {
+                    self.position += 1;
+                    expression = Expression::Add(Box::new(expression), Box::new(self.term()?));
+                }
+                Some(Token::Minus) => {
+                    self.position += 1;
+                    expression = Expression::Subtract(Box::new(expression), Box::new(self.term()?));
+                }
+                _ => return Ok(expression),
+            }
+        }
+    }
+
+    /// register | number | '[' sum ']'
+    fn term(&mut self) -> Result<Expression, ParseError> {
+        match self.next() {
+            Some(Token::Register(register)) => Ok(Expression::Register(register)),
+            Some(Token::Number(number)) => Ok(Expression::Number(number)),
+            Some(Token::Open) => {
+                let address = self.sum()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(Expression::Memory(Box::new(address))),
+                    _ => Err(self.unexpected()),
+                }
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let expression = parser.sum()?;
+        if parser.position < parser.tokens.len() {
+            parser.position += 1;
+            return Err(parser.unexpected());
+        }
+        Ok(expression)
+    }
+
+    /// Whether the value fits a byte: 8-bit registers, memory reads, numbers
+    /// below $100 and arithmetic on those.
+    pub fn is_byte(&self) -> bool {
+        match self {
+            Expression::Register(register) => register.is_byte(),
+            Expression::Number(number) => *number <= 0xFF,
+            Expression::Memory(_) => true,
+            Expression::Add(left, right) | Expression::Subtract(left, right) => {
+                left.is_byte() && right.is_byte()
+            }
+        }
+    }
+
+    pub fn evaluate<M: Model>(&self, console: &Console<M>) -> u16 {
+        let cpu = console.cpu();
+        let value = match self {
+            Expression::Register(register) => match register {
+                Register::A => cpu.a.into(),
+                Register::F => cpu.flags.bits().into(),
+                Register::B => cpu.b.into(),
+                Register::C => cpu.c.into(),
+                Register::D => cpu.d.into(),
+                Register::E => cpu.e.into(),
+                Register::H => cpu.h.into(),
+                Register::L => cpu.l.into(),
+                Register::Af => u16::from_be_bytes([cpu.a, cpu.flags.bits()]),
+                Register::Bc => u16::from_be_bytes([cpu.b, cpu.c]),
+                Register::De => u16::from_be_bytes([cpu.d, cpu.e]),
+                Register::Hl => u16::from_be_bytes([cpu.h, cpu.l]),
+                Register::Sp => cpu.stack_pointer,
+                Register::Pc => cpu.ir_address,
+            },
+            Expression::Number(number) => *number,
+            Expression::Memory(address) => console.peek(address.evaluate(console)).into(),
+            Expression::Add(left, right) => {
+                left.evaluate(console).wrapping_add(right.evaluate(console))
+            }
+            Expression::Subtract(left, right) => {
+                left.evaluate(console).wrapping_sub(right.evaluate(console))
+            }
+        };
+        if self.is_byte() { value & 0xFF } else { value }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format!("{self:?}").to_uppercase())
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Register(register) => write!(f, "{register}"),
+            Expression::Number(number) if *number <= 0xFF => write!(f, "${number:02X}"),
+            Expression::Number(number) => write!(f, "${number:04X}"),
+            Expression::Memory(address) => write!(f, "[{address}]"),
+            Expression::Add(left, right) => write!(f, "{left}+{right}"),
+            Expression::Subtract(left, right) => write!(f, "{left}-{right}"),
+        }
+    }
+}
+
+/// How a [`BreakCondition`] compares its two sides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// Longer operators first, so `<=` isn't taken for `<`.
+    const OPERATORS: [(&str, Comparison); 6] = [
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    fn holds(self, left: u16, right: u16) -> bool {
+        match self {
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (operator, _) = Comparison::OPERATORS
+            .iter()
+            .find(|(_, comparison)| comparison == self)
+            .expect("every comparison has an operator");
+        write!(f, "{operator}")
+    }
+}
+
+/// What a conditional breakpoint tests when it's reached: two expressions
+/// compared, e.g. `A == $90`, `HL != $C000` or `[$FF44] >= 144`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreakCondition {
+    pub left: Expression,
+    pub comparison: Comparison,
+    pub right: Expression,
+}
+
+impl BreakCondition {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let (at, operator, comparison) = Comparison::OPERATORS
+            .iter()
+            .filter_map(|&(operator, comparison)| {
+                source.find(operator).map(|at| (at, operator, comparison))
+            })
+            .min_by_key(|&(at, operator, _)| (at, std::cmp::Reverse(operator.len())))
+            .ok_or(ParseError::MissingComparison)?;
+        Ok(Self {
+            left: Expression::parse(&source[..at])?,
+            comparison,
+            right: Expression::parse(&source[at + operator.len()..])?,
+        })
+    }
+
+    pub fn holds<M: Model>(&self, console: &Console<M>) -> bool {
+        self.comparison
+            .holds(self.left.evaluate(console), self.right.evaluate(console))
+    }
+}
+
+impl fmt::Display for BreakCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.comparison, self.right)
+    }
+}
@@ -1,16 +1,26 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 #[cfg(feature = "gbtrace")]
 use std::path::Path;
 
 use crate::{
-    Console, Dmg, Model,
-    cpu::instructions::Instruction,
+    ClockPhase, Console, Dmg, Model,
+    cpu::instructions::{Instruction, Jump},
     cpu_bus::{BusAccess, BusAccessKind},
     ppu::{self, rendering::Mode},
 };
+use debug_opcodes::DebugOpcodes;
+use expression::BreakCondition;
 use instructions::InstructionsIterator;
+use rewind::Rewind;
+use symbols::{SymbolError, Symbols};
+use tile_changes::TileChanges;
 
+pub mod debug_opcodes;
+pub mod expression;
 pub mod instructions;
+pub mod rewind;
+pub mod symbols;
+pub mod tile_changes;
 
 /// Embedded profile for full T-cycle frame capture with all PPU details.
 #[cfg(feature = "gbtrace")]
@@ -42,6 +52,7 @@ pub enum CpuRegister {
 pub enum WatchCondition {
     BusRead { address: u16 },
     BusWrite { address: u16 },
+    BusAccess { address: u16 },
     DmaRead { address: u16 },
     DmaWrite { address: u16 },
     Scanline(u8),
@@ -57,6 +68,7 @@ impl WatchCondition {
         match self {
             WatchCondition::BusRead { .. }
             | WatchCondition::BusWrite { .. }
+            | WatchCondition::BusAccess { .. }
             | WatchCondition::DmaRead { .. }
             | WatchCondition::DmaWrite { .. } => true,
             WatchCondition::All(conditions) => conditions.iter().any(|c| c.needs_bus_trace()),
@@ -65,14 +77,59 @@ impl WatchCondition {
     }
 }
 
+/// Halts execution when the PPU reaches line `ly` and, if given, `dot` within
+/// it. `ly` is the internal line (153 stays 153 rather than reading as 0),
+/// and `dot` counts 0-455 from the start of the line, as in
+/// [`Ppu::ppu_position`](crate::ppu::Ppu::ppu_position). It fires on the
+/// phase the position is reached, so the CPU is left mid-instruction; the
+/// next step finishes it. While one is set, `step_frame` advances a phase at
+/// a time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScanlineBreakpoint {
+    pub ly: u8,
+    pub dot: Option<u16>,
+}
+
+impl ScanlineBreakpoint {
+    /// Whether the PPU passed this position moving from `before` to `now`.
+    fn reached(&self, before: (u8, u16), now: (u8, u16)) -> bool {
+        let target = (self.ly, self.dot.unwrap_or(0));
+        if now < before {
+            // A new frame, or the LCD switched back on.
+            target <= now
+        } else {
+            before < target && target <= now
+        }
+    }
+}
+
 pub struct Debugger<M: Model = Dmg> {
     game_boy: Console<M>,
     breakpoints: BTreeSet<u16>,
+    /// Conditions on some of `breakpoints`, which only stop execution when
+    /// their condition holds.
+    breakpoint_conditions: BTreeMap<u16, BreakCondition>,
+    /// One-shot breakpoint for step over and run to cursor, cleared by the
+    /// next stop whatever causes it.
+    temporary_breakpoint: Option<u16>,
     watchpoints: Vec<WatchCondition>,
     last_watchpoint_hit: Option<WatchCondition>,
+    scanline_breakpoints: BTreeSet<ScanlineBreakpoint>,
+    /// PPU (LY, dot) at the last scanline-breakpoint check, so a breakpoint
+    /// fires once on arrival rather than on every phase spent there.
+    ppu_position: (u8, u16),
     /// T-cycle counter. Increments once per dot. Not hardware state —
     /// debugging/tracing infrastructure built on top of the emulation core.
     tcycle_count: u64,
+    tile_changes: TileChanges,
+    debug_opcodes: DebugOpcodes,
+    /// Instruction address at the last debug-opcode check, so an opcode is
+    /// acted on once per execution rather than on every phase spent there.
+    debug_opcode_address: u16,
+    debug_breakpoint_hit: bool,
+    debug_messages: Vec<String>,
+    rewind: Rewind<M>,
+    symbols: Symbols,
 }
 
 impl<M: Model> Debugger<M> {
@@ -80,9 +137,20 @@ impl<M: Model> Debugger<M> {
         Self {
             game_boy,
             breakpoints: BTreeSet::new(),
+            breakpoint_conditions: BTreeMap::new(),
+            temporary_breakpoint: None,
             watchpoints: Vec::new(),
             last_watchpoint_hit: None,
+            scanline_breakpoints: BTreeSet::new(),
+            ppu_position: (0, 0),
             tcycle_count: 0,
+            tile_changes: TileChanges::default(),
+            debug_opcodes: DebugOpcodes::default(),
+            debug_opcode_address: 0,
+            debug_breakpoint_hit: false,
+            debug_messages: Vec::new(),
+            rewind: Rewind::new(rewind::DEFAULT_INTERVAL),
+            symbols: Symbols::default(),
         }
     }
 
@@ -98,89 +166,206 @@ impl<M: Model> Debugger<M> {
         self.game_boy
     }
 
+    /// Move the breakpoints, watchpoints and symbols onto another console,
+    /// e.g. one running a rebuilt ROM. The T-cycle count starts again from zero, and
+    /// rewind snapshots of the old console are dropped.
+    pub fn with_game_boy<N: Model>(self, game_boy: Console<N>) -> Debugger<N> {
+        Debugger {
+            game_boy,
+            breakpoints: self.breakpoints,
+            breakpoint_conditions: self.breakpoint_conditions,
+            temporary_breakpoint: None,
+            watchpoints: self.watchpoints,
+            last_watchpoint_hit: None,
+            scanline_breakpoints: self.scanline_breakpoints,
+            ppu_position: (0, 0),
+            tcycle_count: 0,
+            tile_changes: TileChanges::default(),
+            debug_opcodes: self.debug_opcodes,
+            debug_opcode_address: 0,
+            debug_breakpoint_hit: false,
+            debug_messages: Vec::new(),
+            rewind: Rewind::new(self.rewind.interval()),
+            symbols: self.symbols,
+        }
+    }
+
     pub fn tcycle_count(&self) -> u64 {
         self.tcycle_count
     }
 
+    /// Tiles that changed between the last two completed frames.
+    pub fn changed_tiles_since_last_frame(&self) -> &TileChanges {
+        &self.tile_changes
+    }
+
+    /// A frame just completed: snapshot the tiles and hand back the screen.
+    fn frame_completed(&mut self) -> M::Screen {
+        self.tile_changes.frame_completed(self.game_boy.vram());
+        self.rewind
+            .frame_completed(&self.game_boy, self.tcycle_count);
+        self.game_boy.screen().clone()
+    }
+
     pub fn step(&mut self) -> Option<M::Screen> {
         let result = self.game_boy.step();
         self.tcycle_count += result.tcycles as u64;
         if result.new_screen {
-            Some(self.game_boy.screen().clone())
+            Some(self.frame_completed())
         } else {
             None
         }
     }
 
     pub fn step_phase(&mut self) -> Option<M::Screen> {
-        if self.game_boy.step_phase().new_screen {
-            Some(self.game_boy.screen().clone())
+        self.step_phase_traced(false).0
+    }
+
+    /// Step one phase, returning its bus accesses when `trace` is set.
+    fn step_phase_traced(&mut self, trace: bool) -> (Option<M::Screen>, Vec<BusAccess>) {
+        let (result, accesses) = self.game_boy.step_phase_traced(trace);
+        // The phase that leaves a rise next finishes a T-cycle.
+        if self.game_boy.clock_phase() == ClockPhase::Low {
+            self.tcycle_count += 1;
+        }
+        let screen = if result.new_screen {
+            Some(self.frame_completed())
         } else {
             None
-        }
+        };
+        (screen, accesses)
     }
 
     pub fn step_tcycle(&mut self) -> Option<M::Screen> {
         self.tcycle_count += 1;
         if self.game_boy.step_tcycle() {
-            Some(self.game_boy.screen().clone())
+            Some(self.frame_completed())
         } else {
             None
         }
     }
 
+    /// Run until the instruction after this one is reached, so a `CALL`
+    /// runs its whole subroutine. Stops early at a breakpoint.
     pub fn step_over(&mut self) -> Option<M::Screen> {
         let mut it = InstructionsIterator::new(self.game_boy.cpu().ir_address, &self.game_boy);
         Instruction::decode(&mut it);
         let next_address = it.address.unwrap();
+        self.run_to(next_address)
+    }
 
-        let temp_breakpoint = if self.breakpoints.contains(&next_address) {
-            None
-        } else {
-            Some(next_address)
-        };
+    /// Run until the CPU reaches `address`, however many frames that takes.
+    /// Stops early at a breakpoint. Blocks until it gets there: front-ends
+    /// that keep drawing frames should
+    /// [`set_temporary_breakpoint`](Self::set_temporary_breakpoint) and
+    /// [`step_frame`](Self::step_frame) instead.
+    pub fn run_to(&mut self, address: u16) -> Option<M::Screen> {
+        self.set_temporary_breakpoint(address);
+        let mut last_screen = None;
+        while let Some(screen) = self.step_frame() {
+            last_screen = Some(screen);
+        }
+        last_screen
+    }
+
+    /// Break once at `address`: the next stop, there or anywhere else,
+    /// clears it.
+    pub fn set_temporary_breakpoint(&mut self, address: u16) {
+        self.temporary_breakpoint = Some(address);
+    }
+
+    pub fn clear_temporary_breakpoint(&mut self) {
+        self.temporary_breakpoint = None;
+    }
+
+    pub fn temporary_breakpoint(&self) -> Option<u16> {
+        self.temporary_breakpoint
+    }
+
+    /// Continue from `address` instead of the next instruction. Only
+    /// possible between instructions, so returns false after a
+    /// [`step_tcycle`](Self::step_tcycle) or
+    /// [`step_phase`](Self::step_phase) left one half-done, and while the CPU
+    /// is halted or stopped.
+    pub fn set_pc(&mut self, address: u16) -> bool {
+        self.game_boy.jump_to(address)
+    }
 
+    /// Change one byte of memory, as a memory editor does. Only VRAM, OAM,
+    /// work RAM and high RAM can be written; see
+    /// [`poke_raw`](Console::poke_raw). Returns whether the byte was stored.
+    pub fn write_memory(&mut self, address: u16, value: u8) -> bool {
+        self.game_boy.poke_raw(address, value)
+    }
+
+    /// Run until the current subroutine returns: a return instruction that
+    /// lifts the stack pointer above where it stood on entry. Returns from
+    /// deeper calls, recursive ones included, and from interrupt handlers
+    /// only bring it back to that level, so they don't stop here. Stops
+    /// early at a breakpoint.
+    pub fn step_out(&mut self) -> Option<M::Screen> {
+        self.last_watchpoint_hit = None;
+        self.debug_breakpoint_hit = false;
+        let entry_stack_pointer = self.game_boy.cpu().stack_pointer;
         let mut last_screen = None;
 
         loop {
-            let screen = self.step_frame();
-            match screen {
-                Some(screen) => {
-                    last_screen = Some(screen);
-                }
-                None => {
-                    break;
-                }
+            let returning = self.at_return();
+            if let Some(screen) = self.step() {
+                last_screen = Some(screen);
+            }
+            if returning && self.game_boy.cpu().stack_pointer > entry_stack_pointer {
+                break;
+            }
+            let debug_break = self.debug_opcode_triggered();
+            if debug_break || self.breakpoint_triggered() {
+                break;
             }
-        }
-
-        if let Some(temp_breakpoint) = temp_breakpoint {
-            self.breakpoints.remove(&temp_breakpoint);
         }
 
         last_screen
     }
 
+    /// Whether the next instruction is a `RET`, `RET cc` or `RETI`.
+    fn at_return(&self) -> bool {
+        let mut it = InstructionsIterator::new(self.game_boy.cpu().ir_address, &self.game_boy);
+        matches!(
+            Instruction::decode(&mut it),
+            Some(Instruction::Jump(
+                Jump::Return(_) | Jump::ReturnAndEnableInterrupts
+            ))
+        )
+    }
+
     pub fn step_frame(&mut self) -> Option<M::Screen> {
         self.last_watchpoint_hit = None;
-        if self.watchpoints.is_empty() {
+        self.debug_breakpoint_hit = false;
+        self.ppu_position = self.current_ppu_position();
+        let screen = if self.watchpoints.is_empty() && self.scanline_breakpoints.is_empty() {
             self.step_frame_simple()
         } else {
             self.step_frame_watched()
+        };
+        if screen.is_none() {
+            self.temporary_breakpoint = None;
         }
+        screen
     }
 
     fn step_frame_simple(&mut self) -> Option<M::Screen> {
         loop {
             let screen = self.step();
-            if screen.is_some() || self.breakpoint_triggered() {
+            let debug_break = self.debug_opcode_triggered();
+            if screen.is_some() || debug_break || self.breakpoint_triggered() {
                 return screen;
             }
         }
     }
 
     fn step_frame_watched(&mut self) -> Option<M::Screen> {
-        if self.watchpoints.iter().any(|w| w.needs_bus_trace()) {
+        if self.scanline_breakpoints.is_empty()
+            && self.watchpoints.iter().any(|w| w.needs_bus_trace())
+        {
             self.step_frame_watched_traced()
         } else {
             self.step_frame_watched_dots()
@@ -192,7 +377,7 @@ impl<M: Model> Debugger<M> {
             let (result, trace) = self.game_boy.step_traced(true);
             self.tcycle_count += result.tcycles as u64;
             let screen = if result.new_screen {
-                Some(self.game_boy.screen().clone())
+                Some(self.frame_completed())
             } else {
                 None
             };
@@ -202,29 +387,80 @@ impl<M: Model> Debugger<M> {
                 return screen;
             }
 
-            if screen.is_some() || self.breakpoint_triggered() {
+            let debug_break = self.debug_opcode_triggered();
+            if screen.is_some()
+                || debug_break
+                || self.breakpoint_triggered()
+                || self.scanline_breakpoint_triggered()
+            {
                 return screen;
             }
         }
     }
 
     fn step_frame_watched_dots(&mut self) -> Option<M::Screen> {
+        let trace = self.watchpoints.iter().any(|w| w.needs_bus_trace());
         loop {
-            let screen = self.step_phase();
+            let (screen, accesses) = self.step_phase_traced(trace);
 
-            if let Some(hit) = self.check_watchpoints(&[]) {
+            if let Some(hit) = self.check_watchpoints(&accesses) {
                 self.last_watchpoint_hit = Some(hit);
                 return screen;
             }
 
-            if screen.is_some() || self.breakpoint_triggered() {
+            let debug_break = self.debug_opcode_triggered();
+            if screen.is_some()
+                || debug_break
+                || self.breakpoint_triggered()
+                || self.scanline_breakpoint_triggered()
+            {
                 return screen;
             }
         }
     }
 
     fn breakpoint_triggered(&self) -> bool {
-        self.breakpoints.contains(&self.game_boy.cpu().ir_address)
+        let address = self.game_boy.cpu().ir_address;
+        let breakpoint = self.breakpoints.contains(&address)
+            && self
+                .breakpoint_conditions
+                .get(&address)
+                .is_none_or(|condition| condition.holds(&self.game_boy));
+        breakpoint || self.temporary_breakpoint == Some(address)
+    }
+
+    /// Act on a debug opcode the CPU has just reached. True when it should
+    /// halt.
+    fn debug_opcode_triggered(&mut self) -> bool {
+        let address = self.game_boy.cpu().ir_address;
+        if std::mem::replace(&mut self.debug_opcode_address, address) == address {
+            return false;
+        }
+        match self.game_boy.peek(address) {
+            debug_opcodes::BREAKPOINT if self.debug_opcodes.breakpoint => {
+                self.debug_breakpoint_hit = true;
+                true
+            }
+            debug_opcodes::MESSAGE if self.debug_opcodes.messages => {
+                if let Some(message) = debug_opcodes::message_at(&self.game_boy, address) {
+                    self.debug_messages.push(message);
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn current_ppu_position(&self) -> (u8, u16) {
+        self.game_boy.ppu().ppu_position()
+    }
+
+    fn scanline_breakpoint_triggered(&mut self) -> bool {
+        let now = self.current_ppu_position();
+        let before = std::mem::replace(&mut self.ppu_position, now);
+        self.scanline_breakpoints
+            .iter()
+            .any(|bp| bp.reached(before, now))
     }
 
     fn check_watchpoints(&self, trace: &[BusAccess]) -> Option<WatchCondition> {
@@ -247,6 +483,10 @@ impl<M: Model> Debugger<M> {
             WatchCondition::BusWrite { address } => trace
                 .iter()
                 .any(|a| a.kind == BusAccessKind::Write && a.address == *address),
+            WatchCondition::BusAccess { address } => trace.iter().any(|a| {
+                matches!(a.kind, BusAccessKind::Read | BusAccessKind::Write)
+                    && a.address == *address
+            }),
             WatchCondition::DmaRead { address } => trace
                 .iter()
                 .any(|a| a.kind == BusAccessKind::DmaRead && a.address == *address),
@@ -285,21 +525,124 @@ impl<M: Model> Debugger<M> {
         self.last_watchpoint_hit.as_ref()
     }
 
+    pub fn debug_opcodes(&self) -> DebugOpcodes {
+        self.debug_opcodes
+    }
+
+    pub fn set_debug_opcodes(&mut self, debug_opcodes: DebugOpcodes) {
+        self.debug_opcodes = debug_opcodes;
+    }
+
+    /// Whether the last [`step_frame`](Self::step_frame) stopped on an
+    /// `LD B,B`.
+    pub fn debug_breakpoint_hit(&self) -> bool {
+        self.debug_breakpoint_hit
+    }
+
+    /// `LD D,D` messages logged since the last call, oldest first.
+    pub fn take_debug_messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.debug_messages)
+    }
+
     pub fn reset(&mut self) {
         self.game_boy.reset();
         self.tcycle_count = 0;
+        self.rewind.clear();
+    }
+
+    /// Go back to the newest rewind snapshot that's behind the console; see
+    /// [`Rewind`] for how far that is. Returns false when there's none.
+    pub fn rewind(&mut self) -> bool {
+        let Some((mut game_boy, tcycle_count)) = self.rewind.restore(self.tcycle_count) else {
+            return false;
+        };
+        game_boy.audio_mut().channel_mask = self.game_boy.audio().channel_mask;
+        self.game_boy = game_boy;
+        self.tcycle_count = tcycle_count;
+        self.temporary_breakpoint = None;
+        self.last_watchpoint_hit = None;
+        self.debug_breakpoint_hit = false;
+        self.ppu_position = self.current_ppu_position();
+        self.debug_opcode_address = self.game_boy.cpu().ir_address;
+        true
+    }
+
+    /// The rewind buffer, to see how many snapshots it holds or change how
+    /// often they're taken.
+    pub fn rewind_buffer(&self) -> &Rewind<M> {
+        &self.rewind
+    }
+
+    pub fn rewind_buffer_mut(&mut self) -> &mut Rewind<M> {
+        &mut self.rewind
+    }
+
+    /// Replace the symbols with those in the `.sym` file at `path`. On an
+    /// error the current symbols are kept.
+    pub fn load_symbols(&mut self, path: impl AsRef<std::path::Path>) -> Result<(), SymbolError> {
+        self.symbols = Symbols::load(path)?;
+        Ok(())
+    }
+
+    pub fn symbols(&self) -> &Symbols {
+        &self.symbols
+    }
+
+    pub fn set_symbols(&mut self, symbols: Symbols) {
+        self.symbols = symbols;
+    }
+
+    /// The label at `address` as the console sees it now. Switchable ROM is
+    /// looked up in the bank that's mapped in; RAM, I/O and ROM from an MBC
+    /// the debugger can't follow match the address in any bank.
+    pub fn symbol(&self, address: u16) -> Option<&str> {
+        let bank = match address {
+            0x0000..=0x3FFF => Some(0),
+            0x4000..=0x7FFF => self.game_boy.cartridge().rom_bank(),
+            _ => None,
+        };
+        self.symbols.get(bank.map(|bank| bank as u16), address)
     }
 
     pub fn breakpoints(&self) -> &BTreeSet<u16> {
         &self.breakpoints
     }
 
+    /// Stop whenever `address` is reached. Replaces any condition already
+    /// set there.
     pub fn set_breakpoint(&mut self, address: u16) {
         self.breakpoints.insert(address);
+        self.breakpoint_conditions.remove(&address);
+    }
+
+    /// Stop when `address` is reached with `condition` true.
+    pub fn set_conditional_breakpoint(&mut self, address: u16, condition: BreakCondition) {
+        self.breakpoints.insert(address);
+        self.breakpoint_conditions.insert(address, condition);
+    }
+
+    /// The condition on the breakpoint at `address`, if it has one.
+    pub fn breakpoint_condition(&self, address: u16) -> Option<&BreakCondition> {
+        self.breakpoint_conditions.get(&address)
     }
 
     pub fn clear_breakpoint(&mut self, address: u16) {
         self.breakpoints.remove(&address);
+        self.breakpoint_conditions.remove(&address);
+    }
+
+    pub fn scanline_breakpoints(&self) -> &BTreeSet<ScanlineBreakpoint> {
+        &self.scanline_breakpoints
+    }
+
+    pub fn set_scanline_breakpoint(&mut self, ly: u8, dot: Option<u16>) {
+        self.scanline_breakpoints
+            .insert(ScanlineBreakpoint { ly, dot });
+    }
+
+    pub fn clear_scanline_breakpoint(&mut self, ly: u8, dot: Option<u16>) {
+        self.scanline_breakpoints
+            .remove(&ScanlineBreakpoint { ly, dot });
     }
 
     pub fn watchpoints(&self) -> &[WatchCondition] {
@@ -368,6 +711,6 @@ impl<M: Model> Debugger<M> {
             .finish()
             .map_err(|e| format!("Failed to finish trace: {e}"))?;
 
-        Ok(self.game_boy.screen().clone())
+        Ok(self.frame_completed())
     }
 }
@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+
+use crate::{Console, Model};
+
+/// Frames between snapshots unless [`Rewind::set_interval`] says otherwise:
+/// one a second.
+pub const DEFAULT_INTERVAL: u32 = 60;
+
+/// Memory the snapshots may take between them.
+pub const MEMORY_BUDGET: usize = 64 * 1024 * 1024;
+
+struct Snapshot<M: Model> {
+    /// Frames completed when it was taken.
+    frame: u64,
+    /// The debugger's T-cycle count when it was taken.
+    tcycle_count: u64,
+    console: Console<M>,
+}
+
+/// Snapshots of the console taken every few frames as the debugger runs,
+/// newest last, so it can step back in time.
+///
+/// Snapshots are only taken as a frame completes, so going back lands on
+/// the last frame boundary a snapshot was taken at: up to `interval` frames
+/// back, not one instruction. Anything finer has to be stepped forward to
+/// again. The oldest snapshots are dropped to stay within [`MEMORY_BUDGET`].
+pub struct Rewind<M: Model> {
+    snapshots: VecDeque<Snapshot<M>>,
+    /// Frames between snapshots. Zero takes none.
+    interval: u32,
+    /// Frames completed so far, to count out the interval.
+    frame: u64,
+}
+
+impl<M: Model> Rewind<M> {
+    pub fn new(interval: u32) -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            interval,
+            frame: 0,
+        }
+    }
+
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+
+    /// Take a snapshot every `interval` frames from now on; zero stops
+    /// taking them. Existing snapshots are kept.
+    pub fn set_interval(&mut self, interval: u32) {
+        self.interval = interval;
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.frame = 0;
+    }
+
+    /// A frame has just completed on `console`.
+    pub(super) fn frame_completed(&mut self, console: &Console<M>, tcycle_count: u64) {
+        self.frame += 1;
+        if self.interval == 0 || !self.frame.is_multiple_of(self.interval.into()) {
+            return;
+        }
+        while self.snapshots.len() >= capacity(console) {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(Snapshot {
+            frame: self.frame,
+            tcycle_count,
+            console: console.clone(),
+        });
+    }
+
+    /// The console and T-cycle count from the newest snapshot taken before
+    /// `tcycle_count`, skipping one that nothing has run since. Newer ones
+    /// are dropped: execution carries on from here and takes them afresh.
+    pub(super) fn restore(&mut self, tcycle_count: u64) -> Option<(Console<M>, u64)> {
+        while self
+            .snapshots
+            .back()
+            .is_some_and(|snapshot| snapshot.tcycle_count >= tcycle_count)
+        {
+            self.snapshots.pop_back();
+        }
+        let snapshot = self.snapshots.back()?;
+        self.frame = snapshot.frame;
+        Some((snapshot.console.clone(), snapshot.tcycle_count))
+    }
+}
+
+/// Snapshots that fit in [`MEMORY_BUDGET`]: each costs the console itself
+/// plus what it owns on the heap.
+fn capacity<M: Model>(console: &Console<M>) -> usize {
+    let size = std::mem::size_of::<Console<M>>() + console.heap_size();
+    (MEMORY_BUDGET / size).max(1)
+}
@@ -0,0 +1,103 @@
+use std::{collections::BTreeMap, fmt};
+
+/// Names for addresses, from a no$gmb/RGBDS `.sym` file: one `BB:AAAA name`
+/// line per label, with the bank and address in hex.
+///
+/// ```text
+/// ; File generated by rgblink
+/// 00:0150 Main
+/// 01:4000 LoadLevel
+/// 00:c000 wPlayerX
+/// ```
+///
+/// Blank lines and anything after a `;` are ignored. Where several labels
+/// share an address, the first is used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Symbols {
+    banked: BTreeMap<(u16, u16), String>,
+    /// The first label at each address in any bank, for regions the debugger
+    /// doesn't track the bank of.
+    unbanked: BTreeMap<u16, String>,
+}
+
+/// Why a symbol file can't be loaded.
+#[derive(Debug)]
+pub enum SymbolError {
+    Read(std::io::Error),
+    /// A line that isn't `BB:AAAA name`. Lines count from 1.
+    Malformed {
+        line: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for SymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymbolError::Read(error) => write!(f, "{error}"),
+            SymbolError::Malformed { line, text } => {
+                write!(
+                    f,
+                    "line {line}: expected \"BB:AAAA name\", found \"{text}\""
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SymbolError {}
+
+impl Symbols {
+    pub fn parse(source: &str) -> Result<Self, SymbolError> {
+        let mut symbols = Self::default();
+        for (index, line) in source.lines().enumerate() {
+            let content = line.split(';').next().unwrap_or_default().trim();
+            if content.is_empty() {
+                continue;
+            }
+            let malformed = || SymbolError::Malformed {
+                line: index + 1,
+                text: line.trim().to_string(),
+            };
+            let (location, name) = content
+                .split_once(char::is_whitespace)
+                .ok_or_else(malformed)?;
+            let (bank, address) = location.split_once(':').ok_or_else(malformed)?;
+            let bank = u16::from_str_radix(bank, 16).map_err(|_| malformed())?;
+            let address = u16::from_str_radix(address, 16).map_err(|_| malformed())?;
+            symbols.insert(bank, address, name.trim());
+        }
+        Ok(symbols)
+    }
+
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, SymbolError> {
+        Self::parse(&std::fs::read_to_string(path).map_err(SymbolError::Read)?)
+    }
+
+    fn insert(&mut self, bank: u16, address: u16, name: &str) {
+        self.banked
+            .entry((bank, address))
+            .or_insert_with(|| name.to_string());
+        self.unbanked
+            .entry(address)
+            .or_insert_with(|| name.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.banked.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.banked.is_empty()
+    }
+
+    /// The label at `address` in `bank`, or in any bank when `bank` is
+    /// `None`.
+    pub fn get(&self, bank: Option<u16>, address: u16) -> Option<&str> {
+        match bank {
+            Some(bank) => self.banked.get(&(bank, address)),
+            None => self.unbanked.get(&address),
+        }
+        .map(String::as_str)
+    }
+}
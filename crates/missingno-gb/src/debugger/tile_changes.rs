@@ -0,0 +1,50 @@
+use crate::ppu::{
+    memory::Vram,
+    types::tiles::{TileBlock, TileBlockId, TileIndex},
+};
+
+/// Which tiles changed over the last completed frame, for spotting animated
+/// or streamed graphics. Compares a copy of the tile data taken at each
+/// frame boundary, so the cost is one snapshot per frame.
+#[derive(Clone, Default)]
+pub struct TileChanges {
+    /// Tile blocks per VRAM bank at the last frame boundary.
+    snapshot: Vec<[TileBlock; 3]>,
+    /// Per bank and block, bit `n` set when tile `n` changed.
+    changed: Vec<[u128; 3]>,
+}
+
+impl TileChanges {
+    /// Diff against the previous frame's tiles and keep these for the next.
+    /// The first frame has nothing to compare against and reports no changes.
+    pub(super) fn frame_completed(&mut self, vram: &impl Vram) {
+        let tiles: Vec<[TileBlock; 3]> = (0..vram.bank_count())
+            .map(|bank| {
+                std::array::from_fn(|block| *vram.bank(bank).tile_block(TileBlockId(block as u8)))
+            })
+            .collect();
+        self.changed = if self.snapshot.len() == tiles.len() {
+            tiles
+                .iter()
+                .zip(&self.snapshot)
+                .map(|(now, before)| {
+                    std::array::from_fn(|block| now[block].changed_tiles(&before[block]))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.snapshot = tiles;
+    }
+
+    /// Bit `n` is set when tile `n` of `block` changed.
+    pub fn block(&self, bank: u8, block: TileBlockId) -> u128 {
+        self.changed
+            .get(bank as usize)
+            .map_or(0, |blocks| blocks[block.0 as usize])
+    }
+
+    pub fn is_changed(&self, bank: u8, block: TileBlockId, index: TileIndex) -> bool {
+        self.block(bank, block) & 1 << index.0 != 0
+    }
+}
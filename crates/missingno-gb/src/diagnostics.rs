@@ -0,0 +1,163 @@
+//! Running test ROMs to a verdict: blargg-style serial output, mooneye's
+//! register signature, and the completion loops both end in. Shared by the
+//! accuracy tests and the app's test runner.
+
+use crate::{
+    Console, Model,
+    cpu::{Cpu, HaltState},
+    execute::StepResult,
+    interrupts,
+};
+
+/// Common interface for a Game Boy–family console the runners here drive.
+/// Implemented by every [`Console`] and by the accuracy tests' traced runs.
+///
+/// `screen()` deliberately isn't on this trait — the DMG screen stores
+/// 2-bit shade indices while the CGB screen stores RGB pixels, so
+/// callers needing screenshot comparison go through the concrete type.
+pub trait System {
+    fn step(&mut self) -> StepResult;
+    fn read(&self, address: u16) -> u8;
+    fn cpu(&self) -> &Cpu;
+    fn cpu_mut(&mut self) -> &mut Cpu;
+    fn drain_serial_output(&mut self) -> Vec<u8>;
+    fn interrupts(&self) -> &interrupts::Registers;
+    /// True while a CGB double-speed switch holds the CPU stopped in its
+    /// settling blackout (a self-resuming STOP). Defaults false for systems
+    /// without a speed switch.
+    fn speed_switch_in_progress(&self) -> bool {
+        false
+    }
+    /// True while a VRAM DMA holds the CPU (the bus master's hold, not a
+    /// software STOP/HALT). Defaults false for systems without one.
+    fn vram_dma_holds_cpu(&self) -> bool {
+        false
+    }
+    /// Peek a contiguous range of memory, bypassing bus conflicts and
+    /// PPU mode gating. Used by tests that decode assertion records
+    /// from WRAM after the test has halted.
+    fn peek_range(&self, start: u16, len: u16) -> Vec<u8>;
+    /// Drain accumulated audio samples (stereo, f32 pairs). Used by
+    /// gambatte audio tests to check whether the test ROM produced
+    /// any sound (`_outaudio1`) or was silent (`_outaudio0`).
+    fn drain_audio_samples(&mut self) -> Vec<(f32, f32)>;
+    /// Run until `frames` frames have completed; see [`Console::run_frames`].
+    fn run_frames(&mut self, frames: u32);
+}
+
+impl<M: Model> System for Console<M> {
+    fn step(&mut self) -> StepResult {
+        Console::<M>::step(self)
+    }
+    fn read(&self, address: u16) -> u8 {
+        Console::<M>::read(self, address)
+    }
+    fn cpu(&self) -> &Cpu {
+        Console::<M>::cpu(self)
+    }
+    fn cpu_mut(&mut self) -> &mut Cpu {
+        Console::<M>::cpu_mut(self)
+    }
+    fn drain_serial_output(&mut self) -> Vec<u8> {
+        Console::<M>::drain_serial_output(self)
+    }
+    fn interrupts(&self) -> &interrupts::Registers {
+        Console::<M>::interrupts(self)
+    }
+    fn speed_switch_in_progress(&self) -> bool {
+        Console::<M>::speed_switch_in_progress(self)
+    }
+    fn vram_dma_holds_cpu(&self) -> bool {
+        Console::<M>::vram_dma_holds_cpu(self)
+    }
+    fn peek_range(&self, start: u16, len: u16) -> Vec<u8> {
+        Console::<M>::peek_range(self, start, len)
+    }
+    fn drain_audio_samples(&mut self) -> Vec<(f32, f32)> {
+        Console::<M>::drain_audio_samples(self)
+    }
+    fn run_frames(&mut self, frames: u32) {
+        Console::<M>::run_frames(self, frames);
+    }
+}
+
+/// Run the emulator until the serial output contains any of the given needle strings,
+/// or until an infinite loop is detected at a frame boundary, or until a timeout is reached.
+pub fn run_until_serial_match<S: System>(
+    s: &mut S,
+    needles: &[&str],
+    timeout_frames: u32,
+) -> String {
+    let mut output = String::new();
+    for _ in 0..timeout_frames {
+        s.run_frames(1);
+        let bytes = s.drain_serial_output();
+        if !bytes.is_empty() {
+            output.push_str(&String::from_utf8_lossy(&bytes));
+            if needles.iter().any(|needle| output.contains(needle)) {
+                return output;
+            }
+        }
+        if is_infinite_loop(s) {
+            return output;
+        }
+    }
+    output
+}
+
+/// Check if the CPU is stuck in a known completion loop.
+pub fn is_infinite_loop<S: System>(s: &S) -> bool {
+    let pc = s.cpu().ir_address;
+    if s.read(pc) == 0x18 && s.read(pc.wrapping_add(1)) == 0xFE {
+        return true;
+    }
+    if s.read(pc.wrapping_sub(1)) == 0x18 && s.read(pc) == 0xFE {
+        return true;
+    }
+    if s.read(pc) == 0x40
+        && s.read(pc.wrapping_add(1)) == 0x18
+        && s.read(pc.wrapping_add(2)) == 0xFE
+    {
+        return true;
+    }
+
+    // An illegal opcode locks the CPU for good, interrupts or not.
+    if s.cpu().is_locked() {
+        return true;
+    }
+
+    if s.cpu().halt.state != HaltState::Running
+        && !s.speed_switch_in_progress()
+        && !s.vram_dma_holds_cpu()
+    {
+        if s.interrupts().enabled.is_empty() {
+            return true;
+        }
+
+        if s.read(pc.wrapping_sub(1)) == 0x76 {
+            for offset in 0u16..4 {
+                let addr = pc.wrapping_add(offset);
+                if s.read(addr) == 0x18 {
+                    let rel = s.read(addr.wrapping_add(1)) as i8;
+                    let target = addr.wrapping_add(2).wrapping_add(rel as u16);
+                    if target <= pc.wrapping_sub(1) {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+
+    false
+}
+
+pub fn check_mooneye_pass(cpu: &Cpu) -> bool {
+    cpu.b == 3 && cpu.c == 5 && cpu.d == 8 && cpu.e == 13 && cpu.h == 21 && cpu.l == 34
+}
+
+pub fn format_registers(cpu: &Cpu) -> String {
+    format!(
+        "B={} C={} D={} E={} H={} L={} (expected: B=3 C=5 D=8 E=13 H=21 L=34)",
+        cpu.b, cpu.c, cpu.d, cpu.e, cpu.h, cpu.l
+    )
+}
@@ -13,6 +13,18 @@ pub enum OamBusOwner {
     Dma(u8),
 }
 
+/// Where an OAM DMA transfer has got to, for debuggers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DmaStatus {
+    /// Address the transfer copies from, `$XX00`.
+    pub source: u16,
+    /// An $FF46 write is still arming; copying starts 1.5 M-cycles after it.
+    pub starting: bool,
+    /// Bytes left to copy, counting the one in flight. A full 160 until
+    /// the first byte moves.
+    pub remaining: u8,
+}
+
 /// NAVO terminal-count decode: bits 0,1,2,3,4,7 — fires at `dma_a == 0x9F`
 /// (159, the 160th byte).
 const NAVO_DECODE: u8 = 0b1001_1111;
@@ -22,6 +34,8 @@ const NAVO_DECODE: u8 = 0b1001_1111;
 /// (run/counter side) and `dma_phi_n` (arm side) so the FF46-write →
 /// `dma_run` engage latency (1.5 M-cycles) and the 160-byte transfer
 /// emerge from the gate timing rather than a fixed delay.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dma {
     /// Last value written to the DMA register (0xFF46) — source page.
     source_register: u8,
@@ -99,6 +113,24 @@ impl Dma {
         self.dma_run.then_some(self.source_bus)
     }
 
+    /// Progress of the transfer, or `None` when DMA is idle.
+    pub fn status(&self) -> Option<DmaStatus> {
+        let starting = self.lyxe || self.luvy || self.lene;
+        if !starting && !self.dma_run {
+            return None;
+        }
+        let remaining = if self.dma_run && !self.lene {
+            160 - self.dma_a.min(160)
+        } else {
+            160
+        };
+        Some(DmaStatus {
+            source: self.source,
+            starting,
+            remaining,
+        })
+    }
+
     /// Which side drives the OAM SRAM address bus — DMA while `dma_run`,
     /// otherwise the PPU.
     pub fn oam_bus_owner(&self) -> OamBusOwner {
@@ -8,6 +8,9 @@ use super::{
     ppu::{self, memory::Vram},
 };
 
+/// Dots from one VBlank onset to the next with the LCD on.
+const DOTS_PER_FRAME: u32 = 70224;
+
 /// Result of executing one instruction.
 pub struct StepResult {
     /// Whether a new video frame was produced during this instruction.
@@ -49,6 +52,7 @@ impl<M: Model> Console<M> {
         if trace {
             self.bus_trace.enable();
         }
+        let ram_generation = self.external.cartridge.ram_generation();
 
         // If step_tcycle() left us mid-instruction, drain to the next
         // boundary first, then run one full instruction.
@@ -65,18 +69,83 @@ impl<M: Model> Console<M> {
 
         self.resolve_stop(tcycles);
         self.manage_dma_hold();
+        self.fire_scheduled();
 
-        let sram_dirty = self.external.cartridge.take_sram_dirty();
         (
             StepResult {
                 new_screen,
-                sram_dirty,
+                sram_dirty: self.external.cartridge.ram_generation() != ram_generation,
                 tcycles,
             },
             self.bus_trace.take(),
         )
     }
 
+    /// Run whole instructions until at least `mcycles` M-cycles have
+    /// passed or a frame completes, whichever is first. Each instruction is
+    /// stepped exactly as [`step`](Self::step) would; only the bookkeeping
+    /// around them is batched.
+    pub fn run_cycles(&mut self, mcycles: u32) -> StepResult {
+        let budget = mcycles.saturating_mul(4);
+        let ram_generation = self.external.cartridge.ram_generation();
+        let mut new_screen = false;
+        let mut tcycles = 0u32;
+
+        // Finish an instruction step_tcycle() left half-done; step() counts
+        // it towards the instruction that follows.
+        let mut drained = 0;
+        if !self.cpu.at_instruction_boundary() {
+            let r = self.step_instruction();
+            new_screen |= r.new_screen;
+            drained = r.tcycles;
+        }
+
+        loop {
+            let r = self.step_instruction();
+            new_screen |= r.new_screen;
+            tcycles += drained + r.tcycles;
+            self.resolve_stop(drained + r.tcycles);
+            self.manage_dma_hold();
+            self.fire_scheduled();
+            drained = 0;
+
+            if new_screen || tcycles >= budget {
+                break;
+            }
+        }
+
+        StepResult {
+            new_screen,
+            sram_dirty: self.external.cartridge.ram_generation() != ram_generation,
+            tcycles,
+        }
+    }
+
+    /// Run until `frames` frames have completed and return the screen as
+    /// the last one left it. A frame completes at the onset of VBlank (LY
+    /// reaching 144), so the first may be partial. With the LCD off there
+    /// is no VBlank, and a frame's worth of cycles counts as one instead.
+    pub fn run_frames(&mut self, frames: u32) -> &M::Screen {
+        for _ in 0..frames {
+            self.run_frame_with(|console, budget| console.run_cycles(budget));
+        }
+        &self.screen
+    }
+
+    /// One frame of [`run_frames`](Self::run_frames), advancing with `run`,
+    /// which is handed an M-cycle budget it may stop short of.
+    pub(crate) fn run_frame_with(&mut self, mut run: impl FnMut(&mut Self, u32) -> StepResult) {
+        let frame_mcycles = DOTS_PER_FRAME * self.cpu_steps_per_dot() as u32 / 4;
+        let mut mcycles = 0;
+        while mcycles < frame_mcycles || self.ppu.control().video_enabled() {
+            let result = run(self, frame_mcycles);
+            if result.new_screen {
+                break;
+            }
+            mcycles += result.tcycles / 4;
+        }
+    }
+
     /// Run one complete instruction from start to finish.
     ///
     /// Runs phases until the CPU returns to the Fetch phase at a fresh
@@ -120,22 +189,44 @@ impl<M: Model> Console<M> {
                 }
             }
         }
-        // Don't drain sram_dirty here — let the caller (step_traced) do it
-        // so the flag accumulates across multiple step_instruction calls.
-        let sram_dirty = self.external.cartridge.sram_dirty;
+        self.write_trace_log();
+        // The caller compares RAM generations across its whole step.
         StepResult {
             new_screen,
-            sram_dirty,
+            sram_dirty: false,
             tcycles,
         }
     }
 
+    /// Write the instructions the CPU has started since last time to the
+    /// trace log.
+    fn write_trace_log(&mut self) {
+        let Some(mut entries) = self.cpu.trace_entries.take() else {
+            return;
+        };
+        for entry in entries.drain(..) {
+            let pcmem = std::array::from_fn(|i| self.peek(entry.pc.wrapping_add(i as u16)));
+            self.trace_log.record(&entry, pcmem);
+        }
+        self.cpu.trace_entries = Some(entries);
+    }
+
     /// Advance exactly one half-phase — execute rise() or fall()
     /// depending on current clock level.
     pub fn step_phase(&mut self) -> PhaseResult {
         self.execute_phase(CpuGate::Running)
     }
 
+    /// [`step_phase`](Self::step_phase), also returning the bus accesses it
+    /// made when `trace` is set.
+    pub fn step_phase_traced(&mut self, trace: bool) -> (PhaseResult, Vec<BusAccess>) {
+        if trace {
+            self.bus_trace.enable();
+        }
+        let result = self.step_phase();
+        (result, self.bus_trace.take())
+    }
+
     /// Advance to the next T-cycle boundary — the next Low state.
     /// Executes 1 phase if clock is High, 2 if Low. Returns true if
     /// a new frame was produced.
@@ -537,7 +628,7 @@ impl<M: Model> Console<M> {
         self.cpu.mark_instruction_boundary();
         StepResult {
             new_screen,
-            sram_dirty: self.external.cartridge.sram_dirty,
+            sram_dirty: false,
             tcycles: edges / edges_per_tcycle,
         }
     }
@@ -595,8 +686,7 @@ impl<M: Model> Console<M> {
         // The mid-HALT timer wake spends the HALT-wake's WakeIntake M-cycle (the
         // divider ticking through it) before re-engaging; the pending-at-STOP
         // preempt path drains the bare relock tail with no such wake.
-        let woken_ready =
-            woken_by_interrupt && self.model.speed_switch_wake_ready(mcycle_boundary);
+        let woken_ready = woken_by_interrupt && self.model.speed_switch_wake_ready(mcycle_boundary);
         let drain = if woken_ready { u32::MAX } else { 1 };
         if self.model.drain_speed_switch_blackout(drain) {
             // An enabled interrupt is serviced at re-engage, dispatching from the
@@ -889,6 +979,7 @@ impl<M: Model> Console<M> {
             } else {
                 self.cpu.pc = 0x0000;
             }
+            self.cpu.irq.last_vector = Some(self.cpu.pc);
             self.cpu.dispatch.clear_dispatch();
             // cpu_irq_ack1↑: LALU.r_n driven LOW via lety/movu until next
             // M-cycle boundary. Absorbs same-M-cycle SUKO rises.
@@ -1080,6 +1171,7 @@ impl<M: Model> Console<M> {
     /// that collided with DMA on the source bus open-drains at the OAM
     /// slot DMA deposits. (Audio mcycle is at boundary rise.)
     fn tick_mcycle_boundary_fall(&mut self) {
+        self.scheduler.mcycle();
         let double_speed = self.clock.divider() == CpuDivider::Two;
         let oam = self.dma.peek_transfer();
         let hdma_active = self.model.console_state().dma_cpu_hold() || self.cpu.bus_suspended;
@@ -0,0 +1,80 @@
+//! Per-page CPU access counts, for seeing at a glance where a game is busy:
+//! a burst of OAM writes, a hot HRAM routine, a bank being streamed from
+//! ROM. Counting is opt-in through
+//! [`Console::set_heatmap`](crate::Console::set_heatmap), since it costs a
+//! little on every bus access.
+
+/// Frames between decays. Each decay halves every count, so the map shows
+/// roughly the last second of activity.
+pub const DECAY_FRAMES: u32 = 15;
+
+/// CPU reads and writes per 256-byte page of the address space, indexed by
+/// the address's high byte. Opcode fetches count as reads. OAM DMA isn't
+/// counted: it moves the same 160 bytes every time and would drown out the
+/// game's own accesses.
+#[derive(Clone)]
+pub struct Heatmap {
+    reads: [u32; 256],
+    writes: [u32; 256],
+    frames: u32,
+}
+
+impl Heatmap {
+    pub(crate) fn new() -> Self {
+        Self {
+            reads: [0; 256],
+            writes: [0; 256],
+            frames: 0,
+        }
+    }
+
+    pub(crate) fn record_read(&mut self, address: u16) {
+        let page = &mut self.reads[(address >> 8) as usize];
+        *page = page.saturating_add(1);
+    }
+
+    pub(crate) fn record_write(&mut self, address: u16) {
+        let page = &mut self.writes[(address >> 8) as usize];
+        *page = page.saturating_add(1);
+    }
+
+    pub fn reads(&self, page: u8) -> u32 {
+        self.reads[page as usize]
+    }
+
+    pub fn writes(&self, page: u8) -> u32 {
+        self.writes[page as usize]
+    }
+
+    /// The busiest page's reads, for scaling a view of the map.
+    pub fn max_reads(&self) -> u32 {
+        self.reads.iter().copied().max().unwrap_or(0)
+    }
+
+    /// The busiest page's writes, for scaling a view of the map.
+    pub fn max_writes(&self) -> u32 {
+        self.writes.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Count a finished frame, halving every count each [`DECAY_FRAMES`] so
+    /// old activity fades out.
+    pub fn end_frame(&mut self) {
+        self.frames += 1;
+        if self.frames >= DECAY_FRAMES {
+            self.frames = 0;
+            self.decay();
+        }
+    }
+
+    pub fn decay(&mut self) {
+        for count in self.reads.iter_mut().chain(self.writes.iter_mut()) {
+            *count /= 2;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.reads.fill(0);
+        self.writes.fill(0);
+        self.frames = 0;
+    }
+}
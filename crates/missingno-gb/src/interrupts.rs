@@ -7,6 +7,7 @@ pub enum Register {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Interrupt {
     VideoBetweenFrames,
     VideoStatus,
@@ -29,6 +30,7 @@ impl From<Interrupt> for InterruptFlags {
 
 bitflags! {
     #[derive(Copy, Clone)]
+    #[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
     pub struct InterruptFlags: u8 {
         const JOYPAD               = 0b00010000;
         const SERIAL               = 0b00001000;
@@ -63,6 +65,7 @@ impl Interrupt {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Registers {
     pub enabled: InterruptFlags,
     pub requested: InterruptFlags,
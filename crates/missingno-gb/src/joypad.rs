@@ -1,4 +1,5 @@
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Joypad {
     pub read_buttons: bool,
     pub read_dpad: bool,
@@ -7,6 +8,7 @@ pub struct Joypad {
 }
 
 #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Button {
     Start,
     Select,
@@ -16,6 +18,7 @@ pub enum Button {
 }
 
 #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum DirectionalPad {
     Up,
     Down,
@@ -41,52 +44,35 @@ impl Joypad {
         }
     }
 
+    /// JOYP as the CPU reads it: bits 6-7 always 1, bits 4-5 the select
+    /// lines as written (0 = row selected), and bits 0-3 the input lines,
+    /// active low. A line is pulled low by a pressed button on any selected
+    /// row, so with both rows selected the rows are ANDed and with neither
+    /// selected every line reads 1.
     pub fn read_register(&self) -> u8 {
-        // Bits are weirdly inverted for joypad
         let mut value = Self::UNUSED | Self::NONE_PRESSED;
 
         if self.read_buttons {
-            if self.pressed_buttons.contains(&Button::Start) {
-                value ^= Self::START_DOWN;
-            }
-            if self.pressed_buttons.contains(&Button::Select) {
-                value ^= Self::SELECT_UP;
-            }
-            if self.pressed_buttons.contains(&Button::B) {
-                value ^= Self::B_LEFT;
-            }
-            if self.pressed_buttons.contains(&Button::A) {
-                value ^= Self::A_RIGHT;
-            }
+            value &= !self.row_lines(&[
+                (Button::Start, Self::START_DOWN),
+                (Button::Select, Self::SELECT_UP),
+                (Button::B, Self::B_LEFT),
+                (Button::A, Self::A_RIGHT),
+            ]);
         } else {
             value |= Self::READ_BUTTONS;
         }
 
         if self.read_dpad {
-            if self
-                .pressed_buttons
-                .contains(&Button::DirectionalPad(DirectionalPad::Down))
-            {
-                value ^= Self::START_DOWN;
-            }
-            if self
-                .pressed_buttons
-                .contains(&Button::DirectionalPad(DirectionalPad::Up))
-            {
-                value ^= Self::SELECT_UP;
-            }
-            if self
-                .pressed_buttons
-                .contains(&Button::DirectionalPad(DirectionalPad::Left))
-            {
-                value ^= Self::B_LEFT;
-            }
-            if self
-                .pressed_buttons
-                .contains(&Button::DirectionalPad(DirectionalPad::Right))
-            {
-                value ^= Self::A_RIGHT;
-            }
+            value &= !self.row_lines(&[
+                (
+                    Button::DirectionalPad(DirectionalPad::Down),
+                    Self::START_DOWN,
+                ),
+                (Button::DirectionalPad(DirectionalPad::Up), Self::SELECT_UP),
+                (Button::DirectionalPad(DirectionalPad::Left), Self::B_LEFT),
+                (Button::DirectionalPad(DirectionalPad::Right), Self::A_RIGHT),
+            ]);
         } else {
             value |= Self::READ_DPAD;
         }
@@ -94,6 +80,13 @@ impl Joypad {
         value
     }
 
+    /// The lines pulled low by the pressed buttons of one row.
+    fn row_lines(&self, row: &[(Button, u8)]) -> u8 {
+        row.iter()
+            .filter(|(button, _)| self.pressed_buttons.contains(button))
+            .fold(0, |lines, (_, line)| lines | line)
+    }
+
     pub fn write_register(&mut self, value: u8) {
         self.read_buttons = value & Self::READ_BUTTONS == 0;
         self.read_dpad = value & Self::READ_DPAD == 0;
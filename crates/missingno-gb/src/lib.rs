@@ -1,18 +1,25 @@
 pub mod audio;
+mod boot_logo;
 pub mod cartridge;
 pub mod clock;
 pub mod cpu;
 pub mod cpu_bus;
 pub mod debugger;
+pub mod diagnostics;
 pub mod dma;
 pub mod dmg_sram;
 pub mod execute;
+pub mod heatmap;
 pub mod interrupts;
 pub mod joypad;
 pub mod master_clock;
 pub mod memory;
 pub mod ppu;
+pub mod printer;
+pub mod profiler;
 pub mod recording;
+pub mod save_state;
+mod scheduler;
 pub mod serial_transfer;
 pub mod sgb;
 #[cfg(feature = "gbtrace")]
@@ -22,32 +29,41 @@ pub mod test_support;
 pub mod timers;
 #[cfg(feature = "gbtrace")]
 pub mod trace;
+pub mod trace_log;
+pub mod warnings;
 
 use audio::Audio;
 use cartridge::Cartridge;
 use cpu::Cpu;
 use cpu_bus::CpuBus;
 use dma::Dma;
+use heatmap::Heatmap;
 use joypad::{Button, Joypad};
 use memory::{Bus, ExternalBus, HighRam, VramBus};
 use ppu::Ppu;
 use ppu::memory::Vram;
 use ppu::model::PpuModel;
+use profiler::Profile;
+use save_state::Persist;
+use trace_log::TraceLog;
 
 pub use audio::channels::wave::WaveRamCoupling;
 pub use clock::{CpuDivider, CpuGate, Edge, MasterClock, Tick};
 pub use master_clock::ClockPhase;
-pub use memory::BootRom;
+pub use memory::{BootRom, PowerOnMemory};
 pub use ppu::PixelOutput;
+pub use warnings::Warning;
 
 /// Double-buffered LCD framebuffer, abstracted over its pixel storage so
 /// the shared core can drive a DMG shade buffer or a CGB color buffer.
-pub trait ScreenBuffer: Default + Clone {
+pub trait ScreenBuffer: Default + Clone + Persist {
     type Pixel: Copy;
     fn draw_pixel(&mut self, x: u8, y: u8, pixel: Self::Pixel);
     /// Swap back→front and clear back. Returns true for `new_screen` tracking.
     fn present(&mut self) -> bool;
     fn blank(&mut self);
+    /// Bytes the framebuffers take on the heap.
+    fn heap_size(&self) -> usize;
 }
 
 /// CGB-only console-level arbitration state, relocated off the shared
@@ -55,7 +71,7 @@ pub trait ScreenBuffer: Default + Clone {
 /// storage; the DMG model is a ZST `()`, since none of these paths — the
 /// speed-switch blackout, the HDMA bus-park, the VRAM-source OAM-zero conflict
 /// — exist on the DMG.
-pub trait ConsoleShadow {
+pub trait ConsoleShadow: Clone + Persist {
     /// The master-edge count a double-speed switch blackout began on; the
     /// elapsed held edges are `master_edge - anchor`. Re-anchored at each switch.
     fn blackout_anchor(&self) -> u64;
@@ -73,7 +89,8 @@ pub trait ConsoleShadow {
 }
 
 /// The CGB console-level arbitration state.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct CgbConsoleState {
     blackout_anchor: u64,
     dma_cpu_hold: bool,
@@ -135,6 +152,7 @@ pub enum StopAction {
 /// claim that aged through its synchronizer stage before committing (it
 /// wins the bus race against the halt-release fetch).
 #[derive(Copy, Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct VramDmaClaim {
     pub committed: bool,
     pub standing: bool,
@@ -155,7 +173,7 @@ pub fn shared_oam_dma_write_conflict_byte(src_byte: u8, cpu_value: u8, dma_sourc
 /// The per-console divergences from the shared SM83 silicon — the entire
 /// catalogue of how DMG and CGB differ in the step loop and memory map.
 /// Everything not listed here is the same silicon and lives in [`Console`].
-pub trait Model: Default {
+pub trait Model: Default + Clone + Persist {
     /// The PPU's per-console hardware: DMG monochrome, CGB colour.
     type Ppu: PpuModel;
 
@@ -225,7 +243,7 @@ pub trait Model: Default {
 
     /// Post-boot CPU state when no boot ROM is present. DMG seeds the flags
     /// from the header checksum; CGB uses a fixed register file (A=$11).
-    fn cpu_post_boot(checksum: u8) -> Cpu {
+    fn cpu_post_boot(&self, checksum: u8) -> Cpu {
         Cpu::post_boot(checksum)
     }
 
@@ -406,6 +424,15 @@ pub trait Model: Default {
         None
     }
 
+    /// Work RAM when this console banks it itself (CGB SVBK, 32 KiB). `None`
+    /// falls back to the 8 KiB on the external bus.
+    fn work_ram(&self) -> Option<&[u8]> {
+        None
+    }
+    fn work_ram_mut(&mut self) -> Option<&mut [u8]> {
+        None
+    }
+
     /// This console's own memory map: the registers/regions its map defines
     /// that the shared map doesn't. DMG adds nothing. CGB adds KEY1, VBK,
     /// SVBK, BCPS/BCPD, OCPS/OCPD, HDMA1-5, OPRI, and banked WRAM. Consulted
@@ -500,6 +527,9 @@ pub trait Model: Default {
 /// A Game Boy–family console: the SM83 CPU, the shared PPU/APU/timer/DMA
 /// silicon, and the step loop + memory map that drive them. The handful of
 /// DMG/CGB divergences are supplied by the [`Model`] parameter `M`.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "save-state", serde(bound = ""))]
 pub struct Console<M: Model> {
     cpu: Cpu,
 
@@ -532,7 +562,17 @@ pub struct Console<M: Model> {
     /// Shared CPU data bus: current `cpu_port_d[7:0]` value plus the
     /// staged read/write activity for the in-flight M-cycle.
     cpu_bus: CpuBus,
+    #[cfg_attr(
+        feature = "save-state",
+        serde(skip, default = "cpu_bus::BusTrace::new")
+    )]
     bus_trace: cpu_bus::BusTrace,
+    /// Per-page access counts, when turned on.
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    heatmap: Option<Box<Heatmap>>,
+    /// A line per executed instruction, when a sink is set.
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    trace_log: TraceLog,
     /// Conflict write deferred from `commit_write` to after DMA's
     /// `mcycle()` commit. Tuple is `(oam_offset, src_byte, cpu_value)`:
     /// `src_byte` is the byte DMA fetched this M-cycle, used to
@@ -545,14 +585,36 @@ pub struct Console<M: Model> {
     /// Tuple is `(register address, value)`; drained in `tick_mcycle_boundary_fall`
     /// after the byte commit.
     dma_pending_bank_write: Option<(u16, u8)>,
+    /// Internal counter override applied at power-on. `None` keeps the
+    /// model's measured handoff phase.
+    initial_div: Option<u16>,
+    /// M-cycles since reset and the callbacks waiting on them.
+    scheduler: scheduler::Scheduler<M>,
 
     model: M,
 }
 
+/// Which monochrome console a [`Dmg`] emulates. They share the DMG PPU and
+/// timing; software tells them apart by the registers the boot ROM leaves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum DmgModel {
+    /// Early DMG revision.
+    Dmg0,
+    #[default]
+    Dmg,
+    /// Game Boy Pocket.
+    Mgb,
+    /// Super Game Boy. The only model that answers SGB commands.
+    Sgb,
+}
+
 /// The original Game Boy (DMG): SGB co-processor support, the OAM
 /// corruption bug, and a 2-bit shade framebuffer.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dmg {
+    model: DmgModel,
     sgb: Option<sgb::Sgb>,
     /// CGB console arbitration is statically unreachable on DMG — a ZST.
     console_state: (),
@@ -598,7 +660,16 @@ impl Model for Dmg {
     }
 
     fn on_reset(&mut self, cartridge: &Cartridge, _has_boot_rom: bool) {
-        self.sgb = cartridge.supports_sgb().then(sgb::Sgb::new);
+        self.sgb = (self.model == DmgModel::Sgb && cartridge.supports_sgb()).then(sgb::Sgb::new);
+    }
+
+    fn cpu_post_boot(&self, checksum: u8) -> Cpu {
+        match self.model {
+            DmgModel::Dmg0 => Cpu::post_boot_dmg0(),
+            DmgModel::Dmg => Cpu::post_boot(checksum),
+            DmgModel::Mgb => Cpu::post_boot_mgb(checksum),
+            DmgModel::Sgb => Cpu::post_boot_sgb(),
+        }
     }
 }
 
@@ -607,6 +678,10 @@ pub type GameBoy = Console<Dmg>;
 
 impl<M: Model> Console<M> {
     pub fn new(cartridge: Cartridge, boot_rom: Option<BootRom>) -> Self {
+        Self::with_model(cartridge, boot_rom, M::default())
+    }
+
+    fn with_model(cartridge: Cartridge, boot_rom: Option<BootRom>, model: M) -> Self {
         let mut console = Console {
             cpu: Cpu::new(),
             external: ExternalBus::new(cartridge, boot_rom),
@@ -624,11 +699,16 @@ impl<M: Model> Console<M> {
             clock: MasterClock::new(CpuDivider::One),
             cpu_bus: CpuBus::new(),
             bus_trace: cpu_bus::BusTrace::new(),
+            heatmap: None,
+            trace_log: TraceLog::default(),
             dma_conflict_write_pending: None,
             dma_pending_bank_write: None,
-            model: M::default(),
+            initial_div: None,
+            scheduler: scheduler::Scheduler::new(),
+            model,
         };
         console.rebuild_state();
+        console.apply_power_on_memory();
         console
     }
 
@@ -638,12 +718,84 @@ impl<M: Model> Console<M> {
     pub fn reset(&mut self) {
         self.external.reset();
         self.rebuild_state();
+        self.apply_power_on_memory();
+    }
+
+    /// Fill OAM, and VRAM for a boot ROM to find, with their power-on
+    /// contents. WRAM's are filled by the external bus.
+    fn apply_power_on_memory(&mut self) {
+        let power_on = self.external.power_on_memory();
+        self.ppu.oam = power_on.oam();
+        if self.external.boot_rom_mapped()
+            && let Some(contents) = power_on.vram()
+        {
+            self.vram_bus.vram.power_on(&contents);
+        }
+    }
+
+    /// Restart the game at $0100 in the post-boot state without a power
+    /// cycle: the CPU and the on-chip peripherals are re-created, but WRAM
+    /// and the whole cartridge (SRAM, RTC and banking) are left as they
+    /// are, and the boot ROM doesn't replay.
+    pub fn soft_reset(&mut self) {
+        self.external.unmap_boot_rom();
+        self.rebuild_state();
+    }
+
+    /// Carry on from `state`, a loaded save state or snapshot, in place of
+    /// this console. What the host attached stays as it was: the link cable
+    /// and serial callback, the audio output rate, scope and channel mutes,
+    /// and the profiler, heatmap and trace log.
+    pub fn restore(&mut self, state: Console<M>) {
+        let previous = std::mem::replace(self, state);
+        self.serial.keep_host_side(previous.serial);
+        self.audio.keep_host_side(previous.audio);
+        self.cpu.profile = previous.cpu.profile;
+        self.cpu.trace_entries = previous.cpu.trace_entries;
+        self.heatmap = previous.heatmap;
+        self.trace_log = previous.trace_log;
+    }
+
+    /// Bytes a clone of the console takes on the heap beyond its own size:
+    /// the framebuffers, cartridge RAM, undrained audio and the debugger
+    /// views. The ROM is shared between clones and isn't counted.
+    pub(crate) fn heap_size(&self) -> usize {
+        let ram = self.cartridge().ram().map_or(0, |ram| ram.len());
+        let profile = self
+            .cpu
+            .profile
+            .as_ref()
+            .map_or(0, |profile| profile.heap_size());
+        let heatmap = self
+            .heatmap
+            .as_ref()
+            .map_or(0, |_| std::mem::size_of::<Heatmap>());
+        self.screen.heap_size() + ram + self.audio.heap_size() + profile + heatmap
+    }
+
+    /// True while the cosmetic [`BootRom::Logo`] sequence is playing. A
+    /// [`soft_reset`](Self::soft_reset) skips the rest of it.
+    pub fn in_logo_boot(&self) -> bool {
+        self.external.logo_boot_mapped()
+    }
+
+    /// Once [`BootRom::Logo`] has finished, start the game in the same state
+    /// as a console with no boot ROM, rather than whatever the animation
+    /// left behind. Returns true if it handed over. Call between frames.
+    pub fn finish_logo_boot(&mut self) -> bool {
+        if self.in_logo_boot() && self.cpu.ir_address == boot_logo::PARK {
+            self.soft_reset();
+            true
+        } else {
+            false
+        }
     }
 
     /// Re-create every non-cartridge, non-link component to its power-
     /// on or post-boot-ROM initial state. Called from `new` after the
     /// initial struct has been laid out with placeholder values, and
     /// from `reset` after `ExternalBus::reset` has cleared WRAM/latch.
+    /// Starts in the boot ROM only if it's mapped.
     ///
     /// Mirrors the CPU's pending bus read/write so T-cycle 2 staging
     /// has a target for the in-flight M-cycle. The skip-boot CPU
@@ -653,17 +805,21 @@ impl<M: Model> Console<M> {
     /// so the staging block in `rise()` doesn't fire for that first
     /// M-cycle.
     fn rebuild_state(&mut self) {
-        let has_boot_rom = self.external.has_boot_rom();
+        let has_boot_rom = self.external.boot_rom_mapped();
         let header_checksum = self.external.cartridge.header_checksum();
 
+        let profiling = self.cpu.profile.is_some();
+        let trace_entries = self.cpu.trace_entries.take();
         self.cpu = if has_boot_rom {
             Cpu::new()
         } else {
-            M::cpu_post_boot(header_checksum)
+            self.model.cpu_post_boot(header_checksum)
         };
+        self.set_profiling(profiling);
+        self.cpu.trace_entries = trace_entries;
         self.screen = M::Screen::default();
         self.high_ram = HighRam::new();
-        let cgb_cart = self.external.cartridge.is_cgb();
+        let cgb_cart = self.external.cartridge.supports_cgb();
         self.ppu = if has_boot_rom {
             Ppu::new()
         } else {
@@ -675,17 +831,28 @@ impl<M: Model> Console<M> {
             M::joypad_post_boot()
         };
         self.interrupts = interrupts::Registers::new();
+        let on_byte_sent = self.serial.take_byte_callback();
         self.serial = serial_transfer::Serial::new();
+        self.serial.set_byte_callback(on_byte_sent);
         self.timers = if has_boot_rom {
             timers::Timers::new()
         } else {
             M::timers_post_boot(cgb_cart)
         };
+        if let Some(counter) = self.initial_div {
+            self.timers.internal_counter = counter;
+        }
+        let scope_enabled = self.audio.scope().is_some();
+        let output_rate = self.audio.output_rate();
+        let channel_mask = self.audio.channel_mask;
         self.audio = if has_boot_rom {
             Audio::new()
         } else {
             M::audio_post_boot(self.timers.internal_counter, cgb_cart)
         };
+        self.audio.set_scope_enabled(scope_enabled);
+        self.audio.set_output_rate(output_rate);
+        self.audio.channel_mask = channel_mask;
         self.dma = if has_boot_rom {
             Dma::new()
         } else {
@@ -699,7 +866,7 @@ impl<M: Model> Console<M> {
             let logo: [u8; 0x30] = std::array::from_fn(|i| read(0x0104 + i as u16));
             self.vram_bus.vram.init_post_boot(&logo);
             let header = ppu::CartridgeBootHeader {
-                is_cgb: self.external.cartridge.is_cgb(),
+                is_cgb: self.external.cartridge.supports_cgb(),
                 title: std::array::from_fn(|i| read(0x0134 + i as u16)),
                 old_licensee: read(0x014B),
                 new_licensee: [read(0x0144), read(0x0145)],
@@ -708,6 +875,7 @@ impl<M: Model> Console<M> {
         }
 
         self.bus_trace = cpu_bus::BusTrace::new();
+        self.scheduler = scheduler::Scheduler::new();
         // Re-anchor the CPU clock to a rise; the free-running dot phase is left
         // as-is (the old reset touched only `clock_phase`).
         self.clock.engage_on_rise();
@@ -737,6 +905,15 @@ impl<M: Model> Console<M> {
         &self.external.cartridge
     }
 
+    /// The `.sav` contents for a battery-backed cartridge, `None` for any
+    /// other. Only writes that have reached the cartridge are included, so
+    /// this is safe to call mid-frame or mid-instruction. Pass it back to
+    /// [`Cartridge::new`] to restore.
+    pub fn export_sram(&self) -> Option<Vec<u8>> {
+        let cartridge = self.cartridge();
+        cartridge.has_battery().then(|| cartridge.save_data())?
+    }
+
     pub fn cpu(&self) -> &Cpu {
         &self.cpu
     }
@@ -745,6 +922,21 @@ impl<M: Model> Console<M> {
         &mut self.cpu
     }
 
+    /// Continue execution at `address`, as a debugger setting PC. See
+    /// [`Cpu::jump_to`]. The bus staged the in-flight opcode fetch when its
+    /// M-cycle began, so it is restaged at the new address.
+    pub fn jump_to(&mut self, address: u16) -> bool {
+        if !self.cpu.jump_to(address) {
+            return false;
+        }
+        if self.cpu.is_fetch_phase()
+            && let Some(fetch) = self.cpu.pending_bus_read()
+        {
+            self.cpu_bus.stage_read(fetch);
+        }
+        true
+    }
+
     pub fn ppu(&self) -> &Ppu<M::Ppu> {
         &self.ppu
     }
@@ -753,6 +945,36 @@ impl<M: Model> Console<M> {
         &self.vram_bus.vram
     }
 
+    /// A copy of one VRAM bank, laid out as 0x8000–0x9FFF. Banks past
+    /// [`Vram::bank_count`] read as the last bank.
+    pub fn vram_bytes(&self, bank: u8) -> [u8; 0x2000] {
+        let vram = &self.vram_bus.vram;
+        vram.bank(bank.min(vram.bank_count() - 1)).to_bytes()
+    }
+
+    /// A copy of OAM, laid out as 0xFE00–0xFE9F.
+    pub fn oam_bytes(&self) -> [u8; 0xA0] {
+        self.ppu.oam.to_bytes()
+    }
+
+    /// All work RAM banks, bank 0 first (8 KiB on DMG, 32 KiB on CGB).
+    pub fn work_ram(&self) -> &[u8] {
+        self.model.work_ram().unwrap_or(&self.external.work_ram)
+    }
+
+    /// Mutable work RAM. WRAM sits behind no mapper or lock, so direct
+    /// writes here match what a CPU store would do.
+    pub fn work_ram_mut(&mut self) -> &mut [u8] {
+        match self.model.work_ram_mut() {
+            Some(wram) => wram,
+            None => &mut self.external.work_ram,
+        }
+    }
+
+    pub fn high_ram_mut(&mut self) -> &mut [u8; 0x7F] {
+        self.high_ram.data_mut()
+    }
+
     /// Read a contiguous range of memory via peek (bypasses bus conflicts).
     pub fn peek_range(&self, start: u16, len: u16) -> Vec<u8> {
         (0..len).map(|i| self.peek(start.wrapping_add(i))).collect()
@@ -762,6 +984,10 @@ impl<M: Model> Console<M> {
         &self.audio
     }
 
+    pub fn audio_mut(&mut self) -> &mut Audio {
+        &mut self.audio
+    }
+
     pub fn clock_phase(&self) -> ClockPhase {
         self.clock.cpu_edge().into()
     }
@@ -771,6 +997,13 @@ impl<M: Model> Console<M> {
         self.model.cpu_steps_per_dot()
     }
 
+    /// Whether the CPU runs at CGB double speed: KEY1 ($FF4D) bit 7. The
+    /// CPU, timers and serial clock twice as fast as the PPU and DMA. Always
+    /// false on DMG.
+    pub fn double_speed(&self) -> bool {
+        self.cpu_steps_per_dot() == 2
+    }
+
     pub fn screen(&self) -> &M::Screen {
         &self.screen
     }
@@ -795,6 +1028,21 @@ impl<M: Model> Console<M> {
         &self.timers
     }
 
+    /// Override the internal counter (DIV is bits 13-6) the next
+    /// [`reset`](Self::reset) starts from, for reproducible DIV-seeded RNG.
+    pub fn set_initial_div(&mut self, counter: u16) {
+        self.initial_div = Some(counter);
+    }
+
+    /// Choose what WRAM, VRAM and OAM power on holding, and power-cycle into
+    /// it straight away, so call it before running anything. The default is
+    /// all zeroes; see [`PowerOnMemory`]. CGB consoles bank their own WRAM,
+    /// which this doesn't affect.
+    pub fn set_power_on_memory(&mut self, power_on: PowerOnMemory) {
+        self.external.set_power_on_memory(power_on);
+        self.reset();
+    }
+
     pub fn interrupts(&self) -> &interrupts::Registers {
         &self.interrupts
     }
@@ -816,6 +1064,11 @@ impl<M: Model> Console<M> {
         &self.dma
     }
 
+    /// OAM DMA progress, or `None` when no transfer is running.
+    pub fn dma_status(&self) -> Option<dma::DmaStatus> {
+        self.dma.status()
+    }
+
     pub fn serial(&self) -> &serial_transfer::Serial {
         &self.serial
     }
@@ -824,6 +1077,10 @@ impl<M: Model> Console<M> {
         &self.external
     }
 
+    pub fn vram_bus(&self) -> &VramBus<<M::Ppu as PpuModel>::Vram> {
+        &self.vram_bus
+    }
+
     pub fn high_ram(&self) -> &HighRam {
         &self.high_ram
     }
@@ -832,12 +1089,139 @@ impl<M: Model> Console<M> {
         self.serial.drain_output()
     }
 
+    /// Warnings about unemulated behaviour raised since the last call.
+    /// Each distinct warning is only reported once.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        let mut warnings = self.cpu.warnings.take();
+        warnings.extend(self.external.cartridge.take_warnings());
+        warnings
+    }
+
+    /// Start or stop counting executed instructions. Stopping discards the
+    /// counts; a reset keeps counting from zero.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        if enabled != self.cpu.profile.is_some() {
+            self.cpu.profile = enabled.then(|| Box::new(Profile::new()));
+        }
+    }
+
+    pub fn profile(&self) -> Option<&Profile> {
+        self.cpu.profile.as_deref()
+    }
+
+    pub fn profile_mut(&mut self) -> Option<&mut Profile> {
+        self.cpu.profile.as_deref_mut()
+    }
+
+    /// Start or stop counting CPU accesses per page. Stopping discards the
+    /// counts; a reset keeps them.
+    pub fn set_heatmap(&mut self, enabled: bool) {
+        if enabled != self.heatmap.is_some() {
+            self.heatmap = enabled.then(|| Box::new(Heatmap::new()));
+        }
+    }
+
+    pub fn heatmap(&self) -> Option<&Heatmap> {
+        self.heatmap.as_deref()
+    }
+
+    pub fn heatmap_mut(&mut self) -> Option<&mut Heatmap> {
+        self.heatmap.as_deref_mut()
+    }
+
     pub fn set_link(&mut self, link: Box<dyn serial_transfer::SerialLink>) {
         self.serial.set_link(link);
     }
+
+    /// Call `callback` with each byte the game sends over serial on its own
+    /// clock, as the transfer completes. Bytes still collect for
+    /// [`drain_serial_output`](Self::drain_serial_output) as well. Resets
+    /// keep it; a clone of the console doesn't call it.
+    pub fn set_serial_callback(&mut self, callback: Box<dyn FnMut(u8)>) {
+        self.serial.set_byte_callback(Some(callback));
+    }
+
+    /// Write a line to `sink` for every instruction executed from now on,
+    /// in the Gameboy Doctor layout (see [`trace_log`]). Resets keep it; a
+    /// clone of the console doesn't trace.
+    pub fn set_trace_sink(&mut self, sink: Box<dyn std::io::Write>) {
+        self.trace_log.set_sink(Some(sink));
+        self.cpu.trace_entries.get_or_insert_default();
+    }
+
+    /// Stop tracing, handing back the sink so it can be flushed.
+    pub fn take_trace_sink(&mut self) -> Option<Box<dyn std::io::Write>> {
+        self.cpu.trace_entries = None;
+        self.trace_log.set_sink(None)
+    }
 }
 
 impl Console<Dmg> {
+    /// A console emulating a specific monochrome model. [`Console::new`]
+    /// emulates a [`DmgModel::Dmg`].
+    pub fn new_with_model(
+        cartridge: Cartridge,
+        boot_rom: Option<BootRom>,
+        model: DmgModel,
+    ) -> Self {
+        Self::with_model(
+            cartridge,
+            boot_rom,
+            Dmg {
+                model,
+                ..Dmg::default()
+            },
+        )
+    }
+
+    /// The displayed frame as packed RGBA, each shade looked up in
+    /// `palette`. See [`Screen::write_rgba`](ppu::screen::Screen::write_rgba).
+    pub fn framebuffer_rgba(
+        &self,
+        palette: &ppu::types::palette::Palette,
+        rgba: &mut [u8; ppu::screen::RGBA_LEN],
+    ) {
+        self.screen().write_rgba(palette, rgba);
+    }
+
+    /// The displayed frame as an 8-bit greyscale PNG, shaded as
+    /// [`Screen::to_greyscale`](ppu::screen::Screen::to_greyscale) so it
+    /// compares directly against the accuracy tests' reference images.
+    #[cfg(feature = "png")]
+    pub fn capture_png(&self) -> Vec<u8> {
+        use ppu::screen::{NUM_SCANLINES, PIXELS_PER_LINE};
+
+        let mut bytes = Vec::new();
+        let mut encoder =
+            png::Encoder::new(&mut bytes, PIXELS_PER_LINE as u32, NUM_SCANLINES as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .expect("encoding to memory can't fail");
+        writer
+            .write_image_data(&self.screen().to_greyscale())
+            .expect("the frame matches the header");
+        writer.finish().expect("encoding to memory can't fail");
+        bytes
+    }
+
+    /// A DMG running `rom`, rejecting anything [`Cartridge::validate`] does.
+    pub fn load_rom(rom: Vec<u8>) -> Result<Self, cartridge::LoadError> {
+        Ok(Self::new(Cartridge::try_new(rom, None)?, None))
+    }
+
+    /// [`load_rom`](Self::load_rom) that accepts a bad header
+    /// checksum, for homebrew.
+    pub fn load_rom_lenient(rom: Vec<u8>) -> Result<Self, cartridge::LoadError> {
+        Cartridge::validate_lenient(&rom)?;
+        Ok(Self::new(Cartridge::new(rom, None)?, None))
+    }
+
+    pub fn dmg_model(&self) -> DmgModel {
+        self.model.model
+    }
+
     pub fn sgb(&self) -> Option<&sgb::Sgb> {
         self.model.sgb.as_ref()
     }
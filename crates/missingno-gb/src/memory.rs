@@ -7,16 +7,89 @@ use crate::{
 };
 
 use super::cartridge::Cartridge;
-use ppu::memory::{OamAddress, Vram, VramAddress};
+use ppu::memory::{Oam, OamAddress, Vram, VramAddress, VramBank};
 
 /// M-cycles before the external data bus decays to 0xFF. The bus
 /// retains its last driven value via parasitic capacitance; 12 M-cycles
 /// (~2.86 µs) is a board-independent approximation.
 const EXTERNAL_BUS_DECAY_MCYCLES: u8 = 12;
 
+/// What WRAM, VRAM and OAM hold at power-on, before the boot ROM or game
+/// writes them.
+///
+/// Only a boot ROM sees VRAM's: every boot ROM, [`BootRom::Logo`] included,
+/// clears it first, and skipping the boot ROM starts from that cleared VRAM.
+///
+/// The ROM accuracy suites run on [`Dmg`](Self::Dmg), matching the hardware
+/// their expected results were recorded on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerOnMemory {
+    /// All zeroes: the same on every model, and what software that wrongly
+    /// assumes cleared RAM expects.
+    #[default]
+    Zeroed,
+    /// WRAM holds the striped pattern dumped from a DMG-08 (see
+    /// `dmg_sram`), so reads before writes see what gambatte's dump does.
+    /// VRAM holds garbage as on [`Uninitialised`](Self::Uninitialised); the
+    /// tree has no dump of it. OAM starts zeroed, which AGE's `oam-read` and
+    /// `stat-mode-sprites` are checked against.
+    ///
+    /// Bully and gambatte's `oamdma_srcFE00_*` read WRAM before writing it,
+    /// the latter through OAM DMA from $FE00 (which copies WRAM's $DE00), so
+    /// their reference results only match with this pattern.
+    Dmg,
+    /// WRAM as [`Dmg`](Self::Dmg), and VRAM and OAM holding garbage the way
+    /// real units power on. The garbage varies between units; a fixed
+    /// pseudo-random fill stands in for it so runs stay reproducible.
+    Uninitialised,
+}
+
+impl PowerOnMemory {
+    fn fill_work_ram(self, work_ram: &mut [u8; 0x2000]) {
+        *work_ram = [0; 0x2000];
+        if self != PowerOnMemory::Zeroed {
+            dmg_sram::fill(work_ram);
+        }
+    }
+
+    /// VRAM's contents for a boot ROM to find, or `None` for cleared.
+    pub(crate) fn vram(self) -> Option<VramBank> {
+        match self {
+            PowerOnMemory::Dmg | PowerOnMemory::Uninitialised => {
+                Some(VramBank::from_bytes(&garbage::<0x2000>(0x9E37_79B9)))
+            }
+            PowerOnMemory::Zeroed => None,
+        }
+    }
+
+    pub(crate) fn oam(self) -> Oam {
+        match self {
+            PowerOnMemory::Uninitialised => Oam::from_bytes(&garbage::<0xA0>(0x2545_F491)),
+            PowerOnMemory::Dmg | PowerOnMemory::Zeroed => Oam::default(),
+        }
+    }
+}
+
+/// A fixed xorshift32 stream from `seed`, standing in for SRAM's power-on
+/// noise.
+fn garbage<const N: usize>(seed: u32) -> [u8; N] {
+    let mut state = seed;
+    std::array::from_fn(|_| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as u8
+    })
+}
+
 /// High RAM (0xFF80–0xFFFE): 127 bytes of SoC-internal SRAM. Not on
 /// either bus — always accessible to the CPU, even during OAM DMA.
-pub struct HighRam([u8; 0x7F]);
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub struct HighRam(
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))] [u8; 0x7F],
+);
 
 impl HighRam {
     pub fn new() -> Self {
@@ -41,6 +114,10 @@ impl HighRam {
     pub fn data(&self) -> &[u8; 0x7F] {
         &self.0
     }
+
+    pub fn data_mut(&mut self) -> &mut [u8; 0x7F] {
+        &mut self.0
+    }
 }
 
 /// Address on the external data bus: cartridge or work RAM.
@@ -55,9 +132,22 @@ pub enum ExternalAddress {
 /// over 0x0000–0x00FF; the CGB boot ROM is 2304 bytes over 0x0000–0x00FF
 /// AND 0x0200–0x08FF, leaving the 0x0100–0x01FF cartridge-header window
 /// visible so the boot ROM can read the header it inspects.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum BootRom {
-    Dmg(Box<[u8; 0x100]>),
-    Cgb(Box<[u8; 0x900]>),
+    Dmg(
+        #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
+        Box<[u8; 0x100]>,
+    ),
+    Cgb(
+        #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
+        Box<[u8; 0x900]>,
+    ),
+    /// A built-in, purely cosmetic stand-in for the DMG boot ROM: it scrolls
+    /// the cartridge's logo and plays the chime, then waits for
+    /// [`Console::finish_logo_boot`](crate::Console::finish_logo_boot) to
+    /// start the game in the normal post-boot state. Laid out like `Dmg`.
+    Logo,
 }
 
 impl BootRom {
@@ -66,6 +156,7 @@ impl BootRom {
     fn overlay_byte(&self, addr: u16) -> Option<u8> {
         match self {
             BootRom::Dmg(rom) if addr <= 0x00FF => Some(rom[addr as usize]),
+            BootRom::Logo if addr <= 0x00FF => Some(crate::boot_logo::byte(addr)),
             BootRom::Cgb(rom) if addr <= 0x00FF || (0x0200..=0x08FF).contains(&addr) => {
                 Some(rom[addr as usize])
             }
@@ -77,8 +168,11 @@ impl BootRom {
 /// The external data bus connects the SoC to the cartridge and (on
 /// DMG) to work RAM. The bus retains its last driven value through
 /// parasitic capacitance, decaying toward 0xFF when idle.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExternalBus {
     pub cartridge: Cartridge,
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub(crate) work_ram: [u8; 0x2000],
 
     /// Retained value on the data bus. Updated on every CPU read/write
@@ -94,13 +188,15 @@ pub struct ExternalBus {
     /// True while the boot ROM overlay is active. Cleared by writing
     /// to 0xFF50.
     boot_rom_mapped: bool,
+    power_on: PowerOnMemory,
 }
 
 impl ExternalBus {
     pub fn new(cartridge: Cartridge, boot_rom: Option<BootRom>) -> Self {
         let boot_rom_mapped = boot_rom.is_some();
+        let power_on = PowerOnMemory::default();
         let mut work_ram = [0; 0x2000];
-        dmg_sram::fill(&mut work_ram);
+        power_on.fill_work_ram(&mut work_ram);
         Self {
             cartridge,
             work_ram,
@@ -108,6 +204,7 @@ impl ExternalBus {
             decay: 0,
             boot_rom,
             boot_rom_mapped,
+            power_on,
         }
     }
 
@@ -133,18 +230,30 @@ impl ExternalBus {
         self.boot_rom_mapped
     }
 
+    /// True while [`BootRom::Logo`] is mapped and playing.
+    pub fn logo_boot_mapped(&self) -> bool {
+        self.boot_rom_mapped && matches!(self.boot_rom, Some(BootRom::Logo))
+    }
+
+    pub fn power_on_memory(&self) -> PowerOnMemory {
+        self.power_on
+    }
+
+    pub fn set_power_on_memory(&mut self, power_on: PowerOnMemory) {
+        self.power_on = power_on;
+    }
+
     pub fn unmap_boot_rom(&mut self) {
         self.boot_rom_mapped = false;
     }
 
-    /// Reset volatile state for a power-cycle: clear WRAM (filled with
-    /// the same DMG SRAM pattern as a fresh power-on), clear the data-
+    /// Reset volatile state for a power-cycle: refill WRAM with its
+    /// power-on contents, clear the data-
     /// bus latch and decay timer, and re-map the boot ROM if present.
     /// Preserves the cartridge (including its MBC/SRAM state) and the
     /// boot ROM contents.
     pub fn reset(&mut self) {
-        self.work_ram = [0; 0x2000];
-        dmg_sram::fill(&mut self.work_ram);
+        self.power_on.fill_work_ram(&mut self.work_ram);
         self.latch = 0xFF;
         self.decay = 0;
         self.boot_rom_mapped = self.boot_rom.is_some();
@@ -186,6 +295,9 @@ impl ExternalBus {
 
 /// The VRAM data bus connects the SoC to video RAM (0x8000–0x9FFF).
 /// The bus retains its last driven value as a latch (no decay).
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "save-state", serde(bound = ""))]
 pub struct VramBus<V: Vram> {
     pub vram: V,
     /// Retained value on the VRAM data bus.
@@ -215,6 +327,7 @@ impl<V: Vram> VramBus<V> {
 /// During OAM DMA the controller occupies one bus and the CPU can
 /// still freely access the other.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bus {
     External,
     Vram,
@@ -325,6 +438,9 @@ impl<M: Model> Console<M> {
             value,
             kind: BusAccessKind::Read,
         });
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.record_read(address);
+        }
         self.drive_bus(address, value);
     }
 
@@ -358,6 +474,47 @@ impl<M: Model> Console<M> {
         self.read_addr(address)
     }
 
+    /// Store a byte straight into VRAM (the VBK-selected bank), OAM, work
+    /// RAM, or high RAM for tooling. Unlike a CPU store it ignores the PPU's
+    /// VRAM/OAM locks, OAM DMA, and OAM corruption. Anything else (ROM,
+    /// cartridge RAM, I/O) would reach the mapper or a register's side
+    /// effects, so it is refused; returns whether the byte was stored.
+    pub fn poke_raw(&mut self, address: u16, value: u8) -> bool {
+        if !matches!(address, 0x8000..=0x9FFF | 0xC000..=0xFE9F | 0xFF80..=0xFFFE) {
+            return false;
+        }
+        if !self
+            .model
+            .map_write(address, value, &mut self.ppu, &mut self.vram_bus.vram)
+        {
+            self.write_mapped(MappedAddress::map(address), value);
+        }
+        true
+    }
+
+    /// Store `bytes` into VRAM bank `bank` from `address` on, for tooling.
+    /// Like [`poke_raw`](Self::poke_raw) it ignores the PPU's locks, but it
+    /// reaches either CGB bank whatever VBK selects. Refused, storing
+    /// nothing, for a bank the console doesn't have or bytes that would run
+    /// past $9FFF.
+    pub fn poke_vram(&mut self, bank: u8, address: u16, bytes: &[u8]) -> bool {
+        let vram = &mut self.vram_bus.vram;
+        let end = address as usize + bytes.len();
+        if bank >= vram.bank_count() || address < 0x8000 || end > 0xA000 {
+            return false;
+        }
+        let selected = vram.read_bank_select();
+        vram.write_bank_select(bank);
+        for (address, &value) in (address..).zip(bytes) {
+            let MappedAddress::Vram(address) = MappedAddress::map(address) else {
+                unreachable!("$8000-$9FFF maps to VRAM");
+            };
+            vram.cpu_write(address, value);
+        }
+        vram.write_bank_select(selected);
+        true
+    }
+
     /// Value the addressed peripheral first drives onto the CPU bus at
     /// the driver-enable edge (tobe↑ / wafu↑ early in T-cycle 2). DMA
     /// bus redirection happens here; the OAM/VRAM lock is a property
@@ -424,7 +581,11 @@ impl<M: Model> Console<M> {
                 const X_WINDOW: u8 = 0b0000_0111;
                 if self.cpu_steps_per_dot() == 2 {
                     const FAST_BITS: u8 = 0b0000_0101;
-                    let mode2 = if self.ppu.stat_mode2_bus() { 0b0000_0010 } else { 0 };
+                    let mode2 = if self.ppu.stat_mode2_bus() {
+                        0b0000_0010
+                    } else {
+                        0
+                    };
                     (snapshot & !X_WINDOW) | (snapshot & live & FAST_BITS) | mode2
                 } else {
                     (snapshot & !X_WINDOW) | (snapshot & live & X_WINDOW)
@@ -585,6 +746,9 @@ impl<M: Model> Console<M> {
             value,
             kind: BusAccessKind::Write,
         });
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.record_write(address);
+        }
         if self.dma.is_active_on_bus().is_some() {
             // The OAM block (extra rows included) is being written by DMA;
             // CPU writes are ignored.
@@ -211,6 +211,7 @@ impl<P: PpuModel> Ppu<P> {
             return result;
         }
         if self.pixel_pipeline.is_some() {
+            self.lcd_off_position = self.ppu_position();
             self.pixel_pipeline = None;
             self.registers.clear_latches();
             result.lcd_disabled = true;
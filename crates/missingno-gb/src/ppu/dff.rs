@@ -1,5 +1,7 @@
 /// DFF register cell: holds output and an optional pending value resolved after
 /// `commit_in` ticks (1 = the next tick — the default mid-Mode-3 write).
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct DffLatch {
     pub(super) output: u8,
     pub(super) pending: Option<u8>,
@@ -78,6 +80,8 @@ impl DffLatch {
 
 /// Combinational NOR-latch (cross-coupled NOR pair; no clock).
 /// Use for RYDY, PYNU, REJO, XYMU, WUSA. Use `DffLatch` for clocked DFFs.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct NorLatch {
     output: bool,
 }
@@ -1,5 +1,7 @@
 //! WUVU/VENA divider cascade clocked off XOTA.
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dividers {
     /// WUVU.Q — 2-dot period (half M-cycle).
     pub(in crate::ppu) half_mcycle: bool,
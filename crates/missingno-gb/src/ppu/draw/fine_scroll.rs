@@ -1,5 +1,6 @@
 /// ROXY NOR-latch: gates SACU until the fine counter matches SCX & 7. One-shot per line.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 enum Roxy {
     Gating,
     Done,
@@ -13,6 +14,8 @@ enum Roxy {
 /// Known divergence: POVA is modelled as a single-tick rising-edge pulse rather than hardware's
 /// ~1-dot level-AND; the missing SEMU=OR2(TOBA, POVA) contribution is not wired because cp_pad
 /// is not modelled. Benign at the ROXY-clear consumer.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct FineScroll {
     /// 3-bit counter (0–7).
     pub(in crate::ppu) count: u8,
@@ -3,6 +3,8 @@
 /// XUGU NAND5 decode: PX bits 0+1+2+5+7 = 167.
 const TERMINAL_MASK: u8 = 0b1010_0111;
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct PixelCounter(u8);
 
 impl PixelCounter {
@@ -17,6 +17,8 @@ pub enum SpriteFetchPhase {
 /// 6-dot sprite data fetch. Collapses the 3-bit ripple counter (TOXE/TULY/TESE) into a u8,
 /// the fetch-done decode (WUTY) into the counter==5 return, and the 16 sprite temp-latch cells
 /// into `tile_data_low` / `tile_data_high`.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct SpriteFetch {
     pub(in crate::ppu) entry: SpriteStoreEntry,
     /// Used to set the per-slot fetched-flag at WUTY↑.
@@ -164,6 +166,8 @@ impl SpriteFetch {
 }
 
 /// FEPO (sprite X match) freezes SACU; the fetch runs; SACU resumes on the next dot.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) enum SpriteState {
     Idle,
     Fetching(SpriteFetch),
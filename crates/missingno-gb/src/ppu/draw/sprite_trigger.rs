@@ -2,6 +2,8 @@
 //!
 //! RYCE = AND2(!SUDA, SOBU); TAKA is the sprite-fetch-running NAND-latch.
 //! TAKA carries over across scanlines until VEKU clears it.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct SpriteTrigger {
     /// SOBU captures TEKY on ALET rising.
     sobu: bool,
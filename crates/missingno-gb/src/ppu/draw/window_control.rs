@@ -3,6 +3,7 @@ use crate::ppu::{DffLatch, NorLatch, PipelineRegisters, PpuModel, VideoControl};
 
 /// WY/WX/LCDC.5/LCDC.2 as one word crossing the register-file synchroniser.
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 struct RegisterWord {
     wy: u8,
     wx: u8,
@@ -16,6 +17,8 @@ struct RegisterWord {
 /// coinciding TALU↑ capture reads the pre-tick output (DFF chain); the
 /// trigger chain (XOFO, the NUKO slave) and the scan comparator read
 /// post-tick.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 struct RegisterSync {
     pending: RegisterWord,
     output: RegisterWord,
@@ -64,6 +67,8 @@ use super::fine_scroll::FineScroll;
 /// - PYNU nor_latch: S=NUNU, R=XOFO; re-evaluated on both edges.
 /// - REJO nor_latch: S=SARY.q, R=REPU (vblank); re-evaluated on both edges.
 /// - NUNY = AND2(PYNU, NOPA_n). MOSU↑ fires on NUNY 0→1.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct WindowControl {
     /// Window-hit (RYDY nor3 + PUKU feedback). Set on NUNY rise; cleared by PORY during cascade restart.
     rydy: NorLatch,
@@ -222,7 +227,8 @@ impl WindowControl {
         fepo: bool,
         regs: &PipelineRegisters,
     ) {
-        if P::ENABLE_QUALIFIED_WINDOW_HIT && self.compute_xofo(regs, P::WINDOW_CROSSING.is_synced()) {
+        if P::ENABLE_QUALIFIED_WINDOW_HIT && self.compute_xofo(regs, P::WINDOW_CROSSING.is_synced())
+        {
             self.pyco.write_immediate(0);
             return;
         }
@@ -7,17 +7,23 @@ use crate::ppu::line_end_pipeline::LineEndEdge;
 /// LX value SANU decodes as scanline-end (113 = last dot before the RUTU pulse).
 const SANU_DECODE_LX: u8 = 113;
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineCounter {
     pub x: LineCounterX,
     pub y: LineCounterY,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineCounterX {
     pub(in crate::ppu) value: u8,
     pub(in crate::ppu) line_end_detected: bool,
     pub(in crate::ppu) line_end_active: bool,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineCounterY {
     pub(in crate::ppu) value: u8,
     pub(in crate::ppu) vblank: bool,
@@ -8,6 +8,8 @@ pub(in crate::ppu) enum LineEndEdge {
     None,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineEndPipeline {
     pub(in crate::ppu) delayed_line_end: bool,
     /// Pending NYPE D input; set when RUTU fires, consumed at next TALU rising.
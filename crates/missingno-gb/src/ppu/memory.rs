@@ -2,10 +2,12 @@ use super::types::{
     sprites::{self, Sprite, SpriteId},
     tiles::{TileBlock, TileBlockId, TileIndex, TileMap, TileMapId},
 };
+use crate::save_state::Persist;
 
 /// One 8 KiB VRAM bank (0x8000–0x9FFF): tile data and tile maps. The DMG has a
 /// single bank; the CGB has two (the second carrying BG map attributes).
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct VramBank {
     pub(crate) tiles: [TileBlock; 3],
     pub(crate) tile_maps: [TileMap; 2],
@@ -75,6 +77,15 @@ impl VramBank {
         }
     }
 
+    /// The bank as a contiguous copy, laid out as the CPU sees 0x8000–0x9FFF.
+    pub fn to_bytes(&self) -> [u8; 0x2000] {
+        let mut bytes = [0; 0x2000];
+        for (offset, byte) in bytes.iter_mut().enumerate() {
+            *byte = self.read_byte(offset as u16);
+        }
+        bytes
+    }
+
     /// Populate this bank with the state the DMG boot ROM leaves behind: decompressed
     /// Nintendo logo tiles (1-24), ® (tile 25), and tile-map entries. `logo` is
     /// the 48-byte logo region of the cartridge header (0x0104-0x0133).
@@ -114,13 +125,17 @@ impl VramBank {
 /// A console's video RAM: the DMG's single [`VramBank`], or the CGB's two banks
 /// behind a VBK ($FF4F) bank select. The CPU sees the VBK-selected bank; the
 /// pixel fetch addresses banks explicitly.
-pub trait Vram: Default {
+pub trait Vram: Default + Clone + Persist {
     /// CPU read of $8000–$9FFF (the VBK-selected bank on the CGB).
     fn cpu_read(&self, address: VramAddress) -> u8;
     /// CPU write of $8000–$9FFF (the VBK-selected bank on the CGB).
     fn cpu_write(&mut self, address: VramAddress, value: u8);
     /// The bank the pixel fetch reads. The DMG ignores `bank` (one bank only).
     fn bank(&self, bank: u8) -> &VramBank;
+    /// Number of banks (1 on the DMG, 2 on the CGB).
+    fn bank_count(&self) -> u8 {
+        1
+    }
     /// VBK ($FF4F). The DMG has no bank select — reads 0xFF, writes ignored.
     fn read_bank_select(&self) -> u8 {
         0xFF
@@ -128,6 +143,8 @@ pub trait Vram: Default {
     fn write_bank_select(&mut self, _value: u8) {}
     /// Seed bank 0 with the post-boot logo state.
     fn init_post_boot(&mut self, logo: &[u8; 0x30]);
+    /// Power on with every bank holding `contents`.
+    fn power_on(&mut self, contents: &VramBank);
 }
 
 impl Vram for VramBank {
@@ -146,10 +163,17 @@ impl Vram for VramBank {
     fn init_post_boot(&mut self, logo: &[u8; 0x30]) {
         self.seed_post_boot(logo);
     }
+
+    fn power_on(&mut self, contents: &VramBank) {
+        *self = contents.clone();
+    }
 }
 
 /// Sprite attribute memory (0xFE00–0xFE9F): 40 sprites × 4 bytes. SoC-internal.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Oam {
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     sprites: [Sprite; 40],
 }
 
@@ -218,6 +242,15 @@ impl Oam {
         }
     }
 
+    /// OAM as a contiguous copy, laid out as the CPU sees 0xFE00–0xFE9F.
+    pub fn to_bytes(&self) -> [u8; 0xA0] {
+        let mut bytes = [0; 0xA0];
+        for (offset, byte) in bytes.iter_mut().enumerate() {
+            *byte = self.oam_byte(offset as u8);
+        }
+        bytes
+    }
+
     pub(in crate::ppu) fn set_oam_byte(&mut self, offset: u8, value: u8) {
         let sprite = &mut self.sprites[(offset / 4) as usize];
         match offset % 4 {
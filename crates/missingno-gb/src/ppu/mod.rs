@@ -116,6 +116,9 @@ pub enum Register {
     Sprite1Palette,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "save-state", serde(bound = ""))]
 pub struct Ppu<P: PpuModel> {
     /// `None` while LCD is off (VID_RST asserted).
     pub(super) pixel_pipeline: Option<Rendering<P>>,
@@ -138,6 +141,9 @@ pub struct Ppu<P: PpuModel> {
     /// detect that 0→1. The OAM gate has no companion-driver settle of its own.
     oam_onset_settle: u8,
     prev_oam_locked: bool,
+    /// Where the raster was when the LCD last went off, which
+    /// [`ppu_position`](Self::ppu_position) reports until it comes back on.
+    pub(super) lcd_off_position: (u8, u16),
     /// The console's colour hardware (CRAM, OPRI, …); the DMG impl is a unit.
     pub(super) model: P,
 }
@@ -201,6 +207,7 @@ impl<P: PpuModel> Ppu<P> {
             prev_mode3: false,
             oam_onset_settle: 0,
             prev_oam_locked: false,
+            lcd_off_position: (0, 0),
             model: P::default(),
         }
     }
@@ -338,6 +345,7 @@ impl<P: PpuModel> Ppu<P> {
             prev_mode3: false,
             oam_onset_settle: 0,
             prev_oam_locked: false,
+            lcd_off_position: (0, 0),
             model: P::default(),
         };
         let shadow = ppu.model.stat_shadow_mut();
@@ -371,6 +379,37 @@ impl<P: PpuModel> Ppu<P> {
         self.video.dot_position()
     }
 
+    /// Internal LY (0-153). Unlike the LY register, line 153 reads as 153.
+    pub fn ly_hardware(&self) -> u8 {
+        self.video.ly_hardware()
+    }
+
+    /// Internal LY and the dot within that line (0-455), for showing where
+    /// the raster is. LX counts M-cycles and VENA/WUVU the dots within one,
+    /// but LX holds 0 through the RUTU pulse, so it spans dots 0-5 and
+    /// every later value starts two dots into its M-cycle. With the LCD off
+    /// the counters are held at 0, but this freezes where the raster stopped
+    /// so an indicator stays put; it picks up from line 0 when the LCD comes
+    /// back on.
+    pub fn ppu_position(&self) -> (u8, u16) {
+        if self.pixel_pipeline.is_none() {
+            return self.lcd_off_position;
+        }
+        let dividers = &self.video.dividers;
+        let phase = match (dividers.mcycle, dividers.half_mcycle) {
+            (true, false) => 0,
+            (true, true) => 1,
+            (false, false) => 2,
+            (false, true) => 3,
+        };
+        let dot = if self.video.line_end_active() {
+            (phase + 2) % 4
+        } else {
+            u16::from(self.lx()) * 4 + 2 + phase
+        };
+        (self.ly_hardware(), dot)
+    }
+
     /// MEDA has gone 0→1 since the most recent VID_RST deassertion — first VSYNC has fired.
     pub fn vsync_committed(&self) -> bool {
         self.video.line_end.vsync_committed
@@ -14,6 +14,7 @@ use super::registers::PipelineRegisters;
 use super::stat_interrupt::StatShadow;
 use super::types::palette::{PaletteIndex, PaletteMap};
 use super::types::sprites::{self, ObjAttr};
+use crate::save_state::Persist;
 
 /// A CGB colour-palette RAM port. BCPS/BCPD ($FF68/9) address BG palettes;
 /// OCPS/OCPD ($FF6A/B) address OBJ palettes. Index ports are always accessible;
@@ -54,7 +55,7 @@ pub struct CartridgeBootHeader {
 /// The hardware that differs between the DMG and CGB PPUs. The shared pipeline
 /// resolves a pixel by calling [`PpuModel::resolve`]; the result is the final
 /// framebuffer pixel for that console.
-pub trait PpuModel: Default {
+pub trait PpuModel: Default + Clone + Persist {
     /// The DMG window-X comparator (NUKO) drives the §6.1 PANY BG drain-detector
     /// slip whenever the window is armed (REJO), even with WIN_EN off — an
     /// armed-but-disabled 1-dot BG slip. The CGB suppresses that coupling: its
@@ -81,7 +82,7 @@ pub trait PpuModel: Default {
 
     /// Per-tile BG data riding the shifter beyond the two bitplanes: `()` on the
     /// DMG (the BG map has no attribute), the BG map attribute byte on the CGB.
-    type BgCell: Copy + Default;
+    type BgCell: Copy + Default + Persist;
 
     /// The framebuffer pixel this PPU emits — DMG a 2-bit shade index, CGB RGB555.
     type Pixel: Copy;
@@ -124,7 +125,7 @@ pub trait PpuModel: Default {
     /// 1-bit OBP-select; the CGB resolves by OAM index with a 3-bit palette. The
     /// whole FIFO is opaque to the shared pipeline — only the neutral operations
     /// below cross the seam.
-    type ObjFifo: Default;
+    type ObjFifo: Default + Clone + Persist;
 
     /// SACU shift toward the LCD.
     fn obj_shift(fifo: &mut Self::ObjFifo);
@@ -356,7 +357,8 @@ pub fn resolve_shade<C>(mux: &PixelMux<C>, regs: &PipelineRegisters) -> u8 {
 }
 
 /// The original Game Boy PPU: a 2-bit shade per pixel, no colour memory.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct DmgPpu {
     /// The STAT-IRQ block reads the cells combinationally — the synchroniser is
     /// a ZST.
@@ -5,13 +5,16 @@
 use super::{Ppu, PpuModel};
 
 /// Read corruption takes priority over write if both are armed in the same M-cycle.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(super) enum OamBugKind {
     Read,
     Write,
 }
 
 /// `armed = Some` means a CUFE pulse fired in the BOWA→MOPA window.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct OamCorruption {
     pub(super) armed: Option<OamBugKind>,
 }
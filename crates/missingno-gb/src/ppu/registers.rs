@@ -4,11 +4,15 @@ use super::types::palette::Palettes;
 use super::types::sprites::SpriteSize;
 use super::types::tiles::TileAddressMode;
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct BackgroundViewportPosition {
     pub x: DffLatch,
     pub y: DffLatch,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Window {
     pub y: u8,
     pub x: DffLatch,
@@ -18,7 +22,8 @@ pub struct Window {
 /// pre-write value on a CPU write site and holds it for `hold` falls so the
 /// BG/OBJ resolve still sees OLD, then clears. The base hold of 1 covers the
 /// same fall's tick; CGB's clock-domain write lag (e.g. VYXE/RAJY) adds one more.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct OldOverlay {
     value: Option<bool>,
     hold: u8,
@@ -53,7 +58,8 @@ impl OldOverlay {
 /// CGB TILE_SEL reset glitch: an LCDC.4-clearing write reaches the tile-data
 /// addressing at the crossing-capture dot; a bitplane read on that dot returns
 /// the tile index byte instead of VRAM data. Live for one dot.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct TileSelResetGlitch {
     pending: bool,
     active: bool,
@@ -80,6 +86,8 @@ impl TileSelResetGlitch {
 }
 
 /// CPU → pixel pipeline register file (DFF bank). DFF8/DFF9 write-conflict behaviour during Mode 3 is specific to this group.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct PipelineRegisters {
     pub control: Control,
     /// DFF9 latch for full LCDC byte. `write_immediate`-only (no delayed LCDC
@@ -110,6 +110,9 @@ pub struct PipelineSnapshot {
     pub scan_done_prev: bool,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "save-state", serde(bound = ""))]
 pub struct Rendering<P: PpuModel> {
     /// FEPO → WODU → VOGA → WEGO → clears XYMU.
     hblank: HblankPipeline,
@@ -10,6 +10,7 @@ pub(in crate::ppu) const MAX_SPRITES_PER_LINE: usize = 10;
 const FETO_SCAN_DONE_DECODE: u8 = 0b100111;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct SpriteStoreEntry {
     /// OAM sprite number (0-39).
     pub(in crate::ppu) oam_index: u8,
@@ -19,6 +20,8 @@ pub(in crate::ppu) struct SpriteStoreEntry {
     pub(in crate::ppu) x: u8,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct SpriteStore {
     pub(in crate::ppu) entries: [SpriteStoreEntry; MAX_SPRITES_PER_LINE],
     pub(in crate::ppu) count: u8,
@@ -42,6 +45,8 @@ impl SpriteStore {
 
 /// YFEL-FONY 6-bit scan counter with combinational Y comparator. Clocked by GAVA = OR2(XUPY, FETO);
 /// freezes at 39 when FETO holds GAVA high.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct ScanCounter {
     entry: u8,
     /// GAVA held high by FETO; counter frozen at 39.
@@ -6,6 +6,8 @@ use crate::ppu::memory::Oam;
 use super::oam_scan::{ScanCounter, SpriteStore};
 
 /// Scan counter, BESU latch, BYBA/DOBA pipeline, and 10-entry sprite store. AVAP signals Mode 2→3.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct SpriteScanner {
     /// YFEL-FONY 6-bit scan counter + Y comparator.
     counter: ScanCounter,
@@ -1,10 +1,23 @@
-use super::types::palette::PaletteIndex;
+use rgb::RGB8;
+
+use super::types::palette::{Palette, PaletteChoice, PaletteIndex};
 
 pub const NUM_SCANLINES: u8 = 144;
 pub const PIXELS_PER_LINE: u8 = 160;
 
+/// Bytes in a frame of packed RGBA.
+pub const RGBA_LEN: usize = PIXELS_PER_LINE as usize * NUM_SCANLINES as usize * 4;
+
+/// Bytes in a frame of packed RGB.
+pub const RGB_LEN: usize = PIXELS_PER_LINE as usize * NUM_SCANLINES as usize * 3;
+
+/// Grey level of each colour number in [`Screen::to_greyscale`], as in the
+/// reference images accuracy tests compare against.
+pub const GREYSCALE: [u8; 4] = [0xFF, 0xAA, 0x55, 0x00];
+
 /// Double-buffered LCD screen. Heap-allocated to keep `Screen` cheap to move through message enums.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Screen {
     front: Box<Framebuffer>,
     back: Box<Framebuffer>,
@@ -21,9 +34,19 @@ impl Default for Screen {
 
 impl Screen {
     pub fn pixel(&self, x: u8, y: u8) -> PaletteIndex {
+        debug_assert!(
+            x < PIXELS_PER_LINE && y < NUM_SCANLINES,
+            "pixel ({x}, {y}) is off screen"
+        );
         self.front.pixels[y as usize][x as usize]
     }
 
+    /// The displayed frame, top scanline first. Cheaper than [`pixel`](Self::pixel)
+    /// for converting a whole frame.
+    pub fn rows(&self) -> impl Iterator<Item = &[PaletteIndex; PIXELS_PER_LINE as usize]> {
+        self.front.pixels.iter()
+    }
+
     pub fn draw_pixel(&mut self, x: u8, y: u8, pixel: PaletteIndex) {
         self.back.pixels[y as usize][x as usize] = pixel;
     }
@@ -43,6 +66,54 @@ impl Screen {
     pub fn front(&self) -> &Framebuffer {
         &self.front
     }
+
+    /// The displayed frame's colours in `palette`, top scanline first. The
+    /// one shade mapping behind every RGB conversion, so they all agree.
+    fn colors(&self, palette: &Palette) -> impl Iterator<Item = RGB8> + '_ {
+        let colors = [0, 1, 2, 3].map(|shade| palette.color(PaletteIndex(shade)));
+        self.front
+            .pixels
+            .as_flattened()
+            .iter()
+            .map(move |pixel| colors[pixel.0 as usize & 0b11])
+    }
+
+    /// Write the displayed frame into `rgba` as packed RGBA, top scanline
+    /// first with alpha 255, ready to upload to a texture. Reuse the buffer
+    /// between frames to convert without allocating.
+    pub fn write_rgba(&self, palette: &Palette, rgba: &mut [u8; RGBA_LEN]) {
+        for (color, out) in self.colors(palette).zip(rgba.as_chunks_mut::<4>().0) {
+            *out = [color.r, color.g, color.b, 0xFF];
+        }
+    }
+
+    /// [`write_rgba`](Self::write_rgba) into a new buffer.
+    pub fn to_rgba(&self, palette: &Palette) -> Vec<u8> {
+        let mut rgba = vec![0; RGBA_LEN];
+        self.write_rgba(palette, rgba.as_mut_slice().try_into().unwrap());
+        rgba
+    }
+
+    /// The displayed frame as packed RGB with no alpha, for encoders and
+    /// recordings, in the display palette the user picked. Shades match
+    /// [`write_rgba`](Self::write_rgba); a blank screen, as when the LCD is
+    /// off, is all colour 0.
+    pub fn to_rgb8(&self, palette: &PaletteChoice) -> Vec<u8> {
+        self.colors(palette.palette())
+            .flat_map(|color| [color.r, color.g, color.b])
+            .collect()
+    }
+
+    /// The displayed frame as one byte of [`GREYSCALE`] per pixel, top
+    /// scanline first.
+    pub fn to_greyscale(&self) -> Vec<u8> {
+        self.front
+            .pixels
+            .as_flattened()
+            .iter()
+            .map(|pixel| GREYSCALE[pixel.0 as usize & 0b11])
+            .collect()
+    }
 }
 
 impl crate::ScreenBuffer for Screen {
@@ -56,10 +127,18 @@ impl crate::ScreenBuffer for Screen {
     fn blank(&mut self) {
         Screen::blank(self);
     }
+    fn heap_size(&self) -> usize {
+        2 * std::mem::size_of::<Framebuffer>()
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Framebuffer {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "crate::save_state::big_array::nested")
+    )]
     pub pixels: [[PaletteIndex; PIXELS_PER_LINE as usize]; NUM_SCANLINES as usize],
 }
 
@@ -5,6 +5,7 @@ use bitflags::bitflags;
 
 bitflags! {
     #[derive(Copy, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
     pub struct InterruptFlags: u8 {
         const DUMMY                = 0b10000000;
         const CURRENT_LINE_COMPARE = 0b01000000;
@@ -20,7 +21,7 @@ bitflags! {
 /// block reads these copies. Lives on the model — the CGB owns the real cells
 /// ([`SyncedStatCells`]), the DMG a ZST `()`, since the DMG feeds the legs and
 /// comparator combinationally off the cells and never crosses the domain.
-pub trait StatShadow {
+pub trait StatShadow: Clone {
     fn synced_enables(&self) -> InterruptFlags;
     fn set_synced_enables(&mut self, value: InterruptFlags);
     /// PALY's LYC input. DMG reads the cell directly (no synchroniser), so its
@@ -30,7 +31,8 @@ pub trait StatShadow {
 }
 
 /// The CGB FF41/FF45 synchroniser DFFs.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyncedStatCells {
     enables: InterruptFlags,
     lyc: u8,
@@ -68,6 +70,8 @@ impl Default for InterruptFlags {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatInterrupt {
     /// LYC register ($FF45).
     pub(in crate::ppu) lyc: u8,
@@ -4,6 +4,7 @@ use bitflags::bitflags;
 
 bitflags! {
     #[derive(Copy, Clone)]
+    #[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
     pub struct ControlFlags: u8 {
         const VIDEO_ENABLE                 = 0b10000000;
         const WINDOW_TILE_MAP              = 0b01000000;
@@ -17,6 +18,7 @@ bitflags! {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Control(ControlFlags);
 
 impl Default for Control {
@@ -7,7 +7,8 @@ pub struct Palette {
     colors: [RGB8; 4],
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct PaletteIndex(pub u8);
 
 impl Palette {
@@ -33,6 +34,7 @@ impl Palette {
         ],
     };
 
+    /// Plain greyscale, the shades test references are drawn in.
     pub const CLASSIC: Self = Self {
         colors: [
             RGB8::new(0xff, 0xff, 0xff),
@@ -94,7 +96,8 @@ use super::super::DffLatch;
 /// BGP NURA-combiner recovery state. While `active`, a BGP CUPA on a
 /// dot where the LCD has already emitted a pixel produces the OR
 /// overlay on the cp_pad sample; otherwise the new value lands clean.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub(in crate::ppu) struct BgpRecovery {
     /// OR(prior, new) presented on the cp_pad sample when a same-tick
     /// BGP write engages the recovery overlap.
@@ -160,6 +163,8 @@ impl BgpRecovery {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Palettes {
     pub background: DffLatch,
     pub sprite0: DffLatch,
@@ -177,6 +182,7 @@ pub struct Palettes {
 }
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeferredBgpWrite {
     pub value: u8,
     pub ticks_remaining: u8,
@@ -5,6 +5,7 @@ use bitflags::bitflags;
 use crate::ppu::types::tiles::TileIndex;
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sprite {
     pub position: Position,
     pub tile: TileIndex,
@@ -24,6 +25,7 @@ impl Default for Sprite {
 impl Sprite {}
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub x: u8,
     pub y: u8,
@@ -52,6 +54,7 @@ impl Position {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attributes(pub u8);
 
 bitflags! {
@@ -120,6 +123,7 @@ impl Attributes {
 pub struct SpriteId(pub u8);
 
 #[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpriteSize {
     Single,
     Double,
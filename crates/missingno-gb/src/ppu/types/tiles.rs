@@ -12,7 +12,9 @@ impl fmt::Display for TileBlockId {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct TileBlock {
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub data: [u8; 0x800],
 }
 
@@ -29,9 +31,20 @@ impl TileBlock {
             data: self.data[offset..offset + 16].try_into().unwrap(),
         }
     }
+
+    /// Bit `n` is set when tile `n` differs from `previous`.
+    pub fn changed_tiles(&self, previous: &TileBlock) -> u128 {
+        self.data
+            .chunks_exact(16)
+            .zip(previous.data.chunks_exact(16))
+            .enumerate()
+            .filter(|(_, (now, before))| now != before)
+            .fold(0, |mask, (index, _)| mask | 1 << index)
+    }
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct TileIndex(pub u8);
 
 #[derive(Debug, Clone, Copy)]
@@ -40,6 +53,36 @@ pub struct Tile {
 }
 
 impl Tile {
+    /// A tile from 16 bytes of 2bpp data, as VRAM holds it.
+    pub fn from_bytes(data: [u8; 16]) -> Self {
+        Self { data }
+    }
+
+    /// The tile's 2bpp data: two bytes per row, top row first. The first of
+    /// each pair holds bit 0 of every pixel's colour number and the second
+    /// bit 1, leftmost pixel in bit 7.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.data
+    }
+
+    /// Encode 64 colour numbers, row by row, as 2bpp. Only the low two bits
+    /// of each are used.
+    pub fn from_pixels(pixels: &[u8; 64]) -> Self {
+        let mut data = [0; 16];
+        for (y, row) in pixels.chunks_exact(8).enumerate() {
+            for (x, colour) in row.iter().enumerate() {
+                data[y * 2] |= (colour & 0b1) << (7 - x);
+                data[y * 2 + 1] |= (colour >> 1 & 0b1) << (7 - x);
+            }
+        }
+        Self { data }
+    }
+
+    /// The tile's colour numbers, row by row.
+    pub fn pixels(&self) -> [u8; 64] {
+        std::array::from_fn(|i| self.pixel(i as u8 % 8, i as u8 / 8).0)
+    }
+
     pub fn pixel(&self, x: u8, y: u8) -> PaletteIndex {
         let low_byte = self.data[y as usize * 2];
         let high_byte = self.data[(y as usize * 2) + 1];
@@ -78,7 +121,9 @@ impl fmt::Display for TileAddressMode {
 }
 
 #[derive(Copy, Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct TileMap {
+    #[cfg_attr(feature = "save-state", serde(with = "crate::save_state::big_array"))]
     pub data: [TileIndex; 0x400],
 }
 
@@ -5,6 +5,8 @@ use crate::ppu::line_counter::LineCounter;
 use crate::ppu::line_end_pipeline::{LineEndEdge, LineEndPipeline};
 use crate::ppu::stat_interrupt::{StatInterrupt, StatShadow};
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct VideoControl {
     pub dividers: Dividers,
     pub lines: LineCounter,
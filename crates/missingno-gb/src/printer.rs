@@ -0,0 +1,304 @@
+//! The Game Boy Printer, on the far end of the link cable.
+//!
+//! The Game Boy clocks every byte; the printer answers each with a byte of
+//! its own. Commands arrive as packets:
+//!
+//! | Bytes | Contents                                     | Printer sends |
+//! |-------|----------------------------------------------|---------------|
+//! | 2     | Magic, $88 $33                               | $00           |
+//! | 1     | Command                                      | $00           |
+//! | 1     | Compression, 1 for run-length encoded data   | $00           |
+//! | 2     | Data length, little-endian                   | $00           |
+//! | n     | Data                                         | $00           |
+//! | 2     | Checksum: the sum of command through data    | $00           |
+//! | 1     | $00                                          | $81, alive    |
+//! | 1     | $00                                          | Status        |
+//!
+//! DATA packets carry 40 tiles, two rows of 20, as the printer's paper is
+//! 160 pixels wide. PRINT prints everything buffered since the last one.
+
+use crate::ppu::types::{palette::PaletteIndex, tiles::Tile};
+use crate::serial_transfer::SerialLink;
+
+/// Width of the paper in pixels: 20 tiles.
+pub const PRINTER_WIDTH: usize = 160;
+
+const MAGIC: [u8; 2] = [0x88, 0x33];
+/// Sent while the Game Boy shifts out the first byte after the checksum.
+const ALIVE: u8 = 0x81;
+
+const INIT: u8 = 0x01;
+const PRINT: u8 = 0x02;
+const DATA: u8 = 0x04;
+const STATUS: u8 = 0x0F;
+
+/// Tiles across the paper.
+const TILES_PER_ROW: usize = PRINTER_WIDTH / 8;
+/// Bytes in one row of tiles.
+const ROW_BYTES: usize = TILES_PER_ROW * 16;
+/// The printer's 8 KiB of RAM holds at most 9 DATA packets of 2 rows.
+const BUFFER_ROWS: usize = 18;
+
+/// Status inquiries answered as busy after a PRINT, long enough for games
+/// that wait to see printing start before waiting for it to stop.
+const PRINT_POLLS: u8 = 4;
+
+/// Status byte bits.
+mod status {
+    pub const CHECKSUM_ERROR: u8 = 1 << 0;
+    pub const PRINTING: u8 = 1 << 1;
+    pub const IMAGE_DATA_FULL: u8 = 1 << 2;
+    pub const UNPROCESSED_DATA: u8 = 1 << 3;
+    pub const PACKET_ERROR: u8 = 1 << 4;
+}
+
+/// Where the next byte received falls in a packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Magic(usize),
+    Command,
+    Compression,
+    LengthLow,
+    LengthHigh,
+    Data,
+    ChecksumLow,
+    ChecksumHigh,
+    Alive,
+    Status,
+}
+
+pub struct GameBoyPrinter {
+    phase: Phase,
+    command: u8,
+    compressed: bool,
+    length: u16,
+    data: Vec<u8>,
+    checksum: u16,
+    received_checksum: u16,
+    /// Tile data waiting to be printed, decompressed.
+    buffer: Vec<u8>,
+    status: u8,
+    /// Status inquiries left before the current print finishes.
+    printing: u8,
+    /// The byte being shifted out to the Game Boy.
+    response: u8,
+    /// Every line printed so far, top first, as shades.
+    paper: Vec<PaletteIndex>,
+}
+
+impl Default for GameBoyPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameBoyPrinter {
+    pub fn new() -> Self {
+        Self {
+            phase: Phase::Magic(0),
+            command: 0,
+            compressed: false,
+            length: 0,
+            data: Vec::new(),
+            checksum: 0,
+            received_checksum: 0,
+            buffer: Vec::new(),
+            status: 0,
+            printing: 0,
+            response: 0,
+            paper: Vec::new(),
+        }
+    }
+
+    /// Everything printed so far, [`PRINTER_WIDTH`] shades to a line, top
+    /// line first. Shades have the PRINT command's palette applied.
+    pub fn printer_output(&self) -> Vec<PaletteIndex> {
+        self.paper.clone()
+    }
+
+    /// Lines of paper printed so far.
+    pub fn printed_lines(&self) -> usize {
+        self.paper.len() / PRINTER_WIDTH
+    }
+
+    /// Take one byte from the Game Boy, returning the printer's byte for
+    /// the same transfer.
+    pub fn exchange_byte(&mut self, byte: u8) -> u8 {
+        let mut response = 0x00;
+        self.phase = match self.phase {
+            Phase::Magic(index) if byte == MAGIC[index] => {
+                if index + 1 == MAGIC.len() {
+                    Phase::Command
+                } else {
+                    Phase::Magic(index + 1)
+                }
+            }
+            Phase::Magic(_) if byte == MAGIC[0] => Phase::Magic(1),
+            Phase::Magic(_) => Phase::Magic(0),
+            Phase::Command => {
+                self.command = byte;
+                self.checksum = byte as u16;
+                Phase::Compression
+            }
+            Phase::Compression => {
+                self.compressed = byte & 1 != 0;
+                self.checksum = self.checksum.wrapping_add(byte as u16);
+                Phase::LengthLow
+            }
+            Phase::LengthLow => {
+                self.length = byte as u16;
+                self.checksum = self.checksum.wrapping_add(byte as u16);
+                Phase::LengthHigh
+            }
+            Phase::LengthHigh => {
+                self.length |= (byte as u16) << 8;
+                self.checksum = self.checksum.wrapping_add(byte as u16);
+                self.data.clear();
+                if self.length == 0 {
+                    Phase::ChecksumLow
+                } else {
+                    Phase::Data
+                }
+            }
+            Phase::Data => {
+                self.data.push(byte);
+                self.checksum = self.checksum.wrapping_add(byte as u16);
+                if self.data.len() == self.length as usize {
+                    Phase::ChecksumLow
+                } else {
+                    Phase::Data
+                }
+            }
+            Phase::ChecksumLow => {
+                self.received_checksum = byte as u16;
+                Phase::ChecksumHigh
+            }
+            Phase::ChecksumHigh => {
+                self.received_checksum |= (byte as u16) << 8;
+                self.run_command();
+                Phase::Alive
+            }
+            Phase::Alive => {
+                response = ALIVE;
+                Phase::Status
+            }
+            Phase::Status => {
+                response = self.status();
+                Phase::Magic(0)
+            }
+        };
+        response
+    }
+
+    fn run_command(&mut self) {
+        if self.received_checksum != self.checksum {
+            self.status |= status::CHECKSUM_ERROR;
+            return;
+        }
+        self.status &= !(status::CHECKSUM_ERROR | status::PACKET_ERROR);
+
+        match self.command {
+            INIT => {
+                self.buffer.clear();
+                self.status = 0;
+                self.printing = 0;
+            }
+            DATA => {
+                let data = std::mem::take(&mut self.data);
+                if self.compressed {
+                    decompress(&data, &mut self.buffer);
+                } else {
+                    self.buffer.extend_from_slice(&data);
+                }
+                self.buffer.truncate(BUFFER_ROWS * ROW_BYTES);
+                if !self.buffer.is_empty() {
+                    self.status |= status::UNPROCESSED_DATA;
+                }
+                if self.buffer.len() == BUFFER_ROWS * ROW_BYTES {
+                    self.status |= status::IMAGE_DATA_FULL;
+                }
+            }
+            PRINT => {
+                // Sheets, margins, palette, exposure. A sheet count of zero
+                // only feeds paper. Margins and exposure don't change the
+                // image, so they're ignored.
+                if let [sheets, _, palette, _] = self.data[..] {
+                    if sheets > 0 {
+                        self.print(palette);
+                    }
+                    self.buffer.clear();
+                    self.status &= !(status::UNPROCESSED_DATA | status::IMAGE_DATA_FULL);
+                    self.printing = PRINT_POLLS;
+                } else {
+                    self.status |= status::PACKET_ERROR;
+                }
+            }
+            STATUS => {}
+            _ => self.status |= status::PACKET_ERROR,
+        }
+    }
+
+    fn status(&mut self) -> u8 {
+        if self.printing > 0 {
+            self.printing -= 1;
+            self.status | status::PRINTING
+        } else {
+            self.status
+        }
+    }
+
+    /// Append the buffered rows of tiles to the paper. `palette` maps colour
+    /// numbers to shades as BGP does; zero means the identity $E4.
+    fn print(&mut self, palette: u8) {
+        let palette = if palette == 0 { 0xE4 } else { palette };
+        for row in self.buffer.chunks_exact(ROW_BYTES) {
+            let tiles: Vec<Tile> = row
+                .chunks_exact(16)
+                .map(|tile| Tile::from_bytes(tile.try_into().unwrap()))
+                .collect();
+            for y in 0..8 {
+                for x in 0..PRINTER_WIDTH {
+                    let colour = tiles[x / 8].pixel(x as u8 % 8, y).0;
+                    self.paper
+                        .push(PaletteIndex(palette >> (colour * 2) & 0b11));
+                }
+            }
+        }
+    }
+}
+
+/// Expand run-length encoded `data` onto `out`. A control byte with bit 7
+/// set repeats the next byte (control & $7F) + 2 times; otherwise the next
+/// control + 1 bytes are copied as they are.
+fn decompress(data: &[u8], out: &mut Vec<u8>) {
+    let mut bytes = data.iter().copied();
+    while let Some(control) = bytes.next() {
+        if control & 0x80 != 0 {
+            let Some(byte) = bytes.next() else { break };
+            out.extend(std::iter::repeat_n(byte, (control & 0x7F) as usize + 2));
+        } else {
+            out.extend(bytes.by_ref().take(control as usize + 1));
+        }
+    }
+}
+
+impl SerialLink for GameBoyPrinter {
+    fn exchange_bit(&mut self, _out_bit: bool) -> bool {
+        let bit = self.response & 0x80 != 0;
+        self.response <<= 1;
+        bit
+    }
+
+    /// The printer never drives the clock.
+    fn clock(&mut self) -> bool {
+        false
+    }
+
+    fn notify_transfer_start(&mut self, data: u8, internal_clock: bool) {
+        self.response = if internal_clock {
+            self.exchange_byte(data)
+        } else {
+            0
+        };
+    }
+}
@@ -0,0 +1,80 @@
+//! Instruction-frequency counts, for finding hot loops in a game and the
+//! opcodes that dominate emulation time. Counting is opt-in through
+//! [`Console::set_profiling`](crate::Console::set_profiling), since it
+//! costs a little on every instruction.
+
+/// How many times each address and each opcode has been fetched for
+/// execution. Addresses are CPU addresses, so code in different ROM banks
+/// at the same address shares a count. CB-prefixed instructions count
+/// under opcode $CB.
+#[derive(Clone)]
+pub struct Profile {
+    addresses: Box<[u64]>,
+    opcodes: [u64; 256],
+    total: u64,
+}
+
+impl Profile {
+    pub(crate) fn new() -> Self {
+        Self {
+            addresses: vec![0; 0x10000].into_boxed_slice(),
+            opcodes: [0; 256],
+            total: 0,
+        }
+    }
+
+    pub(crate) fn record(&mut self, address: u16, opcode: u8) {
+        self.addresses[address as usize] += 1;
+        self.opcodes[opcode as usize] += 1;
+        self.total += 1;
+    }
+
+    /// Bytes the profile takes on the heap, itself included.
+    pub(crate) fn heap_size(&self) -> usize {
+        std::mem::size_of::<Self>() + std::mem::size_of_val(&*self.addresses)
+    }
+
+    /// Instructions executed since counting started or was last cleared.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn address_count(&self, address: u16) -> u64 {
+        self.addresses[address as usize]
+    }
+
+    pub fn opcode_count(&self, opcode: u8) -> u64 {
+        self.opcodes[opcode as usize]
+    }
+
+    /// Up to `limit` addresses that have executed, most executions first.
+    pub fn hottest_addresses(&self, limit: usize) -> Vec<(u16, u64)> {
+        hottest(&self.addresses, limit)
+            .map(|(address, count)| (address as u16, count))
+            .collect()
+    }
+
+    /// Up to `limit` opcodes that have executed, most executions first.
+    pub fn hottest_opcodes(&self, limit: usize) -> Vec<(u8, u64)> {
+        hottest(&self.opcodes, limit)
+            .map(|(opcode, count)| (opcode as u8, count))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.addresses.fill(0);
+        self.opcodes.fill(0);
+        self.total = 0;
+    }
+}
+
+fn hottest(counts: &[u64], limit: usize) -> impl Iterator<Item = (usize, u64)> {
+    let mut hot: Vec<(usize, u64)> = counts
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    hot.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    hot.into_iter().take(limit)
+}
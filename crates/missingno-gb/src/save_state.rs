@@ -0,0 +1,294 @@
+//! Save states: the whole console written to a file and read back later.
+//!
+//! Everything the console needs to carry on is serialised; what the host
+//! attaches (a serial link, trace sinks, scheduled callbacks, heatmaps) isn't,
+//! and neither is the ROM, which is taken from the cartridge the state is
+//! loaded into once its checksum matches.
+
+/// What every piece of console state implements, so the generic [`Console`]
+/// can be saved whichever model it runs. Only asks for anything with the
+/// `save-state` feature on.
+///
+/// [`Console`]: crate::Console
+#[cfg(feature = "save-state")]
+pub trait Persist: serde::Serialize + serde::de::DeserializeOwned {}
+#[cfg(feature = "save-state")]
+impl<T: serde::Serialize + serde::de::DeserializeOwned> Persist for T {}
+
+#[cfg(not(feature = "save-state"))]
+pub trait Persist {}
+#[cfg(not(feature = "save-state"))]
+impl<T> Persist for T {}
+
+#[cfg(feature = "save-state")]
+pub use state::{SaveState, SaveStateError};
+
+#[cfg(feature = "save-state")]
+mod state {
+    use std::fmt;
+    use std::io::{BufReader, BufWriter};
+    use std::path::Path;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::cartridge::Cartridge;
+    use crate::{Console, Model};
+
+    /// Bumped whenever the serialised layout changes, so old files are
+    /// refused rather than misread.
+    const VERSION: u32 = 1;
+
+    /// A console frozen at one instant, ready to be written out.
+    #[derive(Serialize, Deserialize)]
+    #[serde(bound = "")]
+    pub struct SaveState<M: Model> {
+        version: u32,
+        /// [`Model::TRACE_MODEL_NAME`], so a DMG state isn't read as a CGB one.
+        model: String,
+        /// The [ROM checksum](Cartridge::rom_checksum) of the game it was
+        /// saved from.
+        checksum: u16,
+        console: Console<M>,
+    }
+
+    /// Why a state can't be written or restored.
+    #[derive(Debug)]
+    pub enum SaveStateError {
+        Io(std::io::Error),
+        /// The file isn't a save state, or is from another version.
+        Corrupt,
+        /// Saved from the other console.
+        ModelMismatch,
+        /// Saved from a different game.
+        ChecksumMismatch {
+            expected: u16,
+            found: u16,
+        },
+    }
+
+    impl fmt::Display for SaveStateError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SaveStateError::Io(error) => write!(f, "{error}"),
+                SaveStateError::Corrupt => write!(f, "not a save state this version can read"),
+                SaveStateError::ModelMismatch => write!(f, "saved from a different console"),
+                SaveStateError::ChecksumMismatch { .. } => {
+                    write!(f, "saved from a different game")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for SaveStateError {}
+
+    impl From<std::io::Error> for SaveStateError {
+        fn from(error: std::io::Error) -> Self {
+            SaveStateError::Io(error)
+        }
+    }
+
+    impl From<bincode::Error> for SaveStateError {
+        fn from(error: bincode::Error) -> Self {
+            match *error {
+                bincode::ErrorKind::Io(error) => SaveStateError::Io(error),
+                _ => SaveStateError::Corrupt,
+            }
+        }
+    }
+
+    impl<M: Model> SaveState<M> {
+        /// Freeze `console` as it stands. Host attachments stay behind.
+        pub fn capture(console: &Console<M>) -> Self {
+            Self {
+                version: VERSION,
+                model: M::TRACE_MODEL_NAME.to_string(),
+                checksum: console.cartridge().rom_checksum(),
+                console: console.clone(),
+            }
+        }
+
+        /// The console this state holds, running `cartridge`'s ROM. Fails if
+        /// the state was saved from another game.
+        pub fn into_console(self, cartridge: &Cartridge) -> Result<Console<M>, SaveStateError> {
+            let found = cartridge.rom_checksum();
+            if found != self.checksum {
+                return Err(SaveStateError::ChecksumMismatch {
+                    expected: self.checksum,
+                    found,
+                });
+            }
+            let mut console = self.console;
+            console.external.cartridge.attach_rom(cartridge);
+            Ok(console)
+        }
+
+        pub fn save_to_file(&self, path: &Path) -> Result<(), SaveStateError> {
+            let file = BufWriter::new(std::fs::File::create(path)?);
+            bincode::serialize_into(file, self)?;
+            Ok(())
+        }
+
+        pub fn load_from_file(path: &Path) -> Result<Self, SaveStateError> {
+            let mut file = BufReader::new(std::fs::File::open(path)?);
+            let version: u32 = bincode::deserialize_from(&mut file)?;
+            if version != VERSION {
+                return Err(SaveStateError::Corrupt);
+            }
+            let model: String = bincode::deserialize_from(&mut file)?;
+            if model != M::TRACE_MODEL_NAME {
+                return Err(SaveStateError::ModelMismatch);
+            }
+            let checksum = bincode::deserialize_from(&mut file)?;
+            let console = bincode::deserialize_from(&mut file)?;
+            Ok(Self {
+                version,
+                model,
+                checksum,
+                console,
+            })
+        }
+    }
+}
+
+/// Serialises arrays too long for serde's own impls, boxed or not, for use
+/// as `#[serde(with = "big_array")]`, or in an `Option`. [`nested`] does the
+/// same for arrays of them.
+#[cfg(feature = "save-state")]
+pub mod big_array {
+    use serde::de::{DeserializeOwned, Error};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub trait BigArray: Sized {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+        fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+    }
+
+    impl<T: Serialize + DeserializeOwned, const N: usize> BigArray for [T; N] {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self)
+        }
+        fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let items = Vec::<T>::deserialize(deserializer)?;
+            let len = items.len();
+            items
+                .try_into()
+                .map_err(|_| D::Error::invalid_length(len, &"the array's length"))
+        }
+    }
+
+    impl<T: Serialize + DeserializeOwned, const N: usize> BigArray for Box<[T; N]> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+        fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let items = Vec::<T>::deserialize(deserializer)?;
+            let len = items.len();
+            items
+                .into_boxed_slice()
+                .try_into()
+                .map_err(|_| D::Error::invalid_length(len, &"the array's length"))
+        }
+    }
+
+    impl<T: Serialize + DeserializeOwned, const N: usize> BigArray for Option<[T; N]> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Some(array) => serializer.serialize_some(&array[..]),
+                None => serializer.serialize_none(),
+            }
+        }
+        fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let Some(items) = Option::<Vec<T>>::deserialize(deserializer)? else {
+                return Ok(None);
+            };
+            let len = items.len();
+            items
+                .try_into()
+                .map(Some)
+                .map_err(|_| D::Error::invalid_length(len, &"the array's length"))
+        }
+    }
+
+    pub fn serialize<A: BigArray, S: Serializer>(
+        array: &A,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        array.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, A: BigArray, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<A, D::Error> {
+        A::deserialize(deserializer)
+    }
+
+    /// Arrays, or `Vec`s, of big arrays: banked RAM and framebuffers.
+    pub mod nested {
+        use serde::de::{DeserializeOwned, Error};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use super::BigArray;
+
+        pub trait Nested: Sized {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+        }
+
+        struct Row<'a, T, const N: usize>(&'a [T; N]);
+
+        impl<T: Serialize + DeserializeOwned, const N: usize> Serialize for Row<'_, T, N> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                BigArray::serialize(self.0, serializer)
+            }
+        }
+
+        struct OwnedRow<T, const N: usize>([T; N]);
+
+        impl<'de, T: Serialize + DeserializeOwned, const N: usize> Deserialize<'de> for OwnedRow<T, N> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <[T; N] as BigArray>::deserialize(deserializer).map(OwnedRow)
+            }
+        }
+
+        fn rows<'de, T: Serialize + DeserializeOwned, const N: usize, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<[T; N]>, D::Error> {
+            let rows = Vec::<OwnedRow<T, N>>::deserialize(deserializer)?;
+            Ok(rows.into_iter().map(|row| row.0).collect())
+        }
+
+        impl<T: Serialize + DeserializeOwned, const N: usize> Nested for Vec<[T; N]> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.iter().map(Row))
+            }
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                rows(deserializer)
+            }
+        }
+
+        impl<T: Serialize + DeserializeOwned, const N: usize, const M: usize> Nested for [[T; N]; M] {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.iter().map(Row))
+            }
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let rows = rows(deserializer)?;
+                let len = rows.len();
+                rows.try_into()
+                    .map_err(|_| D::Error::invalid_length(len, &"the array's length"))
+            }
+        }
+
+        pub fn serialize<A: Nested, S: Serializer>(
+            array: &A,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            array.serialize(serializer)
+        }
+
+        pub fn deserialize<'de, A: Nested, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<A, D::Error> {
+            A::deserialize(deserializer)
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! The M-cycle count since reset, and one-shot callbacks pinned to a future
+//! count — for profiling and timing tools that need to act at a precise
+//! point without single-stepping to it.
+
+use crate::{Console, Model};
+
+type Callback<M> = Box<dyn FnOnce(&mut Console<M>)>;
+
+pub(crate) struct Scheduler<M: Model> {
+    elapsed: u64,
+    pending: Vec<(u64, Callback<M>)>,
+}
+
+impl<M: Model> Scheduler<M> {
+    pub(crate) fn new() -> Self {
+        Self {
+            elapsed: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> u64 {
+        self.elapsed
+    }
+
+    /// Count one CPU M-cycle.
+    pub(crate) fn mcycle(&mut self) {
+        self.elapsed += 1;
+    }
+
+    pub(crate) fn schedule(&mut self, cycle: u64, callback: Callback<M>) {
+        self.pending.push((cycle, callback));
+    }
+
+    /// The earliest callback whose cycle has been reached.
+    pub(crate) fn take_due(&mut self) -> Option<Callback<M>> {
+        let index = self
+            .pending
+            .iter()
+            .enumerate()
+            .filter(|(_, (cycle, _))| *cycle <= self.elapsed)
+            .min_by_key(|(_, (cycle, _))| *cycle)
+            .map(|(index, _)| index)?;
+        Some(self.pending.remove(index).1)
+    }
+}
+
+/// Callbacks capture host state, so a clone (a debugger snapshot, a rewind
+/// point) keeps the count but leaves them with the original, the same way
+/// it leaves the serial link behind.
+impl<M: Model> Clone for Scheduler<M> {
+    fn clone(&self) -> Self {
+        Self {
+            elapsed: self.elapsed,
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// Saved the way it clones: the count without the callbacks.
+#[cfg(feature = "save-state")]
+impl<M: Model> serde::Serialize for Scheduler<M> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.elapsed.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "save-state")]
+impl<'de, M: Model> serde::Deserialize<'de> for Scheduler<M> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            elapsed: u64::deserialize(deserializer)?,
+            pending: Vec::new(),
+        })
+    }
+}
+
+impl<M: Model> Console<M> {
+    /// M-cycles the CPU has run since power-on or the last reset. Counts
+    /// CPU M-cycles, so it runs twice as fast in CGB double speed.
+    pub fn cycles_elapsed(&self) -> u64 {
+        self.scheduler.elapsed()
+    }
+
+    /// Run `callback` once [`cycles_elapsed`](Self::cycles_elapsed) reaches
+    /// `cycle`. It fires at the first instruction boundary at or after that
+    /// count, so at most one instruction late; a cycle already passed fires
+    /// at the next boundary. A reset drops callbacks that haven't fired.
+    pub fn schedule_at(&mut self, cycle: u64, callback: impl FnOnce(&mut Self) + 'static) {
+        self.scheduler.schedule(cycle, Box::new(callback));
+    }
+
+    /// Fire every callback that has come due, earliest first. Callbacks
+    /// may schedule further ones; those fire here too if already due.
+    pub(crate) fn fire_scheduled(&mut self) {
+        while let Some(callback) = self.scheduler.take_due() {
+            callback(self);
+        }
+    }
+}
@@ -50,6 +50,32 @@ pub trait SerialLink {
     fn notify_transfer_start(&mut self, _data: u8, _internal_clock: bool) {}
 }
 
+/// A link shared with whoever plugged it in, so a device like the
+/// [printer](crate::printer::GameBoyPrinter) can be inspected while connected.
+impl<L: SerialLink> SerialLink for std::sync::Arc<std::sync::Mutex<L>> {
+    fn exchange_bit(&mut self, out_bit: bool) -> bool {
+        self.lock().unwrap().exchange_bit(out_bit)
+    }
+
+    fn clock(&mut self) -> bool {
+        self.lock().unwrap().clock()
+    }
+
+    fn drain_output(&mut self) -> Vec<u8> {
+        self.lock().unwrap().drain_output()
+    }
+
+    fn tick(&mut self) {
+        self.lock().unwrap().tick();
+    }
+
+    fn notify_transfer_start(&mut self, data: u8, internal_clock: bool) {
+        self.lock()
+            .unwrap()
+            .notify_transfer_start(data, internal_clock);
+    }
+}
+
 /// No device connected. Incoming bits are high (floating line) and no
 /// external clock is provided. Outgoing bytes are captured for test use.
 #[derive(Default)]
@@ -86,7 +112,46 @@ impl SerialLink for Disconnected {
     }
 }
 
+/// SO wired back to SI, as a cable plugged into itself would be: each bit
+/// shifted out comes straight back in, so a transfer on the internal clock
+/// leaves SB unchanged. Nothing drives an external clock. Outgoing bytes are
+/// captured as with [`Disconnected`].
+#[derive(Default)]
+pub struct Loopback {
+    current_byte: u8,
+    bits_collected: u8,
+    output: Vec<u8>,
+}
+
+impl Loopback {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SerialLink for Loopback {
+    fn exchange_bit(&mut self, out_bit: bool) -> bool {
+        self.current_byte = (self.current_byte << 1) | (out_bit as u8);
+        self.bits_collected += 1;
+        if self.bits_collected == 8 {
+            self.output.push(self.current_byte);
+            self.current_byte = 0;
+            self.bits_collected = 0;
+        }
+        out_bit
+    }
+
+    fn clock(&mut self) -> bool {
+        false
+    }
+
+    fn drain_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+}
+
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Registers {
     pub data: u8,
     pub control: Control,
@@ -239,9 +304,21 @@ impl Registers {
 /// the device on the other end of the link cable. Memory-mapped
 /// accesses go through `registers`; the executor drives transfers
 /// via `mcycle` / `start_transfer`.
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Serial {
     pub registers: Registers,
+    #[cfg_attr(feature = "save-state", serde(skip, default = "disconnected"))]
     link: Box<dyn SerialLink>,
+    /// SB as the current transfer was armed: the byte being shifted out.
+    sending: u8,
+    #[cfg_attr(feature = "save-state", serde(skip))]
+    on_byte_sent: Option<Box<dyn FnMut(u8)>>,
+}
+
+/// The link a restored save state starts with, as a clone does.
+#[cfg(feature = "save-state")]
+fn disconnected() -> Box<dyn SerialLink> {
+    Box::new(Disconnected::new())
 }
 
 impl Serial {
@@ -249,13 +326,17 @@ impl Serial {
         Self {
             registers: Registers::new(),
             link: Box::new(Disconnected::new()),
+            sending: 0,
+            on_byte_sent: None,
         }
     }
 
     /// Advance by one M-cycle. See `Registers::mcycle`.
     pub fn mcycle(&mut self, counter: u16, fast_clock_available: bool) -> Option<Interrupt> {
-        self.registers
-            .mcycle(counter, fast_clock_available, &mut *self.link)
+        let interrupt = self
+            .registers
+            .mcycle(counter, fast_clock_available, &mut *self.link);
+        self.report_byte_sent(interrupt)
     }
 
     /// DIV-write edge injection. See `Registers::on_div_write`.
@@ -264,15 +345,47 @@ impl Serial {
         old_counter: u16,
         fast_clock_available: bool,
     ) -> Option<Interrupt> {
-        self.registers
-            .on_div_write(old_counter, fast_clock_available, &mut *self.link)
+        let interrupt =
+            self.registers
+                .on_div_write(old_counter, fast_clock_available, &mut *self.link);
+        self.report_byte_sent(interrupt)
     }
 
     /// Arm a new transfer (called when SC is written with ENABLE).
     pub fn start_transfer(&mut self) {
+        self.sending = self.registers.data;
         self.registers.start_transfer(&mut *self.link);
     }
 
+    /// Call `callback` with each byte sent on the internal clock, as its
+    /// last bit shifts out.
+    pub fn set_byte_callback(&mut self, callback: Option<Box<dyn FnMut(u8)>>) {
+        self.on_byte_sent = callback;
+    }
+
+    pub fn take_byte_callback(&mut self) -> Option<Box<dyn FnMut(u8)>> {
+        self.on_byte_sent.take()
+    }
+
+    /// Take over `previous`'s link device and byte callback when this serial
+    /// replaces it mid-run, as a loaded state does.
+    pub(crate) fn keep_host_side(&mut self, previous: Serial) {
+        self.link = previous.link;
+        self.on_byte_sent = previous.on_byte_sent;
+    }
+
+    /// A finished transfer raises the serial interrupt; pass the byte on if
+    /// the Game Boy was driving the clock.
+    fn report_byte_sent(&mut self, interrupt: Option<Interrupt>) -> Option<Interrupt> {
+        if interrupt.is_some()
+            && self.registers.control.contains(Control::INTERNAL_CLOCK)
+            && let Some(callback) = &mut self.on_byte_sent
+        {
+            callback(self.sending);
+        }
+        interrupt
+    }
+
     /// Drain bytes captured by the link device.
     pub fn drain_output(&mut self) -> Vec<u8> {
         self.link.drain_output()
@@ -289,6 +402,21 @@ impl Serial {
         Self {
             registers: Registers::from_snapshot(snap),
             link: Box::new(Disconnected::new()),
+            sending: 0,
+            on_byte_sent: None,
+        }
+    }
+}
+
+/// A clone is a snapshot of the port, not a second cable: the copy gets a
+/// disconnected link and no byte callback.
+impl Clone for Serial {
+    fn clone(&self) -> Self {
+        Self {
+            registers: self.registers.clone(),
+            link: Box::new(Disconnected::new()),
+            sending: self.sending,
+            on_byte_sent: None,
         }
     }
 }
@@ -307,6 +435,7 @@ pub enum Register {
 
 bitflags! {
     #[derive(Copy, Clone, Debug)]
+    #[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
     pub struct Control: u8 {
         const ENABLE         = 0b10000000;
         /// SIO_FAST — CGB-mode only; unimplemented (reads 1) elsewhere.
@@ -43,6 +43,7 @@ fn screen_to_transfer_data(screen: &Screen) -> Vec<u8> {
 
 /// 15-bit RGB555 color as used by the SNES/SGB.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rgb555(pub u16);
 
 /// Gamma ramp for SNES RGB555 output, derived from SameBoy's SGB-specific
@@ -75,6 +76,7 @@ impl Default for Rgb555 {
 
 /// One SGB palette: 4 colors.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct SgbPalette {
     pub colors: [Rgb555; 4],
 }
@@ -95,6 +97,7 @@ impl Default for SgbPalette {
 
 /// 20x18 attribute map: each cell maps to palette 0-3.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct AttributeMap {
     pub cells: [[u8; 20]; 18],
 }
@@ -109,6 +112,7 @@ impl AttributeMap {
 
 /// Screen masking mode.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub enum MaskMode {
     Disabled,
     Freeze,
@@ -125,6 +129,8 @@ pub struct SgbRenderData {
     pub video_enabled: bool,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 enum CommandState {
     Idle,
     ReceivingBits {
@@ -137,11 +143,14 @@ enum CommandState {
 }
 
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 enum PendingTransfer {
     Palettes,
     Attributes,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sgb {
     palettes: [SgbPalette; 4],
     attribute_map: AttributeMap,
@@ -250,14 +250,19 @@ pub fn capture_mbc(gb: &GameBoy) -> MbcSnapshot {
             rom_bank: m.rom_bank,
             ram_bank: m.ram_bank,
             ram_enabled: m.ram_enabled,
-            mode: m.rumble as u8,
+            mode: m.rumble as u8, // the rumble motor
         },
+        // Both halves share the single-bank fields: A in the low bits, B
+        // above it, and the flash selects/enables in `mode`.
         Mbc::Mbc6(m) => MbcSnapshot {
             mbc_type: "mbc6".into(),
-            rom_bank: m.rom_bank_a as u16,
-            ram_bank: m.ram_bank_a,
+            rom_bank: m.rom_bank_a as u16 | (m.rom_bank_b as u16) << 8,
+            ram_bank: m.ram_bank_a | m.ram_bank_b << 4,
             ram_enabled: m.ram_enabled,
-            mode: 0,
+            mode: m.rom_bank_a_flash as u8
+                | (m.rom_bank_b_flash as u8) << 1
+                | (m.flash_enabled as u8) << 2
+                | (m.flash_write_enabled as u8) << 3,
         },
         Mbc::Mbc7(m) => MbcSnapshot {
             mbc_type: "mbc7".into(),
@@ -339,10 +344,10 @@ impl GameBoy {
         // MBC bank state
         restore_mbc(&snap.mbc, external.cartridge.mbc_mut());
 
-        let sgb = if external.cartridge.supports_sgb() {
-            Some(crate::sgb::Sgb::new())
+        let (model, sgb) = if external.cartridge.supports_sgb() {
+            (crate::DmgModel::Sgb, Some(crate::sgb::Sgb::new()))
         } else {
-            None
+            (crate::DmgModel::Dmg, None)
         };
 
         Console {
@@ -373,14 +378,19 @@ impl GameBoy {
                 .unwrap_or_else(crate::memory::HighRam::new),
             external,
             model: crate::Dmg {
+                model,
                 sgb,
                 ..Default::default()
             },
             bus_trace: crate::cpu_bus::BusTrace::new(),
+            heatmap: None,
+            trace_log: crate::trace_log::TraceLog::default(),
             clock: crate::MasterClock::new(crate::CpuDivider::One),
             cpu_bus: crate::cpu_bus::CpuBus::new(),
             dma_conflict_write_pending: None,
             dma_pending_bank_write: None,
+            initial_div: None,
+            scheduler: crate::scheduler::Scheduler::new(),
         }
     }
 }
@@ -410,8 +420,14 @@ fn restore_mbc(snap: &MbcSnapshot, mbc: &mut Mbc) {
         }
         Mbc::Mbc6(m) => {
             m.rom_bank_a = snap.rom_bank as u8;
-            m.ram_bank_a = snap.ram_bank;
+            m.rom_bank_b = (snap.rom_bank >> 8) as u8;
+            m.ram_bank_a = snap.ram_bank & 0x07;
+            m.ram_bank_b = snap.ram_bank >> 4;
             m.ram_enabled = snap.ram_enabled;
+            m.rom_bank_a_flash = snap.mode & 0x01 != 0;
+            m.rom_bank_b_flash = snap.mode & 0x02 != 0;
+            m.flash_enabled = snap.mode & 0x04 != 0;
+            m.flash_write_enabled = snap.mode & 0x08 != 0;
         }
         Mbc::Mbc7(m) => {
             m.rom_bank = snap.rom_bank as u8;
@@ -1,10 +1,9 @@
 //! Shared test helpers for accuracy/integration tests.
 //!
-//! Enabled by the `test-support` feature. Exposes a [`System`] trait
-//! implemented by both `GameBoy` and downstream system crates (e.g.
-//! `missingno-gbc`'s `GameBoyColor`), and runner/utility functions
-//! generic over that trait so test ROMs and helpers can be reused
-//! across systems.
+//! Enabled by the `test-support` feature. Runner/utility functions are
+//! generic over [`System`] (re-exported from [`crate::diagnostics`] with
+//! the verdict helpers) so test ROMs and helpers can be reused across
+//! systems.
 //!
 //! ROM paths are resolved relative to this crate's `CARGO_MANIFEST_DIR`,
 //! so downstream crates can call [`rom_path`] / [`load_rom`] and pick
@@ -13,83 +12,16 @@
 use std::path::{Path, PathBuf};
 
 use crate::{
-    BootRom, Console, GameBoy, Model, cartridge::Cartridge, cpu::Cpu, execute::StepResult,
-    interrupts, ppu::screen::Screen,
+    BootRom, Console, GameBoy, Model, PowerOnMemory, cartridge::Cartridge, cpu::Cpu,
+    debugger::Debugger, execute::StepResult, interrupts, ppu::screen::Screen,
 };
 
 #[cfg(feature = "gbtrace")]
 use crate::trace::Tracer;
 
-/// Common interface for a Game Boy–family console runnable under the
-/// shared accuracy test helpers. Implemented by `GameBoy` here and by
-/// downstream systems (e.g. `GameBoyColor`).
-/// Common interface for a Game Boy–family console runnable under the
-/// shared accuracy test helpers. Implemented by `GameBoy` here and by
-/// downstream systems (e.g. `GameBoyColor`).
-///
-/// `screen()` deliberately isn't on this trait — the DMG screen stores
-/// 2-bit shade indices while the CGB screen stores RGB pixels, so
-/// callers needing screenshot comparison go through the concrete type.
-pub trait System {
-    fn step(&mut self) -> StepResult;
-    fn read(&self, address: u16) -> u8;
-    fn cpu(&self) -> &Cpu;
-    fn cpu_mut(&mut self) -> &mut Cpu;
-    fn drain_serial_output(&mut self) -> Vec<u8>;
-    fn interrupts(&self) -> &interrupts::Registers;
-    /// True while a CGB double-speed switch holds the CPU stopped in its
-    /// settling blackout (a self-resuming STOP). Defaults false for systems
-    /// without a speed switch.
-    fn speed_switch_in_progress(&self) -> bool {
-        false
-    }
-    /// True while a VRAM DMA holds the CPU (the bus master's hold, not a
-    /// software STOP/HALT). Defaults false for systems without one.
-    fn vram_dma_holds_cpu(&self) -> bool {
-        false
-    }
-    /// Peek a contiguous range of memory, bypassing bus conflicts and
-    /// PPU mode gating. Used by tests that decode assertion records
-    /// from WRAM after the test has halted.
-    fn peek_range(&self, start: u16, len: u16) -> Vec<u8>;
-    /// Drain accumulated audio samples (stereo, f32 pairs). Used by
-    /// gambatte audio tests to check whether the test ROM produced
-    /// any sound (`_outaudio1`) or was silent (`_outaudio0`).
-    fn drain_audio_samples(&mut self) -> Vec<(f32, f32)>;
-}
-
-impl<M: Model> System for Console<M> {
-    fn step(&mut self) -> StepResult {
-        Console::<M>::step(self)
-    }
-    fn read(&self, address: u16) -> u8 {
-        Console::<M>::read(self, address)
-    }
-    fn cpu(&self) -> &Cpu {
-        Console::<M>::cpu(self)
-    }
-    fn cpu_mut(&mut self) -> &mut Cpu {
-        Console::<M>::cpu_mut(self)
-    }
-    fn drain_serial_output(&mut self) -> Vec<u8> {
-        Console::<M>::drain_serial_output(self)
-    }
-    fn interrupts(&self) -> &interrupts::Registers {
-        Console::<M>::interrupts(self)
-    }
-    fn speed_switch_in_progress(&self) -> bool {
-        Console::<M>::speed_switch_in_progress(self)
-    }
-    fn vram_dma_holds_cpu(&self) -> bool {
-        Console::<M>::vram_dma_holds_cpu(self)
-    }
-    fn peek_range(&self, start: u16, len: u16) -> Vec<u8> {
-        Console::<M>::peek_range(self, start, len)
-    }
-    fn drain_audio_samples(&mut self) -> Vec<(f32, f32)> {
-        Console::<M>::drain_audio_samples(self)
-    }
-}
+pub use crate::diagnostics::{
+    System, check_mooneye_pass, format_registers, is_infinite_loop, run_until_serial_match,
+};
 
 pub fn rom_path(relative: &str) -> PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -129,34 +61,25 @@ impl<M: Model> TestRun<M> {
     /// captures once before the instruction executes.
     pub fn step(&mut self) -> StepResult {
         #[cfg(feature = "gbtrace")]
-        {
-            if let Some(tracer) = &mut self.tracer {
-                if tracer.trigger() == crate::trace::Trigger::Tcycle {
-                    return self.step_traced_tcycle();
-                }
-                tracer.capture(&self.gb).unwrap();
-            }
+        if let Some(tracer) = &mut self.tracer {
+            return step_traced(&mut self.gb, tracer);
+        }
 
-            let result = self.gb.step();
+        self.gb.step()
+    }
 
+    /// Run `frames` frames as [`Console::run_frames`] counts them, stepping
+    /// one instruction at a time while a trace is being captured.
+    pub fn run_frames(&mut self, frames: u32) {
+        for _ in 0..frames {
+            #[cfg(feature = "gbtrace")]
             if let Some(tracer) = &mut self.tracer {
-                tracer.advance(result.tcycles);
-                if result.new_screen {
-                    tracer.mark_frame().unwrap();
-                }
+                self.gb.run_frame_with(|gb, _| step_traced(gb, tracer));
+                continue;
             }
 
-            return result;
+            self.gb.run_frame_with(|gb, budget| gb.run_cycles(budget));
         }
-
-        #[cfg(not(feature = "gbtrace"))]
-        self.gb.step()
-    }
-
-    /// Step one instruction by advancing one dot at a time, capturing at each dot.
-    #[cfg(feature = "gbtrace")]
-    fn step_traced_tcycle(&mut self) -> StepResult {
-        crate::trace::step_instruction_tcycle(&mut self.gb, self.tracer.as_mut().unwrap())
     }
 
     #[allow(unused_mut)]
@@ -168,6 +91,24 @@ impl<M: Model> TestRun<M> {
     }
 }
 
+/// Step one instruction, capturing its state into `tracer`. Tcycle-triggered
+/// profiles advance dot by dot and capture at every dot.
+#[cfg(feature = "gbtrace")]
+fn step_traced<M: Model>(gb: &mut Console<M>, tracer: &mut Tracer) -> StepResult {
+    if tracer.trigger() == crate::trace::Trigger::Tcycle {
+        return crate::trace::step_instruction_tcycle(gb, tracer);
+    }
+    tracer.capture(gb).unwrap();
+
+    let result = gb.step();
+
+    tracer.advance(result.tcycles);
+    if result.new_screen {
+        tracer.mark_frame().unwrap();
+    }
+    result
+}
+
 #[cfg(feature = "gbtrace")]
 impl<M: Model> Drop for TestRun<M> {
     fn drop(&mut self) {
@@ -208,6 +149,9 @@ impl<M: Model> System for TestRun<M> {
     fn drain_audio_samples(&mut self) -> Vec<(f32, f32)> {
         self.gb.drain_audio_samples()
     }
+    fn run_frames(&mut self, frames: u32) {
+        TestRun::run_frames(self, frames);
+    }
 }
 
 #[cfg(feature = "gbtrace")]
@@ -276,21 +220,61 @@ fn try_create_tracer<M: Model>(
     Some(tracer)
 }
 
+/// A 32 KiB ROM-only image whose entry point jumps to `main` at $0150, with
+/// each of `handlers` placed at its address (interrupt vectors, routines).
+pub fn synthetic_rom(main: &[u8], handlers: &[(u16, &[u8])]) -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+    rom[0x0150..0x0150 + main.len()].copy_from_slice(main);
+    for &(address, code) in handlers {
+        let address = address as usize;
+        rom[address..address + code.len()].copy_from_slice(code);
+    }
+    rom
+}
+
+/// A DMG with no boot ROM running [`synthetic_rom`].
+pub fn synthetic_game_boy(main: &[u8], handlers: &[(u16, &[u8])]) -> GameBoy {
+    GameBoy::new(
+        Cartridge::new(synthetic_rom(main, handlers), None).unwrap(),
+        None,
+    )
+}
+
+/// A debugger over [`synthetic_game_boy`].
+pub fn synthetic_debugger(main: &[u8], handlers: &[(u16, &[u8])]) -> Debugger {
+    Debugger::new(synthetic_game_boy(main, handlers))
+}
+
+/// The address of the instruction the debugger will execute next.
+pub fn ir_address(debugger: &Debugger) -> u16 {
+    debugger.game_boy().cpu().ir_address
+}
+
+/// Single-step until the instruction at `address` is next.
+pub fn step_to(debugger: &mut Debugger, address: u16) {
+    while ir_address(debugger) != address {
+        debugger.step();
+    }
+}
+
 pub fn load_rom(relative: &str) -> TestRun<crate::Dmg> {
     let path = rom_path(relative);
     let rom = std::fs::read(&path)
         .unwrap_or_else(|e| panic!("Failed to read ROM {}: {e}", path.display()));
     let boot_rom = try_load_boot_rom();
-    let mut gb = GameBoy::new(Cartridge::new(rom, None), boot_rom);
+    let mut gb = GameBoy::new(Cartridge::new(rom, None).unwrap(), boot_rom);
+    gb.set_power_on_memory(PowerOnMemory::Dmg);
     run_boot_rom(&mut gb);
     TestRun::new(gb, relative, "DMG-B")
 }
 
 pub fn load_rom_with_boot_rom(relative: &str, boot_rom: Box<[u8; 256]>) -> TestRun<crate::Dmg> {
-    let gb = GameBoy::new(
-        Cartridge::new(std::fs::read(rom_path(relative)).unwrap(), None),
+    let mut gb = GameBoy::new(
+        Cartridge::new(std::fs::read(rom_path(relative)).unwrap(), None).unwrap(),
         Some(BootRom::Dmg(boot_rom)),
     );
+    gb.set_power_on_memory(PowerOnMemory::Dmg);
     TestRun::new(gb, relative, "DMG-B")
 }
 
@@ -330,39 +314,13 @@ pub fn run_boot_rom<M: Model>(gb: &mut Console<M>) {
     );
 }
 
-/// Run the emulator until the serial output contains any of the given needle strings,
-/// or until an infinite loop is detected at a frame boundary, or until a timeout is reached.
-pub fn run_until_serial_match<S: System>(
-    s: &mut S,
-    needles: &[&str],
-    timeout_frames: u32,
-) -> String {
-    let mut output = String::new();
-    for _ in 0..timeout_frames {
-        while !s.step().new_screen {}
-        let bytes = s.drain_serial_output();
-        if !bytes.is_empty() {
-            output.push_str(&String::from_utf8_lossy(&bytes));
-            if needles.iter().any(|needle| output.contains(needle)) {
-                return output;
-            }
-        }
-        if is_infinite_loop(s) {
-            return output;
-        }
-    }
-    output
-}
-
 /// Run the emulator for a fixed number of frames.
 pub fn run_frames<S: System>(s: &mut S, frames: u32) {
-    for _ in 0..frames {
-        while !s.step().new_screen {}
-    }
+    s.run_frames(frames);
 }
 
 /// Run the emulator for a fixed number of T-cycles. Unlike
-/// [`run_frames`], doesn't depend on the LCD producing frames — used
+/// [`run_frames`], doesn't depend on when frames start — used
 /// by gambatte tests which finish after a fixed cycle count (the
 /// gambatte testrunner runs for 1,053,360 T-cycles, equal to 15 LCD
 /// frames at single speed).
@@ -448,58 +406,6 @@ pub fn run_until_infinite_loop_no_lcd<S: System>(s: &mut S, max_instructions: u3
     false
 }
 
-/// Check if the CPU is stuck in a known completion loop.
-pub fn is_infinite_loop<S: System>(s: &S) -> bool {
-    let pc = s.cpu().ir_address;
-    if s.read(pc) == 0x18 && s.read(pc.wrapping_add(1)) == 0xFE {
-        return true;
-    }
-    if s.read(pc.wrapping_sub(1)) == 0x18 && s.read(pc) == 0xFE {
-        return true;
-    }
-    if s.read(pc) == 0x40
-        && s.read(pc.wrapping_add(1)) == 0x18
-        && s.read(pc.wrapping_add(2)) == 0xFE
-    {
-        return true;
-    }
-
-    if s.cpu().halt.state != crate::cpu::HaltState::Running
-        && !s.speed_switch_in_progress()
-        && !s.vram_dma_holds_cpu()
-    {
-        if s.interrupts().enabled.is_empty() {
-            return true;
-        }
-
-        if s.read(pc.wrapping_sub(1)) == 0x76 {
-            for offset in 0u16..4 {
-                let addr = pc.wrapping_add(offset);
-                if s.read(addr) == 0x18 {
-                    let rel = s.read(addr.wrapping_add(1)) as i8;
-                    let target = addr.wrapping_add(2).wrapping_add(rel as u16);
-                    if target <= pc.wrapping_sub(1) {
-                        return true;
-                    }
-                }
-            }
-        }
-    }
-
-    false
-}
-
-pub fn check_mooneye_pass(cpu: &Cpu) -> bool {
-    cpu.b == 3 && cpu.c == 5 && cpu.d == 8 && cpu.e == 13 && cpu.h == 21 && cpu.l == 34
-}
-
-pub fn format_registers(cpu: &Cpu) -> String {
-    format!(
-        "B={} C={} D={} E={} H={} L={} (expected: B=3 C=5 D=8 E=13 H=21 L=34)",
-        cpu.b, cpu.c, cpu.d, cpu.e, cpu.h, cpu.l
-    )
-}
-
 pub fn format_wram_dump<S: System>(s: &S, start: u16, len: u16) -> String {
     let mut out = String::new();
     let mut offset: u16 = 0;
@@ -520,10 +426,7 @@ pub fn format_wram_dump<S: System>(s: &S, start: u16, len: u16) -> String {
 /// Convert a Screen to a flat greyscale pixel buffer using dmg-acid2 reference palette:
 /// PaletteIndex 0 → 0xFF, 1 → 0xAA, 2 → 0x55, 3 → 0x00
 pub fn screen_to_greyscale(screen: &Screen) -> Vec<u8> {
-    const GREYSCALE: [u8; 4] = [0xFF, 0xAA, 0x55, 0x00];
-    (0..144u8)
-        .flat_map(|y| (0..160u8).map(move |x| GREYSCALE[screen.pixel(x, y).0 as usize]))
-        .collect()
+    screen.to_greyscale()
 }
 
 /// Load a reference PNG as a flat greyscale pixel buffer (values 0x00-0xFF).
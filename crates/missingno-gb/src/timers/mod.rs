@@ -5,6 +5,7 @@ pub use registers::Register;
 pub mod registers;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Timers {
     pub internal_counter: u16,
     pub counter: u8,
@@ -210,3 +211,83 @@ impl Timers {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENABLE: u8 = 0b100;
+    const FREQ_4096: u8 = 0b00;
+    const FREQ_262144: u8 = 0b01;
+    const FREQ_65536: u8 = 0b10;
+
+    /// Timers with TAC already written and the divider parked so that only
+    /// bit 7 (the 4096 Hz tap) is high.
+    fn timers_with_tac(tac: u8) -> Timers {
+        Timers {
+            internal_counter: 1 << 7,
+            control: Control(tac),
+            ..Timers::new()
+        }
+    }
+
+    /// The TAC write glitch is the same falling edge the counter sees on its
+    /// own: the multiplexed `selected_bit && enable` signal dropping from 1 to
+    /// 0. Every (old, new) TAC combination is checked against that rule.
+    #[test]
+    fn tac_write_increments_only_on_selected_signal_falling() {
+        let cases = [
+            // Disabling while the selected bit is high.
+            (ENABLE | FREQ_4096, FREQ_4096, 1),
+            // Switching from a high tap to a low one.
+            (ENABLE | FREQ_4096, ENABLE | FREQ_262144, 1),
+            (ENABLE | FREQ_4096, ENABLE | FREQ_65536, 1),
+            // Disabling and switching to a low tap in one write.
+            (ENABLE | FREQ_4096, FREQ_262144, 1),
+            // Low → high, low → low, high → high: no falling edge.
+            (ENABLE | FREQ_262144, ENABLE | FREQ_4096, 0),
+            (ENABLE | FREQ_262144, ENABLE | FREQ_65536, 0),
+            (ENABLE | FREQ_4096, ENABLE | FREQ_4096, 0),
+            // Enabling never glitches, even onto a high tap.
+            (FREQ_4096, ENABLE | FREQ_4096, 0),
+            (FREQ_262144, ENABLE | FREQ_4096, 0),
+            (FREQ_4096, FREQ_262144, 0),
+        ];
+
+        for (old, new, expected) in cases {
+            let mut timers = timers_with_tac(old);
+            timers.write_register(Register::Control, new);
+            assert_eq!(
+                timers.counter, expected,
+                "TAC {old:#05b} -> {new:#05b} with only divider bit 7 set"
+            );
+        }
+    }
+
+    #[test]
+    fn tac_write_glitch_can_overflow_tima() {
+        let mut timers = timers_with_tac(ENABLE | FREQ_4096);
+        timers.counter = 0xFF;
+        timers.write_register(Register::Control, ENABLE | FREQ_262144);
+        assert_eq!(timers.counter, 0);
+        assert!(timers.overflow_pending);
+
+        timers.mcycle();
+        assert_eq!(timers.counter, timers.modulo);
+        assert!(matches!(
+            timers.take_pending_interrupt(),
+            Some(Interrupt::Timer)
+        ));
+    }
+
+    #[test]
+    fn tac_write_glitch_during_reload_is_overwritten_by_tma() {
+        let mut timers = timers_with_tac(ENABLE | FREQ_4096);
+        timers.counter = 0xFF;
+        timers.modulo = 0x42;
+        timers.write_register(Register::Control, ENABLE | FREQ_262144);
+        timers.mcycle();
+        assert!(timers.reloading);
+        assert_eq!(timers.counter, 0x42);
+    }
+}
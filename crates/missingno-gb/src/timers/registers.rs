@@ -7,6 +7,7 @@ pub enum Register {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Control(pub u8);
 
 impl Control {
@@ -83,6 +83,11 @@ static EXTENSION_DEFS: &[ExtensionDef] = &[
         field_type: FieldType::Bool,
         description: "HALT bug flag — opcode after HALT will be re-read because PC failed to advance",
     },
+    ExtensionDef {
+        name: "isr_vector",
+        field_type: FieldType::UInt8,
+        description: "Low byte of the most recent dispatch's vector (0x00 if cancelled by the IE push bug, 0xFF before any)",
+    },
     ExtensionDef {
         name: "ppu_half_mcycle",
         field_type: FieldType::Bool,
@@ -151,6 +156,7 @@ enum Emitter {
     // [fields.extensions.missingno] in the profile. See EXTENSION_DEFS.
     ExtPendingVectorResolve,
     ExtHaltBug,
+    ExtIsrVector,
     ExtPpuHalfMcycle,
     ExtPpuMcycle,
     ExtPpuScanClock,
@@ -322,6 +328,7 @@ fn resolve_emitter(field: &str, memory: &BTreeMap<String, u16>) -> Emitter {
         // Extension fields
         "pending_vector_resolve" => Emitter::ExtPendingVectorResolve,
         "halt_bug" => Emitter::ExtHaltBug,
+        "isr_vector" => Emitter::ExtIsrVector,
         "ppu_half_mcycle" => Emitter::ExtPpuHalfMcycle,
         "ppu_mcycle" => Emitter::ExtPpuMcycle,
         "ppu_scan_clock" => Emitter::ExtPpuScanClock,
@@ -592,6 +599,9 @@ impl Tracer {
                     w.set_bool(col, gb.cpu().pending_vector_resolve_flag())
                 }
                 Emitter::ExtHaltBug => w.set_bool(col, gb.cpu().halt_bug_flag()),
+                Emitter::ExtIsrVector => {
+                    w.set_u8(col, gb.cpu().last_isr_vector().map_or(0xff, |v| v as u8))
+                }
                 Emitter::ExtPpuHalfMcycle => w.set_bool(col, ppu_sigs.half_mcycle),
                 Emitter::ExtPpuMcycle => w.set_bool(col, ppu_sigs.mcycle),
                 Emitter::ExtPpuScanClock => w.set_bool(col, ppu_sigs.scan_clock),
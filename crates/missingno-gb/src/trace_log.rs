@@ -0,0 +1,100 @@
+//! A plain-text line per executed instruction, in the layout Gameboy Doctor
+//! and BGB-derived tools compare against:
+//!
+//! ```text
+//! A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,50,01
+//! ```
+//!
+//! Each line is the register file as the instruction at `PC` starts, and
+//! the four bytes from `PC` on. Interrupt dispatches and the M-cycles a
+//! halted CPU idles through aren't instructions, so they get no line.
+//! Turned on with [`Console::set_trace_sink`](crate::Console::set_trace_sink).
+
+use std::io::Write;
+
+use crate::cpu::Cpu;
+
+/// The register file as an instruction starts. The CPU captures one as it
+/// routes each fetched opcode, before the previous instruction's results
+/// could be mistaken for this one's.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Entry {
+    a: u8,
+    f: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    h: u8,
+    l: u8,
+    sp: u16,
+    pub(crate) pc: u16,
+}
+
+impl Entry {
+    pub(crate) fn capture(cpu: &Cpu, pc: u16) -> Self {
+        Self {
+            a: cpu.a,
+            f: cpu.flags.bits(),
+            b: cpu.b,
+            c: cpu.c,
+            d: cpu.d,
+            e: cpu.e,
+            h: cpu.h,
+            l: cpu.l,
+            sp: cpu.stack_pointer,
+            pc,
+        }
+    }
+}
+
+/// Where the lines go, if anywhere. A sink that fails a write is dropped,
+/// ending the log rather than stalling the console.
+#[derive(Default)]
+pub(crate) struct TraceLog {
+    sink: Option<Box<dyn Write>>,
+}
+
+impl TraceLog {
+    pub(crate) fn set_sink(&mut self, sink: Option<Box<dyn Write>>) -> Option<Box<dyn Write>> {
+        std::mem::replace(&mut self.sink, sink)
+    }
+
+    /// Log `entry`, whose instruction's first four bytes are `pcmem`.
+    pub(crate) fn record(&mut self, entry: &Entry, pcmem: [u8; 4]) {
+        let Some(sink) = &mut self.sink else {
+            return;
+        };
+        let result = writeln!(
+            sink,
+            "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} \
+             SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+            entry.a,
+            entry.f,
+            entry.b,
+            entry.c,
+            entry.d,
+            entry.e,
+            entry.h,
+            entry.l,
+            entry.sp,
+            entry.pc,
+            pcmem[0],
+            pcmem[1],
+            pcmem[2],
+            pcmem[3],
+        );
+        if result.is_err() {
+            self.sink = None;
+        }
+    }
+}
+
+/// A clone is a snapshot of the console, not a second log: the copy
+/// doesn't trace.
+impl Clone for TraceLog {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
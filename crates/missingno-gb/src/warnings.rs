@@ -0,0 +1,62 @@
+//! Things a game did that the emulator doesn't model. Rather than panic or
+//! carry on silently, the component involved records a [`Warning`] for the
+//! front-end to collect with [`Console::take_warnings`](crate::Console::take_warnings).
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub enum Warning {
+    /// The CPU fetched one of the eleven undefined opcodes and locked up.
+    IllegalOpcode { opcode: u8, address: u16 },
+    /// An MBC3 RAM/RTC select write named neither a RAM bank nor an RTC
+    /// register. The previous mapping stays.
+    Mbc3BankSelect { value: u8 },
+    /// A write to the HuC3 infrared port. The IR transmitter isn't
+    /// emulated.
+    Huc3Infrared,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::IllegalOpcode { opcode, address } => {
+                write!(f, "executed invalid opcode ${opcode:02X} at ${address:04X}")
+            }
+            Warning::Mbc3BankSelect { value } => {
+                write!(f, "selected nonexistent MBC3 RAM/RTC bank ${value:02X}")
+            }
+            Warning::Huc3Infrared => write!(f, "wrote to unimplemented HuC3 IR register"),
+        }
+    }
+}
+
+/// Pending warnings for one component. Each distinct warning is reported
+/// once, so one raised in a hot loop doesn't flood the log; past
+/// [`Self::LIMIT`] distinct warnings, new ones are dropped. Nothing is
+/// allocated until something is recorded.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Warnings {
+    reported: Vec<Warning>,
+    pending: Vec<Warning>,
+}
+
+impl Warnings {
+    const LIMIT: usize = 64;
+
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(&mut self, warning: Warning) {
+        if self.reported.len() < Self::LIMIT && !self.reported.contains(&warning) {
+            self.reported.push(warning);
+            self.pending.push(warning);
+        }
+    }
+
+    pub(crate) fn take(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.pending)
+    }
+}
@@ -17,4 +17,5 @@ mod mooneye_wilbertpol;
 mod samesuite;
 mod scribbltests;
 mod strikethrough;
+mod synthetic;
 mod turtle_tests;
@@ -0,0 +1,226 @@
+//! Flag results of the 8-bit increments and decrements, SCF, CCF and DAA,
+//! per the Pan Docs instruction tables. INC and DEC set H on a carry out of
+//! (or borrow into) bit 3 and never touch C, for registers and `[HL]` alike.
+//! SCF and CCF clear N and H and leave Z alone. DAA clears H, keeps N, and
+//! after an addition can set C but never clear it; after a subtraction it
+//! leaves C as it found it.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// F's bits: the flags live in the top nibble.
+const Z: u8 = 0x80;
+const N: u8 = 0x40;
+const H: u8 = 0x20;
+const C: u8 = 0x10;
+const NONE: u8 = 0x00;
+const ALL: u8 = Z | N | H | C;
+
+/// Loads $C000 with `memory`, AF with `a` and `flags` through the stack,
+/// runs `code`, then spins.
+fn run(a: u8, flags: u8, memory: u8, code: &[u8]) -> GameBoy {
+    let mut main = vec![0x31, 0xFE, 0xDF]; // LD SP,$DFFE
+    main.extend([0x21, 0x00, 0xC0]); // LD HL,$C000
+    main.extend([0x36, memory]); // LD (HL),memory
+    main.extend([0x01, flags, a]); // LD BC,a:flags
+    main.extend([0xC5, 0xF1]); // PUSH BC; POP AF
+    main.extend(code);
+    let spin = 0x0150 + main.len() as u16;
+    main.extend([0x18, 0xFE]); // JR -2
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(gb.cpu().ir_address, spin, "didn't reach the spin");
+    gb
+}
+
+const INC_A: u8 = 0x3C;
+const DEC_A: u8 = 0x3D;
+const INC_HL: u8 = 0x34;
+const DEC_HL: u8 = 0x35;
+
+/// (operand, flags before, result, flags after) for INC.
+const INC_CASES: [(u8, u8, u8, u8); 8] = [
+    (0x00, NONE, 0x01, NONE),
+    (0x0E, NONE, 0x0F, NONE),
+    (0x0F, NONE, 0x10, H),
+    (0x10, NONE, 0x11, NONE),
+    (0x7F, NONE, 0x80, H),
+    (0xFE, NONE, 0xFF, NONE),
+    (0xFF, NONE, 0x00, Z | H),
+    // N, Z and H are all recomputed; C survives.
+    (0x0F, Z | N | C, 0x10, H | C),
+];
+
+/// (operand, flags before, result, flags after) for DEC.
+const DEC_CASES: [(u8, u8, u8, u8); 8] = [
+    (0x02, NONE, 0x01, N),
+    (0x01, NONE, 0x00, Z | N),
+    (0x00, NONE, 0xFF, N | H),
+    (0x10, NONE, 0x0F, N | H),
+    (0x11, NONE, 0x10, N),
+    (0x0F, NONE, 0x0E, N),
+    (0x80, NONE, 0x7F, N | H),
+    // Z and H are recomputed; C survives.
+    (0x10, Z | C, 0x0F, N | H | C),
+];
+
+#[test]
+fn inc_register_flags() {
+    for (value, before, result, after) in INC_CASES {
+        let gb = run(value, before, 0, &[INC_A]);
+        assert_eq!(gb.cpu().a, result, "INC A from {value:02X}");
+        assert_eq!(gb.cpu().flags.bits(), after, "INC A from {value:02X}");
+    }
+}
+
+#[test]
+fn dec_register_flags() {
+    for (value, before, result, after) in DEC_CASES {
+        let gb = run(value, before, 0, &[DEC_A]);
+        assert_eq!(gb.cpu().a, result, "DEC A from {value:02X}");
+        assert_eq!(gb.cpu().flags.bits(), after, "DEC A from {value:02X}");
+    }
+}
+
+#[test]
+fn inc_memory_matches_inc_register() {
+    for (value, before, result, after) in INC_CASES {
+        let gb = run(0, before, value, &[INC_HL]);
+        assert_eq!(gb.peek(0xC000), result, "INC [HL] from {value:02X}");
+        assert_eq!(gb.cpu().flags.bits(), after, "INC [HL] from {value:02X}");
+    }
+}
+
+#[test]
+fn dec_memory_matches_dec_register() {
+    for (value, before, result, after) in DEC_CASES {
+        let gb = run(0, before, value, &[DEC_HL]);
+        assert_eq!(gb.peek(0xC000), result, "DEC [HL] from {value:02X}");
+        assert_eq!(gb.cpu().flags.bits(), after, "DEC [HL] from {value:02X}");
+    }
+}
+
+const SCF: u8 = 0x37;
+const CCF: u8 = 0x3F;
+
+#[test]
+fn scf_sets_carry_and_clears_n_and_h() {
+    for (before, after) in [(NONE, C), (C, C), (N | H, C), (ALL, Z | C)] {
+        let gb = run(0x12, before, 0, &[SCF]);
+        assert_eq!(gb.cpu().flags.bits(), after, "SCF from {before:02X}");
+        assert_eq!(gb.cpu().a, 0x12);
+    }
+}
+
+#[test]
+fn ccf_toggles_carry_and_clears_n_and_h() {
+    for (before, after) in [(NONE, C), (C, NONE), (N | H, C), (ALL, Z), (Z, Z | C)] {
+        let gb = run(0x12, before, 0, &[CCF]);
+        assert_eq!(gb.cpu().flags.bits(), after, "CCF from {before:02X}");
+    }
+}
+
+#[test]
+fn scf_then_ccf_clears_carry() {
+    let gb = run(0, H | N, 0, &[SCF, CCF]);
+    assert_eq!(gb.cpu().flags.bits(), NONE);
+    let gb = run(0, NONE, 0, &[SCF, CCF, CCF]);
+    assert_eq!(gb.cpu().flags.bits(), C);
+}
+
+#[test]
+fn flag_low_nibble_stays_clear() {
+    for code in [[INC_A], [DEC_A], [SCF], [CCF], [0x27]] {
+        let gb = run(0xFF, ALL, 0xFF, &code);
+        assert_eq!(gb.cpu().flags.bits() & 0x0F, 0, "{code:02X?}");
+    }
+}
+
+const DAA: u8 = 0x27;
+
+/// `LD B,operand` followed by `op B` and DAA, from A = `a` with no flags.
+fn bcd(a: u8, op: u8, operand: u8) -> GameBoy {
+    run(a, NONE, 0, &[0x06, operand, op, DAA])
+}
+
+const ADD_B: u8 = 0x80;
+const ADC_B: u8 = 0x88;
+const SUB_B: u8 = 0x90;
+const SBC_B: u8 = 0x98;
+
+#[test]
+fn daa_after_addition() {
+    // (A, B, BCD sum, flags after DAA)
+    for (a, b, sum, flags) in [
+        (0x15, 0x27, 0x42, NONE),
+        (0x09, 0x01, 0x10, NONE),
+        (0x45, 0x45, 0x90, NONE),
+        (0x50, 0x50, 0x00, Z | C),
+        (0x99, 0x01, 0x00, Z | C),
+        (0x99, 0x99, 0x98, C),
+        (0x00, 0x00, 0x00, Z),
+    ] {
+        let gb = bcd(a, ADD_B, b);
+        assert_eq!(gb.cpu().a, sum, "{a:02X} + {b:02X}");
+        assert_eq!(gb.cpu().flags.bits(), flags, "{a:02X} + {b:02X}");
+    }
+}
+
+#[test]
+fn daa_after_subtraction() {
+    // (A, B, BCD difference, flags after DAA)
+    for (a, b, difference, flags) in [
+        (0x42, 0x15, 0x27, N),
+        (0x10, 0x01, 0x09, N),
+        (0x10, 0x20, 0x90, N | C),
+        (0x00, 0x01, 0x99, N | C),
+        (0x45, 0x45, 0x00, Z | N),
+    ] {
+        let gb = bcd(a, SUB_B, b);
+        assert_eq!(gb.cpu().a, difference, "{a:02X} - {b:02X}");
+        assert_eq!(gb.cpu().flags.bits(), flags, "{a:02X} - {b:02X}");
+    }
+}
+
+#[test]
+fn daa_carries_into_a_multi_byte_sum() {
+    // 0199 + 0001: the low byte's DAA carry feeds ADC on the high byte.
+    let gb = run(
+        0x99,
+        NONE,
+        0,
+        &[
+            0x06, 0x01, ADD_B, DAA, 0x4F, 0x3E, 0x01, 0x06, 0x00, ADC_B, DAA,
+        ],
+    );
+    assert_eq!((gb.cpu().a, gb.cpu().c), (0x02, 0x00));
+    assert_eq!(gb.cpu().flags.bits(), NONE);
+
+    // 0200 - 0001: the borrow survives DAA into SBC.
+    let gb = run(
+        0x00,
+        NONE,
+        0,
+        &[
+            0x06, 0x01, SUB_B, DAA, 0x4F, 0x3E, 0x02, 0x06, 0x00, SBC_B, DAA,
+        ],
+    );
+    assert_eq!((gb.cpu().a, gb.cpu().c), (0x01, 0x99));
+    assert_eq!(gb.cpu().flags.bits(), N);
+}
+
+#[test]
+fn daa_adjusts_from_the_flags_alone_after_subtraction() {
+    // After a subtraction only H and C choose the adjustment; A's digits
+    // are not inspected, so an invalid BCD digit passes through.
+    let gb = run(0x1A, N, 0, &[DAA]);
+    assert_eq!(gb.cpu().a, 0x1A);
+    assert_eq!(gb.cpu().flags.bits(), N);
+
+    let gb = run(0x1A, N | H, 0, &[DAA]);
+    assert_eq!(gb.cpu().a, 0x14);
+    assert_eq!(gb.cpu().flags.bits(), N);
+
+    let gb = run(0x1A, N | C, 0, &[DAA]);
+    assert_eq!(gb.cpu().a, 0xBA);
+    assert_eq!(gb.cpu().flags.bits(), N | C);
+}
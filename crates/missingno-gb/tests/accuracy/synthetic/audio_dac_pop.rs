@@ -0,0 +1,72 @@
+//! Each DAC puts a DC level on the mixer while it's on, even with its
+//! channel silent, and floats at the midpoint while off. The output's
+//! high-pass filter blocks that level, so toggling a DAC gives a pop that
+//! decays back to silence instead of a lasting step.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Copies $FF80 into NR22 forever. CH2 is never triggered, so its DAC
+/// sees a constant 0 whenever NR22 powers it.
+fn game_boy() -> GameBoy {
+    let main = [
+        0xF0, 0x80, // LDH A,($80)
+        0xE0, 0x17, // LDH ($17),A
+        0x18, 0xFA, // JR -6
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    gb.poke_raw(0xFF80, 0x00);
+    // Let the filter settle from power-on.
+    test_support::run_frames(&mut gb, 10);
+    gb.drain_audio_samples();
+    gb
+}
+
+fn left(samples: &[(f32, f32)]) -> Vec<f32> {
+    samples.iter().map(|&(left, _)| left).collect()
+}
+
+/// Sets NR22 to `value` and returns a few frames of output.
+fn toggle(gb: &mut GameBoy, value: u8) -> Vec<f32> {
+    gb.poke_raw(0xFF80, value);
+    test_support::run_frames(gb, 4);
+    left(&gb.drain_audio_samples())
+}
+
+fn assert_decays(samples: &[f32], sign: f32) {
+    // The write lands partway through the first frame.
+    let start = samples
+        .iter()
+        .position(|sample| sample.abs() > 0.1)
+        .expect("toggling the DAC pops");
+    let pop = &samples[start..];
+    assert!(pop[0].signum() == sign, "pop starts at {}", pop[0]);
+    assert!(
+        pop.windows(2).all(|pair| pair[1].abs() <= pair[0].abs()),
+        "pop decays monotonically"
+    );
+    let tail = pop.last().unwrap();
+    assert!(tail.abs() < 0.001, "pop decays to silence, ends at {tail}");
+}
+
+#[test]
+fn settled_output_is_silent() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 1);
+    for sample in left(&gb.drain_audio_samples()) {
+        assert!(sample.abs() < 0.001, "{sample}");
+    }
+}
+
+#[test]
+fn powering_a_silent_dac_pops_low() {
+    let mut gb = game_boy();
+    // Volume 0, envelope up: the DAC is on but the channel outputs 0.
+    assert_decays(&toggle(&mut gb, 0x08), -1.0);
+}
+
+#[test]
+fn powering_it_off_again_pops_high() {
+    let mut gb = game_boy();
+    toggle(&mut gb, 0x08);
+    assert_decays(&toggle(&mut gb, 0x00), 1.0);
+}
@@ -0,0 +1,83 @@
+//! NR51 routes each channel to the left output, the right or both, and NR50
+//! scales each side of the mix.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Plays ~440 Hz on channel 2 panned fully left, then fully right, then to
+/// both sides with the right's master volume turned down to 0 (1/8).
+fn panned_tone() -> GameBoy {
+    let main = [
+        0x3E, 0x80, // LD A,$80 (50% duty)
+        0xE0, 0x16, // LDH (NR21),A
+        0x3E, 0xF0, // LD A,$F0 (volume 15, no envelope)
+        0xE0, 0x17, // LDH (NR22),A
+        0x3E, 0xD6, // LD A,$D6 (period $6D6)
+        0xE0, 0x18, // LDH (NR23),A
+        0x3E, 0x86, // LD A,$86 (trigger, period high 6)
+        0xE0, 0x19, // LDH (NR24),A
+        0x3E, 0x20, // LD A,$20 (channel 2 left)
+        0xE0, 0x25, // LDH (NR51),A
+        0xCD, 0x90, 0x01, // CALL wait
+        0x3E, 0x02, // LD A,$02 (channel 2 right)
+        0xE0, 0x25, // LDH (NR51),A
+        0xCD, 0x90, 0x01, // CALL wait
+        0x3E, 0x22, // LD A,$22 (channel 2 both sides)
+        0xE0, 0x25, // LDH (NR51),A
+        0x3E, 0x70, // LD A,$70 (left volume 7, right 0)
+        0xE0, 0x24, // LDH (NR50),A
+        0x18, 0xFE, // JR -2
+    ];
+    // About ten frames: 24576 iterations of 7 M-cycles.
+    let wait = [
+        0x01, 0x00, 0x60, // LD BC,$6000
+        0x0B, // loop: DEC BC
+        0x78, // LD A,B
+        0xB1, // OR C
+        0x20, 0xFB, // JR NZ,loop
+        0xC9, // RET
+    ];
+    test_support::synthetic_game_boy(&main, &[(0x0190, &wait)])
+}
+
+/// Peak-to-peak swing of each side over frames `from..to`, after running
+/// up to `from` and dropping what played so far.
+fn swing(gb: &mut GameBoy, frames_run: &mut u32, from: u32, to: u32) -> (f32, f32) {
+    test_support::run_frames(gb, from - *frames_run);
+    gb.drain_audio_samples();
+    test_support::run_frames(gb, to - from);
+    *frames_run = to;
+    let samples = gb.drain_audio_samples();
+    let range = |side: fn(&(f32, f32)) -> f32| {
+        let max = samples.iter().map(side).fold(f32::MIN, f32::max);
+        let min = samples.iter().map(side).fold(f32::MAX, f32::min);
+        max - min
+    };
+    (range(|s| s.0), range(|s| s.1))
+}
+
+#[test]
+fn a_tone_pans_left_then_right() {
+    let mut gb = panned_tone();
+    let mut frames_run = 0;
+
+    let (left, right) = swing(&mut gb, &mut frames_run, 3, 7);
+    assert!(left > 0.2, "panned left: left swings {left}");
+    assert!(right < 0.01, "panned left: right swings {right}");
+
+    let (left, right) = swing(&mut gb, &mut frames_run, 13, 17);
+    assert!(left < 0.01, "panned right: left swings {left}");
+    assert!(right > 0.2, "panned right: right swings {right}");
+}
+
+#[test]
+fn master_volume_scales_each_side() {
+    let mut gb = panned_tone();
+    let mut frames_run = 0;
+
+    let (left, right) = swing(&mut gb, &mut frames_run, 23, 27);
+    let ratio = left / right;
+    assert!(
+        (7.5..8.5).contains(&ratio),
+        "volume 7 against 0 should be 8:1, got {left} / {right}"
+    );
+}
@@ -0,0 +1,56 @@
+//! A DMG boot ROM overlays $0000–$00FF from power-on, with the CPU starting
+//! at $0000 and its registers zeroed rather than in the post-boot state.
+//! Writing $FF50 unmaps it, and the region reads the cartridge again.
+
+use missingno_gb::{BootRom, GameBoy, cartridge::Cartridge, test_support};
+
+/// Stores $42 at $C000, then falls through to an unmap at $00FC so the
+/// next fetch is the cartridge's $0100.
+fn boot_rom() -> BootRom {
+    let code = [
+        0x3E, 0x42, // LD A,$42
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+    ];
+    let unmap = [
+        0x3E, 0x01, // LD A,$01
+        0xE0, 0x50, // LDH ($50),A
+    ];
+    let mut rom = [0u8; 0x100]; // NOPs
+    rom[..code.len()].copy_from_slice(&code);
+    rom[0xFC..].copy_from_slice(&unmap);
+    BootRom::Dmg(Box::new(rom))
+}
+
+/// Fills $0000–$00FF with $AA, and stores $99 at $C001 once started.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x3E, 0x99, // LD A,$99
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut rom = test_support::synthetic_rom(&main, &[]);
+    rom[..0x100].fill(0xAA);
+    GameBoy::new(Cartridge::new(rom, None).unwrap(), Some(boot_rom()))
+}
+
+#[test]
+fn the_cpu_starts_at_zero_with_cleared_registers() {
+    let gb = game_boy();
+    let cpu = gb.cpu();
+    assert_eq!(cpu.pc, 0x0000);
+    let registers = [cpu.a, cpu.b, cpu.c, cpu.d, cpu.e, cpu.h, cpu.l];
+    assert_eq!(registers, [0; 7]);
+    assert_eq!(cpu.flags.bits(), 0);
+    assert_eq!(cpu.stack_pointer, 0);
+}
+
+#[test]
+fn the_boot_rom_overlays_the_cartridge_until_unmapped() {
+    let mut gb = game_boy();
+    assert_eq!(gb.peek_range(0x0000, 2), [0x3E, 0x42]);
+    assert_eq!(gb.peek(0x0100), 0x00, "the header still shows through");
+
+    assert!(test_support::run_until_infinite_loop_no_lcd(&mut gb, 1000));
+    assert_eq!(gb.peek_range(0xC000, 2), [0x42, 0x99], "boot, then game");
+    assert_eq!(gb.peek_range(0x0000, 2), [0xAA, 0xAA], "cartridge again");
+}
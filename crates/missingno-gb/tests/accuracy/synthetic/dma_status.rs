@@ -0,0 +1,74 @@
+//! The debugger's view of OAM DMA: idle, arming for 1.5 M-cycles after the
+//! $FF46 write, then counting down 160 bytes at one per M-cycle.
+
+use missingno_gb::{GameBoy, dma::DmaStatus, test_support};
+
+/// Starts a DMA from VRAM ($8000), which leaves the CPU free to keep
+/// fetching from ROM, then spins.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x3E, 0x80, // LD A,$80
+        0xE0, 0x46, // LDH ($46),A
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+/// The status at every T-cycle from the $FF46 write until DMA is idle again.
+fn transfer() -> Vec<DmaStatus> {
+    let mut gb = game_boy();
+    assert_eq!(gb.dma_status(), None, "idle before the write");
+    for _ in 0..1000 {
+        if gb.dma_status().is_some() {
+            break;
+        }
+        gb.step_tcycle();
+    }
+    let mut statuses = Vec::new();
+    while let Some(status) = gb.dma_status() {
+        statuses.push(status);
+        assert!(statuses.len() < 1000, "transfer never finished");
+        gb.step_tcycle();
+    }
+    statuses
+}
+
+#[test]
+fn starts_with_the_full_transfer_from_the_written_page() {
+    let statuses = transfer();
+    assert_eq!(
+        statuses[0],
+        DmaStatus {
+            source: 0x8000,
+            starting: true,
+            remaining: 160,
+        }
+    );
+    assert!(statuses.iter().all(|status| status.source == 0x8000));
+}
+
+#[test]
+fn arms_for_a_few_tcycles_then_copies_one_byte_per_mcycle() {
+    let statuses = transfer();
+    let arming = statuses.iter().take_while(|status| status.starting).count();
+    assert!((4..=8).contains(&arming), "armed for {arming} T-cycles");
+
+    let copying = &statuses[arming..];
+    assert!(copying.iter().all(|status| !status.starting));
+
+    // (remaining, T-cycles it was shown for). The counter ticks mid M-cycle,
+    // so the first count is cut short by the arming phase.
+    let mut runs: Vec<(u8, usize)> = Vec::new();
+    for status in copying {
+        match runs.last_mut() {
+            Some((remaining, tcycles)) if *remaining == status.remaining => *tcycles += 1,
+            _ => runs.push((status.remaining, 1)),
+        }
+    }
+    let counts: Vec<u8> = runs.iter().map(|&(remaining, _)| remaining).collect();
+    assert_eq!(counts, (1..=160).rev().collect::<Vec<u8>>());
+    assert!(
+        runs[1..].iter().all(|&(_, tcycles)| tcycles == 4),
+        "{runs:?}"
+    );
+}
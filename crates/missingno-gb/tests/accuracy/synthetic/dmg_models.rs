@@ -0,0 +1,44 @@
+//! Model detection: a cartridge stores A and B as the boot ROM left them,
+//! the way games check for a Pocket or a Super Game Boy.
+
+use missingno_gb::{DmgModel, GameBoy, cartridge::Cartridge, test_support};
+
+fn detect(model: DmgModel) -> (u8, u8) {
+    let rom = test_support::synthetic_rom(
+        &[
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x78, // LD A,B
+            0xEA, 0x01, 0xC0, // LD ($C001),A
+            0x76, // HALT
+        ],
+        &[],
+    );
+
+    let mut gb = GameBoy::new_with_model(Cartridge::new(rom, None).unwrap(), None, model);
+    test_support::run_for_tcycles(&mut gb, 1000);
+    (gb.peek(0xC000), gb.peek(0xC001))
+}
+
+#[test]
+fn boot_registers_identify_the_model() {
+    assert_eq!(detect(DmgModel::Dmg0), (0x01, 0xFF));
+    assert_eq!(detect(DmgModel::Dmg), (0x01, 0x00));
+    assert_eq!(detect(DmgModel::Mgb), (0xFF, 0x00));
+    assert_eq!(detect(DmgModel::Sgb), (0x01, 0x00));
+}
+
+#[test]
+fn new_emulates_a_dmg() {
+    let gb = GameBoy::new(Cartridge::new(vec![0u8; 0x8000], None).unwrap(), None);
+    assert_eq!(gb.dmg_model(), DmgModel::Dmg);
+    assert!(gb.sgb().is_none());
+}
+
+#[test]
+fn new_emulates_a_dmg_for_sgb_cartridges_too() {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x0146] = 0x03; // SGB flag
+    let gb = GameBoy::new(Cartridge::new(rom, None).unwrap(), None);
+    assert_eq!(gb.dmg_model(), DmgModel::Dmg);
+    assert!(gb.sgb().is_none());
+}
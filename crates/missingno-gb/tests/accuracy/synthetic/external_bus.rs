@@ -0,0 +1,117 @@
+//! The external data bus (cartridge and work RAM) holds the last value
+//! driven onto it, decaying to $FF twelve M-cycles after the drive. The
+//! VRAM bus holds its value without decay, and floats to $FF for CPU reads
+//! while the PPU has VRAM locked.
+//!
+//! Each routine runs from high RAM, which sits on neither bus, so opcode
+//! fetches don't refresh the value under test.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Jumps straight to `routine`, copied to $FF80, with $5A at $C123 and $77
+/// at $8000.
+fn game_boy(routine: &[u8]) -> GameBoy {
+    let mut gb = test_support::synthetic_game_boy(&[0xC3, 0x80, 0xFF], &[]); // JP $FF80
+    for (offset, &byte) in routine.iter().enumerate() {
+        gb.poke_raw(0xFF80 + offset as u16, byte);
+    }
+    gb.poke_raw(0xC123, 0x5A);
+    gb.poke_raw(0x8000, 0x77);
+    while gb.cpu().ir_address != 0xFF80 {
+        gb.step();
+    }
+    gb
+}
+
+fn with_nops(code: &[u8]) -> Vec<u8> {
+    let mut routine = code.to_vec();
+    routine.extend([0x00; 40]); // NOP
+    routine.extend([0x18, 0xFE]); // JR -2
+    routine
+}
+
+fn step_nops(gb: &mut GameBoy, count: usize) {
+    for _ in 0..count {
+        gb.step();
+    }
+}
+
+#[test]
+fn read_holds_value_until_decay() {
+    let mut gb = game_boy(&with_nops(&[
+        0xFA, 0x23, 0xC1, // LD A,($C123)
+    ]));
+    gb.step();
+    assert_eq!(gb.external_bus().latch(), 0x5A);
+
+    // The read's own M-cycle plus ten NOPs: eleven M-cycles.
+    step_nops(&mut gb, 10);
+    assert_eq!(gb.external_bus().latch(), 0x5A);
+
+    // The twelfth.
+    step_nops(&mut gb, 1);
+    assert_eq!(gb.external_bus().latch(), 0xFF);
+
+    step_nops(&mut gb, 10);
+    assert_eq!(gb.external_bus().latch(), 0xFF);
+}
+
+#[test]
+fn write_drives_the_bus() {
+    let mut gb = game_boy(&with_nops(&[
+        0x3E, 0x33, // LD A,$33
+        0xEA, 0x24, 0xC1, // LD ($C124),A
+    ]));
+    gb.step();
+    gb.step();
+    assert_eq!(gb.external_bus().latch(), 0x33);
+    step_nops(&mut gb, 12);
+    assert_eq!(gb.external_bus().latch(), 0xFF);
+}
+
+#[test]
+fn another_access_restarts_decay() {
+    let mut routine = vec![0xFA, 0x23, 0xC1]; // LD A,($C123)
+    routine.extend([0x00; 8]); // NOP
+    routine.extend([0xFA, 0x23, 0xC1]); // LD A,($C123)
+    let mut gb = game_boy(&with_nops(&routine));
+
+    gb.step();
+    step_nops(&mut gb, 8);
+    gb.step();
+    step_nops(&mut gb, 10);
+    assert_eq!(gb.external_bus().latch(), 0x5A, "decay restarted");
+    step_nops(&mut gb, 1);
+    assert_eq!(gb.external_bus().latch(), 0xFF);
+}
+
+#[test]
+fn vram_bus_does_not_decay() {
+    let mut gb = game_boy(&with_nops(&[
+        0xAF, // XOR A
+        0xE0, 0x40, // LDH ($40),A   LCD off, unlocking VRAM
+        0xFA, 0x00, 0x80, // LD A,($8000)
+    ]));
+    gb.step();
+    gb.step();
+    gb.step();
+    assert_eq!(gb.vram_bus().latch, 0x77);
+    step_nops(&mut gb, 30);
+    assert_eq!(gb.vram_bus().latch, 0x77);
+}
+
+#[test]
+fn vram_read_during_drawing_floats_high() {
+    let mut gb = game_boy(&[
+        0xF0, 0x41, // LDH A,($41)
+        0xE6, 0x03, // AND $03
+        0xFE, 0x03, // CP $03
+        0x20, 0xF8, // JR NZ,-8
+        0xFA, 0x00, 0x80, // LD A,($8000)
+        0xE0, 0xF0, // LDH ($F0),A
+        0x18, 0xFE, // JR -2
+    ]);
+    gb.poke_raw(0xFFF0, 0x00);
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(gb.peek(0xFFF0), 0xFF);
+}
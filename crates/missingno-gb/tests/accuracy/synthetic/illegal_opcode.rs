@@ -0,0 +1,65 @@
+//! The eleven undefined opcodes hard-lock the CPU: execution stops for good,
+//! interrupts can't wake it, and the rest of the console keeps running.
+
+use missingno_gb::{GameBoy, test_support};
+
+const ILLEGAL: [u8; 11] = [
+    0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD,
+];
+
+/// Enables the VBlank interrupt, then runs `opcode`. The VBlank handler
+/// marks $C000 and the code after the opcode marks $C001.
+fn run(opcode: u8) -> GameBoy {
+    let main = [
+        0xAF, // XOR A
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x3C, // INC A
+        0xE0, 0xFF,   // LDH ($FF),A
+        0xFB,   // EI
+        opcode, //
+        0x3E, 0x01, // LD A,$01
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xFE, // JR -2
+    ];
+    let vblank = [
+        0x3E, 0x01, // LD A,$01
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0xD9, // RETI
+    ];
+
+    let mut gb = test_support::synthetic_game_boy(&main, &[(0x0040, &vblank)]);
+    test_support::run_frames(&mut gb, 3);
+    gb
+}
+
+#[test]
+fn illegal_opcodes_lock_the_cpu() {
+    for opcode in ILLEGAL {
+        let gb = run(opcode);
+        assert!(gb.cpu().is_locked(), "{opcode:02X}");
+        assert_eq!(gb.peek(0xC001), 0, "{opcode:02X}: execution continued");
+        assert!(test_support::is_infinite_loop(&gb), "{opcode:02X}");
+    }
+}
+
+#[test]
+fn interrupts_do_not_wake_a_locked_cpu() {
+    let gb = run(0xDB);
+    assert_eq!(gb.peek(0xC000), 0, "the VBlank handler ran");
+    assert_eq!(gb.cpu().ir_address, 0x015B);
+}
+
+#[test]
+fn locked_console_keeps_producing_frames() {
+    let mut gb = run(0xDB);
+    test_support::run_frames(&mut gb, 2);
+    assert!(gb.cpu().is_locked());
+}
+
+#[test]
+fn reset_unlocks() {
+    let mut gb = run(0xDB);
+    gb.reset();
+    assert!(!gb.cpu().is_locked());
+}
@@ -0,0 +1,70 @@
+//! IME timing around EI, DI and RETI, checked with a hand-assembled ROM so a
+//! failure points at the instruction rather than at a test ROM's verdict.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// The timer handler counts entries in C and stores B at 0xC000 + C. On its
+/// first entry it re-requests the timer interrupt, so a RETI that enables IME
+/// immediately re-enters before the interrupted code runs another instruction.
+const HANDLER: [u8; 15] = [
+    0x0C, // INC C
+    0x79, // LD A,C
+    0xFE, 0x01, // CP 1
+    0x20, 0x04, // JR NZ,+4
+    0x3E, 0x04, // LD A,$04
+    0xE0, 0x0F, // LDH ($0F),A
+    0x26, 0xC0, // LD H,$C0
+    0x69, // LD L,C
+    0x70, // LD (HL),B
+    0xD9, // RETI
+];
+
+const MAIN: [u8; 36] = [
+    0xF3, // DI
+    0x31, 0xFE, 0xDF, // LD SP,$DFFE
+    0x06, 0x00, // LD B,0
+    0x0E, 0x00, // LD C,0
+    0x3E, 0x04, // LD A,$04
+    0xE0, 0xFF, // LDH ($FF),A
+    0xE0, 0x0F, // LDH ($0F),A
+    0xFB, // EI
+    0x04, // INC B
+    0x04, // INC B
+    0xF3, // DI
+    0x3E, 0x04, // LD A,$04
+    0xE0, 0x0F, // LDH ($0F),A
+    0xFB, // EI
+    0xF3, // DI
+    0x00, // NOP
+    0x00, // NOP
+    0x78, // LD A,B
+    0xEA, 0x00, 0xC0, // LD ($C000),A
+    0x79, // LD A,C
+    0xEA, 0x10, 0xC0, // LD ($C010),A
+    0x18, 0xFE, // JR -2
+];
+
+fn run() -> GameBoy {
+    let mut gb = test_support::synthetic_game_boy(&MAIN, &[(0x0050, &HANDLER)]);
+    test_support::run_for_tcycles(&mut gb, 70224);
+    gb
+}
+
+#[test]
+fn ei_enables_ime_after_the_next_instruction() {
+    let gb = run();
+    assert_eq!(gb.peek(0xC001), 1, "B at first dispatch");
+}
+
+#[test]
+fn reti_enables_ime_immediately() {
+    let gb = run();
+    assert_eq!(gb.peek(0xC002), 1, "B at dispatch straight after RETI");
+    assert_eq!(gb.peek(0xC000), 2, "B once the interrupted code resumes");
+}
+
+#[test]
+fn di_straight_after_ei_blocks_dispatch() {
+    let gb = run();
+    assert_eq!(gb.peek(0xC010), 2, "handler entries");
+}
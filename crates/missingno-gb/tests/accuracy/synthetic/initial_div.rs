@@ -0,0 +1,14 @@
+use missingno_gb::{GameBoy, cartridge::Cartridge};
+
+#[test]
+fn initial_div_applies_at_reset() {
+    let mut gb = GameBoy::new(Cartridge::new(vec![0u8; 0x8000], None).unwrap(), None);
+    assert_eq!(gb.timers().internal_counter(), 0xEAF3);
+
+    gb.set_initial_div(0x1240);
+    assert_eq!(gb.timers().internal_counter(), 0xEAF3, "waits for reset");
+
+    gb.reset();
+    assert_eq!(gb.timers().internal_counter(), 0x1240);
+    assert_eq!(gb.peek(0xFF04), 0x49);
+}
@@ -0,0 +1,70 @@
+//! Which vector a dispatch takes: the highest-priority interrupt that is
+//! both requested and enabled, re-resolved after the PC-high push so a push
+//! onto IE can redirect or cancel it.
+
+use missingno_gb::{GameBoy, test_support};
+
+const JR_SELF: [u8; 2] = [0x18, 0xFE];
+
+/// Park every vector, and $0000 for a cancelled dispatch, on a `JR -2`.
+const PARKING: [u16; 6] = [0x0000, 0x0040, 0x0048, 0x0050, 0x0058, 0x0060];
+
+/// Requests `request` with `enable` in IE and SP at `sp`, then enables IME.
+fn run(sp: u16, enable: u8, request: u8) -> GameBoy {
+    let [sp_lo, sp_hi] = sp.to_le_bytes();
+    let main = [
+        0xF3, // DI
+        0x31, sp_lo, sp_hi, // LD SP,sp
+        0x3E, enable, // LD A,enable
+        0xE0, 0xFF, // LDH ($FF),A
+        0x3E, request, // LD A,request
+        0xE0, 0x0F, // LDH ($0F),A
+        0xFB, // EI
+        0x00, // NOP
+        0x18, 0xFE, // JR -2
+    ];
+
+    let parking = PARKING.map(|address| (address, &JR_SELF[..]));
+    let mut gb = test_support::synthetic_game_boy(&main, &parking);
+    test_support::run_for_tcycles(&mut gb, 1000);
+    gb
+}
+
+#[test]
+fn highest_priority_pending_interrupt_is_serviced() {
+    let cases = [
+        (0x1F, 0x1F, 0x40),
+        (0x1F, 0x1E, 0x48),
+        (0x1F, 0x1C, 0x50),
+        (0x1F, 0x18, 0x58),
+        (0x1F, 0x10, 0x60),
+        // Requested but disabled interrupts don't take part.
+        (0x14, 0x1F, 0x50),
+        (0x18, 0x0F, 0x58),
+    ];
+    for (enable, request, vector) in cases {
+        let gb = run(0xDFFE, enable, request);
+        assert_eq!(
+            gb.cpu().last_isr_vector(),
+            Some(vector),
+            "IE={enable:02X} IF={request:02X}"
+        );
+    }
+}
+
+// With SP at $0000 the PC-high push lands on IE at $FFFF. Main runs from
+// $01xx, so IE becomes $01 between choosing the interrupt and resolving
+// its vector.
+
+#[test]
+fn push_onto_ie_redirects_to_a_newly_enabled_interrupt() {
+    let gb = run(0x0000, 0x04, 0x05);
+    assert_eq!(gb.cpu().last_isr_vector(), Some(0x0040));
+}
+
+#[test]
+fn push_onto_ie_cancels_dispatch_to_0000() {
+    let gb = run(0x0000, 0x04, 0x04);
+    assert_eq!(gb.cpu().last_isr_vector(), Some(0x0000));
+    assert_eq!(gb.peek(0xFFFF), 0x01, "IE holds the pushed PC high byte");
+}
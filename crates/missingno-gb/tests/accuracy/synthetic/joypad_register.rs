@@ -0,0 +1,110 @@
+//! JOYP ($FF00) reads: bits 6-7 are unused and read 1, bits 4-5 echo the
+//! row selection, and bits 0-3 are the input lines, active low. Only the
+//! selected rows drive the lines; with both selected they're ANDed.
+
+use missingno_gb::{
+    GameBoy,
+    joypad::{Button, DirectionalPad, Joypad},
+    test_support,
+    test_support::run_frames,
+};
+
+const SELECT_BOTH: u8 = 0x00;
+const SELECT_BUTTONS: u8 = 0x10;
+const SELECT_DPAD: u8 = 0x20;
+const SELECT_NEITHER: u8 = 0x30;
+
+fn dpad(direction: DirectionalPad) -> Button {
+    Button::DirectionalPad(direction)
+}
+
+fn joypad(select: u8, pressed: &[Button]) -> Joypad {
+    let mut joypad = Joypad::new();
+    joypad.write_register(select);
+    for &button in pressed {
+        joypad.press_button(button);
+    }
+    joypad
+}
+
+#[test]
+fn nothing_pressed_reads_all_lines_high() {
+    for (select, expected) in [
+        (SELECT_BOTH, 0xCF),
+        (SELECT_BUTTONS, 0xDF),
+        (SELECT_DPAD, 0xEF),
+        (SELECT_NEITHER, 0xFF),
+    ] {
+        assert_eq!(
+            joypad(select, &[]).read_register(),
+            expected,
+            "select {select:02X}"
+        );
+    }
+}
+
+#[test]
+fn a_held_shows_only_when_buttons_are_selected() {
+    let a = [Button::A];
+    assert_eq!(joypad(SELECT_BUTTONS, &a).read_register(), 0xDE);
+    assert_eq!(joypad(SELECT_DPAD, &a).read_register(), 0xEF);
+    assert_eq!(joypad(SELECT_BOTH, &a).read_register(), 0xCE);
+    assert_eq!(joypad(SELECT_NEITHER, &a).read_register(), 0xFF);
+}
+
+#[test]
+fn down_held_shows_only_when_the_dpad_is_selected() {
+    let down = [dpad(DirectionalPad::Down)];
+    assert_eq!(joypad(SELECT_BUTTONS, &down).read_register(), 0xDF);
+    assert_eq!(joypad(SELECT_DPAD, &down).read_register(), 0xE7);
+    assert_eq!(joypad(SELECT_BOTH, &down).read_register(), 0xC7);
+    assert_eq!(joypad(SELECT_NEITHER, &down).read_register(), 0xFF);
+}
+
+#[test]
+fn both_rows_selected_ands_the_rows() {
+    // Start and Down share P13: both held still reads one low line, not a
+    // pair of presses cancelling out.
+    let start_down = [Button::Start, dpad(DirectionalPad::Down)];
+    assert_eq!(joypad(SELECT_BOTH, &start_down).read_register(), 0xC7);
+
+    let b_right = [Button::B, dpad(DirectionalPad::Right)];
+    assert_eq!(joypad(SELECT_BOTH, &b_right).read_register(), 0xCC);
+}
+
+#[test]
+fn every_button_lands_on_its_line() {
+    let lines = [
+        (Button::A, SELECT_BUTTONS, 0x01),
+        (Button::B, SELECT_BUTTONS, 0x02),
+        (Button::Select, SELECT_BUTTONS, 0x04),
+        (Button::Start, SELECT_BUTTONS, 0x08),
+        (dpad(DirectionalPad::Right), SELECT_DPAD, 0x01),
+        (dpad(DirectionalPad::Left), SELECT_DPAD, 0x02),
+        (dpad(DirectionalPad::Up), SELECT_DPAD, 0x04),
+        (dpad(DirectionalPad::Down), SELECT_DPAD, 0x08),
+    ];
+    for (button, select, line) in lines {
+        let value = joypad(select, &[button]).read_register();
+        assert_eq!(value, 0xC0 | select | (0x0F & !line), "{button:?}");
+    }
+}
+
+/// Selects the buttons row through $FF00, then spins.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x3E, 0x10, // LD A,$10
+        0xE0, 0x00, // LDH ($00),A
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+#[test]
+fn cpu_reads_the_assembled_register() {
+    let mut gb = game_boy();
+    run_frames(&mut gb, 1);
+    gb.press_button(Button::Start);
+    gb.press_button(dpad(DirectionalPad::Up));
+    assert_eq!(gb.read(0xFF00), 0xD7);
+}
@@ -0,0 +1,195 @@
+//! The LY=LYC comparison and the STAT interrupt it raises. STAT bit 2 and
+//! the LYC interrupt leg come from a latch (ROPO) that samples the
+//! comparator two dots into each line, so for two dots after LY changes
+//! they still report the old line's comparison. Writing LYC reaches the
+//! comparator straight away and can raise the interrupt on its own, but
+//! only as a rising edge of the combined STAT line. While the LCD is off
+//! the latch isn't clocked, so the comparison freezes at whatever it was
+//! when the LCD went off.
+//!
+//! mooneye's `intr_*_timing`, `lcdon_timing-GS`, `stat_lyc_onoff` and
+//! `stat_irq_blocking` cover the same ground from ROMs and all pass; these
+//! pin the behaviour they rely on.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Runs `main` with interrupts off, after it sets STAT to `stat` and moves
+/// LYC off every line, then spins.
+fn run(stat: u8, main: &[u8]) -> GameBoy {
+    let mut code = vec![
+        0xF3, // DI
+        0x3E, 0xFF, // LD A,$FF
+        0xE0, 0x45, // LDH ($45),A
+        0x3E, stat, // LD A,stat
+        0xE0, 0x41, // LDH ($41),A
+    ];
+    code.extend_from_slice(main);
+    code.extend_from_slice(&[0x18, 0xFE]); // JR -2
+
+    let mut gb = test_support::synthetic_game_boy(&code, &[]);
+    // Frames stop once the LCD is off, so run for a fixed time instead.
+    test_support::run_for_tcycles(&mut gb, 3 * 70224);
+    gb
+}
+
+const LYC_ENABLE: u8 = 0x40;
+const VBLANK_ENABLE: u8 = 0x10;
+
+/// Waits for line 145, clears IF and writes $91 to LYC, then stores IF and
+/// STAT at $C000 and $C001.
+fn match_current_line(stat: u8) -> GameBoy {
+    run(
+        stat,
+        &[
+            0xF0, 0x44, // LDH A,($44)
+            0xFE, 0x91, // CP $91
+            0x20, 0xFA, // JR NZ,-6
+            0xAF, // XOR A
+            0xE0, 0x0F, // LDH ($0F),A
+            0x3E, 0x91, // LD A,$91
+            0xE0, 0x45, // LDH ($45),A
+            0xF0, 0x0F, // LDH A,($0F)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0xF0, 0x41, // LDH A,($41)
+            0xEA, 0x01, 0xC0, // LD ($C001),A
+        ],
+    )
+}
+
+#[test]
+fn lyc_write_matching_the_current_line_requests_interrupt() {
+    let gb = match_current_line(LYC_ENABLE);
+    assert_eq!(gb.peek(0xC000) & 0x02, 0x02, "IF = {:02X}", gb.peek(0xC000));
+    assert_eq!(
+        gb.peek(0xC001) & 0x04,
+        0x04,
+        "STAT = {:02X}",
+        gb.peek(0xC001)
+    );
+}
+
+#[test]
+fn lyc_write_without_the_enable_requests_nothing() {
+    let gb = match_current_line(0);
+    assert_eq!(gb.peek(0xC000) & 0x02, 0, "IF = {:02X}", gb.peek(0xC000));
+    // The coincidence bit still follows.
+    assert_eq!(
+        gb.peek(0xC001) & 0x04,
+        0x04,
+        "STAT = {:02X}",
+        gb.peek(0xC001)
+    );
+}
+
+#[test]
+fn lyc_match_while_the_stat_line_is_high_requests_nothing() {
+    // The VBlank leg already holds the STAT line high, so the match adds
+    // no rising edge.
+    let gb = match_current_line(LYC_ENABLE | VBLANK_ENABLE);
+    assert_eq!(gb.peek(0xC000) & 0x02, 0, "IF = {:02X}", gb.peek(0xC000));
+}
+
+/// Waits for line 145, sets LYC to `lyc_before`, turns the LCD off, clears
+/// IF and sets LYC to `lyc_after`. Stores STAT, LY and IF at $C000-$C002.
+fn lcd_off(stat: u8, lyc_before: u8, lyc_after: u8) -> GameBoy {
+    run(
+        stat,
+        &[
+            0xF0, 0x44, // LDH A,($44)
+            0xFE, 0x91, // CP $91
+            0x20, 0xFA, // JR NZ,-6
+            0x3E, lyc_before, // LD A,lyc_before
+            0xE0, 0x45, // LDH ($45),A
+            0xAF, // XOR A
+            0xE0, 0x40, // LDH ($40),A
+            0xE0, 0x0F, // LDH ($0F),A
+            0x3E, lyc_after, // LD A,lyc_after
+            0xE0, 0x45, // LDH ($45),A
+            0xF0, 0x41, // LDH A,($41)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0xF0, 0x44, // LDH A,($44)
+            0xEA, 0x01, 0xC0, // LD ($C001),A
+            0xF0, 0x0F, // LDH A,($0F)
+            0xEA, 0x02, 0xC0, // LD ($C002),A
+        ],
+    )
+}
+
+#[test]
+fn comparison_freezes_clear_while_lcd_is_off() {
+    // LY reads 0 with the LCD off, but a new LYC of 0 doesn't match.
+    let gb = lcd_off(LYC_ENABLE, 0xFF, 0x00);
+    assert_eq!(gb.peek(0xC001), 0);
+    assert_eq!(gb.peek(0xC000) & 0x04, 0, "STAT = {:02X}", gb.peek(0xC000));
+    assert_eq!(gb.peek(0xC002) & 0x02, 0, "IF = {:02X}", gb.peek(0xC002));
+}
+
+#[test]
+fn comparison_freezes_set_while_lcd_is_off() {
+    // Matching line 145 when the LCD goes off, and still reported after
+    // LYC moves away.
+    let gb = lcd_off(0, 0x91, 0x50);
+    assert_eq!(
+        gb.peek(0xC000) & 0x04,
+        0x04,
+        "STAT = {:02X}",
+        gb.peek(0xC000)
+    );
+}
+
+/// (LY as the CPU reads it, STAT bit 2) for every dot from the one LY
+/// changes to `line`, through the first 16 dots of the next line.
+fn comparisons(lyc: u8, line: u8) -> Vec<(u8, bool)> {
+    let mut gb = run(0, &[0x3E, lyc, 0xE0, 0x45]); // LD A,lyc; LDH ($45),A
+    while gb.ppu().ly_hardware() == line {
+        gb.step_tcycle();
+    }
+    while gb.ppu().ly_hardware() != line {
+        gb.step_tcycle();
+    }
+    (0..456 + 16)
+        .map(|_| {
+            let dot = (gb.peek(0xFF44), gb.peek(0xFF41) & 0x04 != 0);
+            gb.step_tcycle();
+            dot
+        })
+        .collect()
+}
+
+/// The dots in `dots` where STAT bit 2 is set, as (first, past the last)
+/// runs.
+fn matching_runs(dots: &[(u8, bool)]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for (dot, &(_, matching)) in dots.iter().enumerate() {
+        if !matching {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((_, end)) if *end == dot => *end = dot + 1,
+            _ => runs.push((dot, dot + 1)),
+        }
+    }
+    runs
+}
+
+#[test]
+fn coincidence_follows_ly_two_dots_late() {
+    let dots = comparisons(42, 42);
+    assert_eq!(dots[0], (42, false), "LY changes before the latch samples");
+    assert_eq!(dots[456], (43, true), "the old match outlives the line");
+    assert_eq!(matching_runs(&dots), [(2, 458)]);
+}
+
+#[test]
+fn line_153_matches_lyc_153_only_until_ly_reads_0() {
+    let dots = comparisons(153, 153);
+    assert_eq!(dots[5].0, 153);
+    assert_eq!(dots[6].0, 0, "LY reads 0 six dots into line 153");
+    assert_eq!(matching_runs(&dots), [(2, 10)]);
+}
+
+#[test]
+fn lyc_0_matches_from_line_153_through_line_0() {
+    let dots = comparisons(0, 153);
+    assert_eq!(matching_runs(&dots), [(10, 456 + 16)]);
+}
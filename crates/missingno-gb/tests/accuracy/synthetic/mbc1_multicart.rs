@@ -0,0 +1,87 @@
+//! MBC1M multicarts wire the upper bank register to ROM bits 4–5 rather
+//! than 5–6, so it picks one of four 256 KiB games and only the low four
+//! bits of the lower register select a bank within it. They're told apart
+//! from ordinary 1 MiB MBC1 ROMs by a second game's header at bank $10.
+
+use missingno_gb::cartridge::{Cartridge, mbc::Mbc};
+
+const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+const GAME_SIZE: usize = 256 * 1024;
+
+/// A 1 MiB MBC1 ROM with a header for each of `games` 256 KiB games, each
+/// titled `GAMEn`. Every 16 KiB bank starts with its index in the ROM.
+fn rom(games: usize) -> Vec<u8> {
+    let mut rom = vec![0u8; 4 * GAME_SIZE];
+    for (bank, chunk) in rom.chunks_mut(0x4000).enumerate() {
+        chunk[0] = bank as u8;
+    }
+    for game in 0..games {
+        let base = game * GAME_SIZE;
+        rom[base + 0x0104..base + 0x0134].copy_from_slice(&NINTENDO_LOGO);
+        rom[base + 0x0134..base + 0x0139].copy_from_slice(format!("GAME{game}").as_bytes());
+        rom[base + 0x0147] = 0x01; // MBC1
+        rom[base + 0x0148] = 0x05; // 1 MiB
+    }
+    rom
+}
+
+fn is_multicart(cartridge: &Cartridge) -> bool {
+    matches!(cartridge.mbc(), Mbc::Mbc1(mbc1) if mbc1.multicart)
+}
+
+/// The title in the header at $0134 as the cartridge maps it now.
+fn title(cartridge: &Cartridge) -> String {
+    (0x0134..0x0139)
+        .map(|address| cartridge.read(address) as char)
+        .collect()
+}
+
+#[test]
+fn a_second_header_at_bank_10_marks_a_multicart() {
+    assert!(is_multicart(&Cartridge::new(rom(4), None).unwrap()));
+    assert!(is_multicart(&Cartridge::new(rom(2), None).unwrap()));
+    assert!(!is_multicart(&Cartridge::new(rom(1), None).unwrap()));
+}
+
+#[test]
+fn mode_1_maps_each_games_header_at_bank_0() {
+    let mut cartridge = Cartridge::new(rom(4), None).unwrap();
+    assert_eq!(title(&cartridge), "GAME0");
+
+    cartridge.write(0x6000, 0x01); // mode 1: the upper register banks $0000 too
+    for game in 0..4 {
+        cartridge.write(0x4000, game);
+        assert_eq!(title(&cartridge), format!("GAME{game}"));
+        assert_eq!(cartridge.read(0x0000), game * 0x10);
+    }
+}
+
+#[test]
+fn the_lower_register_banks_within_the_selected_game() {
+    let mut cartridge = Cartridge::new(rom(4), None).unwrap();
+    cartridge.write(0x4000, 0x02); // the third game
+    cartridge.write(0x2000, 0x03);
+    assert_eq!(cartridge.read(0x4000), 0x23);
+
+    cartridge.write(0x2000, 0x13); // bit 4 isn't wired
+    assert_eq!(cartridge.read(0x4000), 0x23);
+
+    cartridge.write(0x2000, 0x10); // but still counts towards the 0 → 1 rule
+    assert_eq!(cartridge.read(0x4000), 0x20);
+
+    cartridge.write(0x2000, 0x00);
+    assert_eq!(cartridge.read(0x4000), 0x21);
+}
+
+#[test]
+fn an_ordinary_1_mib_rom_banks_through_all_64_banks() {
+    let mut cartridge = Cartridge::new(rom(1), None).unwrap();
+    cartridge.write(0x4000, 0x01);
+    cartridge.write(0x2000, 0x13);
+    assert_eq!(cartridge.read(0x4000), 0x33);
+}
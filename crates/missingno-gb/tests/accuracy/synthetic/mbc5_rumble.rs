@@ -0,0 +1,53 @@
+//! On an MBC5 rumble cartridge, bit 3 of the RAM bank register ($4000–$5FFF)
+//! switches the motor rather than selecting a bank.
+
+use missingno_gb::cartridge::Cartridge;
+
+/// A 64 KiB MBC5 cartridge of `kind` with four 8 KiB RAM banks.
+fn cartridge(kind: u8) -> Cartridge {
+    let mut rom = vec![0u8; 0x10000];
+    rom[0x0147] = kind;
+    rom[0x0148] = 0x01; // 64 KiB
+    rom[0x0149] = 0x03; // 32 KiB RAM
+    let mut cartridge = Cartridge::new(rom, None).unwrap();
+    cartridge.write(0x0000, 0x0A); // enable RAM
+    cartridge
+}
+
+/// The RAM bank a write to $A000 lands in.
+fn bank_written(cartridge: &mut Cartridge) -> Option<usize> {
+    cartridge.write(0xA000, 0x5A);
+    let ram = cartridge.ram().unwrap();
+    let bank = (0..4).find(|bank| ram[bank * 0x2000] == 0x5A);
+    cartridge.write(0xA000, 0x00);
+    bank
+}
+
+#[test]
+fn bit_3_drives_the_motor() {
+    let mut cartridge = cartridge(0x1C);
+    assert!(!cartridge.rumble());
+
+    cartridge.write(0x4000, 0x0A);
+    assert!(cartridge.rumble());
+    assert_eq!(bank_written(&mut cartridge), Some(2), "bit 3 is masked off");
+
+    cartridge.write(0x5FFF, 0x02);
+    assert!(!cartridge.rumble());
+    assert_eq!(bank_written(&mut cartridge), Some(2));
+}
+
+#[test]
+fn without_a_motor_bit_3_selects_a_bank() {
+    let mut cartridge = cartridge(0x1B);
+    cartridge.write(0x4000, 0x0A);
+    assert!(!cartridge.rumble());
+    assert_eq!(bank_written(&mut cartridge), None, "bank 10 isn't fitted");
+}
+
+#[test]
+fn a_copy_keeps_the_motor_state() {
+    let mut cartridge = cartridge(0x1E);
+    cartridge.write(0x4000, 0x08);
+    assert!(cartridge.clone().rumble());
+}
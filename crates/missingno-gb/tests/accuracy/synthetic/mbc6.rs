@@ -0,0 +1,108 @@
+//! MBC6 banking and flash, driven through the cartridge bus.
+
+use missingno_gb::cartridge::Cartridge;
+
+/// Each 8 KiB ROM bank is filled with its own bank number.
+fn cartridge() -> Cartridge {
+    let mut rom: Vec<u8> = (0..64u8).flat_map(|bank| [bank; 0x2000]).collect();
+    rom[0x147] = 0x20;
+    Cartridge::new(rom, None).unwrap()
+}
+
+/// Map flash bank 2 into the A half and bank 1 into the B half, so CPU
+/// 0x5555 and 0x6AAA are the flash command addresses 5555 and 2AAA.
+fn map_flash(cart: &mut Cartridge) {
+    cart.write(0x0c00, 0x01);
+    cart.write(0x1000, 0x01);
+    cart.write(0x2000, 2);
+    cart.write(0x2800, 0x08);
+    cart.write(0x3000, 1);
+    cart.write(0x3800, 0x08);
+}
+
+fn command(cart: &mut Cartridge, value: u8) {
+    cart.write(0x5555, 0xaa);
+    cart.write(0x6aaa, 0x55);
+    cart.write(0x5555, value);
+}
+
+#[test]
+fn rom_halves_bank_independently() {
+    let mut cart = cartridge();
+    cart.write(0x2000, 5);
+    cart.write(0x3000, 9);
+    assert_eq!(cart.read(0x4000), 5);
+    assert_eq!(cart.read(0x7fff), 9);
+}
+
+#[test]
+fn ram_windows_bank_independently() {
+    let mut cart = cartridge();
+    cart.write(0x0000, 0x0a);
+    cart.write(0x0400, 3);
+    cart.write(0x0800, 4);
+    cart.write(0xa000, 0x33);
+    cart.write(0xb000, 0x44);
+
+    cart.write(0x0800, 3);
+    assert_eq!(cart.read(0xb000), 0x33);
+    cart.write(0x0400, 4);
+    assert_eq!(cart.read(0xa000), 0x44);
+}
+
+#[test]
+fn flash_programs_and_erases() {
+    let mut cart = cartridge();
+    map_flash(&mut cart);
+    assert_eq!(cart.read(0x4000), 0xff, "flash starts erased");
+
+    command(&mut cart, 0xa0);
+    cart.write(0x4010, 0x5a);
+    assert_eq!(cart.read(0x4010), 0x5a);
+
+    command(&mut cart, 0x80);
+    cart.write(0x5555, 0xaa);
+    cart.write(0x6aaa, 0x55);
+    cart.write(0x4010, 0x30);
+    assert_eq!(cart.read(0x4010), 0xff);
+}
+
+#[test]
+fn flash_program_needs_write_enable() {
+    let mut cart = cartridge();
+    map_flash(&mut cart);
+    cart.write(0x1000, 0x00);
+
+    command(&mut cart, 0xa0);
+    cart.write(0x4010, 0x5a);
+    assert_eq!(cart.read(0x4010), 0xff);
+}
+
+#[test]
+fn flash_id_mode_until_reset() {
+    let mut cart = cartridge();
+    map_flash(&mut cart);
+    command(&mut cart, 0x90);
+    assert_eq!(cart.read(0x4000), 0xc2);
+    assert_eq!(cart.read(0x4001), 0x81);
+
+    cart.write(0x4000, 0xf0);
+    assert_eq!(cart.read(0x4000), 0xff);
+}
+
+#[test]
+fn flash_is_saved_after_ram() {
+    let mut cart = cartridge();
+    map_flash(&mut cart);
+    command(&mut cart, 0xa0);
+    cart.write(0x4000, 0x12);
+
+    let save = cart.ram().unwrap();
+    assert_eq!(save[32 * 1024 + 2 * 0x2000], 0x12);
+
+    let mut rom: Vec<u8> = vec![0; 0x80000];
+    rom[0x147] = 0x20;
+    let mut restored = Cartridge::new(rom, Some(save)).unwrap();
+    map_flash(&mut restored);
+    assert_eq!(restored.read(0x4000), 0x12);
+}
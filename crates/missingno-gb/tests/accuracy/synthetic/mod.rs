@@ -0,0 +1,29 @@
+mod alu_flags;
+mod audio_dac_pop;
+mod audio_panning;
+mod boot_rom;
+mod dma_status;
+mod dmg_models;
+mod external_bus;
+mod illegal_opcode;
+mod ime_timing;
+mod initial_div;
+mod interrupt_priority;
+mod joypad_register;
+mod lyc_interrupt;
+mod mbc1_multicart;
+mod mbc5_rumble;
+mod mbc6;
+mod mode3_length;
+mod mode3_register_writes;
+mod obj_palettes;
+mod obj_priority;
+mod pop_af;
+mod ppu_mode_gating;
+mod ppu_position;
+mod rtc_latch;
+mod serial_link;
+mod sp_offset;
+mod stat_write;
+mod tile_2bpp;
+mod window_line_counter;
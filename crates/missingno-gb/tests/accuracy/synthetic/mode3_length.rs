@@ -0,0 +1,101 @@
+//! Mode 3 stretches for each object on the line and for the window, which
+//! moves the start of HBlank. With SCX 0 an object costs 6 dots plus up to 5
+//! more while the background fetch it interrupts finishes: 11 when it sits
+//! on a tile boundary, 6 from five pixels into a tile on, and 6 for another
+//! object in a tile already paid for. The window start costs 6 dots. Only
+//! the first 10 objects found on a line are drawn.
+
+use missingno_gb::{ppu::Mode, test_support};
+
+/// The line measured, well clear of the first line after the LCDC write.
+const LY: u8 = 40;
+
+/// Mode 3 with nothing extra on the line, as STAT reports it.
+const BASE: usize = 174;
+
+/// Dots STAT reads mode 3 on line [`LY`], with 8x8 objects at the given
+/// OAM X positions on that line and LCDC set to `lcdc`.
+fn mode_3_dots(objects: &[u8], lcdc: u8) -> usize {
+    let main = [
+        0xAF, // XOR A
+        0xE0, 0x4A, // LDH ($4A),A: WY 0
+        0x3E, 0x07, // LD A,7
+        0xE0, 0x4B, // LDH ($4B),A: WX 7
+        0x3E, lcdc, // LD A,lcdc
+        0xE0, 0x40, // LDH ($40),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+
+    for (index, &x) in objects.iter().enumerate() {
+        let entry = 0xFE00 + index as u16 * 4;
+        assert!(gb.poke_raw(entry, LY + 16));
+        assert!(gb.poke_raw(entry + 1, x));
+    }
+
+    // Into the next frame, so the LCDC write has settled.
+    while gb.ppu().ly_hardware() != LY + 1 {
+        gb.step_tcycle();
+    }
+    while gb.ppu().ly_hardware() != LY {
+        gb.step_tcycle();
+    }
+
+    let mut dots = 0;
+    while gb.ppu().ly_hardware() == LY {
+        if gb.ppu().mode() == Mode::Drawing {
+            dots += 1;
+        }
+        gb.step_tcycle();
+    }
+    dots
+}
+
+/// LCD, objects and background on.
+const OBJECTS: u8 = 0x93;
+/// As [`OBJECTS`], with the window on too.
+const WINDOW: u8 = 0xB3;
+
+#[test]
+fn no_objects_is_the_minimum() {
+    assert_eq!(mode_3_dots(&[], OBJECTS), BASE);
+}
+
+#[test]
+fn object_penalty_depends_on_its_place_in_the_tile() {
+    for (x, penalty) in [
+        (8, 11),
+        (9, 10),
+        (10, 9),
+        (11, 8),
+        (12, 7),
+        (13, 6),
+        (15, 6),
+    ] {
+        assert_eq!(mode_3_dots(&[x], OBJECTS), BASE + penalty, "X={x}");
+    }
+}
+
+#[test]
+fn objects_sharing_a_tile_pay_the_fetch_wait_once() {
+    assert_eq!(mode_3_dots(&[16, 16], OBJECTS), BASE + 11 + 6);
+    assert_eq!(mode_3_dots(&[16, 18], OBJECTS), BASE + 11 + 6);
+}
+
+#[test]
+fn several_objects_add_up_to_the_line_limit() {
+    let tiles: Vec<u8> = (1..=12).map(|tile| tile * 8).collect();
+    assert_eq!(mode_3_dots(&tiles[..3], OBJECTS), BASE + 3 * 11);
+    assert_eq!(mode_3_dots(&tiles[..10], OBJECTS), BASE + 10 * 11);
+    assert_eq!(mode_3_dots(&tiles, OBJECTS), BASE + 10 * 11);
+}
+
+#[test]
+fn objects_are_free_with_objects_off() {
+    assert_eq!(mode_3_dots(&[8, 16, 24], 0x91), BASE);
+}
+
+#[test]
+fn window_start_adds_a_fetch() {
+    assert_eq!(mode_3_dots(&[], WINDOW), BASE + 6);
+}
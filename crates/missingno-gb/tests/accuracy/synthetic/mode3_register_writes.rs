@@ -0,0 +1,138 @@
+//! Registers written during mode 3 take effect partway along the line being
+//! drawn. BGP and LCDC's background enable switch at the pixel being pushed
+//! when the write lands, so each M-cycle of delay moves the change 4 pixels
+//! right; the enable bit lands a pixel after BGP would. SCY and SCX's tile
+//! bits are only read when the fetcher starts a tile, so their change lands
+//! on a tile boundary and moves 8 pixels for every two M-cycles of delay.
+//!
+//! Mealybug's `m3_*` tests check the same writes against hardware captures;
+//! all of its DMG tests pass (see `accuracy/mealybug_tearoom.rs`).
+
+use missingno_gb::test_support;
+
+/// The line the write lands on. LYC wakes a HALT at its start.
+const LY: u8 = 64;
+
+/// NOPs between the HALT wake and the write that land it 21 pixels into
+/// mode 3.
+const DELAY: usize = 24;
+
+/// Identity, so a shade reads as the colour number.
+const BGP: u8 = 0xE4;
+
+/// Tile 0 is solid colour 1, tile 1 solid colour 2.
+const COLOUR_1: u8 = 0;
+const COLOUR_2: u8 = 1;
+
+/// Shades of line [`LY`] with `register` holding `old`, except that it is
+/// set to `new` after `nops` NOPs from the start of the line. `map` is the
+/// tile number at each (column, row) of the $9800 map.
+fn line(register: u8, old: u8, new: u8, nops: usize, map: impl Fn(u16, u16) -> u8) -> Vec<u8> {
+    let mut main = vec![0xF3]; // DI
+    main.extend([0x3E, BGP, 0xE0, 0x47]); // LD A,BGP; LDH ($47),A
+    main.extend([0x3E, LY, 0xE0, 0x45]); // LD A,LY; LDH ($45),A: LYC
+    main.extend([0x3E, 0x40, 0xE0, 0x41]); // LD A,$40; LDH ($41),A: LYC interrupt
+    main.extend([0x3E, 0x02, 0xE0, 0xFF]); // LD A,$02; LDH ($FF),A: IE STAT
+    main.extend([0x3E, 0x91, 0xE0, 0x40]); // LD A,$91; LDH ($40),A: LCD and BG on
+    let top = 0x0150 + main.len() as u16;
+    main.extend([0x3E, old, 0xE0, register]); // LD A,old; LDH (register),A
+    main.extend([0xAF, 0xE0, 0x0F]); // XOR A; LDH ($0F),A: clear IF
+    main.push(0x76); // HALT until line LY starts
+    main.extend(std::iter::repeat_n(0x00, nops)); // NOP × nops
+    main.extend([0x3E, new, 0xE0, register]); // LD A,new; LDH (register),A
+    let wait = 0x0150 + main.len() as u16;
+    main.extend([0xF0, 0x44, 0xFE, LY]); // LDH A,($44); CP LY
+    main.extend([0x28, (wait as i16 - (0x0150 + main.len() as i16 + 2)) as u8]); // JR Z,wait
+    main.extend([0xC3, top as u8, (top >> 8) as u8]); // JP top
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    for row in 0..8 {
+        gb.poke_raw(0x8000 + row * 2, 0xFF);
+        gb.poke_raw(0x8001 + row * 2, 0x00);
+        gb.poke_raw(0x8010 + row * 2, 0x00);
+        gb.poke_raw(0x8011 + row * 2, 0xFF);
+    }
+    for row in 0..32 {
+        for column in 0..32 {
+            gb.poke_raw(0x9800 + row * 32 + column, map(column, row));
+        }
+    }
+
+    test_support::run_frames(&mut gb, 3);
+    (0..160).map(|x| gb.screen().pixel(x, LY).0).collect()
+}
+
+/// The first pixel that differs from `unchanged`.
+fn split(drawn: &[u8], unchanged: &[u8]) -> usize {
+    drawn
+        .iter()
+        .zip(unchanged)
+        .position(|(drawn, unchanged)| drawn != unchanged)
+        .expect("the write never showed")
+}
+
+/// [`BGP`] with colour 1 mapped to shade 2.
+const BGP_COLOUR_1_TO_2: u8 = (BGP & !0x0C) | 0x08;
+
+#[test]
+fn bgp_changes_at_the_pixel_being_drawn() {
+    let columns = |_, _| COLOUR_1;
+    for extra in 0..8 {
+        let drawn = line(0x47, BGP, BGP_COLOUR_1_TO_2, DELAY + extra, columns);
+        let at = 21 + 4 * extra;
+        assert_eq!(split(&drawn, &[1; 160]), at, "{extra} extra NOPs");
+        assert!(drawn[at..].iter().all(|&shade| shade == 2));
+    }
+}
+
+#[test]
+fn background_enable_changes_at_the_pixel_being_drawn() {
+    // With the background off the DMG draws colour 0.
+    let columns = |_, _| COLOUR_1;
+    for extra in 0..8 {
+        let drawn = line(0x40, 0x91, 0x90, DELAY + extra, columns);
+        let at = 22 + 4 * extra;
+        assert_eq!(split(&drawn, &[1; 160]), at, "{extra} extra NOPs");
+        assert!(drawn[at..].iter().all(|&shade| shade == 0));
+    }
+}
+
+#[test]
+fn scy_changes_at_the_next_tile() {
+    // Map row 8 (line 64 at SCY 0) is colour 1, row 9 (at SCY 8) colour 2.
+    let rows = |_, row| if row == 9 { COLOUR_2 } else { COLOUR_1 };
+    let mut splits = Vec::new();
+    for extra in 0..8 {
+        let drawn = line(0x42, 0, 8, DELAY + extra, rows);
+        let at = split(&drawn, &[1; 160]);
+        assert_eq!(at % 8, 0, "{extra} extra NOPs: split at {at}");
+        assert!(drawn[at..].iter().all(|&shade| shade == 2));
+        splits.push(at);
+    }
+    for extra in 2..8 {
+        assert_eq!(splits[extra], splits[extra - 2] + 8, "{extra} extra NOPs");
+    }
+}
+
+#[test]
+fn scx_tile_bits_change_at_the_next_tile() {
+    // Columns alternate colours 1 and 2; SCX 8 shifts the map a column.
+    let columns = |column: u16, _| (column % 2) as u8;
+    let stripes: Vec<u8> = (0..160).map(|x| (x / 8 % 2 + 1) as u8).collect();
+    let mut splits = Vec::new();
+    for extra in 0..8 {
+        let drawn = line(0x43, 0, 8, DELAY + extra, columns);
+        let at = split(&drawn, &stripes);
+        assert_eq!(at % 8, 0, "{extra} extra NOPs: split at {at}");
+        assert!(
+            drawn[at..]
+                .iter()
+                .zip(&stripes[at..])
+                .all(|(drawn, stripe)| *drawn == 3 - stripe),
+            "{extra} extra NOPs: {drawn:?}"
+        );
+        splits.push(at);
+    }
+    for extra in 2..8 {
+        assert_eq!(splits[extra], splits[extra - 2] + 8, "{extra} extra NOPs");
+    }
+}
@@ -0,0 +1,132 @@
+//! DMG sprites go through OBP0 or OBP1 (attribute bit 4), flip with
+//! attribute bits 5 and 6, and never draw colour 0: the background shows
+//! through it whatever the palette maps colour 0 to.
+
+use missingno_gb::{GameBoy, test_support};
+
+const BGP: u8 = 0xE4;
+/// Identity.
+const OBP0: u8 = 0xE4;
+/// Colour 0 to shade 3, 1 to 2, 2 to 0, 3 to 3. Colour 0 is never drawn,
+/// so shade 1 never shows.
+const OBP1: u8 = 0xCB;
+/// The background is solid colour 1.
+const BG_SHADE: u8 = 1;
+
+const X_FLIP: u8 = 0x20;
+const Y_FLIP: u8 = 0x40;
+const USE_OBP1: u8 = 0x10;
+
+/// The sprite tile. No row or column reads the same flipped, and every row
+/// has transparent pixels.
+fn tile_colour(x: u8, y: u8) -> u8 {
+    (x + 2 * y) % 4
+}
+
+/// The sprite's top-left corner on screen.
+const ORIGIN: (u8, u8) = (8, 8);
+
+fn run(attributes: u8) -> GameBoy {
+    let mut gb = test_support::synthetic_game_boy(
+        &[
+            0x3E, BGP, // LD A,BGP
+            0xE0, 0x47, // LDH ($47),A
+            0x3E, OBP0, // LD A,OBP0
+            0xE0, 0x48, // LDH ($48),A
+            0x3E, OBP1, // LD A,OBP1
+            0xE0, 0x49, // LDH ($49),A
+            0x3E, 0x93, // LD A,$93   LCD, OBJ and BG on, tiles at $8000
+            0xE0, 0x40, // LDH ($40),A
+            0x18, 0xFE, // JR -2
+        ],
+        &[],
+    );
+    for y in 0..8 {
+        // Tile 0: background colour 1.
+        gb.poke_raw(0x8000 + y * 2, 0xFF);
+        gb.poke_raw(0x8001 + y * 2, 0x00);
+        // Tile 1: the sprite.
+        let (mut low, mut high) = (0u8, 0u8);
+        for x in 0..8 {
+            let colour = tile_colour(x, y as u8);
+            low |= (colour & 1) << (7 - x);
+            high |= (colour >> 1) << (7 - x);
+        }
+        gb.poke_raw(0x8010 + y * 2, low);
+        gb.poke_raw(0x8011 + y * 2, high);
+    }
+    for address in 0x9800..0x9C00 {
+        gb.poke_raw(address, 0);
+    }
+    for address in 0xFE00..0xFEA0 {
+        gb.poke_raw(address, 0);
+    }
+    let (x, y) = ORIGIN;
+    for (i, byte) in [y + 16, x + 8, 1, attributes].into_iter().enumerate() {
+        gb.poke_raw(0xFE00 + i as u16, byte);
+    }
+
+    test_support::run_frames(&mut gb, 3);
+    gb
+}
+
+/// What hardware draws at `(x, y)` within the sprite.
+fn reference(attributes: u8, x: u8, y: u8) -> u8 {
+    let x = if attributes & X_FLIP != 0 { 7 - x } else { x };
+    let y = if attributes & Y_FLIP != 0 { 7 - y } else { y };
+    let palette = if attributes & USE_OBP1 != 0 {
+        OBP1
+    } else {
+        OBP0
+    };
+    match tile_colour(x, y) {
+        0 => BG_SHADE,
+        colour => (palette >> (colour * 2)) & 3,
+    }
+}
+
+fn assert_matches_reference(attributes: u8) {
+    let gb = run(attributes);
+    let (left, top) = ORIGIN;
+    for y in 0..8 {
+        let drawn: Vec<u8> = (0..8)
+            .map(|x| gb.screen().pixel(left + x, top + y).0)
+            .collect();
+        let expected: Vec<u8> = (0..8).map(|x| reference(attributes, x, y)).collect();
+        assert_eq!(drawn, expected, "attributes {attributes:02X}, row {y}");
+    }
+}
+
+#[test]
+fn obp0_sprite() {
+    assert_matches_reference(0);
+}
+
+#[test]
+fn obp1_sprite() {
+    assert_matches_reference(USE_OBP1);
+}
+
+#[test]
+fn x_flipped_sprite() {
+    assert_matches_reference(X_FLIP);
+}
+
+#[test]
+fn y_flipped_sprite() {
+    assert_matches_reference(Y_FLIP);
+}
+
+#[test]
+fn obp1_sprite_flipped_both_ways() {
+    assert_matches_reference(USE_OBP1 | X_FLIP | Y_FLIP);
+}
+
+#[test]
+fn colour_0_shows_the_background_through() {
+    let gb = run(USE_OBP1);
+    let (left, top) = ORIGIN;
+    // Colour 0 sits at x = 0 and 4 on row 0; OBP1 would map it to shade 3.
+    assert_eq!(gb.screen().pixel(left, top).0, BG_SHADE);
+    assert_eq!(gb.screen().pixel(left + 4, top).0, BG_SHADE);
+}
@@ -0,0 +1,60 @@
+//! Per-pixel BG-vs-OBJ priority with a hand-built scene: one sprite with the
+//! behind-background attribute over a tile that is half colour 0, half colour 3.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Every background row is colour 0 on the left half, colour 3 on the right.
+const BG_ROW: [u8; 2] = [0x0F, 0x0F];
+/// Every sprite row is solid colour 1.
+const OBJ_ROW: [u8; 2] = [0xFF, 0x00];
+
+fn run(lcdc: u8) -> GameBoy {
+    let mut gb = test_support::synthetic_game_boy(
+        &[
+            0x3E, 0xE4, // LD A,$E4
+            0xE0, 0x47, // LDH ($47),A
+            0xE0, 0x48, // LDH ($48),A
+            0x3E, lcdc, // LD A,lcdc
+            0xE0, 0x40, // LDH ($40),A
+            0x18, 0xFE, // JR -2
+        ],
+        &[],
+    );
+    for row in 0..8 {
+        for (plane, byte) in BG_ROW.iter().enumerate() {
+            gb.poke_raw(0x8000 + row * 2 + plane as u16, *byte);
+        }
+        for (plane, byte) in OBJ_ROW.iter().enumerate() {
+            gb.poke_raw(0x8010 + row * 2 + plane as u16, *byte);
+        }
+    }
+    for address in 0x9800..0x9C00 {
+        gb.poke_raw(address, 0);
+    }
+    for address in 0xFE00..0xFEA0 {
+        gb.poke_raw(address, 0);
+    }
+    // Sprite 0 at the top-left corner, tile 1, behind background.
+    for (i, byte) in [16, 8, 1, 0x80].into_iter().enumerate() {
+        gb.poke_raw(0xFE00 + i as u16, byte);
+    }
+
+    test_support::run_frames(&mut gb, 3);
+    gb
+}
+
+fn first_row(gb: &GameBoy) -> Vec<u8> {
+    (0..8).map(|x| gb.screen().pixel(x, 0).0).collect()
+}
+
+#[test]
+fn behind_background_sprite_shows_only_over_colour_0() {
+    let gb = run(0x93);
+    assert_eq!(first_row(&gb), [1, 1, 1, 1, 3, 3, 3, 3]);
+}
+
+#[test]
+fn disabled_background_lets_behind_background_sprite_through() {
+    let gb = run(0x92);
+    assert_eq!(first_row(&gb), [1, 1, 1, 1, 1, 1, 1, 1]);
+}
@@ -0,0 +1,42 @@
+//! F only has four flag bits, in its top nibble. `POP AF` drops the low
+//! nibble of the popped byte, so it always reads back as zero.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Pops `value` into AF, pushes AF back and pops it into DE, stores D and
+/// E at $C000 and $C001, then spins at $0162.
+fn round_trip(value: u16) -> GameBoy {
+    let [low, high] = value.to_le_bytes();
+    let main = [
+        0x31, 0xFE, 0xDF, // LD SP,$DFFE
+        0x01, low, high, // LD BC,value
+        0xC5, // PUSH BC
+        0xF1, // POP AF
+        0xF5, // PUSH AF
+        0xD1, // POP DE
+        0x7A, // LD A,D
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0x7B, // LD A,E
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(gb.cpu().ir_address, 0x0162, "didn't reach the spin");
+    gb
+}
+
+#[test]
+fn pop_af_clears_the_low_nibble_of_f() {
+    let gb = round_trip(0xFFFF);
+    assert_eq!(gb.cpu().flags.bits(), 0xF0);
+}
+
+#[test]
+fn push_af_round_trip_keeps_only_the_flags() {
+    let gb = round_trip(0xFFFF);
+    assert_eq!((gb.peek(0xC000), gb.peek(0xC001)), (0xFF, 0xF0));
+
+    let gb = round_trip(0x125A);
+    assert_eq!((gb.peek(0xC000), gb.peek(0xC001)), (0x12, 0x50));
+}
@@ -0,0 +1,108 @@
+//! The three views of the PPU mode, dot by dot around each boundary: the
+//! STAT mode bits (`mode`), the CPU read gates (`*_locked`) and the CPU write
+//! gates (`*_write_locked`). They disagree for a dot at the edges of mode 2
+//! and mode 3, and several Mealybug tests depend on exactly where.
+//!
+//! Dots count from the LY increment. Sprites are off and SCX is 0, so mode 3
+//! has its minimum length.
+
+use missingno_gb::{ppu::Mode, test_support};
+
+#[derive(Debug, PartialEq)]
+struct Gates {
+    mode: Mode,
+    oam_read: bool,
+    oam_write: bool,
+    vram_read: bool,
+    vram_write: bool,
+}
+
+fn gates(mode: Mode, oam: (bool, bool), vram: (bool, bool)) -> Gates {
+    Gates {
+        mode,
+        oam_read: oam.0,
+        oam_write: oam.1,
+        vram_read: vram.0,
+        vram_write: vram.1,
+    }
+}
+
+const LOCKED: (bool, bool) = (true, true);
+const OPEN: (bool, bool) = (false, false);
+
+/// Every dot of line `ly`, from the dot LY changes to it.
+fn line(ly: u8) -> Vec<Gates> {
+    let mut gb = test_support::synthetic_game_boy(&[0x18, 0xFE], &[]); // JR -2
+    while !gb.step_tcycle() {}
+    while gb.ppu().ly_hardware() != ly {
+        gb.step_tcycle();
+    }
+
+    let mut dots = Vec::new();
+    while gb.ppu().ly_hardware() == ly {
+        let ppu = gb.ppu();
+        dots.push(Gates {
+            mode: ppu.mode(),
+            oam_read: ppu.oam_locked(),
+            oam_write: ppu.oam_write_locked(),
+            vram_read: ppu.vram_locked(),
+            vram_write: ppu.vram_write_locked(),
+        });
+        gb.step_tcycle();
+    }
+    dots
+}
+
+const LINES: [u8; 3] = [1, 42, 143];
+
+#[test]
+fn mode_2_onset_locks_oam_reads_a_dot_early() {
+    for ly in LINES {
+        let dots = line(ly);
+        assert_eq!(
+            dots[0],
+            gates(Mode::HorizontalBlank, (true, false), OPEN),
+            "LY={ly}"
+        );
+        assert_eq!(dots[1], gates(Mode::OamScan, LOCKED, OPEN), "LY={ly}");
+    }
+}
+
+#[test]
+fn mode_2_to_3_locks_reads_a_dot_before_writes() {
+    for ly in LINES {
+        let dots = line(ly);
+        assert_eq!(dots[80], gates(Mode::OamScan, LOCKED, OPEN), "LY={ly}");
+        // STAT and the read gates move to mode 3 first. The OAM write gate
+        // briefly opens while it hands over from scan to drawing.
+        assert_eq!(
+            dots[81],
+            gates(Mode::Drawing, (true, false), (true, false)),
+            "LY={ly}"
+        );
+        assert_eq!(dots[82], gates(Mode::Drawing, LOCKED, LOCKED), "LY={ly}");
+    }
+}
+
+#[test]
+fn mode_3_to_0_releases_everything_on_one_dot() {
+    for ly in LINES {
+        let dots = line(ly);
+        assert_eq!(dots[254], gates(Mode::Drawing, LOCKED, LOCKED), "LY={ly}");
+        assert_eq!(
+            dots[255],
+            gates(Mode::HorizontalBlank, OPEN, OPEN),
+            "LY={ly}"
+        );
+    }
+}
+
+#[test]
+fn line_0_reads_mode_3_for_a_dot_leaving_vblank() {
+    // The vblank bit (STAT bit 0) clears a dot after the scan bit (bit 1)
+    // sets, so the first line of a frame briefly reads mode 3.
+    let dots = line(0);
+    assert_eq!(dots[0], gates(Mode::VerticalBlank, (true, false), OPEN));
+    assert_eq!(dots[1], gates(Mode::Drawing, LOCKED, OPEN));
+    assert_eq!(dots[2], gates(Mode::OamScan, LOCKED, OPEN));
+}
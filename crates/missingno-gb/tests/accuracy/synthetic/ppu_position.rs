@@ -0,0 +1,47 @@
+//! The PPU reports which line it's on and which of that line's 456 dots,
+//! frozen where the raster stopped while the LCD is off.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Spins, or turns the LCD off and spins when `lcd_off`.
+fn game_boy(lcd_off: bool) -> GameBoy {
+    let main: &[u8] = if lcd_off {
+        &[
+            0xAF, // XOR A
+            0xE0, 0x40, // LDH (LCDC),A
+            0x18, 0xFE, // JR -2
+        ]
+    } else {
+        &[0x18, 0xFE] // JR -2
+    };
+    test_support::synthetic_game_boy(main, &[])
+}
+
+#[test]
+fn the_dot_counts_every_t_cycle_of_a_line() {
+    let mut gb = game_boy(false);
+    while gb.ppu().ppu_position() != (10, 0) {
+        gb.step_tcycle();
+    }
+    for line in 10..12 {
+        for dot in 0..456 {
+            assert_eq!(gb.ppu().ppu_position(), (line, dot));
+            gb.step_tcycle();
+        }
+    }
+    assert_eq!(gb.ppu().ppu_position(), (12, 0));
+}
+
+#[test]
+fn the_position_freezes_with_the_lcd_off() {
+    let mut gb = game_boy(true);
+    while gb.ppu().control().video_enabled() {
+        gb.step();
+    }
+    let stopped = gb.ppu().ppu_position();
+    assert_ne!(stopped, (0, 0), "stopped mid-frame");
+    for _ in 0..1000 {
+        gb.step_tcycle();
+        assert_eq!(gb.ppu().ppu_position(), stopped);
+    }
+}
@@ -0,0 +1,108 @@
+//! MBC3 clock reads come from a latched copy of the registers. Writing $00
+//! then $01 to $6000-$7FFF copies the running clock into it; until then reads
+//! hold still while the clock runs on. Writes set the running registers.
+
+use missingno_gb::cartridge::Cartridge;
+
+/// Master-clock dots in one RTC second.
+const SECOND: u32 = 1 << 22;
+
+/// MBC3+TIMER+RAM+BATTERY with 8 KiB of RAM, RAM and clock enabled.
+fn cartridge() -> Cartridge {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x0147] = 0x10;
+    rom[0x0149] = 0x02;
+    let mut cartridge = Cartridge::new(rom, None).unwrap();
+    cartridge.write(0x0000, 0x0A); // Enable RAM and clock
+    cartridge
+}
+
+/// Read clock register `register` ($08 seconds through $0C day high).
+fn read(cartridge: &mut Cartridge, register: u8) -> u8 {
+    cartridge.write(0x4000, register);
+    cartridge.read(0xA000)
+}
+
+fn write(cartridge: &mut Cartridge, register: u8, value: u8) {
+    cartridge.write(0x4000, register);
+    cartridge.write(0xA000, value);
+}
+
+fn latch(cartridge: &mut Cartridge) {
+    cartridge.write(0x6000, 0x00);
+    cartridge.write(0x6000, 0x01);
+}
+
+#[test]
+fn reads_change_only_when_latched() {
+    let mut cartridge = cartridge();
+    write(&mut cartridge, 0x08, 30);
+    write(&mut cartridge, 0x09, 59);
+    assert_eq!(read(&mut cartridge, 0x08), 0, "not latched yet");
+
+    latch(&mut cartridge);
+    assert_eq!(read(&mut cartridge, 0x08), 30);
+    assert_eq!(read(&mut cartridge, 0x09), 59);
+
+    cartridge.tick_rtc(SECOND);
+    assert_eq!(
+        read(&mut cartridge, 0x08),
+        30,
+        "the clock runs behind the latch"
+    );
+    latch(&mut cartridge);
+    assert_eq!(read(&mut cartridge, 0x08), 31);
+}
+
+#[test]
+fn latching_needs_00_before_01() {
+    let mut cartridge = cartridge();
+    write(&mut cartridge, 0x08, 30);
+    cartridge.write(0x6000, 0x01);
+    assert_eq!(read(&mut cartridge, 0x08), 0, "$01 alone");
+    latch(&mut cartridge);
+    write(&mut cartridge, 0x08, 45);
+    cartridge.write(0x6000, 0x01);
+    assert_eq!(read(&mut cartridge, 0x08), 30, "$01 again without a $00");
+}
+
+#[test]
+fn minutes_hours_and_days_carry() {
+    let mut cartridge = cartridge();
+    write(&mut cartridge, 0x08, 59);
+    write(&mut cartridge, 0x09, 59);
+    write(&mut cartridge, 0x0A, 23);
+    write(&mut cartridge, 0x0B, 0xFF);
+    write(&mut cartridge, 0x0C, 0x01); // Day 511
+    cartridge.tick_rtc(SECOND);
+    latch(&mut cartridge);
+    let time: Vec<u8> = (0x08..=0x0C).map(|r| read(&mut cartridge, r)).collect();
+    assert_eq!(time, [0, 0, 0, 0, 0x80], "day counter wraps and sets carry");
+}
+
+#[test]
+fn halt_stops_the_clock() {
+    let mut cartridge = cartridge();
+    write(&mut cartridge, 0x0C, 0x40);
+    cartridge.tick_rtc(SECOND * 5);
+    latch(&mut cartridge);
+    assert_eq!(read(&mut cartridge, 0x08), 0);
+
+    write(&mut cartridge, 0x0C, 0x00);
+    cartridge.tick_rtc(SECOND * 5);
+    latch(&mut cartridge);
+    assert_eq!(read(&mut cartridge, 0x08), 5);
+}
+
+#[test]
+fn copied_state_keeps_the_clock() {
+    let mut cartridge = cartridge();
+    write(&mut cartridge, 0x08, 10);
+    cartridge.tick_rtc(SECOND / 2);
+    let mut state = cartridge.clone();
+
+    // Half a second already accrued, so another half ticks the copy over.
+    state.tick_rtc(SECOND / 2);
+    latch(&mut state);
+    assert_eq!(read(&mut state, 0x08), 11);
+}
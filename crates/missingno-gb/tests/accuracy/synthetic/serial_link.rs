@@ -0,0 +1,88 @@
+//! A transfer shifts SB out MSB first while the link's bits shift in, so
+//! after eight clocks SB holds the byte the other end sent. With nothing
+//! connected the line floats high and $FF comes back; looped back, the
+//! byte sent returns unchanged. On the external clock nothing moves until
+//! the other end clocks.
+
+use missingno_gb::{
+    GameBoy,
+    serial_transfer::{Loopback, SerialLink},
+    test_support,
+    test_support::run_until_infinite_loop,
+};
+
+/// Sends $A5 with SC = `control`, waits for the transfer to finish, copies
+/// SB to $C000, then spins.
+fn game_boy(control: u8) -> GameBoy {
+    let main = [
+        0x3E, 0xA5, // LD A,$A5
+        0xE0, 0x01, // LDH ($01),A: SB
+        0x3E, control, // LD A,control
+        0xE0, 0x02, // LDH ($02),A: SC
+        0xF0, 0x02, // wait: LDH A,($02)
+        0xCB, 0x7F, // BIT 7,A
+        0x20, 0xFA, // JR NZ,wait
+        0xF0, 0x01, // LDH A,($01)
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+fn received(mut gb: GameBoy) -> u8 {
+    assert!(
+        run_until_infinite_loop(&mut gb, 10),
+        "transfer never finished"
+    );
+    gb.peek_range(0xC000, 1)[0]
+}
+
+#[test]
+fn nothing_connected_receives_ff() {
+    assert_eq!(received(game_boy(0x81)), 0xFF);
+}
+
+#[test]
+fn loopback_receives_what_it_sent() {
+    let mut gb = game_boy(0x81);
+    gb.set_link(Box::new(Loopback::new()));
+    assert_eq!(received(gb), 0xA5);
+}
+
+/// The other end of the cable drives the clock, sending $3C.
+struct Master {
+    byte: u8,
+    bits: u8,
+}
+
+impl SerialLink for Master {
+    fn exchange_bit(&mut self, _out_bit: bool) -> bool {
+        let bit = self.byte & 0x80 != 0;
+        self.byte <<= 1;
+        self.bits += 1;
+        bit
+    }
+
+    fn clock(&mut self) -> bool {
+        self.bits < 8
+    }
+}
+
+#[test]
+fn external_clock_receives_the_peers_byte() {
+    let mut gb = game_boy(0x80);
+    gb.set_link(Box::new(Master {
+        byte: 0x3C,
+        bits: 0,
+    }));
+    assert_eq!(received(gb), 0x3C);
+}
+
+#[test]
+fn external_clock_waits_for_the_peer() {
+    let mut gb = game_boy(0x80);
+    assert!(
+        !run_until_infinite_loop(&mut gb, 10),
+        "finished without a clock"
+    );
+}
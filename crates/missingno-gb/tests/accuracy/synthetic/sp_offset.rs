@@ -0,0 +1,79 @@
+//! `ADD SP,e8` (4 M-cycles) and `LD HL,SP+e8` (3 M-cycles) add a signed
+//! offset to SP, but set H and C from an unsigned add of the offset byte to
+//! SP's low byte: carries out of bits 3 and 7, even for negative offsets.
+//! Z and N are always cleared.
+
+use missingno_gb::{GameBoy, cpu::flags::Flags, test_support};
+
+/// Address of the instruction under test.
+const INSTRUCTION: u16 = 0x0154;
+
+/// Loads SP, sets Z and N with `SUB A`, runs `opcode e8` at $0154, then
+/// spins.
+fn run(opcode: u8, sp: u16, offset: i8) -> (GameBoy, u32) {
+    let [sp_low, sp_high] = sp.to_le_bytes();
+    let offset = offset as u8;
+    let main = [
+        0x31, sp_low, sp_high, // LD SP,sp
+        0x97,    // SUB A
+        opcode, offset, // ADD SP,e8 or LD HL,SP+e8
+        0x18, 0xFE, // JR -2
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+
+    let mut budget = 10_000;
+    while gb.cpu().ir_address != INSTRUCTION {
+        gb.step_tcycle();
+        budget -= 1;
+        assert!(budget > 0, "never reached the instruction");
+    }
+    let mut tcycles = 0;
+    while gb.cpu().ir_address == INSTRUCTION {
+        gb.step_tcycle();
+        tcycles += 1;
+    }
+    (gb, tcycles)
+}
+
+/// (SP, offset, SP + offset, H, C)
+const CASES: [(u16, i8, u16, bool, bool); 7] = [
+    (0x00FF, 1, 0x0100, true, true),
+    (0x000F, 1, 0x0010, true, false),
+    (0xFFF8, 8, 0x0000, true, true),
+    (0x1000, -1, 0x0FFF, false, false),
+    (0x1001, -1, 0x1000, true, true),
+    (0xD000, -128, 0xCF80, false, false),
+    (0xDFFF, 127, 0xE07E, true, true),
+];
+
+fn assert_flags(gb: &GameBoy, half_carry: bool, carry: bool, case: &str) {
+    let flags = gb.cpu().flags;
+    assert!(!flags.contains(Flags::ZERO), "{case}: Z set");
+    assert!(!flags.contains(Flags::NEGATIVE), "{case}: N set");
+    assert_eq!(flags.contains(Flags::HALF_CARRY), half_carry, "{case}: H");
+    assert_eq!(flags.contains(Flags::CARRY), carry, "{case}: C");
+}
+
+#[test]
+fn add_sp_takes_4_mcycles_with_low_byte_carries() {
+    for (sp, offset, result, half_carry, carry) in CASES {
+        let case = format!("ADD SP,{offset} with SP={sp:04X}");
+        let (gb, tcycles) = run(0xE8, sp, offset);
+        assert_eq!(tcycles, 16, "{case}");
+        assert_eq!(gb.cpu().stack_pointer, result, "{case}");
+        assert_flags(&gb, half_carry, carry, &case);
+    }
+}
+
+#[test]
+fn ld_hl_sp_offset_takes_3_mcycles_with_low_byte_carries() {
+    for (sp, offset, result, half_carry, carry) in CASES {
+        let case = format!("LD HL,SP{offset:+} with SP={sp:04X}");
+        let (gb, tcycles) = run(0xF8, sp, offset);
+        assert_eq!(tcycles, 12, "{case}");
+        let hl = u16::from_be_bytes([gb.cpu().h, gb.cpu().l]);
+        assert_eq!(hl, result, "{case}");
+        assert_eq!(gb.cpu().stack_pointer, sp, "{case}: SP changed");
+        assert_flags(&gb, half_carry, carry, &case);
+    }
+}
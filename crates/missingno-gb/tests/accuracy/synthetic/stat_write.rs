@@ -0,0 +1,66 @@
+//! Writing STAT ($FF41) only reaches the interrupt enables in bits 3-6: the
+//! mode and coincidence bits keep tracking the PPU and bit 7 reads as 1. On
+//! DMG the write briefly raises every enable, so it requests a STAT
+//! interrupt whenever the PPU is in a mode that could raise the line.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Polls until `wait` matches, clears IF, writes $00 to STAT and stores the
+/// STAT and IF reads that follow at $C000 and $C001.
+fn write_zero_to_stat(wait: &[u8]) -> GameBoy {
+    let mut main = vec![
+        0xF3, // DI
+    ];
+    main.extend_from_slice(wait);
+    main.extend_from_slice(&[
+        0xAF, // XOR A
+        0xE0, 0x0F, // LDH ($0F),A
+        0xE0, 0x41, // LDH ($41),A
+        0xF0, 0x41, // LDH A,($41)
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0xF0, 0x0F, // LDH A,($0F)
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xFE, // JR -2
+    ]);
+
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    test_support::run_frames(&mut gb, 3);
+    gb
+}
+
+/// Wait for line 145, inside VBlank.
+const WAIT_VBLANK: [u8; 6] = [
+    0xF0, 0x44, // LDH A,($44)
+    0xFE, 0x91, // CP $91
+    0x20, 0xFA, // JR NZ,-6
+];
+
+/// Move LYC off every line, then wait for mode 3.
+const WAIT_DRAWING: [u8; 12] = [
+    0x3E, 0xFF, // LD A,$FF
+    0xE0, 0x45, // LDH ($45),A
+    0xF0, 0x41, // LDH A,($41)
+    0xE6, 0x03, // AND $03
+    0xFE, 0x03, // CP $03
+    0x20, 0xF8, // JR NZ,-8
+];
+
+#[test]
+fn write_leaves_mode_bits_alone() {
+    let gb = write_zero_to_stat(&WAIT_VBLANK);
+    // Bit 7 set, enables cleared, LY != LYC, mode 1.
+    assert_eq!(gb.peek(0xC000), 0x81);
+}
+
+#[test]
+fn write_during_vblank_requests_stat_interrupt() {
+    let gb = write_zero_to_stat(&WAIT_VBLANK);
+    assert_eq!(gb.peek(0xC001) & 0x02, 0x02, "IF = {:02X}", gb.peek(0xC001));
+}
+
+#[test]
+fn write_during_drawing_requests_nothing() {
+    let gb = write_zero_to_stat(&WAIT_DRAWING);
+    assert_eq!(gb.peek(0xC000), 0x83);
+    assert_eq!(gb.peek(0xC001) & 0x02, 0, "IF = {:02X}", gb.peek(0xC001));
+}
@@ -0,0 +1,87 @@
+//! Tiles are 2bpp and planar: each row is two bytes, the first holding bit
+//! 0 of every pixel's colour number and the second bit 1, leftmost pixel in
+//! bit 7. `Tile` converts between that and one colour number per pixel, and
+//! `poke_vram` writes tile data back for tooling.
+
+use missingno_gb::{
+    GameBoy,
+    ppu::{
+        memory::Vram,
+        types::tiles::{Tile, TileBlockId, TileIndex},
+    },
+    test_support,
+};
+
+/// Pan Docs' example tile, a rounded "A".
+const EXAMPLE: [u8; 16] = [
+    0x3C, 0x7E, 0x42, 0x42, 0x42, 0x42, 0x42, 0x42, 0x7E, 0x5E, 0x7E, 0x0A, 0x7C, 0x56, 0x38, 0x7C,
+];
+
+#[rustfmt::skip]
+const EXAMPLE_PIXELS: [u8; 64] = [
+    0, 2, 3, 3, 3, 3, 2, 0,
+    0, 3, 0, 0, 0, 0, 3, 0,
+    0, 3, 0, 0, 0, 0, 3, 0,
+    0, 3, 0, 0, 0, 0, 3, 0,
+    0, 3, 1, 3, 3, 3, 3, 0,
+    0, 1, 1, 1, 3, 1, 3, 0,
+    0, 3, 1, 3, 1, 3, 2, 0,
+    0, 2, 3, 3, 3, 2, 0, 0,
+];
+
+#[test]
+fn decodes_the_bit_planes() {
+    assert_eq!(Tile::from_bytes(EXAMPLE).pixels(), EXAMPLE_PIXELS);
+}
+
+#[test]
+fn encodes_the_bit_planes() {
+    assert_eq!(Tile::from_pixels(&EXAMPLE_PIXELS).to_bytes(), EXAMPLE);
+}
+
+#[test]
+fn every_byte_pattern_round_trips() {
+    for seed in 0..=255u8 {
+        let bytes: [u8; 16] = std::array::from_fn(|i| seed.wrapping_mul(31) ^ (i as u8 * 17));
+        let pixels = Tile::from_bytes(bytes).pixels();
+        assert_eq!(Tile::from_pixels(&pixels).to_bytes(), bytes);
+    }
+}
+
+#[test]
+fn encoding_ignores_bits_above_the_colour_number() {
+    let pixels = EXAMPLE_PIXELS.map(|colour| colour | 0xFC);
+    assert_eq!(Tile::from_pixels(&pixels).to_bytes(), EXAMPLE);
+}
+
+fn game_boy() -> GameBoy {
+    test_support::synthetic_game_boy(&[0x18, 0xFE], &[]) // JR -2
+}
+
+#[test]
+fn poked_tiles_land_in_vram() {
+    let mut gb = game_boy();
+    let before = gb.vram_bytes(0);
+    assert!(gb.poke_vram(0, 0x8810, &EXAMPLE));
+    let after = gb.vram_bytes(0);
+    assert_eq!(after[0x0810..0x0820], EXAMPLE);
+    assert_eq!(after[..0x0810], before[..0x0810]);
+    assert_eq!(after[0x0820..], before[0x0820..]);
+    let tile = gb
+        .vram()
+        .bank(0)
+        .tile_block(TileBlockId(1))
+        .tile(TileIndex(1));
+    assert_eq!(tile.pixels(), EXAMPLE_PIXELS);
+}
+
+#[test]
+fn pokes_outside_vram_are_refused() {
+    let mut gb = game_boy();
+    let before = gb.vram_bytes(0);
+    assert!(!gb.poke_vram(1, 0x8000, &EXAMPLE), "the DMG has one bank");
+    assert!(!gb.poke_vram(0, 0x9FF8, &EXAMPLE), "runs past $9FFF");
+    assert!(!gb.poke_vram(0, 0x7FF8, &EXAMPLE), "starts in ROM");
+    assert_eq!(gb.vram_bytes(0), before);
+    assert!(gb.poke_vram(0, 0x9FF0, &EXAMPLE), "ends at $9FFF");
+}
@@ -0,0 +1,81 @@
+//! The window keeps its own line counter, which only counts lines it was
+//! drawn on. Turned off for a few lines and back on, it carries on from the
+//! row it had reached, not from the row LY would give.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// LCD on, window map at $9C00, window on, tiles at $8000, background on.
+const WINDOW_ON: u8 = 0xF1;
+/// As [`WINDOW_ON`] with the window off.
+const WINDOW_OFF: u8 = 0xD1;
+
+/// Turns the window off on lines 8 to 15 of every frame.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x3E, 0xE4, // LD A,$E4
+        0xE0, 0x47, // LDH (BGP),A: colour n is shade n
+        0xAF, // XOR A
+        0xE0, 0x4A, // LDH (WY),A: WY 0
+        0x3E, 0x07, // LD A,7
+        0xE0, 0x4B, // LDH (WX),A: WX 7
+        0x3E, WINDOW_ON, // frame: LD A,WINDOW_ON
+        0xE0, 0x40, // LDH (LCDC),A
+        0x06, 0x08, // LD B,8
+        0xCD, 0x90, 0x01, // CALL wait_for_ly
+        0x3E, WINDOW_OFF, // LD A,WINDOW_OFF
+        0xE0, 0x40, // LDH (LCDC),A
+        0x06, 0x10, // LD B,16
+        0xCD, 0x90, 0x01, // CALL wait_for_ly
+        0x3E, WINDOW_ON, // LD A,WINDOW_ON
+        0xE0, 0x40, // LDH (LCDC),A
+        0x06, 0x00, // LD B,0
+        0xCD, 0x90, 0x01, // CALL wait_for_ly
+        0x18, 0xE3, // JR frame
+    ];
+    let wait_for_ly = [
+        0xF0, 0x44, // LDH A,(LY)
+        0xB8, // CP B
+        0x20, 0xFB, // JR NZ,wait_for_ly
+        0xC9, // RET
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[(0x0190, &wait_for_ly)]);
+
+    // Tile n is solid colour n, and window row n is tile n + 1; the
+    // background stays tile 0.
+    for colour in 1..4u16 {
+        let low = if colour & 1 != 0 { 0xFF } else { 0x00 };
+        let high = if colour & 2 != 0 { 0xFF } else { 0x00 };
+        let tile: Vec<u8> = [low, high].repeat(8);
+        assert!(gb.poke_vram(0, 0x8000 + colour * 16, &tile));
+    }
+    for row in 0..3u16 {
+        assert!(gb.poke_vram(0, 0x9C00 + row * 32, &[row as u8 + 1; 32]));
+    }
+    gb
+}
+
+#[test]
+fn the_window_resumes_from_the_row_it_reached() {
+    let mut gb = game_boy();
+    let mut frames = 0;
+    while frames < 3 {
+        if gb.step().new_screen {
+            frames += 1;
+        }
+    }
+
+    let screen = gb.screen();
+    let colour = |y: u8| screen.pixel(80, y).0;
+    for y in 0..8 {
+        assert_eq!(colour(y), 1, "line {y}: window row 0");
+    }
+    for y in 9..16 {
+        assert_eq!(colour(y), 0, "line {y}: window off, background");
+    }
+    for y in 17..24 {
+        assert_eq!(colour(y), 2, "line {y}: window row 1, not row 2");
+    }
+    for y in 25..32 {
+        assert_eq!(colour(y), 3, "line {y}: window row 2");
+    }
+}
@@ -65,7 +65,7 @@ fn missingno_extension_field_roundtrip() {
         extensions,
     };
 
-    let cartridge = Cartridge::new(minimal_rom(), None);
+    let cartridge = Cartridge::new(minimal_rom(), None).unwrap();
     let mut gb = GameBoy::new(cartridge, None);
 
     {
@@ -133,7 +133,7 @@ fn unknown_extension_name_is_rejected() {
         extensions,
     };
 
-    let cartridge = Cartridge::new(minimal_rom(), None);
+    let cartridge = Cartridge::new(minimal_rom(), None).unwrap();
     let gb = GameBoy::new(cartridge, None);
 
     let result = Tracer::create(&path, &profile, &gb, BootRom::Skip, "DMG-B");
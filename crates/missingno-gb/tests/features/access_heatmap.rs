@@ -0,0 +1,78 @@
+//! The access heatmap counts CPU reads and writes per 256-byte page, only
+//! while turned on, and halves its counts as frames pass.
+
+use missingno_gb::{GameBoy, heatmap::DECAY_FRAMES, test_support};
+
+/// Writes $C000 and reads $FF80 on every pass round a loop at $0150.
+fn game_boy() -> GameBoy {
+    let main = [
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0xF0, 0x80, // LDH A,($80)
+        0x18, 0xF9, // JR -7
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+fn run(gb: &mut GameBoy, tcycles: u32) {
+    for _ in 0..tcycles {
+        gb.step_tcycle();
+    }
+}
+
+#[test]
+fn off_until_turned_on() {
+    let mut gb = game_boy();
+    run(&mut gb, 1000);
+    assert!(gb.heatmap().is_none());
+}
+
+#[test]
+fn counts_reads_and_writes_by_page() {
+    let mut gb = game_boy();
+    gb.set_heatmap(true);
+    run(&mut gb, 10_000);
+    let heatmap = gb.heatmap().unwrap();
+
+    // Opcode and operand fetches, then the loop's own accesses.
+    assert!(heatmap.reads(0x01) > 0);
+    assert_eq!(heatmap.writes(0x01), 0);
+    assert!(heatmap.writes(0xC0) > 0);
+    assert_eq!(heatmap.reads(0xC0), 0);
+    assert!(heatmap.reads(0xFF) > 0);
+    assert_eq!(heatmap.writes(0xFF), 0);
+
+    // One write and one HRAM read per pass.
+    assert!(heatmap.writes(0xC0).abs_diff(heatmap.reads(0xFF)) <= 1);
+    assert_eq!(heatmap.max_writes(), heatmap.writes(0xC0));
+    assert_eq!(heatmap.max_reads(), heatmap.reads(0x01));
+    for page in [0x00, 0x80, 0xD0, 0xFE] {
+        assert_eq!(heatmap.reads(page), 0, "reads of page ${page:02X}");
+        assert_eq!(heatmap.writes(page), 0, "writes to page ${page:02X}");
+    }
+}
+
+#[test]
+fn halves_every_decay_period() {
+    let mut gb = game_boy();
+    gb.set_heatmap(true);
+    run(&mut gb, 10_000);
+    let heatmap = gb.heatmap_mut().unwrap();
+    let writes = heatmap.writes(0xC0);
+
+    for _ in 1..DECAY_FRAMES {
+        heatmap.end_frame();
+    }
+    assert_eq!(heatmap.writes(0xC0), writes);
+    heatmap.end_frame();
+    assert_eq!(heatmap.writes(0xC0), writes / 2);
+}
+
+#[test]
+fn turning_off_discards_counts() {
+    let mut gb = game_boy();
+    gb.set_heatmap(true);
+    run(&mut gb, 1000);
+    gb.set_heatmap(false);
+    gb.set_heatmap(true);
+    assert_eq!(gb.heatmap().unwrap().max_reads(), 0);
+}
@@ -0,0 +1,85 @@
+//! Muting a channel takes it out of the mix but leaves it running.
+
+use missingno_gb::{GameBoy, debugger::Debugger, test_support};
+
+/// Plays ~440 Hz on channel 2 (panned to both sides after boot). With
+/// `length`, the note stops itself after 32/256 s.
+fn tone(length: bool) -> GameBoy {
+    // Trigger, period high 6, and the length timer if asked for.
+    let nr24 = if length { 0xC6 } else { 0x86 };
+    let main = [
+        0x3E, 0xA0, // LD A,$A0 (50% duty, length 32)
+        0xE0, 0x16, // LDH (NR21),A
+        0x3E, 0xF0, // LD A,$F0 (volume 15, no envelope)
+        0xE0, 0x17, // LDH (NR22),A
+        0x3E, 0xD6, // LD A,$D6 (period $6D6)
+        0xE0, 0x18, // LDH (NR23),A
+        0x3E, nr24, // LD A,nr24
+        0xE0, 0x19, // LDH (NR24),A
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+/// Peak-to-peak swing of the left output over the samples.
+fn swing(samples: &[(f32, f32)]) -> f32 {
+    let max = samples.iter().map(|s| s.0).fold(f32::MIN, f32::max);
+    let min = samples.iter().map(|s| s.0).fold(f32::MAX, f32::min);
+    max - min
+}
+
+/// The swing over frames 6..10, once the tone and the high-pass filter
+/// have settled.
+fn settled_swing(gb: &mut GameBoy) -> f32 {
+    test_support::run_frames(gb, 6);
+    gb.drain_audio_samples();
+    test_support::run_frames(gb, 4);
+    swing(&gb.drain_audio_samples())
+}
+
+#[test]
+fn a_muted_channel_is_silent() {
+    let mut gb = tone(false);
+    assert!(settled_swing(&mut gb) > 0.2);
+
+    let mut gb = tone(false);
+    gb.audio_mut().set_channel_muted(1, true);
+    assert!(gb.audio().channel_muted(1));
+    assert!(settled_swing(&mut gb) < 0.01);
+
+    gb.audio_mut().set_channel_muted(1, false);
+    assert!(settled_swing(&mut gb) > 0.2);
+}
+
+#[test]
+fn a_muted_channel_keeps_running() {
+    let mut gb = tone(true);
+    gb.audio_mut().set_channel_muted(1, true);
+    test_support::run_frames(&mut gb, 2);
+    assert!(gb.audio().channels().ch2.enabled.enabled);
+
+    // 32/256 s is under eight frames.
+    test_support::run_frames(&mut gb, 8);
+    assert!(!gb.audio().channels().ch2.enabled.enabled);
+}
+
+#[test]
+fn muting_survives_reset_and_rewind() {
+    let mut gb = tone(false);
+    gb.audio_mut().set_channel_muted(1, true);
+    gb.reset();
+    assert!(gb.audio().channel_muted(1));
+
+    // The snapshot is taken with CH2 muted; rewinding keeps the muting
+    // chosen since.
+    let mut debugger = Debugger::new(gb);
+    debugger.rewind_buffer_mut().set_interval(1);
+    debugger.step_frame();
+    let audio = debugger.game_boy_mut().audio_mut();
+    audio.set_channel_muted(1, false);
+    audio.set_channel_muted(2, true);
+    debugger.step();
+    assert!(debugger.rewind());
+    assert!(!debugger.game_boy().audio().channel_muted(1));
+    assert!(debugger.game_boy().audio().channel_muted(2));
+}
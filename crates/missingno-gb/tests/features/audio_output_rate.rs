@@ -0,0 +1,105 @@
+//! `Audio::set_output_rate` resamples the mixer to the host device's rate:
+//! the sample count follows the rate, a tone keeps its pitch, and each
+//! sample comes out as soon as its window closes. `buffer_fill` counts
+//! what's waiting to be drained.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Frames in just over one emulated second (59.7 Hz).
+const FRAMES: u32 = 66;
+
+/// Plays a continuous ~440 Hz square wave on channel 2.
+fn tone() -> GameBoy {
+    let main = [
+        0x3E, 0x80, // LD A,$80 (50% duty)
+        0xE0, 0x16, // LDH ($16),A
+        0x3E, 0xF0, // LD A,$F0 (volume 15, no envelope)
+        0xE0, 0x17, // LDH ($17),A
+        0x3E, 0xD6, // LD A,$D6 (period $6D6: 131072 / 298 = 439.8 Hz)
+        0xE0, 0x18, // LDH ($18),A
+        0x3E, 0x86, // LD A,$86 (trigger, period high 6)
+        0xE0, 0x19, // LDH ($19),A
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+/// Rising zero crossings of the mono signal around its mean.
+fn cycles(samples: &[(f32, f32)]) -> usize {
+    let mono: Vec<f32> = samples.iter().map(|(l, r)| (l + r) / 2.0).collect();
+    let mean = mono.iter().sum::<f32>() / mono.len() as f32;
+    mono.windows(2)
+        .filter(|pair| pair[0] < mean && pair[1] >= mean)
+        .count()
+}
+
+fn record(output_rate: u32) -> Vec<(f32, f32)> {
+    let mut gb = tone();
+    gb.audio_mut().set_output_rate(output_rate);
+    test_support::run_frames(&mut gb, FRAMES);
+    gb.drain_audio_samples()
+}
+
+#[test]
+fn sample_count_follows_output_rate() {
+    for rate in [44100, 48000, 96000] {
+        let samples = record(rate).len() as f32;
+        let expected = rate as f32 * FRAMES as f32 * 70224.0 / 4_194_304.0;
+        // The first frame is partial: the post-boot handoff lands mid-frame.
+        assert!(
+            (samples - expected).abs() < expected / 100.0,
+            "{rate} Hz: {samples} samples, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn tone_keeps_its_pitch_at_48khz() {
+    let samples = record(48000);
+    // Skip the first frame, where the tone starts up.
+    let second = &samples[1000..1000 + 48000];
+    let hz = cycles(second);
+    assert!((438..=442).contains(&hz), "measured {hz} Hz");
+}
+
+#[test]
+fn output_rate_survives_reset() {
+    let mut gb = tone();
+    gb.audio_mut().set_output_rate(48000);
+    gb.reset();
+    assert_eq!(gb.audio().output_rate(), 48000);
+}
+
+#[test]
+fn samples_are_not_held_back() {
+    let mut gb = tone();
+    gb.audio_mut().set_output_rate(48000);
+    gb.drain_audio_samples();
+    // Every complete window has been delivered, and nothing more.
+    let tcycles = 10_000;
+    for _ in 0..tcycles {
+        gb.step_tcycle();
+    }
+    let samples = gb.drain_audio_samples().len() as f32;
+    let windows = tcycles as f32 * 48000.0 / 4_194_304.0;
+    assert!(
+        (samples - windows).abs() <= 1.0,
+        "{samples} samples after {windows} windows"
+    );
+}
+
+#[test]
+fn buffer_fill_counts_undrained_samples() {
+    let mut gb = tone();
+    gb.audio_mut().set_output_rate(48000);
+    test_support::run_frames(&mut gb, 2);
+    let fill = gb.audio().buffer_fill();
+    assert!(fill > 0);
+
+    test_support::run_frames(&mut gb, 2);
+    assert!(gb.audio().buffer_fill() > fill, "grows until drained");
+
+    let drained = gb.drain_audio_samples().len();
+    assert!(drained > fill);
+    assert_eq!(gb.audio().buffer_fill(), 0);
+}
@@ -0,0 +1,65 @@
+//! A copy of the console taken mid-note carries all of the APU's state:
+//! frame sequencer, envelopes, duty and wave positions, length counters and
+//! the noise LFSR. It goes on to produce exactly the samples the original
+//! does, with no click or dropout where the copy was made.
+
+use missingno_gb::{GameBoy, test_support, test_support::run_frames};
+
+/// (register, value) writes that start a note on every channel.
+const NOTES: [(u8, u8); 22] = [
+    (0x26, 0x80), // NR52: APU on
+    (0x24, 0x77), // NR50: full volume
+    (0x25, 0xFF), // NR51: every channel to both sides
+    (0x11, 0x80), // NR11: 50% duty
+    (0x12, 0xF3), // NR12: volume 15, fading
+    (0x13, 0x00),
+    (0x14, 0x87), // NR14: trigger
+    (0x16, 0x40), // NR21: 25% duty
+    (0x17, 0xA1), // NR22: volume 10, fading
+    (0x18, 0x80),
+    (0x19, 0x86), // NR24: trigger
+    (0x30, 0x01), // Wave RAM
+    (0x31, 0x23),
+    (0x32, 0x45),
+    (0x33, 0x67),
+    (0x1A, 0x80), // NR30: DAC on
+    (0x1C, 0x20), // NR32: full volume
+    (0x1D, 0x00),
+    (0x1E, 0x85), // NR34: trigger
+    (0x21, 0xF2), // NR42: volume 15, fading
+    (0x22, 0x55), // NR43: noise
+    (0x23, 0x80), // NR44: trigger
+];
+
+/// Starts a note on every channel, then spins.
+fn game_boy() -> GameBoy {
+    let mut main = Vec::new();
+    for (register, value) in NOTES {
+        main.extend([0x3E, value, 0xE0, register]); // LD A,value; LDH (register),A
+    }
+    main.extend([0x18, 0xFE]); // JR -2
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+#[test]
+fn copy_taken_mid_note_continues_the_same_samples() {
+    let mut gb = game_boy();
+    run_frames(&mut gb, 10);
+    let channels_on = gb.peek(0xFF26) & 0x0F;
+    assert_eq!(channels_on, 0x0F, "every channel still playing");
+
+    let mut copy = gb.clone();
+    gb.drain_audio_samples();
+    copy.drain_audio_samples();
+
+    run_frames(&mut gb, 5);
+    run_frames(&mut copy, 5);
+    let samples = gb.drain_audio_samples();
+    assert!(samples.len() > 1000, "{} samples", samples.len());
+    assert!(
+        samples
+            .iter()
+            .any(|&(left, right)| left != 0.0 || right != 0.0)
+    );
+    assert_eq!(copy.drain_audio_samples(), samples);
+}
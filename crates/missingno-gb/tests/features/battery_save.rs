@@ -0,0 +1,57 @@
+//! `export_sram` hands back the battery RAM for a `.sav`, only for cartridge
+//! types with a battery, and `Cartridge::new` takes it back on load.
+
+use missingno_gb::{
+    GameBoy, cartridge::Cartridge, test_support, test_support::run_until_infinite_loop,
+};
+
+/// A cartridge of type `kind` with 8 KiB of RAM that enables RAM, stores
+/// $42 at $A000 and $99 at $BFFF, then spins.
+fn rom(kind: u8) -> Vec<u8> {
+    let main = [
+        0x3E, 0x0A, // LD A,$0A
+        0xEA, 0x00, 0x00, // LD ($0000),A: enable RAM
+        0x3E, 0x42, // LD A,$42
+        0xEA, 0x00, 0xA0, // LD ($A000),A
+        0x3E, 0x99, // LD A,$99
+        0xEA, 0xFF, 0xBF, // LD ($BFFF),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut rom = test_support::synthetic_rom(&main, &[]);
+    rom[0x0147] = kind;
+    rom[0x0149] = 0x02;
+    rom
+}
+
+fn run(kind: u8, sram: Option<Vec<u8>>) -> GameBoy {
+    let mut gb = GameBoy::new(Cartridge::new(rom(kind), sram).unwrap(), None);
+    assert!(run_until_infinite_loop(&mut gb, 2));
+    gb
+}
+
+#[test]
+fn battery_cartridges_export_their_ram() {
+    // MBC1+RAM+BATTERY, and ROM+RAM+BATTERY, which ignores the RAM enable.
+    for kind in [0x03, 0x09] {
+        let sram = run(kind, None).export_sram().unwrap();
+        assert_eq!(sram.len(), 8 * 1024, "type ${kind:02X}");
+        assert_eq!((sram[0], sram[0x1FFF]), (0x42, 0x99), "type ${kind:02X}");
+    }
+}
+
+#[test]
+fn cartridges_without_a_battery_export_nothing() {
+    for kind in [0x00, 0x01, 0x02, 0x08] {
+        assert_eq!(run(kind, None).export_sram(), None, "type ${kind:02X}");
+    }
+}
+
+#[test]
+fn exported_ram_loads_back() {
+    let mut sram = vec![0u8; 8 * 1024];
+    sram[0x1000] = 0x5A;
+    let gb = run(0x03, Some(sram));
+    let exported = gb.export_sram().unwrap();
+    assert_eq!(exported[0x1000], 0x5A, "kept from the loaded save");
+    assert_eq!(exported[0], 0x42, "written since");
+}
@@ -0,0 +1,95 @@
+//! The cosmetic `BootRom::Logo` sequence: it draws the cartridge's header
+//! logo, scrolls it, plays the chime and hands over at $0100 in the same
+//! state as a console without a boot ROM.
+
+use missingno_gb::{BootRom, GameBoy, cartridge::Cartridge, test_support};
+
+/// A header logo that isn't Nintendo's: the sequence shows whatever is there.
+fn logo() -> [u8; 0x30] {
+    std::array::from_fn(|i| (i as u8).wrapping_mul(0x1D) ^ 0xA5)
+}
+
+fn rom() -> Vec<u8> {
+    let mut rom = test_support::synthetic_rom(&[0x18, 0xFE], &[]); // JR -2
+    rom[0x0104..0x0134].copy_from_slice(&logo());
+    rom
+}
+
+fn game_boy() -> GameBoy {
+    GameBoy::new(Cartridge::new(rom(), None).unwrap(), Some(BootRom::Logo))
+}
+
+/// The sequence clears VRAM with the LCD off for its first frames.
+const LCD_OFF_FRAMES: u32 = 3;
+
+/// Each nibble doubled to a byte, as the tile row the logo draws.
+fn doubled(nibble: u8) -> u8 {
+    (0..4).fold(0, |row, bit| {
+        let pixel = (nibble >> (3 - bit)) & 1;
+        row << 2 | pixel << 1 | pixel
+    })
+}
+
+#[test]
+fn draws_the_header_logo() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, LCD_OFF_FRAMES + 2);
+
+    for (i, &byte) in logo().iter().enumerate() {
+        let rows = 0x8010 + i as u16 * 8;
+        for (row, nibble) in [byte >> 4, byte & 0x0F].into_iter().enumerate() {
+            let address = rows + row as u16 * 4;
+            assert_eq!(gb.peek(address), doubled(nibble), "{address:04X}");
+            assert_eq!(gb.peek(address + 2), doubled(nibble), "{address:04X}");
+        }
+    }
+    assert_eq!(gb.peek(0x9904), 1);
+    assert_eq!(gb.peek(0x992F), 24);
+}
+
+#[test]
+fn scrolls_then_chimes() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, LCD_OFF_FRAMES + 10);
+    let early = gb.peek(0xFF42);
+    test_support::run_frames(&mut gb, 10);
+    let later = gb.peek(0xFF42);
+    assert!(later < early, "SCY {early} then {later}");
+    assert_eq!(gb.peek(0xFF26) & 0x01, 0, "silent while scrolling");
+
+    test_support::run_frames(&mut gb, 90);
+    assert_eq!(gb.peek(0xFF42), 0);
+    assert_eq!(gb.peek(0xFF26) & 0x01, 0x01, "channel 1 chiming");
+}
+
+#[test]
+fn hands_over_in_the_post_boot_state() {
+    let mut gb = game_boy();
+    let mut frames = 0;
+    while !gb.finish_logo_boot() {
+        assert!(gb.in_logo_boot());
+        test_support::run_frames(&mut gb, 1);
+        frames += 1;
+        assert!(frames < 600, "the sequence never finished");
+    }
+
+    let skipped = GameBoy::new(Cartridge::new(rom(), None).unwrap(), None);
+    assert!(!gb.in_logo_boot());
+    assert_eq!(gb.cpu().pc, 0x0100);
+    assert_eq!(gb.cpu().a, skipped.cpu().a);
+    assert_eq!(gb.cpu().stack_pointer, skipped.cpu().stack_pointer);
+    for address in [0xFF40, 0xFF42, 0xFF47, 0xFF26, 0xFF50] {
+        assert_eq!(gb.peek(address), skipped.peek(address), "{address:04X}");
+    }
+}
+
+#[test]
+fn soft_reset_skips_the_sequence() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 10);
+    assert!(gb.in_logo_boot());
+
+    gb.soft_reset();
+    assert!(!gb.in_logo_boot());
+    assert_eq!(gb.cpu().pc, 0x0100);
+}
@@ -0,0 +1,59 @@
+//! `capture_png` encodes the displayed frame as a 160×144 8-bit greyscale
+//! PNG with the shades of the accuracy tests' reference images.
+
+use missingno_gb::{
+    GameBoy,
+    ppu::screen::{NUM_SCANLINES, PIXELS_PER_LINE},
+    test_support,
+    test_support::run_frames,
+};
+
+/// Sets BGP to map colour N to shade N and turns the LCD on, then spins,
+/// with tile 0 drawn as the four shades repeating across each row.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x3E, 0xE4, // LD A,$E4
+        0xE0, 0x47, // LDH ($47),A
+        0x3E, 0x91, // LD A,$91
+        0xE0, 0x40, // LDH ($40),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    for row in 0..8 {
+        assert!(gb.poke_raw(0x8000 + row * 2, 0x55));
+        assert!(gb.poke_raw(0x8000 + row * 2 + 1, 0x33));
+    }
+    run_frames(&mut gb, 3);
+    gb
+}
+
+fn decode(bytes: &[u8]) -> (png::OutputInfo, Vec<u8>) {
+    let mut reader = png::Decoder::new(std::io::Cursor::new(bytes))
+        .read_info()
+        .unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    pixels.truncate(info.buffer_size());
+    (info, pixels)
+}
+
+#[test]
+fn encodes_a_greyscale_frame() {
+    let gb = game_boy();
+    let (info, pixels) = decode(&gb.capture_png());
+    assert_eq!(
+        (info.width, info.height),
+        (PIXELS_PER_LINE as u32, NUM_SCANLINES as u32)
+    );
+    assert_eq!(info.color_type, png::ColorType::Grayscale);
+    assert_eq!(info.bit_depth, png::BitDepth::Eight);
+    assert_eq!(pixels[..4], [0xFF, 0xAA, 0x55, 0x00]);
+    assert_eq!(pixels, gb.screen().to_greyscale());
+}
+
+#[test]
+fn lcd_off_captures_white() {
+    let gb = test_support::synthetic_game_boy(&[0x18, 0xFE], &[]); // JR -2
+    let (_, pixels) = decode(&gb.capture_png());
+    assert!(pixels.iter().all(|&grey| grey == 0xFF));
+}
@@ -0,0 +1,100 @@
+//! `Cartridge::ram_generation` counts writes that reach battery RAM, so a
+//! saver can tell whether anything changed since it last saved.
+
+use missingno_gb::{GameBoy, cartridge::Cartridge, test_support};
+
+fn cartridge(cartridge_type: u8) -> Cartridge {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x0147] = cartridge_type;
+    rom[0x0149] = 0x02; // 8 KiB
+    Cartridge::new(rom, None).unwrap()
+}
+
+#[test]
+fn idling_game_leaves_ram_clean() {
+    // Enables RAM and reads it every frame, but never writes.
+    let main = [
+        0x3E, 0x0A, // LD A,$0A
+        0xEA, 0x00, 0x00, // LD ($0000),A
+        0xFA, 0x00, 0xA0, // LD A,($A000)
+        0x18, 0xFB, // JR -5
+    ];
+    let mut rom = test_support::synthetic_rom(&main, &[]);
+    rom[0x0147] = 0x03; // MBC1+RAM+BATTERY
+    rom[0x0149] = 0x02;
+
+    let mut gb = GameBoy::new(Cartridge::new(rom, None).unwrap(), None);
+    test_support::run_frames(&mut gb, 10);
+    assert_eq!(gb.cartridge().ram_generation(), 0);
+}
+
+#[test]
+fn each_ram_write_bumps_the_generation() {
+    let mut cart = cartridge(0x03);
+    cart.write(0x0000, 0x0A);
+    assert_eq!(cart.ram_generation(), 0, "enabling RAM stores nothing");
+    cart.write(0xA000, 0x42);
+    assert_eq!(cart.ram_generation(), 1);
+    cart.write(0xA001, 0x42);
+    assert_eq!(cart.ram_generation(), 2);
+}
+
+#[test]
+fn steps_report_ram_writes() {
+    let main = [
+        0x3E, 0x0A, // LD A,$0A
+        0xEA, 0x00, 0x00, // LD ($0000),A
+        0xEA, 0x00, 0xA0, // LD ($A000),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut rom = test_support::synthetic_rom(&main, &[]);
+    rom[0x0147] = 0x03; // MBC1+RAM+BATTERY
+    rom[0x0149] = 0x02;
+
+    let mut gb = GameBoy::new(Cartridge::new(rom, None).unwrap(), None);
+    let written: Vec<bool> = (0..6).map(|_| gb.step().sram_dirty).collect();
+    assert_eq!(written, [false, false, false, false, true, false]);
+}
+
+#[test]
+fn write_with_ram_disabled_is_not_dirty() {
+    let mut cart = cartridge(0x03);
+    cart.write(0xA000, 0x42);
+    assert_eq!(cart.ram_generation(), 0);
+}
+
+#[test]
+fn ram_without_battery_is_never_dirty() {
+    let mut cart = cartridge(0x02); // MBC1+RAM
+    cart.write(0x0000, 0x0A);
+    cart.write(0xA000, 0x42);
+    assert_eq!(cart.ram_generation(), 0);
+}
+
+/// Clock `bits` (MSB first) into the MBC7 EEPROM with chip select held.
+fn eeprom_send(cart: &mut Cartridge, bits: u32, count: u32) {
+    for i in (0..count).rev() {
+        let di = ((bits >> i) as u8 & 1) << 1;
+        cart.write(0xA080, 0x80 | di);
+        cart.write(0xA080, 0xC0 | di);
+    }
+}
+
+#[test]
+fn mbc7_eeprom_write_is_dirty() {
+    let mut cart = cartridge(0x22);
+    cart.write(0x0000, 0x0A);
+    cart.write(0x4000, 0x40);
+
+    // EWEN: start bit, opcode 00, address $60.
+    eeprom_send(&mut cart, 0x460, 11);
+    cart.write(0xA080, 0x00);
+    assert_eq!(cart.ram_generation(), 0, "enabling writes stores nothing");
+
+    // WRITE: start bit, opcode 01, address 3.
+    eeprom_send(&mut cart, 0x503, 11);
+    eeprom_send(&mut cart, 0x1234, 16);
+    cart.write(0xA080, 0x00);
+    assert_ne!(cart.ram_generation(), 0);
+    assert_eq!(&cart.ram().unwrap()[6..8], &[0x12, 0x34]);
+}
@@ -0,0 +1,91 @@
+//! A breakpoint with a condition only stops execution when the condition
+//! holds as its address is reached: `A == $90`, `HL != $C000`,
+//! `[$C000] >= 3`.
+
+use missingno_gb::{
+    debugger::{
+        Debugger,
+        expression::{BreakCondition, Comparison, Expression, ParseError, Register},
+    },
+    test_support::{self, ir_address},
+};
+
+/// Counts A up forever, with HL left at $C000.
+fn debugger() -> Debugger {
+    let main = [
+        0x21, 0x00, 0xC0, // LD HL,$C000
+        0xAF, // XOR A
+        0x3C, // loop: INC A
+        0x18, 0xFD, // JR loop
+    ];
+    test_support::synthetic_debugger(&main, &[])
+}
+
+fn condition(source: &str) -> BreakCondition {
+    BreakCondition::parse(source).unwrap_or_else(|e| panic!("{source}: {e}"))
+}
+
+#[test]
+fn stops_only_when_the_condition_holds() {
+    let mut debugger = debugger();
+    debugger.set_conditional_breakpoint(0x0155, condition("A == $90"));
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(ir_address(&debugger), 0x0155);
+    assert_eq!(debugger.game_boy().cpu().a, 0x90);
+}
+
+#[test]
+fn sixteen_bit_registers_can_be_compared() {
+    let mut debugger = debugger();
+    debugger.set_conditional_breakpoint(0x0155, condition("HL != $C000"));
+    assert!(debugger.step_frame().is_some(), "never stopped");
+
+    debugger.set_conditional_breakpoint(0x0155, condition("HL == $C000"));
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(ir_address(&debugger), 0x0155);
+}
+
+#[test]
+fn unconditional_breakpoint_replaces_the_condition() {
+    let mut debugger = debugger();
+    debugger.set_conditional_breakpoint(0x0155, condition("A == $90"));
+    debugger.set_breakpoint(0x0155);
+    assert_eq!(debugger.breakpoint_condition(0x0155), None);
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(debugger.game_boy().cpu().a, 1);
+
+    debugger.clear_breakpoint(0x0155);
+    assert!(debugger.breakpoints().is_empty());
+}
+
+#[test]
+fn conditions_parse_and_print() {
+    assert_eq!(
+        condition("a==144"),
+        BreakCondition {
+            left: Expression::Register(Register::A),
+            comparison: Comparison::Equal,
+            right: Expression::Number(0x90),
+        }
+    );
+    assert_eq!(
+        condition("[HL+1] <= $FF").comparison,
+        Comparison::LessOrEqual
+    );
+    assert_eq!(condition("SP > 0xD000").comparison, Comparison::Greater);
+    assert_eq!(condition("hl != $c000").to_string(), "HL != $C000");
+    assert_eq!(condition("[hl+1]>=3").to_string(), "[HL+$01] >= $03");
+}
+
+#[test]
+fn malformed_conditions_are_rejected() {
+    assert_eq!(
+        BreakCondition::parse("A"),
+        Err(ParseError::MissingComparison)
+    );
+    assert_eq!(BreakCondition::parse("== 3"), Err(ParseError::Empty));
+    assert_eq!(
+        BreakCondition::parse("Q == 3"),
+        Err(ParseError::UnknownRegister("Q".to_string()))
+    );
+}
@@ -0,0 +1,80 @@
+//! `cycles_elapsed` counts CPU M-cycles since reset, and `schedule_at`
+//! runs a one-shot callback at the first instruction boundary at or after a
+//! given count.
+
+use std::{cell::Cell, rc::Rc};
+
+use missingno_gb::{GameBoy, cartridge::Cartridge, test_support};
+
+/// Loops `CALL` (6 M-cycles) into a `RET` (4), so most boundaries sit a few
+/// cycles apart.
+fn game_boy() -> GameBoy {
+    let main = [
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x18, 0xFB, // JR -5
+    ];
+    let mut rom = test_support::synthetic_rom(&main, &[]);
+    rom[0x0200] = 0xC9; // RET
+    GameBoy::new(Cartridge::new(rom, None).unwrap(), None)
+}
+
+#[test]
+fn counts_instruction_mcycles() {
+    let mut gb = game_boy();
+    let start = gb.cycles_elapsed();
+    gb.step(); // NOP
+    assert_eq!(gb.cycles_elapsed() - start, 1);
+    gb.step(); // JP $0150
+    assert_eq!(gb.cycles_elapsed() - start, 5);
+    gb.step(); // CALL $0200
+    assert_eq!(gb.cycles_elapsed() - start, 11);
+}
+
+#[test]
+fn callback_fires_within_one_instruction_of_target() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 1);
+
+    let target = gb.cycles_elapsed() + 1000;
+    let fired_at = Rc::new(Cell::new(None));
+    let recorded = fired_at.clone();
+    gb.schedule_at(target, move |gb| recorded.set(Some(gb.cycles_elapsed())));
+    test_support::run_frames(&mut gb, 1);
+
+    let fired_at = fired_at.get().expect("callback fired");
+    assert!(
+        (target..target + 6).contains(&fired_at),
+        "target {target}, fired at {fired_at}"
+    );
+}
+
+#[test]
+fn callback_fires_once() {
+    let mut gb = game_boy();
+    let count = Rc::new(Cell::new(0));
+    let counter = count.clone();
+    gb.schedule_at(100, move |_| counter.set(counter.get() + 1));
+    test_support::run_frames(&mut gb, 2);
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn reset_restarts_count_and_drops_callbacks() {
+    let mut gb = game_boy();
+    let fired = Rc::new(Cell::new(false));
+    let flag = fired.clone();
+    gb.schedule_at(1_000_000, move |_| flag.set(true));
+    test_support::run_frames(&mut gb, 1);
+
+    gb.reset();
+    assert_eq!(gb.cycles_elapsed(), 0);
+    test_support::run_frames(&mut gb, 60);
+    assert!(!fired.get());
+}
+
+#[test]
+fn clone_keeps_count() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(gb.clone().cycles_elapsed(), gb.cycles_elapsed());
+}
@@ -0,0 +1,80 @@
+//! The debugger can treat `LD B,B` as a breakpoint and log BGB-style
+//! `LD D,D` messages. Both are off until enabled.
+
+use missingno_gb::{
+    debugger::{Debugger, debug_opcodes::DebugOpcodes},
+    test_support::{self, ir_address},
+};
+
+fn debugger(main: &[u8]) -> Debugger {
+    test_support::synthetic_debugger(main, &[])
+}
+
+/// Loops over an `LD B,B` at $0151.
+const BREAKPOINT_LOOP: [u8; 4] = [
+    0x00, // NOP
+    0x40, // LD B,B
+    0x18, 0xFD, // JR -3
+];
+
+#[test]
+fn ld_b_b_halts_at_its_address() {
+    let mut debugger = debugger(&BREAKPOINT_LOOP);
+    debugger.set_debug_opcodes(DebugOpcodes {
+        breakpoint: true,
+        ..DebugOpcodes::default()
+    });
+
+    assert!(debugger.step_frame().is_none());
+    assert!(debugger.debug_breakpoint_hit());
+    assert_eq!(ir_address(&debugger), 0x0151);
+
+    // Resuming runs the LD B,B and halts on it again next time round.
+    assert!(debugger.step_frame().is_none());
+    assert!(debugger.debug_breakpoint_hit());
+    assert_eq!(ir_address(&debugger), 0x0151);
+}
+
+#[test]
+fn ld_b_b_is_ignored_when_disabled() {
+    let mut debugger = debugger(&BREAKPOINT_LOOP);
+    assert!(debugger.step_frame().is_some());
+    assert!(!debugger.debug_breakpoint_hit());
+}
+
+/// Logs "hello" once, then spins.
+const MESSAGE: [u8; 15] = [
+    0x52, // LD D,D
+    0x18, 0x09, // JR +9
+    0x64, 0x64, 0x00, 0x00, // BGB message signature
+    b'h', b'e', b'l', b'l', b'o', //
+    0x00, 0x18, 0xFE, // NOP; JR -2
+];
+
+#[test]
+fn ld_d_d_logs_its_message() {
+    let mut debugger = debugger(&MESSAGE);
+    debugger.set_debug_opcodes(DebugOpcodes {
+        messages: true,
+        ..DebugOpcodes::default()
+    });
+
+    assert!(debugger.step_frame().is_some(), "messages don't halt");
+    assert_eq!(debugger.take_debug_messages(), ["hello"]);
+    debugger.step_frame();
+    assert!(debugger.take_debug_messages().is_empty());
+}
+
+#[test]
+fn ld_d_d_without_a_message_logs_nothing() {
+    let mut debugger = debugger(&[
+        0x52, // LD D,D
+        0x18, 0xFE, // JR -2
+    ]);
+    debugger.set_debug_opcodes(DebugOpcodes {
+        messages: true,
+        ..DebugOpcodes::default()
+    });
+    debugger.step_frame();
+    assert!(debugger.take_debug_messages().is_empty());
+}
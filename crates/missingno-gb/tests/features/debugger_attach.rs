@@ -0,0 +1,35 @@
+//! Attaching the debugger to a running console mid-frame takes it over as
+//! is, so stepping carries on from the exact instruction the emulator
+//! stopped at rather than from a frame boundary.
+
+use missingno_gb::{GameBoy, debugger::Debugger, test_support};
+
+/// Counts in B forever.
+fn game_boy() -> GameBoy {
+    test_support::synthetic_game_boy(&[0x04, 0x18, 0xFD], &[]) // INC B; JR -3
+}
+
+fn position(gb: &GameBoy) -> (u16, u8, u8, u8) {
+    let ppu = gb.ppu();
+    (gb.cpu().ir_address, gb.cpu().b, ppu.ly_hardware(), ppu.lx())
+}
+
+#[test]
+fn attaching_mid_frame_keeps_the_cpu_where_it_was() {
+    let mut gb = game_boy();
+    let result = gb.run_cycles(5000);
+    assert!(!result.new_screen, "stopped mid-frame");
+    let paused = position(&gb);
+    assert_ne!(paused.2, 0, "LY has moved past the frame start");
+
+    let mut reference = gb.clone();
+    let mut debugger = Debugger::new(gb);
+    assert_eq!(position(debugger.game_boy()), paused);
+    assert_eq!(debugger.game_boy().cpu().pc, reference.cpu().pc);
+
+    for _ in 0..25 {
+        debugger.step();
+        reference.step();
+        assert_eq!(position(debugger.game_boy()), position(&reference));
+    }
+}
@@ -0,0 +1,53 @@
+//! Reloading a rebuilt ROM under the debugger keeps its breakpoints.
+
+use missingno_gb::{
+    GameBoy,
+    cartridge::Cartridge,
+    debugger::{Debugger, WatchCondition},
+    test_support::{self, ir_address},
+};
+
+/// `LD A,value` at $0150, then spin.
+fn rom(value: u8) -> Vec<u8> {
+    let rom = test_support::synthetic_rom(&[0x3E, value, 0x18, 0xFE], &[]); // LD A,value; JR -2
+    rom
+}
+
+fn game_boy(value: u8) -> GameBoy {
+    GameBoy::new(Cartridge::new(rom(value), None).unwrap(), None)
+}
+
+#[test]
+fn reloading_keeps_breakpoints_and_watchpoints() {
+    let mut debugger = Debugger::new(game_boy(0x11));
+    debugger.set_breakpoint(0x0152);
+    debugger.set_scanline_breakpoint(100, None);
+    debugger.add_watchpoint(WatchCondition::BusWrite { address: 0xC000 });
+    debugger.step_frame();
+
+    let debugger = debugger.with_game_boy(game_boy(0x22));
+
+    assert_eq!(
+        debugger.breakpoints().iter().copied().collect::<Vec<_>>(),
+        [0x0152]
+    );
+    assert_eq!(debugger.scanline_breakpoints().len(), 1);
+    assert_eq!(
+        debugger.watchpoints(),
+        [WatchCondition::BusWrite { address: 0xC000 }]
+    );
+    assert_eq!(debugger.tcycle_count(), 0);
+}
+
+#[test]
+fn reloaded_rom_stops_at_the_same_breakpoint() {
+    let mut debugger = Debugger::new(game_boy(0x11));
+    debugger.set_breakpoint(0x0152);
+    debugger.step_frame();
+    assert_eq!(debugger.game_boy().cpu().a, 0x11);
+
+    let mut debugger = debugger.with_game_boy(game_boy(0x22));
+    debugger.step_frame();
+    assert_eq!(ir_address(&debugger), 0x0152);
+    assert_eq!(debugger.game_boy().cpu().a, 0x22);
+}
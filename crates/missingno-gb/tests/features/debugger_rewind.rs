@@ -0,0 +1,127 @@
+//! The debugger snapshots the console every few frames, and `rewind` goes
+//! back to the newest snapshot behind it, one snapshot further each time.
+
+use missingno_gb::{
+    debugger::{Debugger, rewind},
+    test_support,
+};
+
+/// Counts up in $C000 forever.
+fn debugger(interval: u32) -> Debugger {
+    let main = [
+        0x21, 0x00, 0xC0, // LD HL,$C000
+        0x34, // loop: INC (HL)
+        0x18, 0xFD, // JR loop
+    ];
+    let mut debugger = test_support::synthetic_debugger(&main, &[]);
+    debugger.rewind_buffer_mut().set_interval(interval);
+    debugger
+}
+
+/// Enough to tell two points in time apart.
+fn moment(debugger: &Debugger) -> (u64, u8, u16) {
+    let gb = debugger.game_boy();
+    (
+        debugger.tcycle_count(),
+        gb.peek(0xC000),
+        gb.cpu().ir_address,
+    )
+}
+
+/// Run `frames` frames, returning the moment each ended.
+fn run_frames(debugger: &mut Debugger, frames: usize) -> Vec<(u64, u8, u16)> {
+    (0..frames)
+        .map(|_| {
+            assert!(debugger.step_frame().is_some());
+            moment(debugger)
+        })
+        .collect()
+}
+
+#[test]
+fn nothing_to_rewind_to_at_first() {
+    let mut debugger = debugger(1);
+    assert!(!debugger.rewind());
+    debugger.step();
+    assert!(!debugger.rewind());
+}
+
+#[test]
+fn steps_back_one_snapshot_at_a_time() {
+    let mut debugger = debugger(1);
+    let frames = run_frames(&mut debugger, 3);
+    for _ in 0..10 {
+        debugger.step();
+    }
+
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), frames[2]);
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), frames[1]);
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), frames[0]);
+    assert!(!debugger.rewind());
+    assert_eq!(moment(&debugger), frames[0]);
+}
+
+#[test]
+fn a_snapshot_nothing_has_run_since_is_skipped() {
+    let mut debugger = debugger(1);
+    let frames = run_frames(&mut debugger, 2);
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), frames[0]);
+}
+
+#[test]
+fn snapshots_are_taken_every_interval_frames() {
+    let mut debugger = debugger(3);
+    let frames = run_frames(&mut debugger, 7);
+    assert_eq!(debugger.rewind_buffer().len(), 2);
+
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), frames[5]);
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), frames[2]);
+    assert!(!debugger.rewind());
+}
+
+#[test]
+fn running_on_after_a_rewind_takes_snapshots_afresh() {
+    let mut debugger = debugger(2);
+    let first = run_frames(&mut debugger, 4);
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), first[1]);
+
+    let again = run_frames(&mut debugger, 2);
+    assert_eq!(again[1], first[3], "the same frames, replayed");
+    assert!(debugger.rewind());
+    assert_eq!(moment(&debugger), first[1]);
+}
+
+#[test]
+fn interval_zero_takes_no_snapshots() {
+    let mut debugger = debugger(0);
+    run_frames(&mut debugger, 3);
+    assert!(debugger.rewind_buffer().is_empty());
+    assert!(!debugger.rewind());
+}
+
+#[test]
+fn reset_drops_the_snapshots() {
+    let mut debugger = debugger(1);
+    run_frames(&mut debugger, 2);
+    debugger.reset();
+    assert!(debugger.rewind_buffer().is_empty());
+}
+
+#[test]
+fn the_budget_counts_what_snapshots_hold_on_the_heap() {
+    // Each snapshot carries the profiler's 512 KiB of counts, so only about
+    // 120 fit in the budget.
+    let mut debugger = debugger(1);
+    debugger.game_boy_mut().set_profiling(true);
+    run_frames(&mut debugger, 140);
+    let kept = debugger.rewind_buffer().len();
+    let per_snapshot = rewind::MEMORY_BUDGET / kept;
+    assert!(per_snapshot > 512 * 1024, "{kept} snapshots kept");
+}
@@ -0,0 +1,121 @@
+//! `Debugger::set_pc` moves execution to another address between
+//! instructions; `run_to` and `step_over` run until a one-shot breakpoint
+//! that doesn't disturb the user's own.
+
+use missingno_gb::{
+    debugger::Debugger,
+    test_support::{self, ir_address, step_to},
+};
+
+/// Spins at $0150 forever. $0200 stores 1 to $C000 and returns; $0210
+/// stores 2 to $C001 and spins.
+fn debugger() -> Debugger {
+    let main = [
+        0x31, 0xFE, 0xDF, // LD SP,$DFFE
+        0x18, 0xFE, // JR -2
+    ];
+    let store_one = [
+        0x3E, 0x01, // LD A,1
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0xC9, // RET
+    ];
+    let store_two = [
+        0x3E, 0x02, // LD A,2
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut debugger =
+        test_support::synthetic_debugger(&main, &[(0x0200, &store_one), (0x0210, &store_two)]);
+    step_to(&mut debugger, 0x0153);
+    debugger
+}
+
+#[test]
+fn single_steps_from_the_new_pc() {
+    let mut debugger = debugger();
+    assert!(debugger.set_pc(0x0210));
+    assert_eq!(ir_address(&debugger), 0x0210);
+    assert_eq!(debugger.game_boy().cpu().pc, 0x0210);
+
+    debugger.step(); // LD A,2
+    assert_eq!(ir_address(&debugger), 0x0212);
+    assert_eq!(debugger.game_boy().cpu().a, 2);
+    debugger.step(); // LD ($C001),A
+    assert_eq!(ir_address(&debugger), 0x0215);
+    assert_eq!(debugger.game_boy().peek(0xC001), 2);
+}
+
+#[test]
+fn routine_returns_to_where_it_was_called_from() {
+    // A `CALL` placed by hand: push the spin address, then jump.
+    let mut debugger = debugger();
+    let sp = debugger.game_boy().cpu().stack_pointer - 2;
+    debugger.game_boy_mut().cpu_mut().stack_pointer = sp;
+    assert!(debugger.game_boy_mut().poke_raw(sp, 0x53));
+    assert!(debugger.game_boy_mut().poke_raw(sp + 1, 0x01));
+    assert!(debugger.set_pc(0x0200));
+
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0153);
+    assert_eq!(debugger.game_boy().peek(0xC000), 1);
+}
+
+#[test]
+fn refused_mid_instruction() {
+    let mut debugger = debugger();
+    debugger.step_tcycle();
+    assert!(!debugger.set_pc(0x0210));
+    debugger.step();
+    assert_eq!(ir_address(&debugger), 0x0153, "still spinning");
+}
+
+#[test]
+fn run_to_stops_at_the_address_once() {
+    let mut debugger = debugger();
+    assert!(debugger.set_pc(0x0210));
+    debugger.run_to(0x0215);
+    assert_eq!(ir_address(&debugger), 0x0215);
+    assert_eq!(debugger.temporary_breakpoint(), None);
+    assert!(debugger.breakpoints().is_empty());
+}
+
+#[test]
+fn run_to_stops_early_at_a_breakpoint() {
+    let mut debugger = debugger();
+    debugger.set_breakpoint(0x0212);
+    assert!(debugger.set_pc(0x0210));
+    debugger.run_to(0x0215);
+    assert_eq!(ir_address(&debugger), 0x0212);
+    assert_eq!(debugger.temporary_breakpoint(), None, "abandoned");
+    assert_eq!(debugger.breakpoints().len(), 1);
+}
+
+#[test]
+fn step_over_runs_a_whole_call() {
+    let main = [
+        0x31, 0xFE, 0xDF, // LD SP,$DFFE
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x18, 0xFE, // JR -2
+    ];
+    let store = [
+        0x3E, 0x01, // LD A,1
+        0xEA, 0x00, 0xC0, // LD ($C000),A
+        0xC9, // RET
+    ];
+    let mut debugger = test_support::synthetic_debugger(&main, &[(0x0200, &store)]);
+    step_to(&mut debugger, 0x0153);
+
+    debugger.step_over();
+    assert_eq!(ir_address(&debugger), 0x0156);
+    assert_eq!(debugger.game_boy().peek(0xC000), 1);
+}
+
+#[test]
+fn temporary_breakpoint_fires_from_step_frame() {
+    let mut debugger = debugger();
+    assert!(debugger.set_pc(0x0210));
+    debugger.set_temporary_breakpoint(0x0215);
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(ir_address(&debugger), 0x0215);
+    assert_eq!(debugger.temporary_breakpoint(), None);
+}
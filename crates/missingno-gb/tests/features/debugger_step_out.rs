@@ -0,0 +1,140 @@
+//! `Debugger::step_out` runs until the current subroutine returns to its
+//! caller, ignoring returns from deeper calls and from interrupt handlers.
+
+use missingno_gb::{
+    debugger::Debugger,
+    test_support::{ir_address, step_to, synthetic_debugger},
+};
+
+fn stack_pointer(debugger: &Debugger) -> u16 {
+    debugger.game_boy().cpu().stack_pointer
+}
+
+/// Main calls $0200, which calls $0210.
+fn nested() -> Debugger {
+    let main = [
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x18, 0xFE, // JR -2
+    ];
+    let outer = [
+        0xCD, 0x10, 0x02, // CALL $0210
+        0x00, // NOP
+        0xC9, // RET
+    ];
+    let inner = [
+        0x00, // NOP
+        0xC9, // RET
+    ];
+    synthetic_debugger(&main, &[(0x0200, &outer), (0x0210, &inner)])
+}
+
+#[test]
+fn steps_out_of_the_innermost_call() {
+    let mut debugger = nested();
+    step_to(&mut debugger, 0x0210);
+    let entry = stack_pointer(&debugger);
+
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0203);
+    assert_eq!(stack_pointer(&debugger), entry + 2);
+
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0153);
+    assert_eq!(stack_pointer(&debugger), entry + 4);
+}
+
+#[test]
+fn runs_over_returns_from_deeper_calls() {
+    let mut debugger = nested();
+    step_to(&mut debugger, 0x0200);
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0153);
+}
+
+#[test]
+fn recursion_returns_one_level() {
+    let main = [
+        0x3E, 0x04, // LD A,4
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x18, 0xFE, // JR -2
+    ];
+    let countdown = [
+        0x3D, // DEC A
+        0x28, 0x03, // JR Z,+3
+        0xCD, 0x00, 0x02, // CALL $0200
+        0xC9, // RET
+    ];
+    let mut debugger = synthetic_debugger(&main, &[(0x0200, &countdown)]);
+
+    // Third level down.
+    for _ in 0..3 {
+        debugger.step();
+        step_to(&mut debugger, 0x0200);
+    }
+    let entry = stack_pointer(&debugger);
+
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0206, "back in the second level");
+    assert_eq!(stack_pointer(&debugger), entry + 2);
+}
+
+#[test]
+fn interrupt_handlers_returning_mid_call_are_ignored() {
+    let main = [
+        0x3E, 0x01, // LD A,$01
+        0xE0, 0xFF, // LDH ($FF),A   IE = VBlank
+        0xFB, // EI
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x18, 0xFE, // JR -2
+    ];
+    // About three frames of busy work.
+    let busy = [
+        0x01, 0x00, 0x20, // LD BC,$2000
+        0x0B, // DEC BC
+        0x78, // LD A,B
+        0xB1, // OR C
+        0x20, 0xFB, // JR NZ,-5
+        0xC9, // RET
+    ];
+    let vblank = [
+        0xF5, // PUSH AF
+        0xF0, 0x80, // LDH A,($80)
+        0x3C, // INC A
+        0xE0, 0x80, // LDH ($80),A
+        0xF1, // POP AF
+        0xD9, // RETI
+    ];
+    let mut debugger = synthetic_debugger(&main, &[(0x0200, &busy), (0x0040, &vblank)]);
+    debugger.game_boy_mut().poke_raw(0xFF80, 0);
+    step_to(&mut debugger, 0x0200);
+
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0158);
+    assert!(debugger.game_boy().peek(0xFF80) > 0, "the handler ran");
+}
+
+#[test]
+fn stops_at_a_breakpoint() {
+    let mut debugger = nested();
+    step_to(&mut debugger, 0x0200);
+    debugger.set_breakpoint(0x0211);
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0211);
+}
+
+#[test]
+fn untaken_conditional_return_keeps_going() {
+    let main = [
+        0xCD, 0x00, 0x02, // CALL $0200
+        0x18, 0xFE, // JR -2
+    ];
+    let routine = [
+        0xAF, // XOR A   sets Z
+        0xC0, // RET NZ
+        0xC8, // RET Z
+    ];
+    let mut debugger = synthetic_debugger(&main, &[(0x0200, &routine)]);
+    step_to(&mut debugger, 0x0200);
+    debugger.step_out();
+    assert_eq!(ir_address(&debugger), 0x0153);
+}
@@ -0,0 +1,91 @@
+//! The debugger names addresses from a `.sym` file, looking switchable ROM
+//! up in whichever bank is mapped in.
+
+use missingno_gb::{
+    GameBoy,
+    cartridge::Cartridge,
+    debugger::{
+        Debugger,
+        symbols::{SymbolError, Symbols},
+    },
+    test_support::{self, step_to},
+};
+
+const SYMBOLS: &str = "\
+; File generated by rgblink
+
+00:0150 Main
+00:0150 Main.alias ; a second name for the same address
+01:4000 LoadLevel
+02:4000 DrawSprites
+00:c000 wPlayerX
+";
+
+/// An MBC5 cartridge with four ROM banks that switches to bank 2, then
+/// spins.
+fn debugger() -> Debugger {
+    let main = [
+        0x3E, 0x02, // LD A,2
+        0xEA, 0x00, 0x20, // LD ($2000),A: ROM bank 2
+        0x18, 0xFE, // JR -2
+    ];
+    let mut rom = test_support::synthetic_rom(&main, &[]);
+    rom.resize(0x10000, 0);
+    rom[0x0147] = 0x19; // MBC5
+    rom[0x0148] = 0x01; // 64 KiB
+    let mut debugger = Debugger::new(GameBoy::new(Cartridge::new(rom, None).unwrap(), None));
+    debugger.set_symbols(Symbols::parse(SYMBOLS).unwrap());
+    debugger
+}
+
+#[test]
+fn parses_banked_labels() {
+    let symbols = Symbols::parse(SYMBOLS).unwrap();
+    assert_eq!(symbols.len(), 4);
+    assert_eq!(symbols.get(Some(0), 0x0150), Some("Main"));
+    assert_eq!(symbols.get(Some(1), 0x4000), Some("LoadLevel"));
+    assert_eq!(symbols.get(Some(2), 0x4000), Some("DrawSprites"));
+    assert_eq!(symbols.get(Some(3), 0x4000), None);
+    assert_eq!(symbols.get(None, 0x4000), Some("LoadLevel"));
+    assert_eq!(symbols.get(None, 0xC000), Some("wPlayerX"));
+}
+
+#[test]
+fn a_malformed_line_is_reported_by_number() {
+    let error = Symbols::parse("00:0150 Main\n\nMain\n").unwrap_err();
+    assert!(matches!(
+        error,
+        SymbolError::Malformed { line: 3, ref text } if text == "Main"
+    ));
+
+    let error = Symbols::parse("zz:0150 Main\n").unwrap_err();
+    assert!(matches!(error, SymbolError::Malformed { line: 1, .. }));
+}
+
+#[test]
+fn a_file_that_fails_to_load_keeps_the_old_symbols() {
+    let mut debugger = debugger();
+    let path = std::env::temp_dir().join("missingno_debugger_symbols_malformed.sym");
+    std::fs::write(&path, "00:0150\n").unwrap();
+    let result = debugger.load_symbols(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        result,
+        Err(SymbolError::Malformed { line: 1, .. })
+    ));
+    assert_eq!(debugger.symbol(0x0150), Some("Main"));
+}
+
+#[test]
+fn switchable_rom_follows_the_mapped_bank() {
+    let mut debugger = debugger();
+    assert_eq!(debugger.symbol(0x0150), Some("Main"));
+    assert_eq!(debugger.symbol(0x4000), Some("LoadLevel"));
+    assert_eq!(debugger.symbol(0xC000), Some("wPlayerX"));
+
+    step_to(&mut debugger, 0x0155);
+    assert_eq!(debugger.game_boy().cartridge().rom_bank(), Some(2));
+    assert_eq!(debugger.symbol(0x4000), Some("DrawSprites"));
+    assert_eq!(debugger.symbol(0x0150), Some("Main"));
+}
@@ -0,0 +1,61 @@
+//! Memory watchpoints halt `step_frame` on the instruction whose bus
+//! access matches, and report which watchpoint fired.
+
+use missingno_gb::{
+    debugger::{Debugger, WatchCondition},
+    test_support::{self, ir_address},
+};
+
+/// Reads $C000, writes it to $C001, then spins.
+fn debugger() -> Debugger {
+    let main = [
+        0xFA, 0x00, 0xC0, // LD A,($C000)
+        0x00, // NOP
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_debugger(&main, &[])
+}
+
+#[test]
+fn read_watchpoint_stops_after_the_read() {
+    let mut debugger = debugger();
+    let condition = WatchCondition::BusRead { address: 0xC000 };
+    debugger.add_watchpoint(condition.clone());
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(debugger.last_watchpoint_hit(), Some(&condition));
+    assert!((0x0150..0x0154).contains(&ir_address(&debugger)));
+}
+
+#[test]
+fn write_watchpoint_ignores_reads() {
+    let mut debugger = debugger();
+    debugger.add_watchpoint(WatchCondition::BusWrite { address: 0xC000 });
+    assert!(debugger.step_frame().is_some(), "ran the whole frame");
+    assert_eq!(debugger.last_watchpoint_hit(), None);
+}
+
+#[test]
+fn access_watchpoint_fires_on_either() {
+    let mut debugger = debugger();
+    let read = WatchCondition::BusAccess { address: 0xC000 };
+    let write = WatchCondition::BusAccess { address: 0xC001 };
+    debugger.add_watchpoint(read.clone());
+    debugger.add_watchpoint(write.clone());
+
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(debugger.last_watchpoint_hit(), Some(&read));
+    assert!(debugger.step_frame().is_none());
+    assert_eq!(debugger.last_watchpoint_hit(), Some(&write));
+    assert!((0x0154..0x0159).contains(&ir_address(&debugger)));
+}
+
+#[test]
+fn removed_watchpoints_no_longer_fire() {
+    let mut debugger = debugger();
+    let condition = WatchCondition::BusAccess { address: 0xC001 };
+    debugger.add_watchpoint(condition.clone());
+    debugger.remove_watchpoint(&condition);
+    assert!(debugger.watchpoints().is_empty());
+    assert!(debugger.step_frame().is_some());
+}
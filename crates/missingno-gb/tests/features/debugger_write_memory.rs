@@ -0,0 +1,45 @@
+//! `Debugger::write_memory` edits RAM the way a memory editor does, and
+//! refuses addresses where a store would reach the mapper or an I/O
+//! register.
+
+use missingno_gb::{debugger::Debugger, test_support};
+
+/// Copies $C000 into $C001 forever.
+fn debugger() -> Debugger {
+    let main = [
+        0xFA, 0x00, 0xC0, // LD A,($C000)
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x18, 0xF8, // JR -8
+    ];
+    test_support::synthetic_debugger(&main, &[])
+}
+
+#[test]
+fn written_bytes_are_seen_by_the_program() {
+    let mut debugger = debugger();
+    assert!(debugger.write_memory(0xC000, 0x42));
+    assert_eq!(debugger.game_boy().peek(0xC000), 0x42);
+    for _ in 0..8 {
+        debugger.step();
+    }
+    assert_eq!(debugger.game_boy().peek(0xC001), 0x42);
+}
+
+#[test]
+fn ram_everywhere_can_be_written() {
+    let mut debugger = debugger();
+    for address in [0x8000, 0x9FFF, 0xC000, 0xDFFF, 0xFE00, 0xFF80, 0xFFFE] {
+        assert!(debugger.write_memory(address, 0x5A), "{address:04X}");
+        assert_eq!(debugger.game_boy().peek(address), 0x5A, "{address:04X}");
+    }
+}
+
+#[test]
+fn rom_and_registers_are_refused() {
+    let mut debugger = debugger();
+    for address in [0x0150, 0x2000, 0xA000, 0xFF40, 0xFFFF] {
+        let before = debugger.game_boy().peek(address);
+        assert!(!debugger.write_memory(address, !before), "{address:04X}");
+        assert_eq!(debugger.game_boy().peek(address), before, "{address:04X}");
+    }
+}
@@ -0,0 +1,92 @@
+//! The displayed frame as packed RGBA for front-ends to upload: one pixel
+//! per four bytes, top scanline first, each shade from the given palette.
+//! `to_rgb8` packs the same colours three bytes to a pixel for encoders.
+
+use missingno_gb::{
+    GameBoy,
+    ppu::{
+        screen::{NUM_SCANLINES, PIXELS_PER_LINE, RGB_LEN, RGBA_LEN, Screen},
+        types::palette::{Palette, PaletteChoice},
+    },
+    test_support,
+    test_support::run_frames,
+};
+
+/// Sets BGP to map colour N to shade N and turns the LCD on, then spins,
+/// with tile 0 drawn as the four shades repeating across each row.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x3E, 0xE4, // LD A,$E4
+        0xE0, 0x47, // LDH ($47),A
+        0x3E, 0x91, // LD A,$91
+        0xE0, 0x40, // LDH ($40),A
+        0x18, 0xFE, // JR -2
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    for row in 0..8 {
+        assert!(gb.poke_raw(0x8000 + row * 2, 0x55));
+        assert!(gb.poke_raw(0x8000 + row * 2 + 1, 0x33));
+    }
+    run_frames(&mut gb, 3);
+    gb
+}
+
+#[test]
+fn packs_each_pixel_from_the_palette() {
+    let gb = game_boy();
+    let mut rgba = [0; RGBA_LEN];
+    gb.framebuffer_rgba(&Palette::CLASSIC, &mut rgba);
+
+    assert_eq!(
+        rgba[..16],
+        [
+            0xFF, 0xFF, 0xFF, 0xFF, //
+            0xAA, 0xAA, 0xAA, 0xFF, //
+            0x55, 0x55, 0x55, 0xFF, //
+            0x00, 0x00, 0x00, 0xFF,
+        ]
+    );
+
+    let screen = gb.screen();
+    for y in 0..NUM_SCANLINES {
+        for x in 0..PIXELS_PER_LINE {
+            let color = Palette::CLASSIC.color(screen.pixel(x, y));
+            let offset = (y as usize * PIXELS_PER_LINE as usize + x as usize) * 4;
+            assert_eq!(
+                rgba[offset..offset + 4],
+                [color.r, color.g, color.b, 0xFF],
+                "({x}, {y})"
+            );
+        }
+    }
+}
+
+#[test]
+fn palette_picks_the_colours() {
+    let gb = game_boy();
+    let rgba = gb.screen().to_rgba(&Palette::POCKET);
+    assert_eq!(rgba.len(), RGBA_LEN);
+    assert_eq!(rgba[..4], [0xC4, 0xCF, 0xA1, 0xFF]);
+    assert_eq!(rgba[12..16], [0x1B, 0x1B, 0x1B, 0xFF]);
+}
+
+#[test]
+fn rgb8_matches_rgba_without_alpha() {
+    let gb = game_boy();
+    let screen = gb.screen();
+    let rgb = screen.to_rgb8(&PaletteChoice::Green);
+    let rgba = screen.to_rgba(&Palette::MONOCHROME_GREEN);
+    assert_eq!(rgb.len(), RGB_LEN);
+    assert!(
+        rgb.chunks_exact(3)
+            .zip(rgba.chunks_exact(4))
+            .all(|(rgb, rgba)| rgb == &rgba[..3])
+    );
+}
+
+#[test]
+fn blank_screen_is_colour_0() {
+    let rgb = Screen::default().to_rgb8(&PaletteChoice::Classic);
+    assert_eq!(rgb.len(), RGB_LEN);
+    assert!(rgb.iter().all(|&byte| byte == 0xFF));
+}
@@ -0,0 +1,39 @@
+//! The debugger and the emulator's own features, driven through
+//! hand-assembled ROMs built with `test_support::synthetic_rom`. Hardware
+//! behaviour is checked under `tests/accuracy/`.
+
+mod access_heatmap;
+mod audio_channel_mute;
+mod audio_output_rate;
+mod audio_state;
+mod battery_save;
+mod boot_logo;
+mod capture_png;
+mod cartridge_ram_generation;
+mod conditional_breakpoints;
+mod cycle_scheduler;
+mod debug_opcodes;
+mod debugger_attach;
+mod debugger_reload;
+mod debugger_rewind;
+mod debugger_set_pc;
+mod debugger_step_out;
+mod debugger_symbols;
+mod debugger_watchpoints;
+mod debugger_write_memory;
+mod framebuffer_rgba;
+mod power_on_memory;
+mod printer;
+mod profiler;
+mod rom_loading;
+mod rtc_save;
+mod run_cycles;
+mod run_frames;
+mod save_state;
+mod scanline_breakpoints;
+mod serial_callback;
+mod soft_reset;
+mod tile_changes;
+mod trace_log;
+mod warnings;
+mod watch_expressions;
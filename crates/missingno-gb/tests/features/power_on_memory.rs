@@ -0,0 +1,89 @@
+//! WRAM's, VRAM's and OAM's power-on contents: zeroes by default, the DMG
+//! pattern or garbage on request.
+
+use missingno_gb::{BootRom, GameBoy, PowerOnMemory, cartridge::Cartridge, test_support};
+
+fn game_boy() -> GameBoy {
+    test_support::synthetic_game_boy(&[0x18, 0xFE], &[]) // JR -2
+}
+
+/// A DMG about to run the [`BootRom::Logo`] stand-in, which still sees
+/// VRAM's power-on contents.
+fn booting(power_on: PowerOnMemory) -> GameBoy {
+    let rom = test_support::synthetic_rom(&[0x18, 0xFE], &[]);
+    let mut gb = GameBoy::new(Cartridge::new(rom, None).unwrap(), Some(BootRom::Logo));
+    gb.set_power_on_memory(power_on);
+    gb
+}
+
+#[test]
+fn memory_powers_on_zeroed_by_default() {
+    let gb = game_boy();
+    assert!(gb.work_ram().iter().all(|&b| b == 0));
+    assert!((0xFE00..0xFEA0).all(|address| gb.peek(address) == 0));
+
+    let gb = GameBoy::new(
+        Cartridge::new(test_support::synthetic_rom(&[], &[]), None).unwrap(),
+        Some(BootRom::Logo),
+    );
+    assert!((0x8000..0xA000).all(|address| gb.peek(address) == 0));
+}
+
+#[test]
+fn dmg_power_on_memory_has_the_dumped_wram_pattern() {
+    let mut gb = game_boy();
+    gb.set_power_on_memory(PowerOnMemory::Dmg);
+    // One of the dump's overrides on top of the stripe fill.
+    assert_eq!(gb.peek(0xC008), 0x4D);
+    assert!((0xFE00..0xFEA0).all(|address| gb.peek(address) == 0));
+}
+
+#[test]
+fn uninitialised_oam_powers_on_holding_garbage() {
+    let uninitialised = || {
+        let mut gb = game_boy();
+        gb.set_power_on_memory(PowerOnMemory::Uninitialised);
+        gb
+    };
+    let gb = uninitialised();
+    assert!((0xFE00..0xFEA0).any(|address| gb.peek(address) != 0));
+    assert_eq!(
+        gb.peek_range(0xFE00, 0xA0),
+        uninitialised().peek_range(0xFE00, 0xA0),
+        "the same on every power-on"
+    );
+    assert_eq!(gb.peek(0xC008), 0x4D, "WRAM keeps the DMG pattern");
+}
+
+#[test]
+fn boot_rom_finds_garbage_vram_unless_zeroed() {
+    for power_on in [PowerOnMemory::Dmg, PowerOnMemory::Uninitialised] {
+        let gb = booting(power_on);
+        assert!(
+            (0x8000..0xA000).any(|address| gb.peek(address) != 0),
+            "{power_on:?}"
+        );
+        assert_eq!(
+            gb.peek_range(0x8000, 0x2000),
+            booting(power_on).peek_range(0x8000, 0x2000),
+            "{power_on:?} is the same on every power-on"
+        );
+    }
+    let gb = booting(PowerOnMemory::Zeroed);
+    assert!((0x8000..0xA000).all(|address| gb.peek(address) == 0));
+}
+
+#[test]
+fn zeroed_power_on_memory_applies_straight_away() {
+    let mut gb = game_boy();
+    gb.set_power_on_memory(PowerOnMemory::Dmg);
+    gb.set_power_on_memory(PowerOnMemory::Zeroed);
+    assert!(gb.work_ram().iter().all(|&b| b == 0));
+    assert!((0xFE00..0xFEA0).all(|address| gb.peek(address) == 0));
+
+    gb.reset();
+    assert!(gb.work_ram().iter().all(|&b| b == 0), "kept across resets");
+
+    gb.set_power_on_memory(PowerOnMemory::Dmg);
+    assert_eq!(gb.peek(0xC008), 0x4D);
+}
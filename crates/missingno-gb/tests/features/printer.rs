@@ -0,0 +1,229 @@
+//! The Game Boy Printer takes packets over the link cable: magic, command,
+//! compression, length, data and checksum, then answers the two trailing
+//! bytes with $81 and its status. DATA buffers tiles, run-length encoded or
+//! not; PRINT lays them out 20 to a row on 160-pixel-wide paper.
+
+use std::sync::{Arc, Mutex};
+
+use missingno_gb::{
+    GameBoy,
+    ppu::types::palette::PaletteIndex,
+    printer::{GameBoyPrinter, PRINTER_WIDTH},
+    test_support,
+    test_support::run_until_infinite_loop,
+};
+
+const INIT: u8 = 0x01;
+const PRINT: u8 = 0x02;
+const DATA: u8 = 0x04;
+const STATUS: u8 = 0x0F;
+
+/// A whole packet, checksum and the two bytes the printer answers included.
+fn packet(command: u8, compressed: bool, data: &[u8]) -> Vec<u8> {
+    let mut packet = vec![0x88, 0x33, command, compressed as u8];
+    packet.extend((data.len() as u16).to_le_bytes());
+    packet.extend(data);
+    let checksum = packet[2..]
+        .iter()
+        .fold(0u16, |sum, &byte| sum.wrapping_add(byte as u16));
+    packet.extend(checksum.to_le_bytes());
+    packet.extend([0x00, 0x00]);
+    packet
+}
+
+/// A tile of solid `colour`.
+fn solid_tile(colour: u8) -> [u8; 16] {
+    let low = if colour & 1 != 0 { 0xFF } else { 0x00 };
+    let high = if colour & 2 != 0 { 0xFF } else { 0x00 };
+    std::array::from_fn(|i| if i % 2 == 0 { low } else { high })
+}
+
+/// Two rows of tiles: black and white alternating, then solid colour 1.
+fn data() -> Vec<u8> {
+    let mut data = Vec::new();
+    for tile in 0..20 {
+        data.extend(solid_tile(if tile % 2 == 0 { 3 } else { 0 }));
+    }
+    for _ in 0..20 {
+        data.extend(solid_tile(1));
+    }
+    data
+}
+
+/// [`data`] run-length encoded: each black or white tile is a run of 16
+/// identical bytes, and each colour 1 tile a literal.
+fn compressed_data() -> Vec<u8> {
+    let mut data = Vec::new();
+    for tile in 0..20 {
+        data.extend([0x80 | 14, if tile % 2 == 0 { 0xFF } else { 0x00 }]);
+    }
+    for _ in 0..20 {
+        data.push(15);
+        data.extend(solid_tile(1));
+    }
+    data
+}
+
+/// The shades [`data`] prints as with the identity palette.
+fn expected_lines() -> Vec<PaletteIndex> {
+    let mut lines = Vec::new();
+    for y in 0..16 {
+        for x in 0..PRINTER_WIDTH {
+            let colour = match y / 8 {
+                0 if x / 8 % 2 == 0 => 3,
+                0 => 0,
+                _ => 1,
+            };
+            lines.push(PaletteIndex(colour));
+        }
+    }
+    lines
+}
+
+/// Feed `bytes` to `printer`, returning its answers.
+fn send(printer: &mut GameBoyPrinter, bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .map(|&byte| printer.exchange_byte(byte))
+        .collect()
+}
+
+/// The status from a packet's answer.
+fn status(answer: &[u8]) -> u8 {
+    assert_eq!(answer[answer.len() - 2], 0x81, "alive");
+    answer[answer.len() - 1]
+}
+
+#[test]
+fn printing_lays_out_the_buffered_tiles() {
+    let mut printer = GameBoyPrinter::new();
+    assert_eq!(status(&send(&mut printer, &packet(INIT, false, &[]))), 0);
+    let answer = send(&mut printer, &packet(DATA, false, &data()));
+    assert_eq!(status(&answer), 0x08, "unprocessed data");
+    assert!(answer[..answer.len() - 2].iter().all(|&byte| byte == 0));
+    assert!(printer.printer_output().is_empty());
+
+    let answer = send(&mut printer, &packet(PRINT, false, &[1, 0x13, 0xE4, 0x40]));
+    assert_eq!(status(&answer), 0x02, "printing");
+    assert_eq!(printer.printed_lines(), 16);
+    assert_eq!(printer.printer_output(), expected_lines());
+}
+
+#[test]
+fn compressed_data_decodes_to_the_same_tiles() {
+    let mut printer = GameBoyPrinter::new();
+    send(&mut printer, &packet(DATA, true, &compressed_data()));
+    send(&mut printer, &packet(PRINT, false, &[1, 0x00, 0xE4, 0x40]));
+    assert_eq!(printer.printer_output(), expected_lines());
+}
+
+#[test]
+fn the_palette_maps_colours_to_shades() {
+    let mut printer = GameBoyPrinter::new();
+    send(&mut printer, &packet(DATA, false, &data()));
+    // Inverted: colour 0 prints black, 3 white.
+    send(&mut printer, &packet(PRINT, false, &[1, 0x00, 0x1B, 0x40]));
+    let inverted: Vec<_> = expected_lines()
+        .into_iter()
+        .map(|shade| PaletteIndex(3 - shade.0))
+        .collect();
+    assert_eq!(printer.printer_output(), inverted);
+}
+
+#[test]
+fn zero_sheets_feeds_without_printing() {
+    let mut printer = GameBoyPrinter::new();
+    send(&mut printer, &packet(DATA, false, &data()));
+    send(&mut printer, &packet(PRINT, false, &[0, 0x00, 0xE4, 0x40]));
+    assert!(printer.printer_output().is_empty());
+    let answer = send(&mut printer, &packet(STATUS, false, &[]));
+    assert_eq!(status(&answer) & 0x08, 0, "buffer cleared");
+}
+
+#[test]
+fn printing_reads_busy_then_finishes() {
+    let mut printer = GameBoyPrinter::new();
+    send(&mut printer, &packet(DATA, false, &data()));
+    send(&mut printer, &packet(PRINT, false, &[1, 0x00, 0xE4, 0x40]));
+    let polls: Vec<u8> = (0..5)
+        .map(|_| status(&send(&mut printer, &packet(STATUS, false, &[]))))
+        .collect();
+    assert_eq!(polls, [0x02, 0x02, 0x02, 0x00, 0x00]);
+}
+
+#[test]
+fn bad_checksums_are_reported_and_ignored() {
+    let mut printer = GameBoyPrinter::new();
+    let mut bad = packet(DATA, false, &data());
+    let checksum = bad.len() - 4;
+    bad[checksum] ^= 0xFF;
+    assert_eq!(status(&send(&mut printer, &bad)), 0x01);
+    send(&mut printer, &packet(PRINT, false, &[1, 0x00, 0xE4, 0x40]));
+    assert!(printer.printer_output().is_empty(), "nothing was buffered");
+}
+
+#[test]
+fn unknown_commands_are_packet_errors() {
+    let mut printer = GameBoyPrinter::new();
+    assert_eq!(status(&send(&mut printer, &packet(0x07, false, &[]))), 0x10);
+    assert_eq!(status(&send(&mut printer, &packet(INIT, false, &[]))), 0x00);
+}
+
+/// Sends `bytes` one serial transfer at a time on the internal clock,
+/// storing each byte received from $C000 on, then spins.
+fn game_boy(bytes: &[u8]) -> GameBoy {
+    let len = (bytes.len() as u16).to_le_bytes();
+    let mut main = vec![
+        0x21, 0x00, 0x02, // LD HL,$0200
+        0x11, 0x00, 0xC0, // LD DE,$C000
+        0x01, len[0], len[1], // LD BC,len
+    ];
+    let top = main.len();
+    main.extend([
+        0x2A, // LD A,(HL+)
+        0xE0, 0x01, // LDH ($01),A: SB
+        0x3E, 0x81, // LD A,$81
+        0xE0, 0x02, // LDH ($02),A: SC, internal clock
+        0xF0, 0x02, // wait: LDH A,($02)
+        0xCB, 0x7F, // BIT 7,A
+        0x20, 0xFA, // JR NZ,wait
+        0xF0, 0x01, // LDH A,($01)
+        0x12, // LD (DE),A
+        0x13, // INC DE
+        0x0B, // DEC BC
+        0x78, // LD A,B
+        0xB1, // OR C
+    ]);
+    main.extend([0x20, (top as i16 - (main.len() as i16 + 2)) as u8]); // JR NZ,top
+    main.extend([0x18, 0xFE]); // JR -2
+    test_support::synthetic_game_boy(&main, &[(0x0200, bytes)])
+}
+
+#[test]
+fn prints_over_the_link_cable() {
+    let packets = [
+        packet(INIT, false, &[]),
+        packet(DATA, true, &compressed_data()),
+        packet(DATA, false, &[]),
+        packet(PRINT, false, &[1, 0x13, 0xE4, 0x40]),
+        packet(STATUS, false, &[]),
+    ];
+    let bytes = packets.concat();
+    let printer = Arc::new(Mutex::new(GameBoyPrinter::new()));
+    let mut gb = game_boy(&bytes);
+    gb.set_link(Box::new(printer.clone()));
+    assert!(run_until_infinite_loop(&mut gb, 120));
+
+    let received = gb.peek_range(0xC000, bytes.len() as u16);
+    let mut answers = Vec::new();
+    let mut start = 0;
+    for packet in &packets {
+        answers.push(status(&received[start..start + packet.len()]));
+        start += packet.len();
+    }
+    assert_eq!(answers, [0x00, 0x08, 0x08, 0x02, 0x02]);
+
+    let printer = printer.lock().unwrap();
+    assert_eq!(printer.printer_output().len(), PRINTER_WIDTH * 16);
+    assert_eq!(printer.printer_output(), expected_lines());
+}
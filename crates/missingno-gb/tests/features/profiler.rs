@@ -0,0 +1,73 @@
+//! With profiling on, the console counts how often each address and each
+//! opcode is fetched for execution.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Ten passes round a `DEC B` loop, then a spin at $0155.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x06, 0x0A, // LD B,10
+        0x05, // DEC B
+        0x20, 0xFD, // JR NZ,-3
+        0x18, 0xFE, // JR -2
+    ];
+    test_support::synthetic_game_boy(&main, &[])
+}
+
+fn run_to_spin(gb: &mut GameBoy) {
+    while gb.cpu().ir_address != 0x0155 {
+        gb.step();
+    }
+}
+
+#[test]
+fn off_by_default() {
+    let mut gb = game_boy();
+    run_to_spin(&mut gb);
+    assert!(gb.profile().is_none());
+}
+
+#[test]
+fn counts_each_address_and_opcode() {
+    let mut gb = game_boy();
+    gb.set_profiling(true);
+    run_to_spin(&mut gb);
+
+    let profile = gb.profile().unwrap();
+    assert_eq!(profile.address_count(0x0150), 1);
+    assert_eq!(profile.address_count(0x0152), 10);
+    assert_eq!(profile.address_count(0x0153), 10);
+    assert_eq!(profile.address_count(0x0151), 0, "operands aren't counted");
+    assert_eq!(profile.opcode_count(0x05), 10);
+    assert_eq!(profile.opcode_count(0x20), 10);
+    assert_eq!(profile.hottest_addresses(2), [(0x0152, 10), (0x0153, 10)]);
+    assert_eq!(profile.hottest_opcodes(2), [(0x05, 10), (0x20, 10)]);
+}
+
+#[test]
+fn clearing_starts_over() {
+    let mut gb = game_boy();
+    gb.set_profiling(true);
+    run_to_spin(&mut gb);
+    gb.profile_mut().unwrap().clear();
+    assert_eq!(gb.profile().unwrap().total(), 0);
+
+    for _ in 0..5 {
+        gb.step();
+    }
+    let profile = gb.profile().unwrap();
+    assert_eq!(profile.hottest_addresses(4), [(0x0155, 5)]);
+    assert_eq!(profile.total(), 5);
+}
+
+#[test]
+fn reset_keeps_profiling_on_with_fresh_counts() {
+    let mut gb = game_boy();
+    gb.set_profiling(true);
+    run_to_spin(&mut gb);
+    gb.reset();
+    assert_eq!(gb.profile().unwrap().total(), 0);
+
+    gb.set_profiling(false);
+    assert!(gb.profile().is_none());
+}
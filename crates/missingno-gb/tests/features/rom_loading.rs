@@ -0,0 +1,207 @@
+use missingno_gb::{
+    DmgModel, GameBoy,
+    cartridge::{Cartridge, CgbSupport, Destination, Licensee, LoadError},
+};
+
+/// A 32 KiB ROM-only image with a correct header checksum.
+fn rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x134..0x13a].copy_from_slice(b"LOADER");
+    rom[0x14d] = rom[0x134..0x14d]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_sub(*byte).wrapping_sub(1));
+    rom
+}
+
+#[test]
+fn garbage_file_is_too_short() {
+    let garbage = b"not a rom!".to_vec();
+    assert_eq!(
+        GameBoy::load_rom(garbage.clone()).err(),
+        Some(LoadError::TooShort { len: 10 })
+    );
+    assert!(GameBoy::load_rom_lenient(garbage).is_err());
+}
+
+#[test]
+fn truncated_rom_is_rejected() {
+    let mut half = rom();
+    half.truncate(0x4000);
+    assert_eq!(
+        GameBoy::load_rom(half.clone()).err(),
+        Some(LoadError::Truncated {
+            len: 0x4000,
+            expected: 0x8000
+        })
+    );
+    assert!(GameBoy::load_rom_lenient(half).is_err());
+
+    let mut short_of_header = rom();
+    short_of_header[0x148] = 0x01; // 64 KiB
+    let truncated = LoadError::Truncated {
+        len: 0x8000,
+        expected: 0x10000,
+    };
+    assert_eq!(
+        Cartridge::validate_lenient(&short_of_header),
+        Err(truncated.clone())
+    );
+    assert_eq!(Cartridge::new(short_of_header, None).err(), Some(truncated));
+}
+
+#[test]
+fn header_only_rom_never_runs() {
+    let mut header_only = rom();
+    header_only.truncate(0x150);
+    let truncated = LoadError::Truncated {
+        len: 0x150,
+        expected: 0x8000,
+    };
+    assert_eq!(
+        Cartridge::new(header_only.clone(), None).err(),
+        Some(truncated.clone())
+    );
+    assert_eq!(
+        GameBoy::load_rom_lenient(header_only.clone()).err(),
+        Some(truncated)
+    );
+
+    // Padded out to its two banks, the same image runs.
+    header_only.resize(0x8000, 0);
+    let mut gb = GameBoy::new(Cartridge::new(header_only, None).unwrap(), None);
+    gb.run_frames(1);
+}
+
+#[test]
+fn unsupported_mapper_is_reported() {
+    let mut rom = rom();
+    rom[0x147] = 0xfd; // Bandai TAMA5
+    assert_eq!(
+        Cartridge::validate_lenient(&rom),
+        Err(LoadError::UnsupportedMbc(0xfd))
+    );
+    assert_eq!(
+        Cartridge::new(rom, None).err(),
+        Some(LoadError::UnsupportedMbc(0xfd))
+    );
+}
+
+#[test]
+fn bad_checksum_is_only_accepted_leniently() {
+    let mut rom = rom();
+    assert!(GameBoy::load_rom(rom.clone()).is_ok());
+
+    rom[0x14d] ^= 0xff;
+    assert!(matches!(
+        GameBoy::load_rom(rom.clone()).err(),
+        Some(LoadError::HeaderChecksum { .. })
+    ));
+    assert!(GameBoy::load_rom_lenient(rom).is_ok());
+}
+
+#[test]
+fn cgb_flag_is_read_from_the_header() {
+    for (flag, support) in [
+        (0x00, CgbSupport::None),
+        (0x40, CgbSupport::None),
+        (0x80, CgbSupport::Enhanced),
+        (0x84, CgbSupport::Enhanced),
+        (0xC0, CgbSupport::Only),
+    ] {
+        let mut rom = rom();
+        rom[0x143] = flag;
+        let cartridge = Cartridge::new(rom, None).unwrap();
+        assert_eq!(cartridge.cgb_support(), support, "${flag:02X}");
+        assert_eq!(cartridge.supports_cgb(), support != CgbSupport::None);
+        assert_eq!(cartridge.cgb_only(), support == CgbSupport::Only);
+    }
+}
+
+/// A ROM with the given CGB ($0143) and SGB ($0146) flags.
+fn flagged(cgb: u8, sgb: u8) -> Cartridge {
+    let mut rom = rom();
+    rom[0x143] = cgb;
+    rom[0x146] = sgb;
+    Cartridge::new(rom, None).unwrap()
+}
+
+#[test]
+fn console_support_flags_are_decoded_together() {
+    for (name, cgb, sgb, supports) in [
+        ("DMG only", 0x00, 0x00, (false, false, false)),
+        ("SGB enhanced", 0x00, 0x03, (true, false, false)),
+        ("CGB enhanced", 0x80, 0x00, (false, true, false)),
+        ("CGB only", 0xC0, 0x00, (false, true, true)),
+        ("CGB and SGB enhanced", 0x80, 0x03, (true, true, false)),
+    ] {
+        let cartridge = flagged(cgb, sgb);
+        let decoded = (
+            cartridge.supports_sgb(),
+            cartridge.supports_cgb(),
+            cartridge.cgb_only(),
+        );
+        assert_eq!(decoded, supports, "{name}");
+        assert_eq!(cartridge.header().title, "LOADER", "{name}");
+    }
+}
+
+#[test]
+fn sgb_flag_other_than_3_is_unsupported() {
+    assert!(!flagged(0x00, 0x01).supports_sgb());
+}
+
+#[test]
+fn sgb_starts_only_for_sgb_enhanced_carts() {
+    let sgb = GameBoy::new_with_model(flagged(0x00, 0x03), None, DmgModel::Sgb);
+    assert!(sgb.sgb().is_some());
+
+    let dmg_only = GameBoy::new_with_model(flagged(0x00, 0x00), None, DmgModel::Sgb);
+    assert!(dmg_only.sgb().is_none());
+
+    let on_a_dmg = GameBoy::new(flagged(0x00, 0x03), None);
+    assert!(on_a_dmg.sgb().is_none());
+}
+
+#[test]
+fn header_fields_are_decoded() {
+    let mut rom = rom();
+    rom[0x144..0x146].copy_from_slice(b"01");
+    rom[0x147] = 0x1b; // MBC5+RAM+BATTERY
+    rom[0x148] = 0x02; // 128 KiB
+    rom[0x149] = 0x03; // 32 KiB
+    rom[0x14a] = 0x01; // overseas
+    rom[0x14b] = 0x33; // see the new licensee code
+    rom.resize(128 * 1024, 0);
+    let header = Cartridge::new(rom.clone(), None).unwrap().header().clone();
+    assert_eq!(header.cartridge_type, 0x1b);
+    assert_eq!(header.rom_size, Some(128 * 1024));
+    assert_eq!(header.ram_size, 32 * 1024);
+    assert_eq!(header.licensee, Licensee::New(*b"01"));
+    assert_eq!(header.destination, Destination::Overseas);
+    assert!(header.has_battery);
+    assert!(
+        !header.header_checksum_valid,
+        "the fields above changed the sum"
+    );
+
+    rom[0x14b] = 0x01;
+    rom[0x14a] = 0x00;
+    rom[0x147] = 0x06; // MBC2+BATTERY, which has 512 bytes built in
+    rom[0x149] = 0x00;
+    rom[0x148] = 0x52; // not a size
+    let header = Cartridge::new(rom, None).unwrap().header().clone();
+    assert_eq!(header.licensee, Licensee::Old(0x01));
+    assert_eq!(header.destination, Destination::Japan);
+    assert_eq!(header.ram_size, 512);
+    assert_eq!(header.rom_size, None);
+}
+
+#[test]
+fn header_checksum_validity_is_reported() {
+    assert!(
+        Cartridge::new(rom(), None)
+            .unwrap()
+            .header()
+            .header_checksum_valid
+    );
+}
@@ -0,0 +1,170 @@
+//! MBC3 battery saves carry the RTC in the footer BGB and VBA-M append after
+//! the RAM: current and latched registers as little-endian `u32`s, then the
+//! Unix time of the save. Loading one runs the clock on by the time since;
+//! saving writes the same 48-byte layout back.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use missingno_gb::cartridge::{
+    Cartridge,
+    mbc::{
+        Mbc,
+        mbc3::{ClockRegisters, RTC_FOOTER_LEN},
+    },
+};
+
+const RAM_LEN: usize = 8 * 1024;
+const HALT: u8 = 0x40;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// MBC3+TIMER+RAM+BATTERY with 8 KiB of RAM.
+fn rom() -> Vec<u8> {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x0147] = 0x10;
+    rom[0x0149] = 0x02;
+    rom
+}
+
+/// A save as BGB writes it: `ram`, then (seconds, minutes, hours, day low,
+/// day high) current and latched, then `saved_at` in `time_len` bytes.
+fn bgb_save(
+    ram: &[u8],
+    current: [u8; 5],
+    latched: [u8; 5],
+    saved_at: u64,
+    time_len: usize,
+) -> Vec<u8> {
+    let mut save = ram.to_vec();
+    for register in current.into_iter().chain(latched) {
+        save.extend_from_slice(&(register as u32).to_le_bytes());
+    }
+    save.extend_from_slice(&saved_at.to_le_bytes()[..time_len]);
+    save
+}
+
+fn ram() -> Vec<u8> {
+    (0..RAM_LEN).map(|i| (i * 7) as u8).collect()
+}
+
+fn registers(registers: &ClockRegisters) -> [u8; 5] {
+    [
+        registers.seconds,
+        registers.minutes,
+        registers.hours,
+        registers.days_lower,
+        registers.days_upper,
+    ]
+}
+
+/// (current, latched) clock registers.
+fn clock(cartridge: &Cartridge) -> ([u8; 5], [u8; 5]) {
+    let Mbc::Mbc3(mbc3) = cartridge.mbc() else {
+        panic!("not an MBC3");
+    };
+    let clock = mbc3.clock.as_ref().expect("no RTC");
+    (registers(&clock.registers), registers(&clock.latched))
+}
+
+#[test]
+fn halted_clock_round_trips_byte_for_byte() {
+    let current = [12, 34, 5, 0x67, HALT | 0x01];
+    let latched = [11, 33, 4, 0x66, 0x01];
+    let save = bgb_save(&ram(), current, latched, 1_000_000_000, 8);
+
+    let cartridge = Cartridge::new(rom(), Some(save.clone())).unwrap();
+    assert_eq!(clock(&cartridge), (current, latched));
+    assert_eq!(cartridge.ram().unwrap(), ram(), "RAM excludes the footer");
+
+    let saved = cartridge.save_data().unwrap();
+    assert_eq!(saved.len(), RAM_LEN + RTC_FOOTER_LEN);
+    assert_eq!(saved[..RAM_LEN + 40], save[..RAM_LEN + 40]);
+    let saved_at = u64::from_le_bytes(saved[RAM_LEN + 40..].try_into().unwrap());
+    assert!(saved_at.abs_diff(now()) <= 2, "stamped {saved_at}");
+}
+
+#[test]
+fn running_clock_catches_up_on_load() {
+    // Saved 1h 2m 5s ago.
+    let save = bgb_save(&ram(), [0, 0, 0, 0, 0], [0; 5], now() - 3725, 8);
+    let (current, latched) = clock(&Cartridge::new(rom(), Some(save)).unwrap());
+    assert_eq!(current[1..], [2, 1, 0, 0]);
+    assert!((5..=7).contains(&current[0]), "{} seconds", current[0]);
+    assert_eq!(latched, [0; 5], "latched registers don't run");
+}
+
+#[test]
+fn catching_up_carries_into_days_and_overflow() {
+    // Day 511, 23:59:50, saved a minute ago: wraps to day 0 with carry.
+    let save = bgb_save(&ram(), [50, 59, 23, 0xFF, 0x01], [0; 5], now() - 60, 8);
+    let (current, _) = clock(&Cartridge::new(rom(), Some(save)).unwrap());
+    assert_eq!(current[1..], [0, 0, 0x00, 0x80]);
+    assert!((50..=52).contains(&current[0]), "{} seconds", current[0]);
+}
+
+#[test]
+fn vba_footer_with_32_bit_time_loads() {
+    let current = [1, 2, 3, 4, HALT];
+    let save = bgb_save(&ram(), current, [5, 6, 7, 8, 0], 1_500_000_000, 4);
+    assert_eq!(save.len(), RAM_LEN + 44);
+    let cartridge = Cartridge::new(rom(), Some(save)).unwrap();
+    assert_eq!(clock(&cartridge), (current, [5, 6, 7, 8, 0]));
+    assert_eq!(
+        cartridge.save_data().unwrap().len(),
+        RAM_LEN + RTC_FOOTER_LEN,
+        "always written back with the 64-bit time"
+    );
+}
+
+#[test]
+fn out_of_range_fields_are_masked_like_register_writes() {
+    let save = bgb_save(&ram(), [0xFF, 0xFF, 0xFF, 0xFF, 0xFF], [0; 5], 0, 8);
+    let (current, _) = clock(&Cartridge::new(rom(), Some(save)).unwrap());
+    assert_eq!(current, [0x3F, 0x3F, 0x1F, 0xFF, 0xC1]);
+}
+
+#[test]
+fn save_without_footer_starts_the_clock_at_zero() {
+    let cartridge = Cartridge::new(rom(), Some(ram())).unwrap();
+    assert_eq!(clock(&cartridge), ([0; 5], [0; 5]));
+    assert_eq!(
+        cartridge.save_data().unwrap().len(),
+        RAM_LEN + RTC_FOOTER_LEN
+    );
+}
+
+#[test]
+fn timer_without_ram_saves_just_the_footer() {
+    let mut rom = rom();
+    rom[0x0147] = 0x0F; // MBC3+TIMER+BATTERY
+    rom[0x0149] = 0x00;
+    let save = bgb_save(&[], [9, 8, 7, 6, HALT], [0; 5], 0, 8);
+    let cartridge = Cartridge::new(rom, Some(save)).unwrap();
+    assert_eq!(clock(&cartridge).0, [9, 8, 7, 6, HALT]);
+    assert_eq!(cartridge.ram(), None);
+    assert_eq!(cartridge.save_data().unwrap().len(), RTC_FOOTER_LEN);
+}
+
+#[test]
+fn cartridges_without_a_clock_save_only_ram() {
+    let mut rom = rom();
+    rom[0x0147] = 0x13; // MBC3+RAM+BATTERY
+    let cartridge = Cartridge::new(rom, Some(ram())).unwrap();
+    assert_eq!(cartridge.save_data(), Some(ram()));
+}
+
+#[test]
+fn setting_the_clock_marks_the_save_dirty() {
+    let mut cartridge = Cartridge::new(rom(), None).unwrap();
+    cartridge.write(0x0000, 0x0A); // Enable RAM and clock
+    cartridge.write(0x4000, 0x08); // Map RTCS
+    assert_eq!(cartridge.ram_generation(), 0);
+    cartridge.write(0xA000, 30);
+    assert_eq!(cartridge.ram_generation(), 1);
+    assert_eq!(clock(&cartridge).0[0], 30);
+}
@@ -0,0 +1,71 @@
+//! `run_cycles` batches instructions without changing what each one does.
+
+use missingno_gb::{GameBoy, test_support};
+
+fn game_boy() -> GameBoy {
+    test_support::synthetic_game_boy(
+        &[
+            0x3C, // INC A
+            0xE0, 0x80, // LDH ($80),A
+            0xF0, 0x04, // LDH A,($04)
+            0x18, 0xF9, // JR -7
+        ],
+        &[],
+    )
+}
+
+fn state(gb: &GameBoy) -> (u16, u8, u16, u8, u8) {
+    (
+        gb.cpu().pc,
+        gb.cpu().a,
+        gb.timers().internal_counter,
+        gb.peek(0xFF44),
+        gb.peek(0xFF80),
+    )
+}
+
+#[test]
+fn stops_at_the_first_instruction_boundary_past_the_budget() {
+    let mut gb = game_boy();
+    for mcycles in [1, 7, 100, 1000] {
+        let result = gb.run_cycles(mcycles);
+        assert!(!result.new_screen);
+        assert!(result.tcycles >= mcycles * 4);
+        // The longest instruction is 6 M-cycles.
+        assert!(
+            result.tcycles < mcycles * 4 + 24,
+            "{} for {mcycles}",
+            result.tcycles
+        );
+    }
+}
+
+#[test]
+fn stops_at_the_end_of_a_frame() {
+    let mut gb = game_boy();
+    let result = gb.run_cycles(u32::MAX);
+    assert!(result.new_screen);
+    assert_eq!(gb.peek(0xFF44), 144);
+}
+
+#[test]
+fn matches_stepping_one_instruction_at_a_time() {
+    let mut batched = game_boy();
+    let mut stepped = game_boy();
+
+    for _ in 0..3 {
+        let batch = batched.run_cycles(u32::MAX);
+
+        let mut tcycles = 0;
+        loop {
+            let result = stepped.step();
+            tcycles += result.tcycles;
+            if result.new_screen {
+                break;
+            }
+        }
+
+        assert_eq!(batch.tcycles, tcycles);
+        assert_eq!(state(&batched), state(&stepped));
+    }
+}
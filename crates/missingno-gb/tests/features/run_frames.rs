@@ -0,0 +1,78 @@
+//! `run_frames` counts frames by VBlank onset, stopping as LY reaches 144,
+//! and still returns with the LCD off by counting a frame's worth of
+//! cycles instead.
+
+use missingno_gb::{GameBoy, test_support};
+
+/// Counts loop passes in $FF80, or turns the LCD off and spins when
+/// `lcd_off`.
+fn game_boy(lcd_off: bool) -> GameBoy {
+    let main: &[u8] = if lcd_off {
+        &[
+            0xAF, // XOR A
+            0xE0, 0x40, // LDH (LCDC),A
+            0x18, 0xFE, // JR -2
+        ]
+    } else {
+        &[
+            0x3C, // INC A
+            0xE0, 0x80, // LDH ($80),A
+            0x18, 0xFB, // JR -5
+        ]
+    };
+    test_support::synthetic_game_boy(main, &[])
+}
+
+#[test]
+fn stops_at_the_onset_of_vblank() {
+    let mut gb = game_boy(false);
+    for frames in [1, 2, 5] {
+        gb.run_frames(frames);
+        let (line, dot) = gb.ppu().ppu_position();
+        assert_eq!(line, 144, "after {frames}");
+        // Within the instruction that crossed into VBlank.
+        assert!(dot < 24, "dot {dot} after {frames}");
+    }
+}
+
+#[test]
+fn matches_the_step_loop() {
+    let mut batched = game_boy(false);
+    let mut stepped = game_boy(false);
+    batched.run_frames(3);
+    for _ in 0..3 {
+        while !stepped.step().new_screen {}
+    }
+    assert_eq!(batched.cpu().pc, stepped.cpu().pc);
+    assert_eq!(batched.peek(0xFF80), stepped.peek(0xFF80));
+    assert_eq!(
+        batched.timers().internal_counter,
+        stepped.timers().internal_counter
+    );
+}
+
+#[test]
+fn a_frame_with_the_lcd_off_lasts_a_frame() {
+    let mut gb = game_boy(true);
+    gb.run_frames(1);
+    let stopped = gb.ppu().ppu_position();
+
+    let before = gb.timers().internal_counter;
+    gb.run_frames(1);
+    assert_eq!(gb.ppu().ppu_position(), stopped, "LCD off");
+    let mcycles = gb.timers().internal_counter.wrapping_sub(before);
+    // 70224 dots, finishing the instruction that crossed it.
+    assert!((17556..17562).contains(&mcycles), "{mcycles} M-cycles");
+}
+
+#[test]
+fn test_helper_counts_lcd_off_frames_the_same() {
+    let mut console = game_boy(true);
+    let mut helper = game_boy(true);
+    console.run_frames(3);
+    test_support::run_frames(&mut helper, 3);
+    assert_eq!(
+        console.timers().internal_counter,
+        helper.timers().internal_counter
+    );
+}
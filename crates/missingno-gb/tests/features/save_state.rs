@@ -0,0 +1,104 @@
+//! A save state written to a file loads back into the same game and carries
+//! on exactly where it left off; another game's state is refused.
+
+use missingno_gb::save_state::{SaveState, SaveStateError};
+use missingno_gb::{GameBoy, cartridge::Cartridge, test_support};
+
+/// Counts up in $C000 forever.
+const MAIN: [u8; 6] = [
+    0x21, 0x00, 0xC0, // LD HL,$C000
+    0x34, // loop: INC (HL)
+    0x18, 0xFD, // JR loop
+];
+
+fn game_boy() -> GameBoy {
+    test_support::synthetic_game_boy(&MAIN, &[])
+}
+
+/// Enough to tell two points in time apart.
+fn moment(gb: &GameBoy) -> (u64, u8, u16, Vec<u8>) {
+    (
+        gb.cycles_elapsed(),
+        gb.peek(0xC000),
+        gb.cpu().ir_address,
+        gb.peek_range(0xFE00, 0xA0),
+    )
+}
+
+fn state_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("missingno_save_state_{name}.state1"))
+}
+
+#[test]
+fn a_saved_state_carries_on_where_it_left_off() {
+    let mut gb = game_boy();
+    gb.run_frames(3);
+    let path = state_path("round_trip");
+    SaveState::capture(&gb).save_to_file(&path).unwrap();
+
+    let mut restored = SaveState::load_from_file(&path)
+        .unwrap()
+        .into_console(gb.cartridge())
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(moment(&restored), moment(&gb));
+
+    gb.run_frames(2);
+    restored.run_frames(2);
+    assert_eq!(moment(&restored), moment(&gb));
+    assert_eq!(restored.work_ram(), gb.work_ram());
+}
+
+#[test]
+fn another_games_state_is_refused() {
+    let mut rom = test_support::synthetic_rom(&MAIN, &[]);
+    rom[0x0134..0x0138].copy_from_slice(b"GAME");
+    let other = Cartridge::new(rom, None).unwrap();
+
+    let path = state_path("other_game");
+    SaveState::capture(&game_boy()).save_to_file(&path).unwrap();
+    let state = SaveState::<missingno_gb::Dmg>::load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(
+        state.into_console(&other).err(),
+        Some(SaveStateError::ChecksumMismatch { .. })
+    ));
+}
+
+#[test]
+fn a_file_that_isnt_a_state_is_refused() {
+    let path = state_path("garbage");
+    std::fs::write(&path, b"not a save state").unwrap();
+    let result = SaveState::<missingno_gb::Dmg>::load_from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(result.err(), Some(SaveStateError::Corrupt)));
+}
+
+#[test]
+fn a_loaded_state_plays_at_the_running_consoles_rate() {
+    let mut saved = game_boy();
+    saved.audio_mut().set_output_rate(22050);
+    saved.audio_mut().set_channel_muted(0, true);
+    saved.run_frames(2);
+    let path = state_path("host_side");
+    SaveState::capture(&saved).save_to_file(&path).unwrap();
+
+    let mut gb = game_boy();
+    gb.audio_mut().set_output_rate(48000);
+    gb.audio_mut().set_channel_muted(1, true);
+    let state = SaveState::load_from_file(&path)
+        .unwrap()
+        .into_console(gb.cartridge())
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    gb.restore(state);
+
+    assert_eq!(moment(&gb), moment(&saved));
+    assert_eq!(gb.audio().output_rate(), 48000);
+    assert!(!gb.audio().channel_muted(0));
+    assert!(gb.audio().channel_muted(1));
+    gb.drain_audio_samples();
+    gb.run_frames(1);
+    // 48000 Hz over a 59.7 Hz frame.
+    assert!(gb.drain_audio_samples().len().abs_diff(804) <= 2);
+}
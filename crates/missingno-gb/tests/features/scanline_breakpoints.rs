@@ -0,0 +1,64 @@
+//! Scanline breakpoints halt `step_frame` on the phase the PPU reaches the
+//! line, or the dot within it, whether or not a bus watchpoint is also set.
+
+use missingno_gb::{
+    debugger::{Debugger, WatchCondition},
+    test_support,
+};
+
+fn debugger(watched: bool) -> Debugger {
+    let mut debugger = test_support::synthetic_debugger(&[0x18, 0xFE], &[]); // JR -2
+    if watched {
+        // Never written, so it only changes how the frame is stepped.
+        debugger.add_watchpoint(WatchCondition::BusWrite { address: 0xC000 });
+    }
+    debugger
+}
+
+fn stops_at(watched: bool, ly: u8, dot: Option<u16>) -> (u8, u16) {
+    let mut debugger = debugger(watched);
+    debugger.set_scanline_breakpoint(ly, dot);
+    assert!(debugger.step_frame().is_none(), "stopped before VBlank");
+    debugger.game_boy().ppu().ppu_position()
+}
+
+#[test]
+fn halts_at_the_start_of_the_line() {
+    for watched in [false, true] {
+        assert_eq!(stops_at(watched, 64, None), (64, 0), "watched: {watched}");
+    }
+}
+
+#[test]
+fn halts_at_the_dot() {
+    for watched in [false, true] {
+        assert_eq!(
+            stops_at(watched, 64, Some(200)),
+            (64, 200),
+            "watched: {watched}"
+        );
+    }
+}
+
+#[test]
+fn halts_at_the_last_dot_of_a_line() {
+    for watched in [false, true] {
+        assert_eq!(
+            stops_at(watched, 10, Some(455)),
+            (10, 455),
+            "watched: {watched}"
+        );
+    }
+}
+
+#[test]
+fn fires_once_a_frame() {
+    for watched in [false, true] {
+        let mut debugger = debugger(watched);
+        debugger.set_scanline_breakpoint(64, None);
+        assert!(debugger.step_frame().is_none());
+        assert!(debugger.step_frame().is_some(), "ran on to VBlank");
+        assert!(debugger.step_frame().is_none());
+        assert_eq!(debugger.game_boy().ppu().ppu_position(), (64, 0));
+    }
+}
@@ -0,0 +1,63 @@
+//! `set_serial_callback` hears each byte the game sends on its own clock
+//! as the transfer completes, alongside the buffered serial output. Bytes
+//! clocked by the other end of the cable aren't reported.
+
+use std::{cell::RefCell, rc::Rc};
+
+use missingno_gb::{GameBoy, test_support, test_support::run_until_infinite_loop};
+
+/// Sends "Hi" with SC = `control`, waiting for each transfer, then spins.
+fn game_boy(control: u8) -> GameBoy {
+    let main = [
+        0x3E, 0x48, // LD A,'H'
+        0xCD, 0x60, 0x01, // CALL send
+        0x3E, 0x69, // LD A,'i'
+        0xCD, 0x60, 0x01, // CALL send
+        0x18, 0xFE, // JR -2
+    ];
+    let send = [
+        0xE0, 0x01, // send: LDH ($01),A: SB
+        0x3E, control, // LD A,control
+        0xE0, 0x02, // LDH ($02),A: SC
+        0xF0, 0x02, // wait: LDH A,($02)
+        0xCB, 0x7F, // BIT 7,A
+        0x20, 0xFA, // JR NZ,wait
+        0xC9, // RET
+    ];
+    test_support::synthetic_game_boy(&main, &[(0x0160, &send)])
+}
+
+fn listen(gb: &mut GameBoy) -> Rc<RefCell<Vec<u8>>> {
+    let heard = Rc::new(RefCell::new(Vec::new()));
+    let sink = heard.clone();
+    gb.set_serial_callback(Box::new(move |byte| sink.borrow_mut().push(byte)));
+    heard
+}
+
+#[test]
+fn each_byte_is_pushed_as_it_completes() {
+    let mut gb = game_boy(0x81);
+    let heard = listen(&mut gb);
+    assert!(run_until_infinite_loop(&mut gb, 10));
+    assert_eq!(*heard.borrow(), b"Hi");
+    assert_eq!(gb.drain_serial_output(), b"Hi", "still buffered");
+}
+
+#[test]
+fn resets_keep_the_callback() {
+    let mut gb = game_boy(0x81);
+    let heard = listen(&mut gb);
+    assert!(run_until_infinite_loop(&mut gb, 10));
+    gb.reset();
+    assert!(run_until_infinite_loop(&mut gb, 10));
+    assert_eq!(*heard.borrow(), b"HiHi");
+}
+
+#[test]
+fn external_clock_transfers_are_not_reported() {
+    // Nothing drives the clock, so the first transfer never finishes.
+    let mut gb = game_boy(0x80);
+    let heard = listen(&mut gb);
+    assert!(!run_until_infinite_loop(&mut gb, 2));
+    assert!(heard.borrow().is_empty());
+}
@@ -0,0 +1,57 @@
+//! A soft reset restarts the game at $0100 without touching the cartridge,
+//! so battery RAM a game wrote before the reset is still there after it.
+
+use missingno_gb::{GameBoy, cartridge::Cartridge, test_support};
+
+/// Counts boots: each pass through $0100 increments the byte at $A000 in
+/// cartridge RAM, then mirrors it into WRAM at $C000.
+const MAIN: [u8; 15] = [
+    0x3E, 0x0A, // LD A,$0A
+    0xEA, 0x00, 0x00, // LD ($0000),A
+    0x21, 0x00, 0xA0, // LD HL,$A000
+    0x34, // INC (HL)
+    0x7E, // LD A,(HL)
+    0xEA, 0x00, 0xC0, // LD ($C000),A
+    0x18, 0xFE, // JR -2
+];
+
+fn game_boy() -> GameBoy {
+    let mut rom = test_support::synthetic_rom(&MAIN, &[]);
+    rom[0x0147] = 0x03; // MBC1+RAM+BATTERY
+    rom[0x0149] = 0x02; // 8 KiB
+
+    GameBoy::new(Cartridge::new(rom, Some(vec![0; 0x2000])).unwrap(), None)
+}
+
+fn boots(gb: &GameBoy) -> u8 {
+    gb.cartridge().ram().unwrap()[0]
+}
+
+#[test]
+fn soft_reset_keeps_cartridge_ram() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(boots(&gb), 1);
+
+    gb.soft_reset();
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(boots(&gb), 2, "RAM written before the reset survived it");
+}
+
+#[test]
+fn soft_reset_keeps_wram() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 1);
+
+    gb.soft_reset();
+    assert_eq!(gb.peek(0xC000), 1);
+}
+
+#[test]
+fn soft_reset_restarts_at_0100() {
+    let mut gb = game_boy();
+    test_support::run_frames(&mut gb, 1);
+
+    gb.soft_reset();
+    assert_eq!(gb.cpu().pc, 0x0100);
+}
@@ -0,0 +1,62 @@
+//! The debugger diffs tile data at each frame boundary, reporting which
+//! tiles changed over the last completed frame.
+
+use missingno_gb::{
+    debugger::Debugger,
+    ppu::types::tiles::{TileBlockId, TileIndex},
+    test_support,
+};
+
+/// Bumps the first byte of tile 1 ($8010) once per frame, in VBlank.
+fn debugger() -> Debugger {
+    let main = [
+        0x21, 0x10, 0x80, // LD HL,$8010
+        0xF0, 0x44, // LDH A,($44)
+        0xFE, 0x90, // CP $90
+        0x20, 0xFA, // JR NZ,-6
+        0x34, // INC (HL)
+        0xF0, 0x44, // LDH A,($44)
+        0xFE, 0x90, // CP $90
+        0x28, 0xFA, // JR Z,-6
+        0x18, 0xF1, // JR -15
+    ];
+    test_support::synthetic_debugger(&main, &[])
+}
+
+#[test]
+fn first_frame_reports_no_changes() {
+    let mut debugger = debugger();
+    debugger.step_frame();
+    let changes = debugger.changed_tiles_since_last_frame();
+    assert_eq!(changes.block(0, TileBlockId(0)), 0);
+}
+
+#[test]
+fn rewritten_tile_is_reported_each_frame() {
+    let mut debugger = debugger();
+    for _ in 0..4 {
+        debugger.step_frame();
+        let changes = debugger.changed_tiles_since_last_frame();
+        if changes.block(0, TileBlockId(0)) == 0 {
+            continue;
+        }
+        assert!(changes.is_changed(0, TileBlockId(0), TileIndex(1)));
+        assert_eq!(changes.block(0, TileBlockId(0)), 1 << 1);
+        assert_eq!(changes.block(0, TileBlockId(1)), 0);
+        assert_eq!(changes.block(0, TileBlockId(2)), 0);
+        return;
+    }
+    panic!("tile 1 never reported as changed");
+}
+
+#[test]
+fn untouched_frames_report_nothing() {
+    let mut debugger = test_support::synthetic_debugger(&[0x18, 0xFE], &[]); // JR -2
+    for _ in 0..3 {
+        debugger.step_frame();
+    }
+    let changes = debugger.changed_tiles_since_last_frame();
+    for block in 0..3 {
+        assert_eq!(changes.block(0, TileBlockId(block)), 0);
+    }
+}
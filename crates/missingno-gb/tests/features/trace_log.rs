@@ -0,0 +1,123 @@
+//! With a trace sink set, the console writes a Gameboy Doctor line for
+//! every instruction it executes: the registers as it starts, and the four
+//! bytes at its address.
+
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use missingno_gb::{GameBoy, test_support};
+
+/// A sink the test can read back while the console holds it.
+#[derive(Clone, Default)]
+struct Shared(Rc<RefCell<Vec<u8>>>);
+
+impl Shared {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.borrow().clone())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl Write for Shared {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn game_boy(main: &[u8], vectors: &[(u16, &[u8])]) -> (GameBoy, Shared) {
+    let mut gb = test_support::synthetic_game_boy(main, vectors);
+    let sink = Shared::default();
+    gb.set_trace_sink(Box::new(sink.clone()));
+    (gb, sink)
+}
+
+/// The address of each logged instruction.
+fn pcs(lines: &[String]) -> Vec<u16> {
+    lines
+        .iter()
+        .map(|line| {
+            let pc = line.split(' ').find_map(|f| f.strip_prefix("PC:")).unwrap();
+            u16::from_str_radix(pc, 16).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn lines_match_the_gameboy_doctor_layout() {
+    let (mut gb, sink) = game_boy(
+        &[
+            0x3E, 0x12, // LD A,$12
+            0x21, 0x00, 0xC0, // LD HL,$C000
+            0x22, // LD (HL+),A
+            0xE5, // PUSH HL
+            0xD1, // POP DE
+            0x87, // ADD A,A
+            0x18, 0xFE, // JR -2
+        ],
+        &[],
+    );
+    while sink.lines().len() < 10 {
+        gb.step();
+    }
+
+    assert_eq!(
+        sink.lines()[..10],
+        [
+            "A:01 F:80 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,50,01",
+            "A:01 F:80 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0101 PCMEM:C3,50,01,00",
+            "A:01 F:80 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0150 PCMEM:3E,12,21,00",
+            "A:12 F:80 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0152 PCMEM:21,00,C0,22",
+            "A:12 F:80 B:00 C:13 D:00 E:D8 H:C0 L:00 SP:FFFE PC:0155 PCMEM:22,E5,D1,87",
+            "A:12 F:80 B:00 C:13 D:00 E:D8 H:C0 L:01 SP:FFFE PC:0156 PCMEM:E5,D1,87,18",
+            "A:12 F:80 B:00 C:13 D:00 E:D8 H:C0 L:01 SP:FFFC PC:0157 PCMEM:D1,87,18,FE",
+            "A:12 F:80 B:00 C:13 D:C0 E:01 H:C0 L:01 SP:FFFE PC:0158 PCMEM:87,18,FE,00",
+            "A:24 F:00 B:00 C:13 D:C0 E:01 H:C0 L:01 SP:FFFE PC:0159 PCMEM:18,FE,00,00",
+            "A:24 F:00 B:00 C:13 D:C0 E:01 H:C0 L:01 SP:FFFE PC:0159 PCMEM:18,FE,00,00",
+        ]
+    );
+}
+
+#[test]
+fn halting_and_interrupt_dispatch_get_no_lines() {
+    let (mut gb, sink) = game_boy(
+        &[
+            0x3E, 0x04, // LD A,$04
+            0xE0, 0xFF, // LDH (IE),A: timer
+            0x3E, 0x05, // LD A,$05
+            0xE0, 0x07, // LDH (TAC),A: start the timer, 16 T-cycles a tick
+            0xFB, // EI
+            0x76, // HALT
+            0x18, 0xFE, // JR -2
+        ],
+        &[(0x0050, &[0xD9])], // RETI
+    );
+    while sink.lines().len() < 11 {
+        gb.step();
+    }
+
+    assert_eq!(
+        pcs(&sink.lines())[6..11],
+        [0x0158, 0x0159, 0x0050, 0x015A, 0x015A]
+    );
+}
+
+#[test]
+fn taking_the_sink_stops_the_log() {
+    let (mut gb, sink) = game_boy(&[0x18, 0xFE], &[]); // JR -2
+    for _ in 0..3 {
+        gb.step();
+    }
+    assert!(gb.take_trace_sink().is_some());
+    let logged = sink.lines().len();
+
+    gb.step();
+    assert_eq!(sink.lines().len(), logged);
+    assert!(gb.take_trace_sink().is_none());
+}
@@ -0,0 +1,70 @@
+//! Behaviour the emulator doesn't model is reported through
+//! `take_warnings` instead of panicking or passing silently, and each
+//! distinct warning only once.
+
+use missingno_gb::{Warning, cartridge::Cartridge, test_support};
+
+fn cartridge(cartridge_type: u8) -> Cartridge {
+    let mut rom = vec![0u8; 0x8000];
+    rom[0x147] = cartridge_type;
+    rom[0x149] = 0x03; // 32 KiB RAM
+    Cartridge::new(rom, None).unwrap()
+}
+
+#[test]
+fn illegal_opcode_is_reported_once() {
+    let mut gb = test_support::synthetic_game_boy(&[0xDB], &[]);
+
+    test_support::run_frames(&mut gb, 2);
+    assert_eq!(
+        gb.take_warnings(),
+        [Warning::IllegalOpcode {
+            opcode: 0xDB,
+            address: 0x0150
+        }]
+    );
+    test_support::run_frames(&mut gb, 2);
+    assert_eq!(gb.take_warnings(), []);
+}
+
+#[test]
+fn clean_run_has_no_warnings() {
+    let mut gb = test_support::synthetic_game_boy(&[0x18, 0xFE], &[]); // JR -2
+    test_support::run_frames(&mut gb, 2);
+    assert_eq!(gb.take_warnings(), []);
+}
+
+#[test]
+fn mbc3_bank_select_past_the_rtc_warns_instead_of_panicking() {
+    let mut cartridge = cartridge(0x10); // MBC3+TIMER+RAM+BATTERY
+    cartridge.write(0x0000, 0x0A);
+    cartridge.write(0x4000, 0x01);
+    cartridge.write(0xA000, 0x42);
+
+    for _ in 0..100 {
+        cartridge.write(0x4000, 0x0F);
+    }
+    assert_eq!(
+        cartridge.take_warnings(),
+        [Warning::Mbc3BankSelect { value: 0x0F }]
+    );
+    assert_eq!(cartridge.read(0xA000), 0x42, "RAM bank 1 stays mapped");
+}
+
+#[test]
+fn huc3_infrared_write_warns() {
+    let mut cartridge = cartridge(0xFE);
+    cartridge.write(0x0000, 0x0E);
+    cartridge.write(0xA000, 0x01);
+    cartridge.write(0xA000, 0x00);
+    assert_eq!(cartridge.take_warnings(), [Warning::Huc3Infrared]);
+}
+
+#[test]
+fn warnings_read_as_messages() {
+    let warning = Warning::IllegalOpcode {
+        opcode: 0xDB,
+        address: 0x1234,
+    };
+    assert_eq!(warning.to_string(), "executed invalid opcode $DB at $1234");
+}
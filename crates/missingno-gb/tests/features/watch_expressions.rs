@@ -0,0 +1,103 @@
+//! Watch expressions name registers and memory for the debugger to show
+//! live: `A`, `HL`, `[HL]`, `[$C000]`, `[SP+2]`.
+
+use missingno_gb::{
+    GameBoy,
+    debugger::expression::{Expression, ParseError, Register},
+    test_support,
+};
+
+/// Loads known values into the registers and $C000-$C001, then spins at
+/// $0165.
+fn game_boy() -> GameBoy {
+    let main = [
+        0x31, 0xFE, 0xDF, // LD SP,$DFFE
+        0x21, 0x00, 0xC0, // LD HL,$C000
+        0x36, 0x42, // LD (HL),$42
+        0x3E, 0xFF, // LD A,$FF
+        0xEA, 0x01, 0xC0, // LD ($C001),A
+        0x01, 0x34, 0x12, // LD BC,$1234
+        0x11, 0x00, 0xE0, // LD DE,$E000
+        0x3E, 0x80, // LD A,$80
+        0x18, 0xFE, // JR -2
+    ];
+    let mut gb = test_support::synthetic_game_boy(&main, &[]);
+    test_support::run_frames(&mut gb, 1);
+    assert_eq!(gb.cpu().ir_address, 0x0165, "didn't reach the spin");
+    gb
+}
+
+fn evaluate(gb: &GameBoy, source: &str) -> u16 {
+    Expression::parse(source)
+        .unwrap_or_else(|e| panic!("{source}: {e}"))
+        .evaluate(gb)
+}
+
+#[test]
+fn registers_and_pairs() {
+    let gb = game_boy();
+    assert_eq!(evaluate(&gb, "a"), 0x80);
+    assert_eq!(evaluate(&gb, "B"), 0x12);
+    assert_eq!(evaluate(&gb, "BC"), 0x1234);
+    assert_eq!(evaluate(&gb, "hl"), 0xC000);
+    assert_eq!(evaluate(&gb, "SP"), 0xDFFE);
+    assert_eq!(evaluate(&gb, "PC"), 0x0165);
+}
+
+#[test]
+fn memory_reads() {
+    let gb = game_boy();
+    assert_eq!(evaluate(&gb, "[HL]"), 0x42);
+    assert_eq!(evaluate(&gb, "[0xC000]"), 0x42);
+    assert_eq!(evaluate(&gb, "[$C001]"), 0xFF);
+    assert_eq!(evaluate(&gb, "[HL+1]"), 0xFF);
+    assert_eq!(evaluate(&gb, "[ [HL] + 49087 ]"), 0xFF);
+}
+
+#[test]
+fn byte_arithmetic_wraps_at_8_bits() {
+    let gb = game_boy();
+    assert!(Expression::parse("[HL]+A").unwrap().is_byte());
+    assert_eq!(evaluate(&gb, "[$C001]+1"), 0x00);
+    assert_eq!(evaluate(&gb, "HL-1"), 0xBFFF);
+    assert_eq!(evaluate(&gb, "DE+$2000"), 0x0000);
+}
+
+#[test]
+fn parses_precedence_free_sums_left_to_right() {
+    assert_eq!(
+        Expression::parse("HL - 1 + 2"),
+        Ok(Expression::Add(
+            Box::new(Expression::Subtract(
+                Box::new(Expression::Register(Register::Hl)),
+                Box::new(Expression::Number(1)),
+            )),
+            Box::new(Expression::Number(2)),
+        ))
+    );
+}
+
+#[test]
+fn rejects_malformed_expressions() {
+    assert_eq!(Expression::parse("  "), Err(ParseError::Empty));
+    assert_eq!(
+        Expression::parse("IX"),
+        Err(ParseError::UnknownRegister("IX".to_string()))
+    );
+    assert_eq!(
+        Expression::parse("$10000"),
+        Err(ParseError::BadNumber("$10000".to_string()))
+    );
+    assert_eq!(
+        Expression::parse("[HL"),
+        Err(ParseError::Unexpected(String::new()))
+    );
+    assert_eq!(
+        Expression::parse("HL]"),
+        Err(ParseError::Unexpected("]".to_string()))
+    );
+    assert_eq!(
+        Expression::parse("A * 2"),
+        Err(ParseError::Unexpected("*".to_string()))
+    );
+}
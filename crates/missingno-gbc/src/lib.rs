@@ -63,7 +63,13 @@ const SWITCH_TO_SINGLE_RELOCK_EDGES: u32 = 2;
 /// One CGB colour-palette RAM (BG or OBJ): 8 palettes × 4 colours × 2 bytes,
 /// addressed by a 6-bit index that auto-increments on data writes (BCPS/OCPS
 /// bit 7). Data writes during mode 3 are dropped but still advance the index.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorRam {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "missingno_gb::save_state::big_array")
+    )]
     data: [u8; 64],
     index: u8,
     auto_increment: bool,
@@ -140,6 +146,7 @@ impl ColorRam {
 /// BG palette, bit 3 tile VRAM bank, bit 5 X-flip, bit 6 Y-flip, bit 7 BG-to-OBJ
 /// priority (bit 4 unused). Rides the BG shifter across its tile's 8 pixels.
 #[derive(Copy, Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct BgAttribute(pub u8);
 
 impl BgAttribute {
@@ -167,7 +174,8 @@ impl BgAttribute {
 
 /// CGB video RAM: two 8 KiB banks selected by VBK ($FF4F). Bank 1 additionally
 /// carries the BG map attributes (read by the colour fetch as it lands).
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct CgbVram {
     banks: [VramBank; 2],
     /// VBK bit 0 — the bank the CPU sees at $8000-$9FFF.
@@ -187,6 +195,10 @@ impl Vram for CgbVram {
         &self.banks[bank as usize]
     }
 
+    fn bank_count(&self) -> u8 {
+        2
+    }
+
     fn read_bank_select(&self) -> u8 {
         0xFE | self.selected
     }
@@ -195,6 +207,12 @@ impl Vram for CgbVram {
         self.selected = value & 0x01;
     }
 
+    fn power_on(&mut self, contents: &VramBank) {
+        for bank in &mut self.banks {
+            *bank = contents.clone();
+        }
+    }
+
     fn init_post_boot(&mut self, logo: &[u8; 0x30]) {
         self.banks[0].seed_post_boot(logo);
     }
@@ -262,7 +280,8 @@ fn dmg_compat_palettes(
 /// per-pixel source slot (the OAM-scan store index = OAM-priority rank). When OPRI
 /// selects CGB priority, a lower-slot object's pixel overwrites a higher one;
 /// otherwise stages fill only when transparent (DMG fetch-order).
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct CgbObjShifter {
     low: u8,
     high: u8,
@@ -339,7 +358,8 @@ impl CgbObjShifter {
 /// `dmg_compat` marks a DMG cartridge running on the CGB: the boot palette is
 /// installed in CRAM and the DMG palette registers (BGP/OBP) index it. `opri`
 /// is OPRI ($FF6C): false = CGB object priority (by OAM index), true = DMG (by X).
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct CgbPpu {
     bg_cram: ColorRam,
     obj_cram: ColorRam,
@@ -618,7 +638,8 @@ impl CgbPpu {
 
 /// How the active VRAM DMA is paced. GDMA holds the CPU and flows continuously;
 /// HDMA copies one 16-byte block per HBlank, releasing the CPU between blocks.
-#[derive(Default, PartialEq)]
+#[derive(Clone, Default, PartialEq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 enum TransferMode {
     #[default]
     Idle,
@@ -630,7 +651,8 @@ enum TransferMode {
 /// as bytes are copied and persist after a transfer, so a follow-on transfer
 /// continues where the last left off. The step loop ticks it each M-cycle: a
 /// transfer flows `quota` bytes per M-cycle while it holds the CPU.
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 struct VramDma {
     /// Running source pointer, 16-byte aligned (HDMA1/HDMA2).
     source: u16,
@@ -710,9 +732,15 @@ impl VramDma {
 
 /// The Game Boy Color [`Model`]. Remaining CGB features (the color pixel
 /// pipeline) attach here as they land.
+#[derive(Clone)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cgb {
     /// 8 × 4 KiB work-RAM banks. C000-CFFF is fixed bank 0; D000-DFFF is the
     /// SVBK-selected bank.
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "missingno_gb::save_state::big_array")
+    )]
     wram: Box<[u8; 0x8000]>,
     /// SVBK ($FF70) bits 0-2 as written; the effective D000 bank is `max(svbk, 1)`.
     svbk: u8,
@@ -920,7 +948,15 @@ impl Model for Cgb {
         (0x8000..=0x9FFF).contains(&source).then_some(0xFF)
     }
 
-    fn cpu_post_boot(_checksum: u8) -> Cpu {
+    fn work_ram(&self) -> Option<&[u8]> {
+        Some(&self.wram[..])
+    }
+
+    fn work_ram_mut(&mut self) -> Option<&mut [u8]> {
+        Some(&mut self.wram[..])
+    }
+
+    fn cpu_post_boot(&self, _checksum: u8) -> Cpu {
         Cpu::post_boot_cgb()
     }
 
@@ -1149,7 +1185,7 @@ impl Model for Cgb {
         // With a real boot ROM that decision is the boot ROM's (via KEY0);
         // only HLE it on the skip-boot path.
         if !has_boot_rom {
-            self.dmg_compat = !cartridge.is_cgb();
+            self.dmg_compat = !cartridge.supports_cgb();
         }
     }
 
@@ -1172,8 +1208,8 @@ impl Model for Cgb {
             // in bits 6-0. Idle/done/stopped reads bit 7 = 1 (done = $FF). A GDMA
             // is never observable here — it holds the CPU for its whole duration.
             0xFF55 => {
-                let visible =
-                    (self.vram_dma.remaining / 16).saturating_sub(self.vram_dma.granted_ahead as u16);
+                let visible = (self.vram_dma.remaining / 16)
+                    .saturating_sub(self.vram_dma.granted_ahead as u16);
                 let active = self.vram_dma.mode == TransferMode::HBlank && visible > 0;
                 Some(((!active as u8) << 7) | (visible.wrapping_sub(1) & 0x7F) as u8)
             }
@@ -1568,4 +1604,176 @@ mod dmg_palette_tests {
         assert_eq!(dmg_compat_palettes(&e, 0x01, [0, 0]).0, bg_of(combo_e));
         assert_eq!(dmg_compat_palettes(&r, 0x01, [0, 0]).0, bg_of(combo_r));
     }
+
+    /// A cartridge titled `title` from Nintendo (old licensee $01), with the
+    /// given CGB flag.
+    fn cartridge(title: &str, cgb_flag: u8) -> missingno_gb::cartridge::Cartridge {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0134..0x0134 + title.len()].copy_from_slice(title.as_bytes());
+        rom[0x0143] = cgb_flag;
+        rom[0x014B] = 0x01;
+        missingno_gb::cartridge::Cartridge::new(rom, None).unwrap()
+    }
+
+    fn bg_palette(console: &GameBoyColor, palette: u8) -> [Color555; 4] {
+        std::array::from_fn(|index| console.ppu().model().bg_color(palette, index as u8))
+    }
+
+    fn obj_palette(console: &GameBoyColor, palette: u8) -> [Color555; 4] {
+        std::array::from_fn(|index| console.ppu().model().obj_color(palette, index as u8))
+    }
+
+    #[test]
+    fn dmg_game_starts_colourised_with_its_title_palette() {
+        // Combination 3: palette 24 for the background and both object palettes.
+        let expected = dmg_palette_data::PALETTES[24].map(Color555);
+        let console = GameBoyColor::new(cartridge("TETRIS", 0x00), None);
+        assert_eq!(bg_palette(&console, 0), expected);
+        assert_eq!(obj_palette(&console, 0), expected);
+        assert_eq!(obj_palette(&console, 1), expected);
+    }
+
+    #[test]
+    fn cgb_game_starts_without_a_compat_palette() {
+        let console = GameBoyColor::new(cartridge("TETRIS", 0x80), None);
+        assert_eq!(bg_palette(&console, 0), [Color555::grey(31); 4]);
+    }
+}
+
+#[cfg(test)]
+mod color_ram_tests {
+    use super::*;
+
+    #[test]
+    fn data_writes_advance_the_index_only_with_auto_increment() {
+        let mut ram = ColorRam::default();
+        ram.write_index(0x02);
+        ram.write_data(0x1F);
+        ram.write_data(0x7C);
+        assert_eq!(ram.read_index(), 0x42);
+        assert_eq!(ram.read_data(), 0x7C);
+
+        ram.write_index(0x82); // palette 0, colour 1, auto-increment
+        ram.write_data(0x1F);
+        ram.write_data(0x00);
+        assert_eq!(ram.read_index(), 0xC4);
+        assert_eq!(ram.color(0, 1), Color555(0x001F));
+    }
+
+    #[test]
+    fn the_index_wraps_after_the_last_palette() {
+        let mut ram = ColorRam::default();
+        ram.write_index(0xBE); // palette 7, colour 3, auto-increment
+        ram.write_data(0x00);
+        ram.write_data(0x7C);
+        assert_eq!(ram.read_index(), 0xC0);
+        assert_eq!(ram.color(7, 3), Color555(0x7C00));
+    }
+
+    #[test]
+    fn a_blocked_write_still_advances() {
+        let mut ram = ColorRam::default();
+        ram.write_index(0x80);
+        ram.skip_data();
+        assert_eq!(ram.read_index(), 0xC1);
+        assert_eq!(ram.data[0], 0);
+    }
+}
+
+#[cfg(test)]
+mod speed_switch_tests {
+    use super::*;
+
+    /// Arms KEY1 and STOPs, then waits for $FF80 to go non-zero before
+    /// switching back.
+    fn console() -> GameBoyColor {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0143] = 0x80;
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+        rom[0x0150..0x0163].copy_from_slice(&[
+            0x3E, 0x01, // LD A,1
+            0xE0, 0x4D, // LDH ($4D),A: arm the switch
+            0x10, 0x00, // STOP
+            0xF0, 0x80, // LDH A,($80)
+            0xA7, // AND A
+            0x28, 0xFB, // JR Z,-5
+            0x3E, 0x01, // LD A,1
+            0xE0, 0x4D, // LDH ($4D),A
+            0x10, 0x00, // STOP
+            0x18, 0xFE, // JR -2
+        ]);
+        let mut console = GameBoyColor::new(
+            missingno_gb::cartridge::Cartridge::new(rom, None).unwrap(),
+            None,
+        );
+        console.poke_raw(0xFF80, 0);
+        console
+    }
+
+    /// Longer than the switch's blackout at either speed.
+    const SETTLE_MCYCLES: u32 = 4 * 17556;
+
+    #[test]
+    fn stop_with_key1_armed_switches_speed_both_ways() {
+        let mut console = console();
+        assert!(!console.double_speed());
+        assert_eq!(console.peek(0xFF4D), 0x7E);
+
+        console.run_cycles(SETTLE_MCYCLES);
+        assert!(console.double_speed());
+        assert!(!console.speed_switch_in_progress());
+        assert_eq!(console.peek(0xFF4D), 0xFE, "bit 7 set, arm cleared");
+
+        console.poke_raw(0xFF80, 1);
+        console.run_cycles(SETTLE_MCYCLES);
+        assert!(!console.double_speed());
+        assert_eq!(console.peek(0xFF4D), 0x7E);
+    }
+}
+
+#[cfg(all(test, feature = "save-state"))]
+mod save_state_tests {
+    use missingno_gb::save_state::SaveState;
+
+    use super::*;
+
+    /// Switches WRAM bank 3 in, then counts up in $D000 forever.
+    fn console() -> GameBoyColor {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0143] = 0x80;
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+        rom[0x0150..0x015A].copy_from_slice(&[
+            0x3E, 0x03, // LD A,3
+            0xE0, 0x70, // LDH ($70),A: SVBK
+            0x21, 0x00, 0xD0, // LD HL,$D000
+            0x34, // loop: INC (HL)
+            0x18, 0xFD, // JR loop
+        ]);
+        GameBoyColor::new(
+            missingno_gb::cartridge::Cartridge::new(rom, None).unwrap(),
+            None,
+        )
+    }
+
+    #[test]
+    fn a_saved_state_keeps_the_cgb_state() {
+        let mut console = console();
+        console.run_frames(2);
+        let path = std::env::temp_dir().join("missingno_gbc_save_state.state1");
+        SaveState::capture(&console).save_to_file(&path).unwrap();
+
+        let mut restored = SaveState::<Cgb>::load_from_file(&path)
+            .unwrap()
+            .into_console(console.cartridge())
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(restored.peek(0xFF70) & 0x07, 3);
+        assert_eq!(restored.peek(0xD000), console.peek(0xD000));
+
+        console.run_frames(1);
+        restored.run_frames(1);
+        assert_eq!(restored.peek(0xD000), console.peek(0xD000));
+        assert_eq!(restored.cycles_elapsed(), console.cycles_elapsed());
+        assert_eq!(restored.work_ram(), console.work_ram());
+    }
 }
@@ -44,6 +44,7 @@ fn green_mix_table() -> &'static [[u8; 32]; 32] {
 /// A 15-bit RGB555 color, packed `0b_bbbbb_ggggg_rrrrr` (5 bits per
 /// channel), as stored in CGB palette RAM.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color555(pub u16);
 
 impl Color555 {
@@ -93,6 +94,7 @@ pub const GREYSCALE: [Color555; 4] = [
 const GREYSCALE_BYTE: [u8; 4] = [0xFF, 0xAA, 0x55, 0x00];
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Screen {
     front: Box<Framebuffer>,
     back: Box<Framebuffer>,
@@ -184,10 +186,19 @@ impl ScreenBuffer for Screen {
         self.front.clear();
         self.back.clear();
     }
+
+    fn heap_size(&self) -> usize {
+        2 * std::mem::size_of::<Framebuffer>()
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "save-state", derive(serde::Serialize, serde::Deserialize))]
 pub struct Framebuffer {
+    #[cfg_attr(
+        feature = "save-state",
+        serde(with = "missingno_gb::save_state::big_array::nested")
+    )]
     pub pixels: [[Color555; PIXELS_PER_LINE as usize]; NUM_SCANLINES as usize],
 }
 
@@ -2,9 +2,9 @@
 //! CGB BG/OBJ palettes ($FF68-$FF6B), tile attributes (bank 1 of
 //! VRAM tilemap), BG/OBJ priority, etc.
 //!
-//! Expected to fail until CGB PPU support lands. The test runs to
-//! completion via `LD B,B` then we compare against the reference PNG
-//! shipped with the ROM.
+//! The test runs to completion via `LD B,B`, then we compare the screen in
+//! full colour against the reference PNG shipped with the ROM, so a wrong
+//! palette shows up as well as a wrong shade.
 
 use crate::common;
 
@@ -17,15 +17,15 @@ fn cgb_acid2() {
         "cgb-acid2 timed out without reaching LD B,B breakpoint"
     );
 
-    let actual = gbc.screen().to_greyscale_bytes();
-    let expected = common::load_cgb_reference_png("cgb-acid2/cgb-acid2.png");
+    let actual = gbc.screen().to_rgb_bytes();
+    let expected = common::load_cgb_reference_png_rgb("cgb-acid2/cgb-acid2.png");
 
     let mut mismatches = 0;
-    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+    for (i, (a, e)) in actual.chunks(3).zip(expected.chunks(3)).enumerate() {
         if a != e {
             if mismatches < 10 {
                 let (x, y) = (i % 160, i / 160);
-                eprintln!("Pixel mismatch at ({x}, {y}): got 0x{a:02X}, expected 0x{e:02X}");
+                eprintln!("Pixel mismatch at ({x}, {y}): got {a:02X?}, expected {e:02X?}");
             }
             mismatches += 1;
         }
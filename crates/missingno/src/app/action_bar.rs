@@ -129,7 +129,7 @@ impl ActionBar {
                     )
                     .clip(true)
                     .width(Fill),
-                    controls(app.running(), is_debugger),
+                    controls(app.running(), is_debugger, app.recording_audio()),
                     self.trailing()
                 ]
             }
@@ -153,14 +153,30 @@ impl ActionBar {
     }
 }
 
-fn controls(running: bool, debugger: bool) -> Element<'static, app::Message> {
+fn controls(running: bool, debugger: bool, recording: bool) -> Element<'static, app::Message> {
     let mut r = row![];
 
     if debugger {
-        r = r.push(step(running)).push(step_over(running));
+        r = r
+            .push(step(running))
+            .push(step_over(running))
+            .push(step_out(running));
     }
 
-    r.push(play_pause(running)).spacing(s()).wrap().into()
+    r.push(record(recording))
+        .push(play_pause(running))
+        .spacing(s())
+        .wrap()
+        .into()
+}
+
+fn record(recording: bool) -> Button<'static, app::Message> {
+    if recording {
+        buttons::danger("Stop recording")
+    } else {
+        buttons::standard("Record audio")
+    }
+    .on_press(app::Message::ToggleAudioRecording)
 }
 
 fn play_pause(running: bool) -> Button<'static, app::Message> {
@@ -193,3 +209,12 @@ fn step_over(running: bool) -> Button<'static, app::Message> {
         button.on_press(debugger::Message::StepOver.into())
     }
 }
+
+fn step_out(running: bool) -> Button<'static, app::Message> {
+    let button = buttons::standard("Out");
+    if running {
+        button
+    } else {
+        button.on_press(debugger::Message::StepOut.into())
+    }
+}
@@ -1,22 +1,35 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 
+/// Samples queued between the emulator and the device callback.
+const BUFFER_LEN: usize = 4096;
+
+/// Queued samples past which the emulator is ahead of the device and a
+/// frame's audio is skipped.
+pub const BACKLOG_LIMIT: usize = BUFFER_LEN / 2;
+
 pub struct AudioOutput {
     _stream: cpal::Stream,
     producer: rtrb::Producer<(f32, f32)>,
+    sample_rate: u32,
 }
 
 impl AudioOutput {
     pub fn new() -> Option<Self> {
         let host = cpal::default_host();
         let device = host.default_output_device()?;
+        // Run at the device's own rate so the OS doesn't resample on top of us.
+        let sample_rate = device
+            .default_output_config()
+            .map(|config| config.sample_rate())
+            .unwrap_or(missingno_gb::audio::DEFAULT_OUTPUT_RATE);
 
         let config = cpal::StreamConfig {
             channels: 2,
-            sample_rate: 44100,
+            sample_rate,
             buffer_size: cpal::BufferSize::Default,
         };
 
-        let (producer, mut consumer) = rtrb::RingBuffer::new(4096);
+        let (producer, mut consumer) = rtrb::RingBuffer::new(BUFFER_LEN);
 
         let stream = device
             .build_output_stream(
@@ -38,12 +51,67 @@ impl AudioOutput {
         Some(Self {
             _stream: stream,
             producer,
+            sample_rate,
         })
     }
 
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Samples queued and not yet played.
+    pub fn buffer_fill(&self) -> usize {
+        BUFFER_LEN - self.producer.slots()
+    }
+
+    /// Queue a frame's `samples` for the device. When the emulator has got
+    /// ahead of real time and they don't all fit, the whole batch is dropped:
+    /// a skipped frame is a short gap, where a squeezed one changes pitch.
     pub fn push_samples(&mut self, samples: &[(f32, f32)]) {
-        for &sample in samples {
-            let _ = self.producer.push(sample);
+        push_batch(&mut self.producer, samples);
+    }
+}
+
+/// Push all of `samples`, or none of them if they don't fit.
+fn push_batch(producer: &mut rtrb::Producer<(f32, f32)>, samples: &[(f32, f32)]) -> bool {
+    if samples.len() > producer.slots() {
+        return false;
+    }
+    for &sample in samples {
+        let _ = producer.push(sample);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(len: usize) -> Vec<(f32, f32)> {
+        (0..len).map(|i| (i as f32, -(i as f32))).collect()
+    }
+
+    #[test]
+    fn batches_that_fit_are_queued_in_order() {
+        let (mut producer, mut consumer) = rtrb::RingBuffer::new(8);
+        assert!(push_batch(&mut producer, &ramp(5)));
+        assert!(push_batch(&mut producer, &ramp(3)));
+        let queued: Vec<f32> = std::iter::from_fn(|| consumer.pop().ok())
+            .map(|(left, _)| left)
+            .collect();
+        assert_eq!(queued, [0.0, 1.0, 2.0, 3.0, 4.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn a_batch_that_overflows_is_dropped_whole() {
+        let (mut producer, mut consumer) = rtrb::RingBuffer::new(8);
+        assert!(push_batch(&mut producer, &ramp(5)));
+        assert!(!push_batch(&mut producer, &ramp(4)));
+        assert_eq!(consumer.slots(), 5);
+
+        for _ in 0..5 {
+            consumer.pop().unwrap();
         }
+        assert!(push_batch(&mut producer, &ramp(4)), "fits once drained");
     }
 }
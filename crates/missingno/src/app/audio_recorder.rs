@@ -0,0 +1,154 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use super::settings::RecordingFormat;
+
+/// Bytes before the first sample: the RIFF header, a 16-byte `fmt ` chunk
+/// and the `data` chunk's header.
+const HEADER_LEN: u32 = 44;
+
+/// Writes the emulated audio to a stereo WAV file as it's drained.
+///
+/// The header goes out first with zero lengths, so a recording can be
+/// stopped at any point: [`finish`](Self::finish) goes back and fills in
+/// how much was written.
+pub struct AudioRecorder<W: Write + Seek = BufWriter<File>> {
+    writer: W,
+    format: RecordingFormat,
+    /// Sample bytes written so far.
+    data_len: u32,
+}
+
+impl AudioRecorder {
+    pub fn create(path: &Path, sample_rate: u32, format: RecordingFormat) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), sample_rate, format)
+    }
+}
+
+impl<W: Write + Seek> AudioRecorder<W> {
+    pub fn new(mut writer: W, sample_rate: u32, format: RecordingFormat) -> io::Result<Self> {
+        let (format_tag, bytes_per_sample): (u16, u16) = match format {
+            RecordingFormat::Pcm16 => (1, 2),
+            RecordingFormat::Float32 => (3, 4),
+        };
+        let block_align = 2 * bytes_per_sample;
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(HEADER_LEN - 8).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?;
+        writer.write_all(&format_tag.to_le_bytes())?;
+        writer.write_all(&2u16.to_le_bytes())?;
+        writer.write_all(&sample_rate.to_le_bytes())?;
+        writer.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+        writer.write_all(&block_align.to_le_bytes())?;
+        writer.write_all(&(bytes_per_sample * 8).to_le_bytes())?;
+        writer.write_all(b"data")?;
+        writer.write_all(&0u32.to_le_bytes())?;
+
+        Ok(Self {
+            writer,
+            format,
+            data_len: 0,
+        })
+    }
+
+    pub fn push(&mut self, samples: &[(f32, f32)]) -> io::Result<()> {
+        for &(left, right) in samples {
+            for sample in [left, right] {
+                match self.format {
+                    RecordingFormat::Pcm16 => {
+                        let sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+                        self.writer.write_all(&sample.to_le_bytes())?;
+                        self.data_len = self.data_len.saturating_add(2);
+                    }
+                    RecordingFormat::Float32 => {
+                        self.writer.write_all(&sample.to_le_bytes())?;
+                        self.data_len = self.data_len.saturating_add(4);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fill in the RIFF and `data` lengths, completing the file.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.writer
+            .write_all(&(HEADER_LEN - 8).saturating_add(self.data_len).to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(HEADER_LEN as u64 - 4))?;
+        self.writer.write_all(&self.data_len.to_le_bytes())?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn u16_at(wav: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes(wav[offset..offset + 2].try_into().unwrap())
+    }
+
+    fn u32_at(wav: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(wav[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn record(format: RecordingFormat, samples: &[(f32, f32)]) -> Vec<u8> {
+        let mut recorder = AudioRecorder::new(Cursor::new(Vec::new()), 48000, format).unwrap();
+        recorder.push(samples).unwrap();
+        recorder.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn finishing_fills_in_the_lengths() {
+        let wav = record(RecordingFormat::Pcm16, &[(0.0, 0.0); 3]);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(&wav, 40), 3 * 2 * 2);
+        assert_eq!(wav.len(), 44 + 12);
+    }
+
+    #[test]
+    fn an_empty_recording_is_still_a_valid_file() {
+        let wav = record(RecordingFormat::Float32, &[]);
+        assert_eq!(wav.len(), 44);
+        assert_eq!(u32_at(&wav, 4), 36);
+        assert_eq!(u32_at(&wav, 40), 0);
+    }
+
+    #[test]
+    fn pcm16_scales_and_clamps_samples() {
+        let wav = record(RecordingFormat::Pcm16, &[(1.0, -0.5), (2.0, -2.0)]);
+        assert_eq!(u16_at(&wav, 20), 1, "PCM");
+        assert_eq!(u16_at(&wav, 34), 16, "bits per sample");
+        assert_eq!(u32_at(&wav, 28), 48000 * 4, "byte rate");
+        let samples: Vec<i16> = wav[44..]
+            .chunks_exact(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect();
+        assert_eq!(samples, [i16::MAX, -16384, i16::MAX, -i16::MAX]);
+    }
+
+    #[test]
+    fn float32_keeps_samples_as_they_are() {
+        let wav = record(RecordingFormat::Float32, &[(0.25, -0.75)]);
+        assert_eq!(u16_at(&wav, 20), 3, "IEEE float");
+        assert_eq!(u16_at(&wav, 22), 2, "channels");
+        assert_eq!(u32_at(&wav, 24), 48000, "sample rate");
+        assert_eq!(u16_at(&wav, 32), 8, "block align");
+        assert_eq!(u16_at(&wav, 34), 32, "bits per sample");
+        assert_eq!(f32::from_le_bytes(wav[44..48].try_into().unwrap()), 0.25);
+        assert_eq!(f32::from_le_bytes(wav[48..52].try_into().unwrap()), -0.75);
+    }
+}
@@ -1,15 +1,19 @@
 use missingno_gb::{
-    BootRom, Console, Dmg, GameBoy, Model, cartridge::Cartridge, execute::StepResult,
-    joypad::Button, ppu::types::palette::Palette, serial_transfer::SerialLink, sgb::MaskMode,
+    BootRom, Console, Dmg, DmgModel, GameBoy, Model, Warning, cartridge::Cartridge,
+    execute::StepResult, joypad::Button, ppu::types::palette::Palette, serial_transfer::SerialLink,
+    sgb::MaskMode,
 };
 use missingno_gbc::{Cgb, GameBoyColor};
 
 use crate::app::library::activity::FrameCapture;
 use crate::app::screen::{CgbScreen, GameBoyScreen, ScreenDisplay, SgbScreen};
+use crate::app::settings::ConsoleModel;
 use crate::render::cram_palettes;
 
-/// The console a loaded game runs on, picked from the cartridge header:
-/// CGB-aware ROMs get the CGB core, everything else the DMG core.
+/// The console a loaded game runs on. Picked from the cartridge header
+/// unless the user chose a model: CGB-aware ROMs get the CGB core,
+/// everything else the DMG core (as an SGB when the game supports one).
+#[derive(Clone)]
 pub enum AnyConsole {
     Dmg(GameBoy),
     Cgb(GameBoyColor),
@@ -27,19 +31,47 @@ impl From<GameBoyColor> for AnyConsole {
     }
 }
 
+/// A CGB-only cartridge was asked to run on the Game Boy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequiresCgb;
+
+impl std::fmt::Display for RequiresCgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "this game only runs on a Game Boy Color")
+    }
+}
+
 impl AnyConsole {
-    pub fn new(cartridge: Cartridge, boot_rom: Option<BootRom>) -> Self {
-        if cartridge.is_cgb() {
+    pub fn new(
+        cartridge: Cartridge,
+        boot_rom: Option<BootRom>,
+        model: ConsoleModel,
+    ) -> Result<Self, RequiresCgb> {
+        let cgb = match model {
+            ConsoleModel::Auto => cartridge.supports_cgb(),
+            ConsoleModel::GameBoy if cartridge.cgb_only() => {
+                return Err(RequiresCgb);
+            }
+            ConsoleModel::GameBoy => false,
+            ConsoleModel::GameBoyColor => true,
+        };
+        Ok(if cgb {
             Self::Cgb(GameBoyColor::new(cartridge, boot_rom))
+        } else if cartridge.supports_sgb() {
+            Self::Dmg(GameBoy::new_with_model(cartridge, boot_rom, DmgModel::Sgb))
         } else {
             Self::Dmg(GameBoy::new(cartridge, boot_rom))
-        }
+        })
     }
 
-    pub fn step(&mut self) -> StepResult {
+    pub fn is_cgb(&self) -> bool {
+        matches!(self, Self::Cgb(_))
+    }
+
+    pub fn run_cycles(&mut self, mcycles: u32) -> StepResult {
         match self {
-            Self::Dmg(console) => console.step(),
-            Self::Cgb(console) => console.step(),
+            Self::Dmg(console) => console.run_cycles(mcycles),
+            Self::Cgb(console) => console.run_cycles(mcycles),
         }
     }
 
@@ -50,6 +82,23 @@ impl AnyConsole {
         }
     }
 
+    /// Hand over to the game once the cosmetic boot logo has finished.
+    pub fn finish_logo_boot(&mut self) -> bool {
+        match self {
+            Self::Dmg(console) => console.finish_logo_boot(),
+            Self::Cgb(console) => console.finish_logo_boot(),
+        }
+    }
+
+    /// Cut the cosmetic boot logo short and start the game.
+    pub fn skip_logo_boot(&mut self) {
+        match self {
+            Self::Dmg(console) if console.in_logo_boot() => console.soft_reset(),
+            Self::Cgb(console) if console.in_logo_boot() => console.soft_reset(),
+            _ => {}
+        }
+    }
+
     pub fn press_button(&mut self, button: Button) {
         match self {
             Self::Dmg(console) => console.press_button(button),
@@ -71,6 +120,20 @@ impl AnyConsole {
         }
     }
 
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        match self {
+            Self::Dmg(console) => console.take_warnings(),
+            Self::Cgb(console) => console.take_warnings(),
+        }
+    }
+
+    pub fn set_audio_output_rate(&mut self, hz: u32) {
+        match self {
+            Self::Dmg(console) => console.audio_mut().set_output_rate(hz),
+            Self::Cgb(console) => console.audio_mut().set_output_rate(hz),
+        }
+    }
+
     pub fn set_link(&mut self, link: Box<dyn SerialLink>) {
         match self {
             Self::Dmg(console) => console.set_link(link),
@@ -78,6 +141,16 @@ impl AnyConsole {
         }
     }
 
+    /// Carry on from a save state of the same kind of console, keeping this
+    /// one's link, audio output rate and channel mutes.
+    pub fn restore(&mut self, state: AnyConsole) {
+        match (self, state) {
+            (Self::Dmg(console), AnyConsole::Dmg(state)) => console.restore(state),
+            (Self::Cgb(console), AnyConsole::Cgb(state)) => console.restore(state),
+            _ => unreachable!("save states only load into the console they came from"),
+        }
+    }
+
     pub fn cartridge(&self) -> &Cartridge {
         match self {
             Self::Dmg(console) => console.cartridge(),
@@ -227,3 +300,50 @@ impl ConsoleUi for Cgb {
         FrameCapture::capture_cgb(console.screen())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cartridge(cgb_flag: u8) -> Cartridge {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x143] = cgb_flag;
+        Cartridge::new(rom, None).unwrap()
+    }
+
+    #[test]
+    fn cgb_only_game_refuses_game_boy() {
+        let error = AnyConsole::new(cartridge(0xC0), None, ConsoleModel::GameBoy)
+            .err()
+            .expect("a CGB-only game started on a Game Boy");
+        assert_eq!(error.to_string(), "this game only runs on a Game Boy Color");
+    }
+
+    #[test]
+    fn automatic_follows_the_header() {
+        let console = AnyConsole::new(cartridge(0xC0), None, ConsoleModel::Auto);
+        assert!(matches!(console, Ok(AnyConsole::Cgb(_))));
+        let console = AnyConsole::new(cartridge(0x00), None, ConsoleModel::Auto);
+        assert!(matches!(console, Ok(AnyConsole::Dmg(_))));
+    }
+
+    #[test]
+    fn chosen_model_overrides_the_header() {
+        let console = AnyConsole::new(cartridge(0x80), None, ConsoleModel::GameBoy);
+        assert!(matches!(console, Ok(AnyConsole::Dmg(_))));
+        let console = AnyConsole::new(cartridge(0x00), None, ConsoleModel::GameBoyColor);
+        assert!(matches!(console, Ok(AnyConsole::Cgb(_))));
+    }
+
+    #[test]
+    fn sgb_games_start_on_an_sgb() {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x146] = 0x03;
+        let cartridge = Cartridge::new(rom, None).unwrap();
+        let Ok(AnyConsole::Dmg(console)) = AnyConsole::new(cartridge, None, ConsoleModel::Auto)
+        else {
+            panic!("an SGB game started on a Game Boy Color");
+        };
+        assert_eq!(console.dmg_model(), DmgModel::Sgb);
+    }
+}
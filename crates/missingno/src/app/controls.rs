@@ -7,8 +7,11 @@ use iced::{
     stream,
 };
 
-use crate::app;
-use crate::app::settings::{Action, Bindings};
+use crate::app::{
+    self, emulator,
+    save_states::{self, Slot},
+    settings::{Action, Bindings},
+};
 use missingno_gb::joypad;
 
 /// Current keyboard bindings, updated from settings.
@@ -24,31 +27,82 @@ pub fn update_bindings(keyboard: &Bindings, gamepad: &Bindings) {
 
 pub fn event_handler(
     event: Event,
-    _status: event::Status,
+    status: event::Status,
     _window: iced::window::Id,
 ) -> Option<app::Message> {
+    let Event::Keyboard(event) = event else {
+        return None;
+    };
     let guard = KEYBOARD_BINDINGS.lock().unwrap();
-    let bindings = guard.as_ref()?;
+    key_message(&event, status, guard.as_ref()?)
+}
+
+/// The message for a key event under `bindings`, falling back to the save
+/// state hotkeys. OS auto-repeat is dropped so a held button stays pressed
+/// rather than flickering, and presses a focused text field captured are
+/// left to it. Releases always go through, so a button can't stick down if
+/// focus moves while it's held.
+fn key_message(
+    event: &keyboard::Event,
+    status: event::Status,
+    bindings: &Bindings,
+) -> Option<app::Message> {
     match event {
-        Event::Keyboard(keyboard::Event::KeyPressed { key, .. }) => {
-            let key_str = key_to_string(&key)?;
-            let action = bindings.find_action(&key_str)?;
-            Some(action_to_press_message(action))
-        }
-        Event::Keyboard(keyboard::Event::KeyReleased { key, .. }) => {
-            let key_str = key_to_string(&key)?;
-            let action = bindings.find_action(&key_str)?;
-            // Only game buttons produce release messages
-            if action.is_game_button() {
-                Some(app::Message::ReleaseButton(action_to_joypad(action)?))
-            } else {
-                None
+        keyboard::Event::KeyPressed {
+            key,
+            modifiers,
+            repeat,
+            ..
+        } => {
+            if *repeat || status == event::Status::Captured {
+                return None;
+            }
+            let key_str = key_to_string(key)?;
+            match bindings.find_action(&key_str) {
+                Some(action) => Some(action_to_press_message(action)),
+                None => save_state_message(key, *modifiers),
             }
         }
+        keyboard::Event::KeyReleased { key, .. } => {
+            let key_str = key_to_string(key)?;
+            action_to_release_message(bindings.find_action(&key_str)?)
+        }
         _ => None,
     }
 }
 
+/// F5 quicksaves and F9 quickloads. The numbered slots take a modifier so
+/// they stay clear of those: Ctrl+F1–F8 saves to a slot, Shift+F1–F8 loads it.
+fn save_state_message(key: &Key, modifiers: keyboard::Modifiers) -> Option<app::Message> {
+    const KEYS: [key::Named; save_states::SLOTS] = [
+        key::Named::F1,
+        key::Named::F2,
+        key::Named::F3,
+        key::Named::F4,
+        key::Named::F5,
+        key::Named::F6,
+        key::Named::F7,
+        key::Named::F8,
+    ];
+    let Key::Named(named) = key else {
+        return None;
+    };
+    let slot = || KEYS.iter().position(|k| k == named).map(Slot::Numbered);
+    if modifiers.shift() {
+        slot().map(app::Message::LoadState)
+    } else if modifiers.control() {
+        slot().map(app::Message::SaveState)
+    } else if modifiers.is_empty() {
+        match named {
+            key::Named::F5 => Some(app::Message::SaveState(Slot::Quick)),
+            key::Named::F9 => Some(app::Message::LoadState(Slot::Quick)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
 /// Convert an action press into the appropriate app message.
 fn action_to_press_message(action: Action) -> app::Message {
     match action {
@@ -61,12 +115,22 @@ fn action_to_press_message(action: Action) -> app::Message {
         Action::Screenshot => app::Message::TakeScreenshot,
         Action::ToggleFullscreen => app::Message::ToggleFullscreen,
         Action::Pause => app::Message::TogglePause,
+        Action::FastForward => emulator::Message::SetFastForward(true).into(),
         _ => unreachable!(),
     }
 }
 
+/// The message for letting go of an action. Only game buttons and held
+/// emulator actions care.
+fn action_to_release_message(action: Action) -> Option<app::Message> {
+    match action {
+        Action::FastForward => Some(emulator::Message::SetFastForward(false).into()),
+        action => Some(app::Message::ReleaseButton(action_to_joypad(action)?)),
+    }
+}
+
 /// Map a game button action to a joypad button. Returns None for non-game actions.
-fn action_to_joypad(action: Action) -> Option<joypad::Button> {
+pub fn action_to_joypad(action: Action) -> Option<joypad::Button> {
     match action {
         Action::GbA => Some(joypad::Button::A),
         Action::GbB => Some(joypad::Button::B),
@@ -113,9 +177,8 @@ pub fn capture_event_handler(
                 Some(app::Message::Settings(
                     super::settings::view::Message::CancelCapture,
                 ))
-            } else if key == Key::Named(key::Named::Backspace)
-                || key == Key::Named(key::Named::Delete)
-            {
+            } else if key == Key::Named(key::Named::Delete) {
+                // Backspace is left bindable: it's Select by default.
                 Some(app::Message::Settings(
                     super::settings::view::Message::ClearBinding,
                 ))
@@ -159,13 +222,11 @@ pub fn gamepad_subscription() -> Subscription<app::Message> {
                         }
                         gilrs::EventType::ButtonReleased(button, ..) => {
                             if let Some(button_str) = gamepad_button_to_string(button) {
-                                if let Some(action) = bindings.find_action(&button_str) {
-                                    if action.is_game_button() {
-                                        if let Some(btn) = action_to_joypad(action) {
-                                            let _ =
-                                                sender.try_send(app::Message::ReleaseButton(btn));
-                                        }
-                                    }
+                                if let Some(message) = bindings
+                                    .find_action(&button_str)
+                                    .and_then(action_to_release_message)
+                                {
+                                    let _ = sender.try_send(message);
                                 }
                             }
                         }
@@ -335,3 +396,245 @@ fn gamepad_button_to_string(button: gilrs::Button) -> Option<String> {
     };
     Some(s.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use iced::keyboard::{Location, Modifiers, key::Physical};
+    use missingno_gb::{GameBoy, cartridge::Cartridge};
+
+    use super::*;
+    use crate::app::{console::AnyConsole, emulator::Emulator};
+
+    fn pressed(named: key::Named, repeat: bool) -> keyboard::Event {
+        keyboard::Event::KeyPressed {
+            key: Key::Named(named),
+            modified_key: Key::Named(named),
+            physical_key: Physical::Unidentified(key::NativeCode::Unidentified),
+            location: Location::Standard,
+            modifiers: Modifiers::default(),
+            text: None,
+            repeat,
+        }
+    }
+
+    fn character(c: &str) -> keyboard::Event {
+        keyboard::Event::KeyPressed {
+            key: Key::Character(c.into()),
+            modified_key: Key::Character(c.into()),
+            physical_key: Physical::Unidentified(key::NativeCode::Unidentified),
+            location: Location::Standard,
+            modifiers: Modifiers::default(),
+            text: None,
+            repeat: false,
+        }
+    }
+
+    fn released(named: key::Named) -> keyboard::Event {
+        keyboard::Event::KeyReleased {
+            key: Key::Named(named),
+            modified_key: Key::Named(named),
+            physical_key: Physical::Unidentified(key::NativeCode::Unidentified),
+            location: Location::Standard,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    fn message(event: keyboard::Event, status: event::Status) -> Option<app::Message> {
+        key_message(&event, status, &Bindings::default_keyboard())
+    }
+
+    fn modified(named: key::Named, modifiers: Modifiers) -> keyboard::Event {
+        let keyboard::Event::KeyPressed {
+            key,
+            modified_key,
+            physical_key,
+            location,
+            text,
+            repeat,
+            ..
+        } = pressed(named, false)
+        else {
+            unreachable!()
+        };
+        keyboard::Event::KeyPressed {
+            key,
+            modified_key,
+            physical_key,
+            location,
+            modifiers,
+            text,
+            repeat,
+        }
+    }
+
+    const RIGHT: joypad::Button = joypad::Button::DirectionalPad(joypad::DirectionalPad::Right);
+
+    #[test]
+    fn arrow_keys_press_and_release_the_dpad() {
+        let press = message(
+            pressed(key::Named::ArrowRight, false),
+            event::Status::Ignored,
+        );
+        assert!(matches!(press, Some(app::Message::PressButton(RIGHT))));
+        let release = message(released(key::Named::ArrowRight), event::Status::Ignored);
+        assert!(matches!(release, Some(app::Message::ReleaseButton(RIGHT))));
+    }
+
+    #[test]
+    fn ctrl_saves_and_shift_loads_numbered_slots() {
+        let save = message(
+            modified(key::Named::F1, Modifiers::CTRL),
+            event::Status::Ignored,
+        );
+        assert!(matches!(
+            save,
+            Some(app::Message::SaveState(Slot::Numbered(0)))
+        ));
+        let load = message(
+            modified(key::Named::F8, Modifiers::SHIFT),
+            event::Status::Ignored,
+        );
+        assert!(matches!(
+            load,
+            Some(app::Message::LoadState(Slot::Numbered(7)))
+        ));
+        let bare = message(pressed(key::Named::F1, false), event::Status::Ignored);
+        assert!(bare.is_none(), "slots need a modifier");
+        // F11 and F12 stay bound to fullscreen and screenshots.
+        let screenshot = message(pressed(key::Named::F12, false), event::Status::Ignored);
+        assert!(matches!(screenshot, Some(app::Message::TakeScreenshot)));
+    }
+
+    #[test]
+    fn f5_quicksaves_and_f9_quickloads() {
+        let save = message(pressed(key::Named::F5, false), event::Status::Ignored);
+        assert!(matches!(save, Some(app::Message::SaveState(Slot::Quick))));
+        let load = message(pressed(key::Named::F9, false), event::Status::Ignored);
+        assert!(matches!(load, Some(app::Message::LoadState(Slot::Quick))));
+        let slot = message(
+            modified(key::Named::F5, Modifiers::CTRL),
+            event::Status::Ignored,
+        );
+        assert!(matches!(
+            slot,
+            Some(app::Message::SaveState(Slot::Numbered(4)))
+        ));
+    }
+
+    #[test]
+    fn default_keys_cover_every_button() {
+        let buttons = [
+            (key::Named::Enter, joypad::Button::Start),
+            (key::Named::Backspace, joypad::Button::Select),
+            (key::Named::ArrowRight, RIGHT),
+        ];
+        for (named, button) in buttons {
+            let press = message(pressed(named, false), event::Status::Ignored);
+            assert!(
+                matches!(press, Some(app::Message::PressButton(pressed)) if pressed == button),
+                "{named:?}"
+            );
+        }
+        let a = message(character("x"), event::Status::Ignored);
+        assert!(matches!(
+            a,
+            Some(app::Message::PressButton(joypad::Button::A))
+        ));
+        let b = message(character("z"), event::Status::Ignored);
+        assert!(matches!(
+            b,
+            Some(app::Message::PressButton(joypad::Button::B))
+        ));
+    }
+
+    #[test]
+    fn unbound_keys_are_ignored() {
+        let unbound = message(pressed(key::Named::F10, false), event::Status::Ignored);
+        assert!(unbound.is_none());
+        let release = message(released(key::Named::F10), event::Status::Ignored);
+        assert!(release.is_none());
+    }
+
+    #[test]
+    fn fast_forward_is_held() {
+        let press = message(pressed(key::Named::Tab, false), event::Status::Ignored);
+        assert!(matches!(
+            press,
+            Some(app::Message::Emulator(emulator::Message::SetFastForward(
+                true
+            )))
+        ));
+        let release = message(released(key::Named::Tab), event::Status::Ignored);
+        assert!(matches!(
+            release,
+            Some(app::Message::Emulator(emulator::Message::SetFastForward(
+                false
+            )))
+        ));
+    }
+
+    #[test]
+    fn auto_repeat_is_ignored() {
+        let repeat = message(
+            pressed(key::Named::ArrowRight, true),
+            event::Status::Ignored,
+        );
+        assert!(repeat.is_none());
+        let pause = message(pressed(key::Named::Space, true), event::Status::Ignored);
+        assert!(pause.is_none(), "a held pause key would toggle repeatedly");
+    }
+
+    #[test]
+    fn keys_typed_into_a_text_field_are_ignored() {
+        let typed = message(
+            pressed(key::Named::ArrowRight, false),
+            event::Status::Captured,
+        );
+        assert!(typed.is_none());
+        // Releases still reach the game so nothing sticks down.
+        let release = message(released(key::Named::ArrowRight), event::Status::Captured);
+        assert!(matches!(release, Some(app::Message::ReleaseButton(RIGHT))));
+    }
+
+    /// A game loop that selects the d-pad and copies P1 to $C000 each pass.
+    fn emulator() -> Emulator {
+        let main = [
+            0x3E, 0x20, // LD A,$20
+            0xE0, 0x00, // LDH ($00),A
+            0xF0, 0x00, // LDH A,($00)
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x18, 0xF9, // JR -7
+        ];
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+        rom[0x0150..0x0150 + main.len()].copy_from_slice(&main);
+        let console = GameBoy::new(Cartridge::new(rom, None).unwrap(), None);
+        Emulator::new(AnyConsole::Dmg(console), false)
+    }
+
+    fn p1(emulator: &mut Emulator) -> u8 {
+        let _ = emulator.update(crate::app::emulator::Message::EmulateFrame);
+        match emulator.console() {
+            AnyConsole::Dmg(console) => console.peek(0xC000) & 0x0F,
+            AnyConsole::Cgb(console) => console.peek(0xC000) & 0x0F,
+        }
+    }
+
+    #[test]
+    fn game_sees_arrow_keys_in_the_emulator_view() {
+        let mut emulator = emulator();
+        assert_eq!(p1(&mut emulator), 0x0F, "nothing held");
+
+        let Some(app::Message::PressButton(button)) = message(
+            pressed(key::Named::ArrowRight, false),
+            event::Status::Ignored,
+        ) else {
+            panic!("right arrow isn't bound to a button");
+        };
+        emulator.press_button(button);
+        assert_eq!(p1(&mut emulator), 0x0E, "right reads low");
+
+        emulator.release_button(button);
+        assert_eq!(p1(&mut emulator), 0x0F);
+    }
+}
@@ -3,7 +3,7 @@ use iced::{
     widget::{checkbox, column, row, text},
 };
 
-use crate::app::Message;
+use crate::app::{Message, debugger};
 use missingno_gb::audio::channels::{
     Enabled,
     noise::NoiseChannel,
@@ -13,33 +13,37 @@ use missingno_gb::audio::channels::{
     wave::WaveChannel,
 };
 
-pub fn ch1(channel: &PulseSweepChannel) -> Element<'static, Message> {
+pub fn ch1(channel: &PulseSweepChannel, muted: bool) -> Element<'static, Message> {
     column![
         enabled("Channel 1", channel.enabled),
+        mute(0, muted),
         volume_and_envelope(channel.volume_and_envelope)
     ]
     .into()
 }
 
-pub fn ch2(channel: &PulseChannel) -> Element<'static, Message> {
+pub fn ch2(channel: &PulseChannel, muted: bool) -> Element<'static, Message> {
     column![
         enabled("Channel 2", channel.enabled),
+        mute(1, muted),
         volume_and_envelope(channel.volume_and_envelope)
     ]
     .into()
 }
 
-pub fn ch3(channel: &WaveChannel) -> Element<'static, Message> {
+pub fn ch3(channel: &WaveChannel, muted: bool) -> Element<'static, Message> {
     column![
         enabled("Channel 3", channel.enabled),
+        mute(2, muted),
         text!("Vol {}%", (channel.volume.volume() * 100.0) as u8)
     ]
     .into()
 }
 
-pub fn ch4(channel: &NoiseChannel) -> Element<'static, Message> {
+pub fn ch4(channel: &NoiseChannel, muted: bool) -> Element<'static, Message> {
     column![
         enabled("Channel 4", channel.enabled),
+        mute(3, muted),
         volume_and_envelope(channel.volume_and_envelope)
     ]
     .into()
@@ -61,6 +65,14 @@ pub fn enabled(label: &'static str, enabled: Enabled) -> Element<'static, Messag
     .into()
 }
 
+/// Silences `channel` (0 for CH1) in the mix without stopping it.
+fn mute(channel: usize, muted: bool) -> Element<'static, Message> {
+    checkbox(muted)
+        .label("Mute")
+        .on_toggle(move |muted| debugger::Message::SetChannelMuted(channel, muted).into())
+        .into()
+}
+
 fn volume_and_envelope(register: VolumeAndEnvelope) -> Element<'static, Message> {
     if register.sweep_pace() == 0 {
         text!("Vol static")
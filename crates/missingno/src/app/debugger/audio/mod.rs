@@ -1,13 +1,20 @@
-use iced::widget::{column, pane_grid, row, rule, slider, text};
+use iced::{
+    Element,
+    widget::{column, pane_grid, row, rule, slider, text},
+};
 
 use crate::app::{
     Message,
     debugger::panes::{checkbox_title_bar, pane},
-    ui::sizes::{l, s},
+    ui::{
+        palette,
+        sizes::{l, s},
+    },
 };
-use missingno_gb::audio::Audio;
+use missingno_gb::audio::{Audio, scope::Scope};
 
 mod channels;
+mod scope;
 
 pub struct AudioPane;
 
@@ -36,18 +43,32 @@ impl AudioPane {
                 ]
                 .spacing(l()),
                 row![
-                    channels::ch1(&audio.channels().ch1),
+                    channels::ch1(&audio.channels().ch1, audio.channel_muted(0)),
                     rule::vertical(1),
-                    channels::ch2(&audio.channels().ch2),
+                    channels::ch2(&audio.channels().ch2, audio.channel_muted(1)),
                     rule::vertical(1),
-                    channels::ch3(&audio.channels().ch3),
+                    channels::ch3(&audio.channels().ch3, audio.channel_muted(2)),
                     rule::vertical(1),
-                    channels::ch4(&audio.channels().ch4),
+                    channels::ch4(&audio.channels().ch4, audio.channel_muted(3)),
                 ]
                 .spacing(s())
             ]
+            .extend(audio.scope().map(scopes))
             .spacing(s())
             .into(),
         )
     }
 }
+
+fn scopes(history: &Scope) -> Element<'static, Message> {
+    column![
+        rule::horizontal(1),
+        scope::channel("Channel 1", history.channel(0), palette::PURPLE),
+        scope::channel("Channel 2", history.channel(1), palette::TEAL),
+        scope::channel("Channel 3", history.channel(2), palette::PEACH),
+        scope::channel("Channel 4", history.channel(3), palette::GREEN),
+        scope::mixed(history.mixed()),
+    ]
+    .spacing(s())
+    .into()
+}
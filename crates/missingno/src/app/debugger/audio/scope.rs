@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+
+use iced::{
+    Color, Element, Length, Point, Rectangle, Renderer, Theme, mouse,
+    widget::{
+        canvas::{self, Frame, Geometry, Path, Stroke},
+        column, text,
+    },
+};
+
+use crate::app::{Message, ui::palette};
+
+const TRACE_HEIGHT: f32 = 32.0;
+
+/// One oscilloscope trace. Samples are normalised to 0.0-1.0 and drawn
+/// oldest first, left to right.
+struct Trace {
+    samples: Vec<f32>,
+    color: Color,
+}
+
+impl canvas::Program<Message> for Trace {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        frame.stroke(
+            &Path::line(
+                Point::new(0.0, bounds.height / 2.0),
+                Point::new(bounds.width, bounds.height / 2.0),
+            ),
+            Stroke::default()
+                .with_color(palette::SURFACE2)
+                .with_width(1.0),
+        );
+
+        if self.samples.len() > 1 {
+            let step = bounds.width / (self.samples.len() - 1) as f32;
+            let trace = Path::new(|path| {
+                for (i, sample) in self.samples.iter().enumerate() {
+                    let point = Point::new(i as f32 * step, (1.0 - sample) * bounds.height);
+                    if i == 0 {
+                        path.move_to(point);
+                    } else {
+                        path.line_to(point);
+                    }
+                }
+            });
+            frame.stroke(
+                &trace,
+                Stroke::default().with_color(self.color).with_width(1.0),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+pub fn channel(
+    label: &'static str,
+    samples: &VecDeque<u8>,
+    color: Color,
+) -> Element<'static, Message> {
+    trace(
+        label,
+        samples.iter().map(|&s| s as f32 / 15.0).collect(),
+        color,
+    )
+}
+
+pub fn mixed(samples: &VecDeque<f32>) -> Element<'static, Message> {
+    trace("Mixed", samples.iter().copied().collect(), palette::TEXT)
+}
+
+fn trace(label: &'static str, samples: Vec<f32>, color: Color) -> Element<'static, Message> {
+    column![
+        text(label).size(11.0).color(palette::MUTED),
+        canvas::Canvas::new(Trace { samples, color })
+            .width(Length::Fill)
+            .height(TRACE_HEIGHT),
+    ]
+    .into()
+}
@@ -0,0 +1,139 @@
+use iced::{
+    Background, Border, Color, Element, Length,
+    alignment::Vertical,
+    widget::{Column, Row, button, column, container, pane_grid, row, text, toggler},
+};
+
+use crate::app::{
+    self,
+    debugger::{
+        self,
+        panes::{pane, title_bar_with_detail},
+    },
+    ui::{
+        fonts, palette,
+        sizes::{l, m, s},
+    },
+};
+use missingno_gb::{Console, Model};
+
+const CELL_SIZE: f32 = 12.0;
+
+/// Where the CPU has been reading and writing lately, one cell per
+/// 256-byte page: each row is 4KiB of the address space.
+pub struct HeatmapPane;
+
+impl HeatmapPane {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn content<'a, M: Model>(
+        &self,
+        console: &'a Console<M>,
+    ) -> pane_grid::Content<'a, app::Message> {
+        let heatmap = console.heatmap();
+        let detail = row![
+            toggler(heatmap.is_some())
+                .label("count")
+                .size(14.0)
+                .on_toggle(|on| debugger::Message::SetHeatmap(on).into()),
+            button(text("Clear").font(fonts::monospace()).size(11.0))
+                .on_press(debugger::Message::ClearHeatmap.into())
+                .style(button::text)
+                .padding(0),
+        ]
+        .spacing(m());
+
+        let body: Element<'a, app::Message> = match heatmap {
+            Some(heatmap) => row![
+                grid(
+                    "Reads",
+                    palette::TEAL,
+                    |page| heatmap.reads(page),
+                    heatmap.max_reads()
+                ),
+                grid(
+                    "Writes",
+                    palette::PEACH,
+                    |page| heatmap.writes(page),
+                    heatmap.max_writes()
+                ),
+            ]
+            .spacing(l())
+            .into(),
+            None => text("Turn on counting to see where memory is busy.")
+                .size(13.0)
+                .color(palette::MUTED)
+                .into(),
+        };
+
+        pane(title_bar_with_detail("Heatmap", detail), body)
+    }
+}
+
+fn grid<'a>(
+    label: &'a str,
+    color: Color,
+    count: impl Fn(u8) -> u32,
+    max: u32,
+) -> Element<'a, app::Message> {
+    let rows = (0..16u8).map(|high| {
+        let cells = (0..16u8).map(|low| cell(color, heat(count(high << 4 | low), max)));
+        row![
+            text(format!("${:X}000", high))
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(palette::OVERLAY0)
+                .width(40.0),
+            Row::from_iter(cells).spacing(1.0),
+        ]
+        .align_y(Vertical::Center)
+        .into()
+    });
+    column![
+        text(label).size(11.0).color(palette::MUTED),
+        Column::from_iter(rows).spacing(1.0),
+    ]
+    .spacing(s())
+    .into()
+}
+
+fn cell<'a>(color: Color, heat: f32) -> Element<'a, app::Message> {
+    container("")
+        .width(Length::Fixed(CELL_SIZE))
+        .height(Length::Fixed(CELL_SIZE))
+        .style(move |_: &iced::Theme| container::Style {
+            background: Some(Background::Color(Color {
+                a: heat.max(0.04),
+                ..color
+            })),
+            border: Border::default().rounded(2.0),
+            ..Default::default()
+        })
+        .into()
+}
+
+/// How hot a page looks, from 0 to 1. Counts span several orders of
+/// magnitude (a tight loop's fetches against the odd register write), so
+/// the scale is logarithmic to keep quiet pages visible.
+fn heat(count: u32, max: u32) -> f32 {
+    if count == 0 {
+        return 0.0;
+    }
+    ((count as f32).ln_1p() / (max as f32).ln_1p()).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heat_is_logarithmic_up_to_the_busiest_page() {
+        assert_eq!(heat(0, 1000), 0.0);
+        assert_eq!(heat(1000, 1000), 1.0);
+        let quiet = heat(10, 10_000);
+        assert!(quiet > 0.2 && quiet < 0.3, "{quiet}");
+        assert!(heat(100, 10_000) > quiet);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+
+use iced::{
+    Element, Length,
+    alignment::Horizontal,
+    widget::{Row, button, column, pane_grid, scrollable, shader, text},
+};
+
+use crate::app::{
+    self,
+    console::ConsoleUi,
+    debugger::{
+        self,
+        panes::{pane, title_bar_with_detail},
+    },
+    screen::{ScreenDisplay, ScreenView},
+    ui::{fonts, palette, sizes::s},
+};
+use missingno_gb::{Console, ppu::types::palette::PaletteChoice};
+
+/// Frames kept. Each holds a full console clone, so this bounds memory.
+const MAX_FRAMES: usize = 16;
+
+const THUMBNAIL_WIDTH: f32 = 80.0;
+const THUMBNAIL_HEIGHT: f32 = 72.0;
+
+struct Frame<M: ConsoleUi> {
+    number: u64,
+    console: Console<M>,
+    display: ScreenDisplay,
+    thumbnail: ScreenView,
+}
+
+/// The last few rendered frames, each paired with the console state at the
+/// end of that frame so the debugger can step back to it.
+pub struct FrameHistory<M: ConsoleUi> {
+    frames: VecDeque<Frame<M>>,
+    palette: PaletteChoice,
+}
+
+impl<M: ConsoleUi> FrameHistory<M> {
+    pub fn new() -> Self {
+        Self {
+            frames: VecDeque::with_capacity(MAX_FRAMES),
+            palette: PaletteChoice::default(),
+        }
+    }
+
+    pub fn record(&mut self, number: u64, console: &Console<M>, display: ScreenDisplay) {
+        if self.frames.len() == MAX_FRAMES {
+            self.frames.pop_front();
+        }
+
+        let mut thumbnail = ScreenView::new();
+        thumbnail.palette = self.palette;
+        thumbnail.apply(display.clone());
+
+        self.frames.push_back(Frame {
+            number,
+            console: console.clone(),
+            display,
+            thumbnail,
+        });
+    }
+
+    /// The saved state and display for `number`. Later frames are dropped:
+    /// execution resumes from here and will record them afresh.
+    pub fn rewind_to(&mut self, number: u64) -> Option<(Console<M>, ScreenDisplay)> {
+        let index = self.frames.iter().position(|f| f.number == number)?;
+        self.frames.truncate(index + 1);
+        let frame = &self.frames[index];
+        Some((frame.console.clone(), frame.display.clone()))
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn set_palette(&mut self, palette: PaletteChoice) {
+        self.palette = palette;
+        for frame in &mut self.frames {
+            frame.thumbnail.palette = palette;
+        }
+    }
+}
+
+pub struct FrameHistoryPane;
+
+impl FrameHistoryPane {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn content<'a, M: ConsoleUi>(
+        &self,
+        history: &'a FrameHistory<M>,
+        current_frame: u64,
+    ) -> pane_grid::Content<'a, app::Message> {
+        let detail = text(format!("{}/{MAX_FRAMES}", history.frames.len()))
+            .font(fonts::monospace())
+            .size(11.0)
+            .color(palette::MUTED);
+
+        let thumbnails = history
+            .frames
+            .iter()
+            .map(|frame| thumbnail(frame, frame.number == current_frame));
+
+        pane(
+            title_bar_with_detail("Frame History", detail),
+            scrollable(Row::from_iter(thumbnails).spacing(s()).padding(s()))
+                .horizontal()
+                .anchor_right()
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+        )
+    }
+}
+
+fn thumbnail<M: ConsoleUi>(frame: &Frame<M>, current: bool) -> Element<'_, app::Message> {
+    let label = text(frame.number.to_string())
+        .font(fonts::monospace())
+        .size(11.0)
+        .color(if current {
+            palette::TEXT
+        } else {
+            palette::MUTED
+        });
+
+    button(
+        column![
+            shader(&frame.thumbnail)
+                .width(THUMBNAIL_WIDTH)
+                .height(THUMBNAIL_HEIGHT),
+            label,
+        ]
+        .spacing(2.0)
+        .align_x(Horizontal::Center),
+    )
+    .on_press(debugger::Message::RewindToFrame(frame.number).into())
+    .style(button::text)
+    .padding(0)
+    .into()
+}
@@ -1,10 +1,9 @@
-use std::collections::BTreeSet;
-
 use iced::{
     Background, Border, Element, Length,
     alignment::Vertical,
+    mouse,
     widget::text::Span,
-    widget::{Column, button, container, pane_grid, rich_text, row, text},
+    widget::{Column, button, container, mouse_area, pane_grid, rich_text, row, text, tooltip},
 };
 
 use crate::app::{
@@ -12,11 +11,18 @@ use crate::app::{
     debugger::{
         self,
         panes::{pane, title_bar, title_bar_with_detail},
+        sidebar::tooltip_style,
     },
     ui::{fonts, palette, sizes::s},
 };
-use missingno_gb::debugger::instructions::{InstructionsIterator, addresses_before};
-use missingno_gb::{Console, Model, cpu::instructions::Instruction};
+use missingno_gb::debugger::{
+    Debugger,
+    instructions::{InstructionsIterator, addresses_before},
+};
+use missingno_gb::{
+    Model,
+    cpu::instructions::{Address, Instruction, Jump, jump::Location},
+};
 
 // Syntax highlighting — mapped from palette colors.
 use palette::{
@@ -41,23 +47,31 @@ impl InstructionsPane {
 
     pub fn content<M: Model>(
         &self,
-        memory: &Console<M>,
-        pc: u16,
-        breakpoints: &BTreeSet<u16>,
+        debugger: &Debugger<M>,
     ) -> pane_grid::Content<'_, app::Message> {
+        let memory = debugger.game_boy();
+        let pc = memory.cpu().ir_address;
+        let breakpoints = debugger.breakpoints();
         let mut instructions = Vec::new();
+        let mut push = |address: u16, decoded: Instruction| {
+            if let Some(name) = debugger.symbol(address) {
+                instructions.push(label_row(name));
+            }
+            let text = with_symbolic_target(address, &decoded, |target| debugger.symbol(target));
+            instructions.push(instruction_row(
+                address,
+                text,
+                address == pc,
+                breakpoints.contains(&address),
+            ));
+        };
 
         // Instructions before PC (backward sweep)
         let before = addresses_before(pc, CONTEXT_BEFORE, memory);
         for &addr in &before {
             let mut iter = InstructionsIterator::new(addr, memory);
             if let Some(decoded) = Instruction::decode(&mut iter) {
-                instructions.push(instruction_row(
-                    addr,
-                    decoded,
-                    false,
-                    breakpoints.contains(&addr),
-                ));
+                push(addr, decoded);
             }
         }
 
@@ -66,12 +80,7 @@ impl InstructionsPane {
         for _ in 0..CONTEXT_AFTER {
             if let Some(address) = iterator.address {
                 if let Some(decoded) = Instruction::decode(&mut iterator) {
-                    instructions.push(instruction_row(
-                        address,
-                        decoded,
-                        address == pc,
-                        breakpoints.contains(&address),
-                    ));
+                    push(address, decoded);
                 } else {
                     break;
                 }
@@ -105,9 +114,51 @@ impl InstructionsPane {
     }
 }
 
+/// The text of `instruction` at `address`, with the target of a jump or
+/// call swapped for its label where `symbol` has one.
+fn with_symbolic_target<'a>(
+    address: u16,
+    instruction: &Instruction,
+    symbol: impl Fn(u16) -> Option<&'a str>,
+) -> String {
+    let formatted = instruction.to_string();
+    let Instruction::Jump(Jump::Jump(_, location) | Jump::Call(_, location)) = instruction else {
+        return formatted;
+    };
+    let target = match location {
+        Location::Address(Address::Fixed(target)) => *target,
+        // Relative to the end of the two-byte `jr`.
+        Location::Address(Address::Relative(offset)) => address
+            .wrapping_add(2)
+            .wrapping_add_signed((*offset).into()),
+        _ => return formatted,
+    };
+    match (
+        symbol(target),
+        formatted.strip_suffix(&location.to_string()),
+    ) {
+        (Some(name), Some(head)) => format!("{head}{name}"),
+        _ => formatted,
+    }
+}
+
+/// A label heading the instruction at its address.
+fn label_row(name: &str) -> Element<'static, app::Message> {
+    container(
+        text(format!("{name}:"))
+            .font(fonts::monospace())
+            .size(13.0)
+            .color(palette::TEAL),
+    )
+    .padding([0.0, s()])
+    .align_y(Vertical::Center)
+    .height(Length::Fixed(ROW_HEIGHT))
+    .into()
+}
+
 fn instruction_row(
     address: u16,
-    instruction: Instruction,
+    instruction: String,
     is_current: bool,
     is_breakpoint: bool,
 ) -> Element<'static, app::Message> {
@@ -144,17 +195,30 @@ fn instruction_row(
         .into(),
     );
 
-    let the_row = row![
-        gutter,
-        text(format!("{:04X}", address))
-            .font(fonts::monospace())
-            .size(13.0)
-            .color(palette::OVERLAY0),
-        highlighted_instruction(&instruction),
-    ]
-    .align_y(Vertical::Center)
-    .spacing(s())
-    .height(Length::Fixed(ROW_HEIGHT));
+    let address_label = tooltip(
+        mouse_area(
+            text(format!("{:04X}", address))
+                .font(fonts::monospace())
+                .size(13.0)
+                .color(palette::OVERLAY0),
+        )
+        .on_press(debugger::Message::RunToCursor(address).into())
+        .on_right_press(debugger::Message::SetPc(address).into())
+        .interaction(mouse::Interaction::Pointer),
+        container(
+            text("Click: run to here · Right-click: set PC here")
+                .font(fonts::monospace())
+                .size(13.0),
+        )
+        .padding([2.0, s()]),
+        tooltip::Position::Top,
+    )
+    .style(tooltip_style);
+
+    let the_row = row![gutter, address_label, highlighted_instruction(&instruction),]
+        .align_y(Vertical::Center)
+        .spacing(s())
+        .height(Length::Fixed(ROW_HEIGHT));
 
     if is_current {
         container(the_row)
@@ -180,9 +244,8 @@ fn instruction_row(
     }
 }
 
-fn highlighted_instruction(instruction: &Instruction) -> Element<'static, app::Message> {
-    let formatted = instruction.to_string();
-    let spans = tokenize(&formatted);
+fn highlighted_instruction(instruction: &str) -> Element<'static, app::Message> {
+    let spans = tokenize(instruction);
     rich_text(spans).font(fonts::monospace()).size(13.0).into()
 }
 
@@ -261,3 +324,37 @@ fn classify_operand(operand: &str) -> iced::Color {
     // Fallback
     palette::TEXT
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(address: u16, bytes: &[u8]) -> String {
+        let instruction = Instruction::decode(&mut bytes.iter().copied()).unwrap();
+        with_symbolic_target(address, &instruction, |target| match target {
+            0x0150 => Some("Main"),
+            0x4000 => Some("LoadLevel"),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn jump_and_call_targets_are_named() {
+        assert_eq!(text(0x0100, &[0xC3, 0x50, 0x01]), "jp Main");
+        assert_eq!(text(0x0100, &[0xCC, 0x00, 0x40]), "call z, LoadLevel");
+        // $0160 + 2 - 18 = $0150
+        assert_eq!(text(0x0160, &[0x20, 0xEE]), "jp nz, Main");
+    }
+
+    #[test]
+    fn unnamed_targets_and_other_instructions_are_unchanged() {
+        assert_eq!(text(0x0100, &[0xC3, 0x00, 0x02]), "jp $0200");
+        assert_eq!(text(0x0100, &[0xE9]), "jp hl");
+        assert_eq!(
+            text(0x0100, &[0x21, 0x50, 0x01]),
+            Instruction::decode(&mut [0x21, 0x50, 0x01].into_iter())
+                .unwrap()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use iced::{
+    Element, Length,
+    alignment::Vertical,
+    mouse::ScrollDelta,
+    widget::{
+        Column, Row, button, column, container, mouse_area, pane_grid, responsive, row, text,
+        text_input, vertical_slider,
+    },
+};
+
+use crate::app::{
+    self,
+    debugger::{
+        self,
+        panes::{self, PaneMessage, pane, title_bar_with_detail},
+    },
+    ui::{
+        fonts, palette,
+        sizes::{s, xs},
+    },
+};
+use missingno_gb::{Console, Model};
+
+/// Bytes on each row of the dump.
+const ROW_BYTES: usize = 16;
+/// Rows covering the whole address space.
+const ROWS: usize = 0x1_0000 / ROW_BYTES;
+/// Fixed height per row so the rows that fit can be counted.
+const ROW_HEIGHT: f32 = 20.0;
+/// Rows moved per line of mouse wheel.
+const WHEEL_ROWS: f32 = 3.0;
+
+const PC_COLOR: iced::Color = palette::PURPLE;
+const SP_COLOR: iced::Color = palette::PEACH;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Move the dump by this many rows, negative towards $0000.
+    Scroll(isize),
+    ScrollTo(usize),
+    AddressInputChanged(String),
+    GoTo,
+    Select(u16),
+    ValueInputChanged(String),
+    /// The debugger has tried to store the selected byte.
+    Written {
+        address: u16,
+        stored: bool,
+    },
+}
+
+impl From<Message> for app::Message {
+    fn from(message: Message) -> Self {
+        panes::Message::Pane(PaneMessage::Memory(message)).into()
+    }
+}
+
+/// A hex dump of the whole address space as the debugger sees it, with the
+/// bytes at PC and SP picked out. A selected byte can be overwritten where
+/// the debugger allows it.
+pub struct MemoryPane {
+    /// First row shown.
+    top: usize,
+    address_input: String,
+    selected: Option<u16>,
+    value_input: String,
+    error: Option<String>,
+}
+
+impl MemoryPane {
+    pub fn new() -> Self {
+        Self {
+            top: 0,
+            address_input: String::new(),
+            selected: None,
+            value_input: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Scroll(rows) => self.top = self.top.saturating_add_signed(rows).min(ROWS - 1),
+            Message::ScrollTo(row) => self.top = row.min(ROWS - 1),
+            Message::AddressInputChanged(input) => self.address_input = hex_digits(&input, 4),
+            Message::GoTo => {
+                if let Some(address) = parse_hex(&self.address_input) {
+                    self.top = address as usize / ROW_BYTES;
+                    self.select(address);
+                    self.address_input.clear();
+                }
+            }
+            Message::Select(address) => self.select(address),
+            Message::ValueInputChanged(input) => self.value_input = hex_digits(&input, 2),
+            Message::Written {
+                address,
+                stored: true,
+            } => self.select(address.wrapping_add(1)),
+            Message::Written {
+                address,
+                stored: false,
+            } => self.error = Some(format!("${address:04X} can't be edited")),
+        }
+    }
+
+    fn select(&mut self, address: u16) {
+        self.selected = Some(address);
+        self.value_input.clear();
+        self.error = None;
+    }
+
+    pub fn content<'a, M: Model>(
+        &'a self,
+        console: &'a Console<M>,
+    ) -> pane_grid::Content<'a, app::Message> {
+        let pc = console.cpu().ir_address;
+        let sp = console.cpu().stack_pointer;
+        let detail = row![
+            text(format!("PC ${pc:04X}"))
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(PC_COLOR),
+            text(format!("SP ${sp:04X}"))
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(SP_COLOR),
+        ]
+        .spacing(s());
+
+        let dump = responsive(move |size| {
+            let rows = ((size.height / ROW_HEIGHT) as usize).clamp(1, ROWS);
+            let last_top = ROWS - rows;
+            let top = self.top.min(last_top);
+            let lines = Column::from_iter((top..top + rows).map(|row| {
+                let address = (row * ROW_BYTES) as u16;
+                let bytes = console.peek_range(address, ROW_BYTES as u16);
+                dump_row(address, bytes, pc, sp, self.selected)
+            }));
+            // Slider values grow upwards; rows grow downwards.
+            let slider = vertical_slider(
+                0.0..=last_top as f32,
+                (last_top - top) as f32,
+                move |value| Message::ScrollTo(last_top - value as usize).into(),
+            )
+            .width(8.0);
+            row![
+                mouse_area(container(lines).width(Length::Fill))
+                    .on_scroll(|delta| Message::Scroll(scroll_rows(delta)).into()),
+                slider,
+            ]
+            .spacing(xs())
+            .into()
+        });
+
+        pane(
+            title_bar_with_detail("Memory", detail),
+            column![self.toolbar(), dump].spacing(xs()).into(),
+        )
+    }
+
+    fn toolbar(&self) -> Element<'_, app::Message> {
+        let go_to = text_input("Go to", &self.address_input)
+            .font(fonts::monospace())
+            .size(13.0)
+            .width(72.0)
+            .on_input(|input| Message::AddressInputChanged(input).into())
+            .on_submit(Message::GoTo.into());
+
+        let mut toolbar = row![go_to].spacing(s()).align_y(Vertical::Center);
+        if let Some(address) = self.selected {
+            let write = parse_hex(&self.value_input).map(|value| {
+                debugger::Message::WriteMemory {
+                    address,
+                    value: value as u8,
+                }
+                .into()
+            });
+            toolbar = toolbar.push(
+                text(format!("${address:04X} ="))
+                    .font(fonts::monospace())
+                    .size(13.0)
+                    .color(palette::MUTED),
+            );
+            toolbar = toolbar.push(
+                text_input("00", &self.value_input)
+                    .font(fonts::monospace())
+                    .size(13.0)
+                    .width(40.0)
+                    .on_input(|input| Message::ValueInputChanged(input).into())
+                    .on_submit_maybe(write),
+            );
+        }
+        if let Some(error) = &self.error {
+            toolbar = toolbar.push(text(error).size(12.0).color(palette::RED));
+        }
+        toolbar.into()
+    }
+}
+
+fn dump_row<'a>(
+    address: u16,
+    bytes: Vec<u8>,
+    pc: u16,
+    sp: u16,
+    selected: Option<u16>,
+) -> Element<'a, app::Message> {
+    let mut cells = Row::new()
+        .push(
+            text(format!("{address:04X}"))
+                .font(fonts::monospace())
+                .size(13.0)
+                .color(palette::OVERLAY0)
+                .width(40.0),
+        )
+        .spacing(2.0)
+        .align_y(Vertical::Center)
+        .height(Length::Fixed(ROW_HEIGHT));
+
+    for (offset, &byte) in bytes.iter().enumerate() {
+        let address = address.wrapping_add(offset as u16);
+        let color = if address == pc {
+            PC_COLOR
+        } else if address == sp {
+            SP_COLOR
+        } else {
+            palette::TEXT
+        };
+        let is_selected = selected == Some(address);
+        cells = cells.push(
+            button(
+                text(format!("{byte:02X}"))
+                    .font(fonts::monospace())
+                    .size(13.0)
+                    .color(color),
+            )
+            .padding([0.0, 2.0])
+            .style(move |theme, status| {
+                let mut style = button::text(theme, status);
+                if is_selected {
+                    style.background = Some(palette::SURFACE2.into());
+                }
+                style
+            })
+            .on_press(Message::Select(address).into()),
+        );
+    }
+
+    cells
+        .push(
+            text(ascii(&bytes))
+                .font(fonts::monospace())
+                .size(13.0)
+                .color(palette::MUTED),
+        )
+        .into()
+}
+
+/// Printable ASCII as itself, anything else as a dot.
+fn ascii(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            0x20..=0x7E => byte as char,
+            _ => '.',
+        })
+        .collect()
+}
+
+/// Keep only the first `max` hex digits typed.
+fn hex_digits(input: &str, max: usize) -> String {
+    input
+        .chars()
+        .filter(char::is_ascii_hexdigit)
+        .take(max)
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+fn parse_hex(input: &str) -> Option<u16> {
+    u16::from_str_radix(input, 16).ok()
+}
+
+/// Rows to move for a turn of the wheel or a trackpad swipe. Wheel up
+/// moves back through memory.
+fn scroll_rows(delta: ScrollDelta) -> isize {
+    let rows = match delta {
+        ScrollDelta::Lines { y, .. } => y * WHEEL_ROWS,
+        ScrollDelta::Pixels { y, .. } => y / ROW_HEIGHT,
+    };
+    // A slow swipe still moves at least a row.
+    let rows = if rows == 0.0 {
+        0.0
+    } else if rows.abs() < 1.0 {
+        rows.signum()
+    } else {
+        rows.round()
+    };
+    -(rows as isize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn go_to_scrolls_to_and_selects_the_address() {
+        let mut memory = MemoryPane::new();
+        memory.update(Message::AddressInputChanged("$c0x1f".to_string()));
+        assert_eq!(memory.address_input, "C01F");
+        memory.update(Message::GoTo);
+        assert_eq!(memory.top, 0xC01);
+        assert_eq!(memory.selected, Some(0xC01F));
+        assert!(memory.address_input.is_empty());
+    }
+
+    #[test]
+    fn a_stored_byte_moves_the_selection_on() {
+        let mut memory = MemoryPane::new();
+        memory.update(Message::Select(0xC000));
+        memory.update(Message::ValueInputChanged("1234".to_string()));
+        assert_eq!(memory.value_input, "12");
+        memory.update(Message::Written {
+            address: 0xC000,
+            stored: true,
+        });
+        assert_eq!(memory.selected, Some(0xC001));
+        assert!(memory.value_input.is_empty());
+
+        memory.update(Message::Written {
+            address: 0xC001,
+            stored: false,
+        });
+        assert_eq!(memory.error.as_deref(), Some("$C001 can't be edited"));
+    }
+
+    #[test]
+    fn scrolling_stays_within_the_address_space() {
+        let mut memory = MemoryPane::new();
+        memory.update(Message::Scroll(-3));
+        assert_eq!(memory.top, 0);
+        memory.update(Message::Scroll(ROWS as isize * 2));
+        assert_eq!(memory.top, ROWS - 1);
+    }
+
+    #[test]
+    fn wheel_up_scrolls_towards_zero() {
+        assert_eq!(scroll_rows(ScrollDelta::Lines { x: 0.0, y: 1.0 }), -3);
+        assert_eq!(scroll_rows(ScrollDelta::Pixels { x: 0.0, y: -2.0 }), 1);
+        assert_eq!(scroll_rows(ScrollDelta::Pixels { x: 0.0, y: 0.0 }), 0);
+    }
+
+    #[test]
+    fn ascii_sidebar_dots_unprintable_bytes() {
+        assert_eq!(ascii(b"Hi!\x00\x7F~"), "Hi!..~");
+    }
+}
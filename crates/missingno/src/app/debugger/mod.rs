@@ -5,7 +5,7 @@ use iced::{
     Element, Length, Subscription, Task,
     alignment::Vertical,
     time,
-    widget::{Column, button, column, container, pane_grid, row, text, text_input},
+    widget::{Column, button, column, container, pane_grid, row, text, text_input, toggler},
 };
 
 use crate::app::{
@@ -13,28 +13,51 @@ use crate::app::{
     console::{AnyConsole, ConsoleUi},
     emulator::Emulator,
     library::activity::FrameCapture,
-    screen::ScreenView,
+    screen::{ScreenDisplay, ScreenView},
+    settings::{ScreenFilter, ScreenScaling},
     ui::{
         fonts, icons, palette,
         sizes::{s, xs},
     },
 };
-use missingno_gb::{joypad::Button, ppu::types::palette::PaletteChoice};
+use missingno_gb::{
+    Warning,
+    debugger::{
+        WatchCondition,
+        debug_opcodes::DebugOpcodes,
+        expression::{BreakCondition, ParseError},
+    },
+    joypad::Button,
+    ppu::types::palette::PaletteChoice,
+};
 
-use panes::DebuggerPanes;
+use history::FrameHistory;
+use panes::{DebuggerPanes, PaneLayout};
+use ppu::tile_files::{self, TileFormat, TileSelection};
+use serial::SerialLog;
 use sidebar::Sidebar;
+use test_runner::TestRunner;
+use watches::Watches;
 
 mod audio;
+mod heatmap;
+mod history;
 mod instructions;
 mod interrupts;
+mod memory;
 pub mod panes;
 mod ppu;
+mod profiler;
 mod screen;
+mod serial;
 mod sidebar;
+mod test_runner;
+mod watches;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BottomPanel {
     Breakpoints,
+    Watches,
 }
 
 #[derive(Debug, Clone)]
@@ -58,14 +81,51 @@ enum MainSplit {
 pub enum Message {
     Step,
     StepOver,
+    StepOut,
     StepFrame,
     CaptureFrame,
     CaptureFrameTo(std::path::PathBuf),
+    RewindToFrame(u64),
 
     SetBreakpoint(u16),
     ClearBreakpoint(u16),
+    RunToCursor(u16),
+    SetPc(u16),
+    WriteMemory {
+        address: u16,
+        value: u8,
+    },
     BreakpointInputChanged(String),
+    BreakpointConditionChanged(String),
     AddBreakpoint,
+    WatchpointInputChanged(String),
+    AddWatchpoint(WatchCondition),
+    RemoveWatchpoint(WatchCondition),
+    SetDebugOpcodes(DebugOpcodes),
+    ScreenshotOnDebugBreak(bool),
+
+    ClearSerialLog,
+    SetProfiling(bool),
+    ClearProfile,
+    SetHeatmap(bool),
+    ClearHeatmap,
+    SetChannelMuted(usize, bool),
+    ExportTiles {
+        bank: u8,
+        selection: TileSelection,
+        format: TileFormat,
+    },
+    ImportTiles {
+        bank: u8,
+        selection: TileSelection,
+    },
+    ImportTilesFrom {
+        bank: u8,
+        selection: TileSelection,
+        path: std::path::PathBuf,
+    },
+    TestRunner(test_runner::Message),
+    Watches(watches::Message),
 
     BottomPane(BottomPaneMessage),
     MainSplitResize(pane_grid::ResizeEvent),
@@ -101,6 +161,40 @@ impl AnyDebugger {
         }
     }
 
+    /// A copy of the console being debugged, for a save state.
+    pub fn console(&self) -> AnyConsole {
+        match self {
+            Self::Dmg(debugger) => AnyConsole::Dmg(debugger.game_boy().clone()),
+            Self::Cgb(debugger) => AnyConsole::Cgb(debugger.game_boy().clone()),
+        }
+    }
+
+    pub fn is_cgb(&self) -> bool {
+        matches!(self, Self::Cgb(_))
+    }
+
+    /// Carry on from a save state of the same kind of console.
+    pub fn restore_console(&mut self, console: AnyConsole) {
+        match (self, console) {
+            (Self::Dmg(debugger), AnyConsole::Dmg(console)) => {
+                debugger.debugger.game_boy_mut().restore(console);
+            }
+            (Self::Cgb(debugger), AnyConsole::Cgb(console)) => {
+                debugger.debugger.game_boy_mut().restore(console);
+            }
+            _ => unreachable!("save states only load into the console they came from"),
+        }
+    }
+
+    /// Swap in a console running a reloaded ROM, keeping breakpoints,
+    /// watchpoints and the pane layout.
+    pub fn reload(self, console: AnyConsole) -> Self {
+        match self {
+            Self::Dmg(debugger) => debugger.reload(console),
+            Self::Cgb(debugger) => debugger.reload(console),
+        }
+    }
+
     pub fn disable_debugger(self, use_sgb_colors: bool) -> Emulator {
         match self {
             Self::Dmg(debugger) => debugger.into_emulator(use_sgb_colors),
@@ -136,6 +230,40 @@ impl AnyDebugger {
         }
     }
 
+    pub fn set_presentation(
+        &mut self,
+        scaling: ScreenScaling,
+        grid: bool,
+        filter: ScreenFilter,
+        frame_blending: bool,
+    ) {
+        match self {
+            Self::Dmg(debugger) => debugger.set_presentation(scaling, grid, filter, frame_blending),
+            Self::Cgb(debugger) => debugger.set_presentation(scaling, grid, filter, frame_blending),
+        }
+    }
+
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        match self {
+            Self::Dmg(debugger) => debugger.debugger.game_boy_mut().take_warnings(),
+            Self::Cgb(debugger) => debugger.debugger.game_boy_mut().take_warnings(),
+        }
+    }
+
+    pub fn pane_layout(&self) -> Option<PaneLayout> {
+        match self {
+            Self::Dmg(debugger) => debugger.panes.layout(),
+            Self::Cgb(debugger) => debugger.panes.layout(),
+        }
+    }
+
+    pub fn restore_pane_layout(&mut self, layout: &PaneLayout) {
+        match self {
+            Self::Dmg(debugger) => debugger.panes.restore_layout(layout),
+            Self::Cgb(debugger) => debugger.panes.restore_layout(layout),
+        }
+    }
+
     pub fn cartridge(&self) -> &missingno_gb::cartridge::Cartridge {
         match self {
             Self::Dmg(debugger) => debugger.game_boy().cartridge(),
@@ -143,6 +271,16 @@ impl AnyDebugger {
         }
     }
 
+    pub fn load_symbols(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<(), missingno_gb::debugger::symbols::SymbolError> {
+        match self {
+            Self::Dmg(debugger) => debugger.debugger.load_symbols(path),
+            Self::Cgb(debugger) => debugger.debugger.load_symbols(path),
+        }
+    }
+
     pub fn drain_audio_samples(&mut self) -> Vec<(f32, f32)> {
         match self {
             Self::Dmg(debugger) => debugger.drain_audio_samples(),
@@ -202,6 +340,31 @@ impl AnyDebugger {
             Self::Cgb(debugger) => debugger.release_button(button),
         }
     }
+
+    /// Move the test runner out, so a suite's results survive loading one
+    /// of its ROMs into a fresh debugger.
+    pub fn take_test_runner(&mut self) -> TestRunner {
+        match self {
+            Self::Dmg(debugger) => std::mem::replace(&mut *debugger.test_runner, TestRunner::new()),
+            Self::Cgb(debugger) => std::mem::replace(&mut *debugger.test_runner, TestRunner::new()),
+        }
+    }
+
+    pub fn restore_test_runner(&mut self, test_runner: TestRunner) {
+        match self {
+            Self::Dmg(debugger) => debugger.restore_test_runner(test_runner),
+            Self::Cgb(debugger) => debugger.restore_test_runner(test_runner),
+        }
+    }
+}
+
+/// What's been typed into the breakpoints panel.
+#[derive(Default)]
+struct BreakpointInputs {
+    address: String,
+    condition: String,
+    error: Option<ParseError>,
+    watch_address: String,
 }
 
 pub struct Debugger<M: ConsoleUi> {
@@ -213,11 +376,22 @@ pub struct Debugger<M: ConsoleUi> {
     bottom_panes: Option<pane_grid::State<BottomPanel>>,
     bottom_handles: HashMap<BottomPanel, pane_grid::Pane>,
     main_split: Option<pane_grid::State<MainSplit>>,
-    breakpoint_input: String,
+    /// Boxed so `Debugger` stays about the size of `Emulator`.
+    watches: Box<Watches>,
+    /// Boxed for the same reason.
+    inputs: Box<BreakpointInputs>,
+    /// Take a screenshot whenever an `LD B,B` halts execution.
+    screenshot_on_debug_break: bool,
+    /// Boxed for the same reason.
+    serial_log: Box<SerialLog>,
+    /// Boxed for the same reason.
+    test_runner: Box<TestRunner>,
+    history: FrameHistory<M>,
 }
 
 impl<M: ConsoleUi> Debugger<M> {
-    pub fn new(console: missingno_gb::Console<M>) -> Self {
+    pub fn new(mut console: missingno_gb::Console<M>) -> Self {
+        console.audio_mut().set_scope_enabled(true);
         Self {
             debugger: missingno_gb::debugger::Debugger::new(console),
             sidebar: Sidebar::new(),
@@ -227,11 +401,17 @@ impl<M: ConsoleUi> Debugger<M> {
             bottom_panes: None,
             bottom_handles: HashMap::new(),
             main_split: None,
-            breakpoint_input: String::new(),
+            inputs: Box::default(),
+            watches: Box::new(Watches::new()),
+            screenshot_on_debug_break: false,
+            serial_log: Box::new(SerialLog::new()),
+            test_runner: Box::new(TestRunner::new()),
+            history: FrameHistory::new(),
         }
     }
 
-    pub fn from_console(console: missingno_gb::Console<M>, screen_view: ScreenView) -> Self {
+    pub fn from_console(mut console: missingno_gb::Console<M>, screen_view: ScreenView) -> Self {
+        console.audio_mut().set_scope_enabled(true);
         Self {
             debugger: missingno_gb::debugger::Debugger::new(console),
             sidebar: Sidebar::new(),
@@ -241,7 +421,12 @@ impl<M: ConsoleUi> Debugger<M> {
             bottom_panes: None,
             bottom_handles: HashMap::new(),
             main_split: None,
-            breakpoint_input: String::new(),
+            inputs: Box::default(),
+            watches: Box::new(Watches::new()),
+            screenshot_on_debug_break: false,
+            serial_log: Box::new(SerialLog::new()),
+            test_runner: Box::new(TestRunner::new()),
+            history: FrameHistory::new(),
         }
     }
 
@@ -258,28 +443,96 @@ impl<M: ConsoleUi> Debugger<M> {
         AnyConsole: From<missingno_gb::Console<M>>,
     {
         let screen_view = self.panes.take_screen_view();
-        Emulator::from_debugger(
-            self.debugger.game_boy_take().into(),
-            screen_view,
-            use_sgb_colors,
-        )
+        let mut console = self.debugger.game_boy_take();
+        console.audio_mut().set_scope_enabled(false);
+        for channel in 0..4 {
+            console.audio_mut().set_channel_muted(channel, false);
+        }
+        console.set_profiling(false);
+        console.set_heatmap(false);
+        Emulator::from_debugger(console.into(), screen_view, use_sgb_colors)
     }
 
-    fn screen_update_task(&self, screen: Option<M::Screen>) -> Task<app::Message> {
-        match M::screen_display(self.debugger.game_boy(), screen) {
-            Some(display) => Task::done(screen::Message::Update(display).into()),
-            None => Task::none(),
+    fn reload(self, console: AnyConsole) -> AnyDebugger {
+        match console {
+            AnyConsole::Dmg(game_boy) => AnyDebugger::Dmg(self.with_console(game_boy)),
+            AnyConsole::Cgb(console) => AnyDebugger::Cgb(self.with_console(console)),
         }
     }
 
+    /// The reloaded ROM may target the other model, so this can change `M`.
+    /// Frame history belongs to the old ROM and is dropped.
+    fn with_console<N: ConsoleUi>(self, mut console: missingno_gb::Console<N>) -> Debugger<N> {
+        console.audio_mut().set_scope_enabled(true);
+        console.set_profiling(self.debugger.game_boy().profile().is_some());
+        console.set_heatmap(self.debugger.game_boy().heatmap().is_some());
+        let mut debugger = Debugger {
+            debugger: self.debugger.with_game_boy(console),
+            sidebar: self.sidebar,
+            panes: self.panes,
+            running: self.running,
+            frame: 0,
+            bottom_panes: self.bottom_panes,
+            bottom_handles: self.bottom_handles,
+            main_split: self.main_split,
+            inputs: self.inputs,
+            watches: self.watches,
+            screenshot_on_debug_break: self.screenshot_on_debug_break,
+            serial_log: self.serial_log,
+            test_runner: self.test_runner,
+            history: FrameHistory::new(),
+        };
+        debugger.watches.refresh(debugger.debugger.game_boy());
+        debugger
+    }
+
+    fn collect_serial_output(&mut self) {
+        let output = self.debugger.game_boy_mut().drain_serial_output();
+        self.serial_log.append(&output);
+        for message in self.debugger.take_debug_messages() {
+            self.serial_log.log_message(&message);
+        }
+    }
+
+    fn screen_update_task(&self, screen: Option<M::Screen>) -> Task<app::Message> {
+        display_task(M::screen_display(self.debugger.game_boy(), screen))
+    }
+
     pub fn update(&mut self, message: Message) -> Task<app::Message> {
+        let executes = matches!(
+            message,
+            Message::Step
+                | Message::StepOver
+                | Message::StepOut
+                | Message::StepFrame
+                | Message::CaptureFrameTo(_)
+                | Message::RewindToFrame(_)
+                | Message::SetPc(_)
+                | Message::WriteMemory { .. }
+                | Message::ImportTilesFrom { .. }
+        );
+        let task = self.handle(message);
+        if executes {
+            self.watches.refresh(self.debugger.game_boy());
+        }
+        task
+    }
+
+    fn handle(&mut self, message: Message) -> Task<app::Message> {
         match message {
             Message::Step => {
                 let screen = self.debugger.step();
+                self.collect_serial_output();
                 self.screen_update_task(screen)
             }
             Message::StepOver => {
                 let screen = self.debugger.step_over();
+                self.collect_serial_output();
+                self.screen_update_task(screen)
+            }
+            Message::StepOut => {
+                let screen = self.debugger.step_out();
+                self.collect_serial_output();
                 self.screen_update_task(screen)
             }
             Message::StepFrame => {
@@ -288,8 +541,33 @@ impl<M: ConsoleUi> Debugger<M> {
                 if screen.is_none() {
                     self.running = false;
                 }
-                self.screen_update_task(screen)
+                self.collect_serial_output();
+
+                let new_frame = screen.is_some();
+                if new_frame && let Some(heatmap) = self.debugger.game_boy_mut().heatmap_mut() {
+                    heatmap.end_frame();
+                }
+                let display = M::screen_display(self.debugger.game_boy(), screen);
+                if new_frame && let Some(display) = &display {
+                    self.history
+                        .record(self.frame, self.debugger.game_boy(), display.clone());
+                }
+                let task = display_task(display);
+                if self.screenshot_on_debug_break && self.debugger.debug_breakpoint_hit() {
+                    task.chain(Task::done(app::Message::TakeScreenshot))
+                } else {
+                    task
+                }
             }
+            Message::RewindToFrame(number) => match self.history.rewind_to(number) {
+                Some((console, display)) => {
+                    *self.debugger.game_boy_mut() = console;
+                    self.frame = number;
+                    self.running = false;
+                    display_task(Some(display))
+                }
+                None => Task::none(),
+            },
             Message::CaptureFrame => {
                 let title = self
                     .debugger
@@ -312,6 +590,7 @@ impl<M: ConsoleUi> Debugger<M> {
             Message::CaptureFrameTo(path) => match self.debugger.capture_frame(&path) {
                 Ok(screen) => {
                     self.frame += 1;
+                    self.collect_serial_output();
                     self.screen_update_task(Some(screen))
                 }
                 Err(_) => Task::none(),
@@ -325,22 +604,175 @@ impl<M: ConsoleUi> Debugger<M> {
                 self.debugger.clear_breakpoint(address);
                 Task::none()
             }
+            Message::RunToCursor(address) => {
+                if !self.running {
+                    self.debugger.set_temporary_breakpoint(address);
+                    self.running = true;
+                }
+                Task::none()
+            }
+            Message::SetPc(address) => {
+                if !self.running {
+                    self.debugger.set_pc(address);
+                }
+                Task::none()
+            }
+            Message::WriteMemory { address, value } => {
+                let stored = self.debugger.write_memory(address, value);
+                self.panes
+                    .update(panes::Message::Pane(panes::PaneMessage::Memory(
+                        memory::Message::Written { address, stored },
+                    )));
+                Task::none()
+            }
             Message::BreakpointInputChanged(input) => {
-                self.breakpoint_input = input
+                self.inputs.address = input
                     .chars()
                     .filter(|c| c.is_ascii_hexdigit())
                     .take(4)
                     .collect();
                 Task::none()
             }
+            Message::BreakpointConditionChanged(input) => {
+                self.inputs.condition = input;
+                self.inputs.error = None;
+                Task::none()
+            }
             Message::AddBreakpoint => {
-                if self.breakpoint_input.len() == 4 {
-                    self.debugger
-                        .set_breakpoint(u16::from_str_radix(&self.breakpoint_input, 16).unwrap());
-                    self.breakpoint_input.clear();
+                if self.inputs.address.len() == 4 {
+                    let address = u16::from_str_radix(&self.inputs.address, 16).unwrap();
+                    let condition = self.inputs.condition.trim();
+                    if condition.is_empty() {
+                        self.debugger.set_breakpoint(address);
+                    } else {
+                        match BreakCondition::parse(condition) {
+                            Ok(condition) => {
+                                self.debugger.set_conditional_breakpoint(address, condition)
+                            }
+                            Err(error) => {
+                                self.inputs.error = Some(error);
+                                return Task::none();
+                            }
+                        }
+                    }
+                    self.inputs.address.clear();
+                    self.inputs.condition.clear();
                 }
                 Task::none()
             }
+            Message::WatchpointInputChanged(input) => {
+                self.inputs.watch_address = input
+                    .chars()
+                    .filter(|c| c.is_ascii_hexdigit())
+                    .take(4)
+                    .collect();
+                Task::none()
+            }
+            Message::AddWatchpoint(condition) => {
+                self.debugger.add_watchpoint(condition);
+                self.inputs.watch_address.clear();
+                Task::none()
+            }
+            Message::RemoveWatchpoint(condition) => {
+                self.debugger.remove_watchpoint(&condition);
+                Task::none()
+            }
+
+            Message::SetDebugOpcodes(debug_opcodes) => {
+                self.debugger.set_debug_opcodes(debug_opcodes);
+                Task::none()
+            }
+            Message::ScreenshotOnDebugBreak(enabled) => {
+                self.screenshot_on_debug_break = enabled;
+                Task::none()
+            }
+
+            Message::ClearSerialLog => {
+                self.serial_log.clear();
+                Task::none()
+            }
+            Message::SetChannelMuted(channel, muted) => {
+                self.debugger
+                    .game_boy_mut()
+                    .audio_mut()
+                    .set_channel_muted(channel, muted);
+                Task::none()
+            }
+            Message::SetProfiling(enabled) => {
+                self.debugger.game_boy_mut().set_profiling(enabled);
+                Task::none()
+            }
+            Message::ClearProfile => {
+                if let Some(profile) = self.debugger.game_boy_mut().profile_mut() {
+                    profile.clear();
+                }
+                Task::none()
+            }
+            Message::SetHeatmap(enabled) => {
+                self.debugger.game_boy_mut().set_heatmap(enabled);
+                Task::none()
+            }
+            Message::ClearHeatmap => {
+                if let Some(heatmap) = self.debugger.game_boy_mut().heatmap_mut() {
+                    heatmap.clear();
+                }
+                Task::none()
+            }
+            Message::ExportTiles {
+                bank,
+                selection,
+                format,
+            } => {
+                // Snapshot now: the game may overwrite the tiles while the
+                // dialog is open.
+                let vram = self.debugger.game_boy().vram_bytes(bank);
+                let file = tile_files::export(&vram, selection, format);
+                let extension = format.extension();
+                let dialog = rfd::AsyncFileDialog::new()
+                    .set_file_name(format!("{}.{extension}", selection.file_stem()))
+                    .add_filter(extension, &[extension]);
+
+                Task::perform(dialog.save_file(), move |handle| {
+                    if let Some(handle) = handle {
+                        let _ = std::fs::write(handle.path(), &file);
+                    }
+                    app::Message::None
+                })
+            }
+            Message::ImportTiles { bank, selection } => {
+                let dialog = rfd::AsyncFileDialog::new().add_filter("Tiles", &["png", "bin"]);
+                Task::perform(dialog.pick_file(), move |handle| match handle {
+                    Some(handle) => Message::ImportTilesFrom {
+                        bank,
+                        selection,
+                        path: handle.path().to_path_buf(),
+                    }
+                    .into(),
+                    None => app::Message::None,
+                })
+            }
+            Message::ImportTilesFrom {
+                bank,
+                selection,
+                path,
+            } => {
+                let format = TileFormat::from_path(&path);
+                if let Ok(file) = std::fs::read(&path)
+                    && let Some(bytes) = tile_files::import(&file, selection, format)
+                {
+                    self.debugger.game_boy_mut().poke_vram(
+                        bank,
+                        0x8000 + selection.offset() as u16,
+                        &bytes,
+                    );
+                }
+                Task::none()
+            }
+            Message::TestRunner(message) => self.test_runner.update(message),
+            Message::Watches(message) => {
+                self.watches.update(message, self.debugger.game_boy());
+                Task::none()
+            }
 
             Message::BottomPane(msg) => {
                 match msg {
@@ -409,6 +841,18 @@ impl<M: ConsoleUi> Debugger<M> {
 
     pub fn set_palette(&mut self, palette: PaletteChoice) {
         self.panes.set_palette(palette);
+        self.history.set_palette(palette);
+    }
+
+    pub fn set_presentation(
+        &mut self,
+        scaling: ScreenScaling,
+        grid: bool,
+        filter: ScreenFilter,
+        frame_blending: bool,
+    ) {
+        self.panes
+            .set_presentation(scaling, grid, filter, frame_blending);
     }
 
     pub fn view(&self) -> Element<'_, app::Message> {
@@ -417,7 +861,14 @@ impl<M: ConsoleUi> Debugger<M> {
         let center: Element<'_, app::Message> = if let Some(split_state) = &self.main_split {
             pane_grid(split_state, |_handle, zone, _maximized| {
                 let content: Element<'_, app::Message> = match zone {
-                    MainSplit::Top => self.panes.view(&self.debugger, &colors),
+                    MainSplit::Top => self.panes.view(
+                        &self.debugger,
+                        &self.serial_log,
+                        &self.test_runner,
+                        &self.history,
+                        self.frame,
+                        &colors,
+                    ),
                     MainSplit::Bottom => self.bottom_pane_grid(
                         self.bottom_panes
                             .as_ref()
@@ -430,7 +881,14 @@ impl<M: ConsoleUi> Debugger<M> {
             .spacing(s())
             .into()
         } else {
-            self.panes.view(&self.debugger, &colors)
+            self.panes.view(
+                &self.debugger,
+                &self.serial_log,
+                &self.test_runner,
+                &self.history,
+                self.frame,
+                &colors,
+            )
         };
 
         row![
@@ -450,6 +908,7 @@ impl<M: ConsoleUi> Debugger<M> {
         pane_grid(state, |_handle, panel, _maximized| {
             let content: Element<'_, app::Message> = match panel {
                 BottomPanel::Breakpoints => self.breakpoints_content(),
+                BottomPanel::Watches => self.watches.view(),
             };
 
             panes::pane(panes::title_bar(panel.label()), content)
@@ -463,22 +922,102 @@ impl<M: ConsoleUi> Debugger<M> {
     }
 
     fn breakpoints_content(&self) -> Element<'_, app::Message> {
-        let breakpoint_list = Column::from_iter(
+        let breakpoint_list =
+            Column::from_iter(self.debugger.breakpoints().iter().map(|&address| {
+                breakpoint_row(address, self.debugger.breakpoint_condition(address))
+            }));
+
+        let input = row![
+            text_input("Address (hex)...", &self.inputs.address)
+                .font(fonts::monospace())
+                .on_input(|value| Message::BreakpointInputChanged(value).into())
+                .on_submit(Message::AddBreakpoint.into()),
+            text_input("Condition, e.g. A == $90", &self.inputs.condition)
+                .font(fonts::monospace())
+                .on_input(|value| Message::BreakpointConditionChanged(value).into())
+                .on_submit(Message::AddBreakpoint.into()),
+        ]
+        .spacing(xs());
+        let mut input = column![input].spacing(xs());
+        if let Some(error) = &self.inputs.error {
+            input = input.push(text(error.to_string()).size(12.0).color(palette::RED));
+        }
+
+        let hit = self.debugger.last_watchpoint_hit();
+        let watchpoint_list = Column::from_iter(
             self.debugger
-                .breakpoints()
+                .watchpoints()
                 .iter()
-                .map(|&address| breakpoint_row(address)),
+                .map(|condition| watchpoint_row(condition, hit == Some(condition))),
         );
 
-        let input = text_input("Address (hex)...", &self.breakpoint_input)
-            .font(fonts::monospace())
-            .on_input(|value| Message::BreakpointInputChanged(value).into())
-            .on_submit(Message::AddBreakpoint.into());
+        // Only a whole address can be watched.
+        let address = (self.inputs.watch_address.len() == 4)
+            .then(|| u16::from_str_radix(&self.inputs.watch_address, 16).unwrap());
+        let watch_button = |label, condition: fn(u16) -> WatchCondition| {
+            button(text(label).size(12.0))
+                .on_press_maybe(
+                    address.map(|address| Message::AddWatchpoint(condition(address)).into()),
+                )
+                .style(button::text)
+        };
+        let watchpoint_input = row![
+            text_input("Watch address (hex)...", &self.inputs.watch_address)
+                .font(fonts::monospace())
+                .on_input(|value| Message::WatchpointInputChanged(value).into())
+                .on_submit_maybe(address.map(|address| {
+                    Message::AddWatchpoint(WatchCondition::BusAccess { address }).into()
+                })),
+            watch_button("Read", |address| WatchCondition::BusRead { address }),
+            watch_button("Write", |address| WatchCondition::BusWrite { address }),
+            watch_button("Access", |address| WatchCondition::BusAccess { address }),
+        ]
+        .spacing(xs())
+        .align_y(Vertical::Center);
+
+        let debug_opcodes = self.debugger.debug_opcodes();
+        let debug_opcode_toggles = column![
+            toggler(debug_opcodes.breakpoint)
+                .label("Break on LD B,B")
+                .size(14.0)
+                .on_toggle(move |breakpoint| {
+                    Message::SetDebugOpcodes(DebugOpcodes {
+                        breakpoint,
+                        ..debug_opcodes
+                    })
+                    .into()
+                }),
+            toggler(self.screenshot_on_debug_break)
+                .label("Screenshot on LD B,B")
+                .size(14.0)
+                .on_toggle_maybe(
+                    debug_opcodes
+                        .breakpoint
+                        .then_some(|on| Message::ScreenshotOnDebugBreak(on).into()),
+                ),
+            toggler(debug_opcodes.messages)
+                .label("Log LD D,D messages")
+                .size(14.0)
+                .on_toggle(move |messages| {
+                    Message::SetDebugOpcodes(DebugOpcodes {
+                        messages,
+                        ..debug_opcodes
+                    })
+                    .into()
+                }),
+        ]
+        .spacing(xs());
 
-        column![breakpoint_list, input,]
-            .spacing(s())
-            .padding(s())
-            .into()
+        column![
+            breakpoint_list,
+            input,
+            watchpoint_list,
+            watchpoint_input,
+            debug_opcode_toggles
+        ]
+        .spacing(s())
+        .padding(s())
+        .into()
     }
 
     fn icon_rail(&self) -> Element<'_, app::Message> {
@@ -493,17 +1032,20 @@ impl<M: ConsoleUi> Debugger<M> {
             )
         });
 
-        let panel_buttons = [(BottomPanel::Breakpoints, Icon::Circle, "Breakpoints")]
-            .into_iter()
-            .map(|(panel, icon, label)| {
-                let shown = self.bottom_handles.contains_key(&panel);
-                let message = if shown {
-                    BottomPaneMessage::Close(panel)
-                } else {
-                    BottomPaneMessage::Show(panel)
-                };
-                rail_icon(icon, label, shown, Message::BottomPane(message).into())
-            });
+        let panel_buttons = [
+            (BottomPanel::Breakpoints, Icon::Circle, "Breakpoints"),
+            (BottomPanel::Watches, Icon::Eye, "Watches"),
+        ]
+        .into_iter()
+        .map(|(panel, icon, label)| {
+            let shown = self.bottom_handles.contains_key(&panel);
+            let message = if shown {
+                BottomPaneMessage::Close(panel)
+            } else {
+                BottomPaneMessage::Show(panel)
+            };
+            rail_icon(icon, label, shown, Message::BottomPane(message).into())
+        });
 
         column![
             column(pane_buttons).spacing(xs()),
@@ -543,11 +1085,14 @@ impl<M: ConsoleUi> Debugger<M> {
 
     pub fn pause(&mut self) {
         self.running = false;
+        self.debugger.clear_temporary_breakpoint();
     }
 
     pub fn reset(&mut self) {
         self.debugger.reset();
         self.frame = 0;
+        self.history.clear();
+        self.watches.refresh(self.debugger.game_boy());
     }
 
     pub fn press_button(&mut self, button: Button) {
@@ -557,12 +1102,28 @@ impl<M: ConsoleUi> Debugger<M> {
     pub fn release_button(&mut self, button: Button) {
         self.debugger.game_boy_mut().release_button(button);
     }
+
+    fn restore_test_runner(&mut self, test_runner: TestRunner) {
+        if test_runner.has_results() {
+            self.panes
+                .update(panes::Message::ShowPane(panes::DebuggerPane::TestRunner));
+        }
+        *self.test_runner = test_runner;
+    }
+}
+
+fn display_task(display: Option<ScreenDisplay>) -> Task<app::Message> {
+    match display {
+        Some(display) => Task::done(screen::Message::Update(display).into()),
+        None => Task::none(),
+    }
 }
 
 impl BottomPanel {
     fn label(&self) -> &'static str {
         match self {
             BottomPanel::Breakpoints => "Breakpoints",
+            BottomPanel::Watches => "Watches",
         }
     }
 }
@@ -596,15 +1157,53 @@ fn rail_icon<'a>(
     .into()
 }
 
-fn breakpoint_row(address: u16) -> Element<'static, app::Message> {
+/// A watchpoint with a button to remove it, picked out when it's what
+/// stopped execution.
+fn watchpoint_row(condition: &WatchCondition, hit: bool) -> Element<'static, app::Message> {
+    let color = if hit { palette::YELLOW } else { palette::TEXT };
     container(
         row![
-            button(icons::breakpoint_enabled())
-                .on_press(Message::ClearBreakpoint(address).into())
+            button(icons::m_muted(icons::Icon::Close))
+                .on_press(Message::RemoveWatchpoint(condition.clone()).into())
                 .style(button::text),
-            text(format!("{:04X}", address)).font(fonts::monospace())
+            text(watchpoint_label(condition))
+                .font(fonts::monospace())
+                .color(color)
         ]
         .align_y(Vertical::Center),
     )
     .into()
 }
+
+fn watchpoint_label(condition: &WatchCondition) -> String {
+    match condition {
+        WatchCondition::BusRead { address } => format!("Read {address:04X}"),
+        WatchCondition::BusWrite { address } => format!("Write {address:04X}"),
+        WatchCondition::BusAccess { address } => format!("Access {address:04X}"),
+        WatchCondition::DmaRead { address } => format!("DMA read {address:04X}"),
+        WatchCondition::DmaWrite { address } => format!("DMA write {address:04X}"),
+        condition => format!("{condition:?}"),
+    }
+}
+
+fn breakpoint_row(
+    address: u16,
+    condition: Option<&BreakCondition>,
+) -> Element<'static, app::Message> {
+    let mut the_row = row![
+        button(icons::breakpoint_enabled())
+            .on_press(Message::ClearBreakpoint(address).into())
+            .style(button::text),
+        text(format!("{:04X}", address)).font(fonts::monospace())
+    ]
+    .spacing(s())
+    .align_y(Vertical::Center);
+    if let Some(condition) = condition {
+        the_row = the_row.push(
+            text(format!("if {condition}"))
+                .font(fonts::monospace())
+                .color(palette::MUTED),
+        );
+    }
+    container(the_row).into()
+}
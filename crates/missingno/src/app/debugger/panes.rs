@@ -5,10 +5,14 @@ use iced::{
     Border, Color, Element, Theme,
     widget::{
         container, pane_grid,
-        pane_grid::Axis::{Horizontal, Vertical},
+        pane_grid::{
+            Axis::{self, Horizontal, Vertical},
+            Configuration, Node,
+        },
         toggler,
     },
 };
+use serde::{Deserialize, Serialize};
 
 use crate::app::{
     self,
@@ -16,15 +20,22 @@ use crate::app::{
     debugger::{
         self,
         audio::AudioPane,
+        heatmap::HeatmapPane,
+        history::{FrameHistory, FrameHistoryPane},
         instructions::InstructionsPane,
+        memory::{self, MemoryPane},
         ppu::{
             sprites::{self, SpritesPane},
-            tile_maps::TileMapPane,
+            tile_maps::{self, TileMapPane},
             tiles::{self, TilesPane},
         },
+        profiler::ProfilerPane,
         screen::{self, ScreenPane},
+        serial::{SerialLog, SerialPane},
+        test_runner::{TestRunner, TestRunnerPane},
     },
     screen::ScreenView,
+    settings::{ScreenFilter, ScreenScaling},
     ui::{
         fonts,
         icons::Icon,
@@ -45,6 +56,7 @@ pub enum Message {
 
     ResizePane(pane_grid::ResizeEvent),
     DragPane(pane_grid::DragEvent),
+    ResetLayout,
 
     Pane(PaneMessage),
 }
@@ -54,6 +66,8 @@ pub enum PaneMessage {
     Screen(screen::Message),
     Sprites(sprites::Message),
     Tiles(tiles::Message),
+    TileMap(tile_maps::Message),
+    Memory(memory::Message),
 }
 
 impl From<Message> for app::Message {
@@ -68,14 +82,96 @@ pub struct DebuggerPanes {
     palette: PaletteChoice,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DebuggerPane {
     Screen,
     Instructions,
     Tiles,
-    TileMap(TileMapId),
+    TileMap(#[serde(with = "TileMapIdDef")] TileMapId),
     Sprites,
     Audio,
+    Serial,
+    TestRunner,
+    FrameHistory,
+    Profiler,
+    Heatmap,
+    Memory,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "TileMapId")]
+struct TileMapIdDef(u8);
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Axis")]
+enum AxisDef {
+    Horizontal,
+    Vertical,
+}
+
+/// The arrangement of open panes: which are shown, and how the grid is
+/// split between them. Saved to settings so the debugger reopens the way
+/// it was left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PaneLayout {
+    Pane(DebuggerPane),
+    Split {
+        #[serde(with = "AxisDef")]
+        axis: Axis,
+        ratio: f32,
+        a: Box<PaneLayout>,
+        b: Box<PaneLayout>,
+    },
+}
+
+impl Default for PaneLayout {
+    /// Instructions on the left third, the screen beside them.
+    fn default() -> Self {
+        PaneLayout::Split {
+            axis: Vertical,
+            ratio: 1.0 / 3.0,
+            a: Box::new(PaneLayout::Pane(DebuggerPane::Instructions)),
+            b: Box::new(PaneLayout::Pane(DebuggerPane::Screen)),
+        }
+    }
+}
+
+impl PaneLayout {
+    fn panes(&self, panes: &mut Vec<DebuggerPane>) {
+        match self {
+            PaneLayout::Pane(pane) => panes.push(*pane),
+            PaneLayout::Split { a, b, .. } => {
+                a.panes(panes);
+                b.panes(panes);
+            }
+        }
+    }
+
+    /// A layout from an older version, or edited by hand, may name a pane
+    /// that no longer exists or name one twice.
+    fn is_valid(&self) -> bool {
+        let mut panes = Vec::new();
+        self.panes(&mut panes);
+        panes
+            .iter()
+            .enumerate()
+            .all(|(i, pane)| DebuggerPanes::AVAILABLE.contains(pane) && !panes[..i].contains(pane))
+    }
+
+    fn configuration(&self, screen_pane: &mut Option<ScreenPane>) -> Configuration<PaneInstance> {
+        match self {
+            PaneLayout::Pane(DebuggerPane::Screen) => Configuration::Pane(PaneInstance::Screen(
+                screen_pane.take().unwrap_or_else(ScreenPane::new),
+            )),
+            PaneLayout::Pane(pane) => Configuration::Pane(DebuggerPanes::construct_pane(*pane)),
+            PaneLayout::Split { axis, ratio, a, b } => Configuration::Split {
+                axis: *axis,
+                ratio: ratio.clamp(0.0, 1.0),
+                a: Box::new(a.configuration(screen_pane)),
+                b: Box::new(b.configuration(screen_pane)),
+            },
+        }
+    }
 }
 
 enum PaneInstance {
@@ -85,9 +181,31 @@ enum PaneInstance {
     TileMap(TileMapPane),
     Sprites(SpritesPane),
     Audio(AudioPane),
+    Serial(SerialPane),
+    TestRunner(TestRunnerPane),
+    FrameHistory(FrameHistoryPane),
+    Profiler(ProfilerPane),
+    Heatmap(HeatmapPane),
+    Memory(MemoryPane),
 }
 
 impl DebuggerPanes {
+    const AVAILABLE: [DebuggerPane; 13] = [
+        DebuggerPane::Screen,
+        DebuggerPane::Instructions,
+        DebuggerPane::Tiles,
+        DebuggerPane::TileMap(TileMapId(0)),
+        DebuggerPane::TileMap(TileMapId(1)),
+        DebuggerPane::Sprites,
+        DebuggerPane::Audio,
+        DebuggerPane::Serial,
+        DebuggerPane::TestRunner,
+        DebuggerPane::FrameHistory,
+        DebuggerPane::Profiler,
+        DebuggerPane::Heatmap,
+        DebuggerPane::Memory,
+    ];
+
     pub fn new() -> Self {
         Self::build(ScreenPane::new())
     }
@@ -97,17 +215,14 @@ impl DebuggerPanes {
     }
 
     pub fn take_screen_view(self) -> ScreenView {
+        self.screen_view()
+    }
+
+    fn screen_view(&self) -> ScreenView {
         if let Some(panes) = &self.panes {
             for (_, pane) in panes.iter() {
                 if let PaneInstance::Screen(screen_pane) = pane {
-                    let view = screen_pane.screen_view();
-                    return ScreenView {
-                        screen: view.screen.clone(),
-                        palette: view.palette,
-                        sgb_render_data: view.sgb_render_data,
-                        use_sgb_colors: view.use_sgb_colors,
-                        cgb_rgba: view.cgb_rgba.clone(),
-                    };
+                    return screen_pane.screen_view().clone();
                 }
             }
         }
@@ -115,29 +230,68 @@ impl DebuggerPanes {
     }
 
     fn build(screen_pane: ScreenPane) -> Self {
-        let mut handles = HashMap::new();
-
-        let (mut panes, instructions_handle) =
-            pane_grid::State::new(Self::construct_pane(DebuggerPane::Instructions));
-        handles.insert(DebuggerPane::Instructions, instructions_handle);
-
-        let (screen_handle, split) = panes
-            .split(
-                Vertical,
-                instructions_handle,
-                PaneInstance::Screen(screen_pane),
-            )
-            .unwrap();
-        handles.insert(DebuggerPane::Screen, screen_handle);
-        panes.resize(split, 1.0 / 3.0);
-
-        Self {
+        Self::with_layout(
+            &PaneLayout::default(),
+            screen_pane,
+            PaletteChoice::default(),
+        )
+    }
+
+    fn with_layout(layout: &PaneLayout, screen_pane: ScreenPane, palette: PaletteChoice) -> Self {
+        let mut screen_pane = Some(screen_pane);
+        let panes = pane_grid::State::with_configuration(layout.configuration(&mut screen_pane));
+        let handles = panes
+            .iter()
+            .map(|(handle, pane)| (pane.kind(), *handle))
+            .collect();
+
+        let mut debugger_panes = Self {
             panes: Some(panes),
             handles,
-            palette: PaletteChoice::default(),
+            palette,
+        };
+        debugger_panes.set_palette(palette);
+        debugger_panes
+    }
+
+    /// The current arrangement, or `None` with every pane closed.
+    pub fn layout(&self) -> Option<PaneLayout> {
+        let panes = self.panes.as_ref()?;
+        Some(Self::node_layout(panes, panes.layout()))
+    }
+
+    fn node_layout(panes: &pane_grid::State<PaneInstance>, node: &Node) -> PaneLayout {
+        match node {
+            Node::Split {
+                axis, ratio, a, b, ..
+            } => PaneLayout::Split {
+                axis: *axis,
+                ratio: *ratio,
+                a: Box::new(Self::node_layout(panes, a)),
+                b: Box::new(Self::node_layout(panes, b)),
+            },
+            Node::Pane(handle) => PaneLayout::Pane(
+                panes
+                    .get(*handle)
+                    .expect("layout only names open panes")
+                    .kind(),
+            ),
         }
     }
 
+    /// Rearrange into `layout`, falling back to the default for one that
+    /// doesn't fit this version. The screen keeps its last frame; other
+    /// panes start afresh.
+    pub fn restore_layout(&mut self, layout: &PaneLayout) {
+        let default = PaneLayout::default();
+        let layout = if layout.is_valid() { layout } else { &default };
+        *self = Self::with_layout(
+            layout,
+            ScreenPane::with_screen(self.screen_view()),
+            self.palette,
+        );
+    }
+
     fn construct_pane(pane: DebuggerPane) -> PaneInstance {
         match pane {
             DebuggerPane::Screen => PaneInstance::Screen(ScreenPane::new()),
@@ -146,6 +300,12 @@ impl DebuggerPanes {
             DebuggerPane::TileMap(map) => PaneInstance::TileMap(TileMapPane::new(map)),
             DebuggerPane::Sprites => PaneInstance::Sprites(SpritesPane::new()),
             DebuggerPane::Audio => PaneInstance::Audio(AudioPane::new()),
+            DebuggerPane::Serial => PaneInstance::Serial(SerialPane::new()),
+            DebuggerPane::TestRunner => PaneInstance::TestRunner(TestRunnerPane::new()),
+            DebuggerPane::FrameHistory => PaneInstance::FrameHistory(FrameHistoryPane::new()),
+            DebuggerPane::Profiler => PaneInstance::Profiler(ProfilerPane::new()),
+            DebuggerPane::Heatmap => PaneInstance::Heatmap(HeatmapPane::new()),
+            DebuggerPane::Memory => PaneInstance::Memory(MemoryPane::new()),
         }
     }
 
@@ -191,6 +351,7 @@ impl DebuggerPanes {
                     }
                 }
             }
+            Message::ResetLayout => self.restore_layout(&PaneLayout::default()),
 
             Message::Pane(pane_message) => {
                 if let Some(panes) = &mut self.panes {
@@ -216,6 +377,20 @@ impl DebuggerPanes {
                                 }
                             });
                         }
+                        PaneMessage::TileMap(message) => {
+                            panes.iter_mut().for_each(|(_, pane)| {
+                                if let PaneInstance::TileMap(tile_map_pane) = pane {
+                                    tile_map_pane.update(*message);
+                                }
+                            });
+                        }
+                        PaneMessage::Memory(message) => {
+                            panes.iter_mut().for_each(|(_, pane)| {
+                                if let PaneInstance::Memory(memory_pane) = pane {
+                                    memory_pane.update(message.clone());
+                                }
+                            });
+                        }
                     }
                 }
             }
@@ -237,20 +412,40 @@ impl DebuggerPanes {
         }
     }
 
+    pub fn set_presentation(
+        &mut self,
+        scaling: ScreenScaling,
+        grid: bool,
+        filter: ScreenFilter,
+        frame_blending: bool,
+    ) {
+        if let Some(panes) = &mut self.panes {
+            panes.iter_mut().for_each(|(_, pane)| {
+                if let PaneInstance::Screen(screen_pane) = pane {
+                    screen_pane.set_presentation(scaling, grid, filter, frame_blending);
+                }
+            });
+        }
+    }
+
     pub fn view<'a, M: ConsoleUi>(
         &'a self,
         debugger: &'a Debugger<M>,
+        serial_log: &'a SerialLog,
+        test_runner: &'a TestRunner,
+        history: &'a FrameHistory<M>,
+        current_frame: u64,
         colors: &ConsoleColors,
     ) -> Element<'a, app::Message> {
         if let Some(panes) = &self.panes {
             pane_grid(panes, |_handle, instance, _is_maximized| match instance {
                 PaneInstance::Screen(screen) => screen.content(),
-                PaneInstance::Instructions(instructions) => instructions.content(
-                    debugger.game_boy(),
-                    debugger.game_boy().cpu().ir_address,
-                    debugger.breakpoints(),
+                PaneInstance::Instructions(instructions) => instructions.content(debugger),
+                PaneInstance::Tiles(tiles) => tiles.content(
+                    debugger.game_boy().vram(),
+                    debugger.changed_tiles_since_last_frame(),
+                    colors,
                 ),
-                PaneInstance::Tiles(tiles) => tiles.content(debugger.game_boy().vram(), colors),
                 PaneInstance::TileMap(tile_map) => tile_map.content(
                     debugger.game_boy().ppu(),
                     debugger.game_boy().vram(),
@@ -262,6 +457,12 @@ impl DebuggerPanes {
                     colors,
                 ),
                 PaneInstance::Audio(audio) => audio.content(debugger.game_boy().audio()),
+                PaneInstance::Serial(serial) => serial.content(serial_log),
+                PaneInstance::TestRunner(runner) => runner.content(test_runner),
+                PaneInstance::FrameHistory(frames) => frames.content(history, current_frame),
+                PaneInstance::Profiler(profiler) => profiler.content(debugger.game_boy()),
+                PaneInstance::Heatmap(heatmap) => heatmap.content(debugger.game_boy()),
+                PaneInstance::Memory(memory) => memory.content(debugger.game_boy()),
             })
             .on_resize(10.0, |resize| Message::ResizePane(resize).into())
             .on_drag(|drag| Message::DragPane(drag).into())
@@ -280,15 +481,26 @@ impl DebuggerPanes {
     }
 
     pub fn available_panes(&self) -> &[DebuggerPane] {
-        &[
-            DebuggerPane::Screen,
-            DebuggerPane::Instructions,
-            DebuggerPane::Tiles,
-            DebuggerPane::TileMap(TileMapId(0)),
-            DebuggerPane::TileMap(TileMapId(1)),
-            DebuggerPane::Sprites,
-            DebuggerPane::Audio,
-        ]
+        &Self::AVAILABLE
+    }
+}
+
+impl PaneInstance {
+    fn kind(&self) -> DebuggerPane {
+        match self {
+            PaneInstance::Screen(_) => DebuggerPane::Screen,
+            PaneInstance::Instructions(_) => DebuggerPane::Instructions,
+            PaneInstance::Tiles(_) => DebuggerPane::Tiles,
+            PaneInstance::TileMap(tile_map) => DebuggerPane::TileMap(tile_map.tile_map()),
+            PaneInstance::Sprites(_) => DebuggerPane::Sprites,
+            PaneInstance::Audio(_) => DebuggerPane::Audio,
+            PaneInstance::Serial(_) => DebuggerPane::Serial,
+            PaneInstance::TestRunner(_) => DebuggerPane::TestRunner,
+            PaneInstance::FrameHistory(_) => DebuggerPane::FrameHistory,
+            PaneInstance::Profiler(_) => DebuggerPane::Profiler,
+            PaneInstance::Heatmap(_) => DebuggerPane::Heatmap,
+            PaneInstance::Memory(_) => DebuggerPane::Memory,
+        }
     }
 }
 
@@ -311,6 +523,12 @@ impl DebuggerPane {
             DebuggerPane::TileMap(_) => Icon::Image,
             DebuggerPane::Sprites => Icon::Human,
             DebuggerPane::Audio => Icon::Sliders,
+            DebuggerPane::Serial => Icon::Wifi,
+            DebuggerPane::TestRunner => Icon::Debug,
+            DebuggerPane::FrameHistory => Icon::Clock,
+            DebuggerPane::Profiler => Icon::Eye,
+            DebuggerPane::Heatmap => Icon::Grid,
+            DebuggerPane::Memory => Icon::CircuitBoard,
         }
     }
 }
@@ -324,6 +542,12 @@ impl fmt::Display for DebuggerPane {
             DebuggerPane::TileMap(map) => write!(f, "{}", map),
             DebuggerPane::Sprites => write!(f, "Sprites"),
             DebuggerPane::Audio => write!(f, "Audio"),
+            DebuggerPane::Serial => write!(f, "Serial"),
+            DebuggerPane::TestRunner => write!(f, "Test Runner"),
+            DebuggerPane::FrameHistory => write!(f, "Frame History"),
+            DebuggerPane::Profiler => write!(f, "Profiler"),
+            DebuggerPane::Heatmap => write!(f, "Heatmap"),
+            DebuggerPane::Memory => write!(f, "Memory"),
         }
     }
 }
@@ -403,3 +627,69 @@ pub fn checkbox_title_bar(label: &str, checked: bool) -> pane_grid::TitleBar<'_,
     )
     .style(title_style)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(pane: DebuggerPane) -> Box<PaneLayout> {
+        Box::new(PaneLayout::Pane(pane))
+    }
+
+    #[test]
+    fn rearranged_layout_survives_a_restart() {
+        let mut panes = DebuggerPanes::new();
+        panes.update(Message::ShowPane(DebuggerPane::TileMap(TileMapId(1))));
+        panes.update(Message::ClosePane(DebuggerPane::Instructions));
+        let layout = panes.layout().unwrap();
+
+        let saved = ron::ser::to_string(&layout).unwrap();
+        let loaded: PaneLayout = ron::from_str(&saved).unwrap();
+        let mut restarted = DebuggerPanes::new();
+        restarted.restore_layout(&loaded);
+
+        assert_eq!(restarted.layout(), Some(layout));
+        assert!(restarted.plane_shown(DebuggerPane::TileMap(TileMapId(1))));
+        assert!(!restarted.plane_shown(DebuggerPane::Instructions));
+    }
+
+    #[test]
+    fn split_ratios_are_kept() {
+        let layout = PaneLayout::Split {
+            axis: Horizontal,
+            ratio: 0.75,
+            a: pane(DebuggerPane::Screen),
+            b: Box::new(PaneLayout::Split {
+                axis: Vertical,
+                ratio: 0.25,
+                a: pane(DebuggerPane::Tiles),
+                b: pane(DebuggerPane::Audio),
+            }),
+        };
+        let mut panes = DebuggerPanes::new();
+        panes.restore_layout(&layout);
+        assert_eq!(panes.layout(), Some(layout));
+    }
+
+    #[test]
+    fn invalid_layout_falls_back_to_default() {
+        let layout = PaneLayout::Split {
+            axis: Vertical,
+            ratio: 0.5,
+            a: pane(DebuggerPane::Screen),
+            b: pane(DebuggerPane::Screen),
+        };
+        let mut panes = DebuggerPanes::new();
+        panes.restore_layout(&layout);
+        assert_eq!(panes.layout(), Some(PaneLayout::default()));
+    }
+
+    #[test]
+    fn reset_restores_default() {
+        let mut panes = DebuggerPanes::new();
+        panes.update(Message::ShowPane(DebuggerPane::Sprites));
+        panes.update(Message::ResetLayout);
+        assert_eq!(panes.layout(), Some(PaneLayout::default()));
+        assert!(!panes.plane_shown(DebuggerPane::Sprites));
+    }
+}
@@ -26,6 +26,7 @@ use missingno_gb::ppu::{
 
 pub mod sprites;
 mod tile_atlas;
+pub mod tile_files;
 pub mod tile_maps;
 mod tile_widget;
 pub mod tiles;
@@ -48,6 +49,7 @@ pub fn ppu_sidebar<'a, P: PpuModel>(
         row![
             label_value("ly", &ppu.video.ly().to_string()),
             label_value("lx", &ppu.lx().to_string()),
+            label_value("dot", &ppu.ppu_position().1.to_string()),
         ]
         .spacing(s())
         .align_y(Vertical::Center),
@@ -3,17 +3,41 @@ use iced::{
     widget::{container, shader},
 };
 
-use crate::app::{Message, texture_renderer::TextureRenderer};
+use crate::app::{
+    Message,
+    texture_renderer::TextureRenderer,
+    ui::palette::{BLUE, RED},
+};
 use missingno_gb::ppu::types::{palette::Palette, tiles::TileBlock};
 
-/// Renders a grid of tiles as a single texture atlas
-pub fn tile_block_atlas(block: &TileBlock, palette: &Palette) -> Element<'static, Message> {
+/// Renders a grid of tiles as a single texture atlas. Tiles whose bit is set
+/// in `highlighted` get a border drawn over their edge pixels, as does the
+/// `selected` tile in another colour.
+pub fn tile_block_atlas(
+    block: &TileBlock,
+    palette: &Palette,
+    highlighted: u128,
+    selected: Option<u8>,
+) -> Element<'static, Message> {
     // 16 tiles wide × 8 tiles tall = 128 tiles total
     // Each tile is 8×8 pixels
     const ATLAS_WIDTH: u32 = 16 * 8; // 128 pixels
     const ATLAS_HEIGHT: u32 = 8 * 8; // 64 pixels
     const ATLAS_SIZE: usize = (ATLAS_WIDTH * ATLAS_HEIGHT * 4) as usize;
 
+    const HIGHLIGHT: [u8; 4] = [
+        (RED.r * 255.0) as u8,
+        (RED.g * 255.0) as u8,
+        (RED.b * 255.0) as u8,
+        255,
+    ];
+    const SELECTED: [u8; 4] = [
+        (BLUE.r * 255.0) as u8,
+        (BLUE.g * 255.0) as u8,
+        (BLUE.b * 255.0) as u8,
+        255,
+    ];
+
     let mut pixels = Vec::with_capacity(ATLAS_SIZE);
 
     // Render tiles row by row
@@ -24,11 +48,19 @@ pub fn tile_block_atlas(block: &TileBlock, palette: &Palette) -> Element<'static
             for tile_col in 0..16 {
                 let tile_index = tile_row * 16 + tile_col;
                 let tile = block.tile(missingno_gb::ppu::types::tiles::TileIndex(tile_index));
+                let highlight = highlighted & 1 << tile_index != 0;
 
                 // For each pixel in this tile's row
                 for pixel_x in 0..8 {
-                    let color = palette.color(tile.pixel(pixel_x, pixel_y));
-                    pixels.extend_from_slice(&[color.r, color.g, color.b, 255]);
+                    let edge = pixel_x == 0 || pixel_x == 7 || pixel_y == 0 || pixel_y == 7;
+                    if edge && selected == Some(tile_index) {
+                        pixels.extend_from_slice(&SELECTED);
+                    } else if highlight && edge {
+                        pixels.extend_from_slice(&HIGHLIGHT);
+                    } else {
+                        let color = palette.color(tile.pixel(pixel_x, pixel_y));
+                        pixels.extend_from_slice(&[color.r, color.g, color.b, 255]);
+                    }
                 }
             }
         }
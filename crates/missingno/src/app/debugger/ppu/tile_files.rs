@@ -0,0 +1,242 @@
+//! Tile data in and out of the tile viewer, for editing in other tools.
+//!
+//! `.bin` files hold raw 2bpp tile data, byte for byte as VRAM stores it.
+//! PNGs are greyscale, colour number 0 white through 3 black, with a block
+//! laid out 16 tiles wide as the viewer shows it.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use image::{GrayImage, ImageFormat, Luma};
+use missingno_gb::ppu::types::tiles::Tile;
+
+/// Bytes of one tile: two bit planes for each of its eight rows.
+const TILE_BYTES: usize = 16;
+/// Tiles in one of the three 128-tile blocks.
+const BLOCK_TILES: usize = 128;
+/// Tiles across a block, matching the viewer.
+const BLOCK_COLUMNS: usize = 16;
+/// Offset of the end of tile data ($9800) within a VRAM bank.
+const TILE_DATA_END: usize = 0x1800;
+
+/// Grey level of each colour number in an exported PNG.
+const SHADES: [u8; 4] = [0xFF, 0xAA, 0x55, 0x00];
+
+/// What the tile viewer has selected for export or import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileSelection {
+    Tile { block: u8, index: u8 },
+    Block(u8),
+}
+
+impl TileSelection {
+    /// Offset of the first selected tile within a VRAM bank.
+    pub fn offset(self) -> usize {
+        match self {
+            Self::Tile { block, index } => {
+                (block as usize * BLOCK_TILES + index as usize) * TILE_BYTES
+            }
+            Self::Block(block) => block as usize * BLOCK_TILES * TILE_BYTES,
+        }
+    }
+
+    fn tiles(self) -> usize {
+        match self {
+            Self::Tile { .. } => 1,
+            Self::Block(_) => BLOCK_TILES,
+        }
+    }
+
+    fn columns(self) -> usize {
+        match self {
+            Self::Tile { .. } => 1,
+            Self::Block(_) => BLOCK_COLUMNS,
+        }
+    }
+
+    /// Default file name, without an extension: `tile_8010` or `block_1`.
+    pub fn file_stem(self) -> String {
+        match self {
+            Self::Tile { .. } => format!("tile_{:04X}", 0x8000 + self.offset()),
+            Self::Block(block) => format!("block_{block}"),
+        }
+    }
+}
+
+impl std::fmt::Display for TileSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tile { .. } => write!(f, "${:04X}", 0x8000 + self.offset()),
+            Self::Block(block) => write!(f, "block {block}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileFormat {
+    Bin,
+    Png,
+}
+
+impl TileFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Bin => "bin",
+            Self::Png => "png",
+        }
+    }
+
+    /// PNGs by extension; anything else is read as raw 2bpp.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("png") => Self::Png,
+            _ => Self::Bin,
+        }
+    }
+}
+
+/// The selected tiles of `vram`, one bank, encoded as `format`.
+pub fn export(vram: &[u8], selection: TileSelection, format: TileFormat) -> Vec<u8> {
+    let start = selection.offset();
+    let bytes = &vram[start..start + selection.tiles() * TILE_BYTES];
+    match format {
+        TileFormat::Bin => bytes.to_vec(),
+        TileFormat::Png => encode_png(bytes, selection.columns()),
+    }
+}
+
+/// Tile data decoded from a `format` file, ready to write at `selection`.
+/// `None` if the file can't be read or would run past the end of tile data.
+pub fn import(file: &[u8], selection: TileSelection, format: TileFormat) -> Option<Vec<u8>> {
+    let bytes = match format {
+        TileFormat::Bin => file
+            .len()
+            .is_multiple_of(TILE_BYTES)
+            .then(|| file.to_vec())?,
+        TileFormat::Png => decode_png(file)?,
+    };
+    (!bytes.is_empty() && selection.offset() + bytes.len() <= TILE_DATA_END).then_some(bytes)
+}
+
+fn encode_png(bytes: &[u8], columns: usize) -> Vec<u8> {
+    let tiles: Vec<[u8; 64]> = bytes
+        .chunks_exact(TILE_BYTES)
+        .map(|tile| Tile::from_bytes(tile.try_into().unwrap()).pixels())
+        .collect();
+    let rows = tiles.len().div_ceil(columns);
+    let image = GrayImage::from_fn(columns as u32 * 8, rows as u32 * 8, |x, y| {
+        let (x, y) = (x as usize, y as usize);
+        let colour = tiles[y / 8 * columns + x / 8][y % 8 * 8 + x % 8];
+        Luma([SHADES[colour as usize]])
+    });
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .expect("encoding to memory can't fail");
+    png
+}
+
+/// Tiles read left to right, top to bottom. Each pixel takes the colour
+/// number of the nearest of [`SHADES`].
+fn decode_png(file: &[u8]) -> Option<Vec<u8>> {
+    let image = image::load_from_memory_with_format(file, ImageFormat::Png)
+        .ok()?
+        .to_luma8();
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    if !width.is_multiple_of(8) || !height.is_multiple_of(8) {
+        return None;
+    }
+
+    let mut bytes = Vec::new();
+    for tile_y in (0..height).step_by(8) {
+        for tile_x in (0..width).step_by(8) {
+            let pixels: [u8; 64] = std::array::from_fn(|i| {
+                let Luma([level]) =
+                    *image.get_pixel((tile_x + i % 8) as u32, (tile_y + i / 8) as u32);
+                3 - ((level as u16 + 42) / 85) as u8
+            });
+            bytes.extend(Tile::from_pixels(&pixels).to_bytes());
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAST_TILE: TileSelection = TileSelection::Tile {
+        block: 2,
+        index: 127,
+    };
+
+    /// A bank's tile data with every byte different from its neighbours.
+    fn vram() -> Vec<u8> {
+        (0..0x2000)
+            .map(|i: usize| (i * 7 + i / 256) as u8)
+            .collect()
+    }
+
+    fn round_trip(selection: TileSelection, format: TileFormat) {
+        let mut vram = vram();
+        let before = vram.clone();
+        let file = export(&vram, selection, format);
+        let bytes = import(&file, selection, format).unwrap();
+        let start = selection.offset();
+        vram[start..start + bytes.len()].copy_from_slice(&bytes);
+        assert_eq!(vram, before, "{selection} as {format:?}");
+    }
+
+    #[test]
+    fn export_then_import_leaves_vram_unchanged() {
+        for format in [TileFormat::Bin, TileFormat::Png] {
+            round_trip(TileSelection::Tile { block: 0, index: 0 }, format);
+            round_trip(LAST_TILE, format);
+            for block in 0..3 {
+                round_trip(TileSelection::Block(block), format);
+            }
+        }
+    }
+
+    #[test]
+    fn png_blocks_are_laid_out_as_the_viewer_shows_them() {
+        let png = export(&vram(), TileSelection::Block(1), TileFormat::Png);
+        let image = image::load_from_memory(&png).unwrap();
+        assert_eq!((image.width(), image.height()), (128, 64));
+    }
+
+    #[test]
+    fn png_shades_snap_to_the_nearest_colour() {
+        let image = GrayImage::from_fn(8, 8, |x, _| {
+            Luma([[0xF0, 0xB0, 0x40, 0x10][x as usize % 4]])
+        });
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let bytes = import(&png, TileSelection::Block(0), TileFormat::Png).unwrap();
+        let tile = Tile::from_bytes(bytes.try_into().unwrap());
+        assert_eq!(tile.pixels()[..4], [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn imports_that_run_past_tile_data_are_refused() {
+        let block = export(&vram(), TileSelection::Block(0), TileFormat::Bin);
+        assert!(import(&block, LAST_TILE, TileFormat::Bin).is_none());
+        assert!(import(&block, TileSelection::Block(2), TileFormat::Bin).is_some());
+        assert!(
+            import(&block[..15], LAST_TILE, TileFormat::Bin).is_none(),
+            "partial tile"
+        );
+    }
+
+    #[test]
+    fn png_dimensions_must_be_whole_tiles() {
+        let mut png = Vec::new();
+        GrayImage::new(12, 8)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert!(import(&png, TileSelection::Block(0), TileFormat::Png).is_none());
+    }
+}
@@ -1,23 +1,73 @@
+use std::{fmt, sync::Arc};
+
 use iced::{
-    Length,
+    Element, Length,
     Length::Fill,
-    widget::{Stack, container, pane_grid, responsive, shader},
+    widget::{
+        Stack, button, container, pane_grid, responsive, row, scrollable,
+        scrollable::{Direction, Scrollbar},
+        shader, text,
+    },
 };
 
 use crate::app::{
-    Message,
+    self,
     console::ConsoleColors,
-    debugger::panes::{pane, title_bar},
+    debugger::panes::{self, pane, title_bar_with_detail},
     texture_renderer::TextureRenderer,
+    ui::{fonts, palette, sizes::s},
 };
 use crate::render::{tile_map_rgba, tile_map_rgba_cgb};
 use missingno_gb::ppu::{Ppu, memory::Vram, model::PpuModel, types::tiles::TileMapId};
 
 mod viewport_overlay;
 
+/// Tile maps are 32x32 tiles of 8x8 pixels.
+const MAP_SIZE: f32 = 256.0;
+
+/// How the 256x256 map is sized within the pane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zoom {
+    /// Scale to the largest square that fits the pane.
+    Fit,
+    /// Integer scale, scrolling when the map outgrows the pane.
+    Scale(u8),
+}
+
+impl Zoom {
+    fn next(self) -> Self {
+        match self {
+            Zoom::Fit => Zoom::Scale(1),
+            Zoom::Scale(1) => Zoom::Scale(2),
+            Zoom::Scale(_) => Zoom::Fit,
+        }
+    }
+}
+
+impl fmt::Display for Zoom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zoom::Fit => write!(f, "fit"),
+            Zoom::Scale(scale) => write!(f, "{scale}x"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    SetZoom(TileMapId, Zoom),
+}
+
+impl From<Message> for app::Message {
+    fn from(message: Message) -> Self {
+        panes::Message::Pane(panes::PaneMessage::TileMap(message)).into()
+    }
+}
+
 pub struct TileMapPane {
     tile_map: TileMapId,
     title: String,
+    zoom: Zoom,
 }
 
 impl TileMapPane {
@@ -25,6 +75,18 @@ impl TileMapPane {
         Self {
             tile_map,
             title: tile_map.to_string(),
+            zoom: Zoom::Fit,
+        }
+    }
+
+    pub fn tile_map(&self) -> TileMapId {
+        self.tile_map
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::SetZoom(tile_map, zoom) if tile_map == self.tile_map => self.zoom = zoom,
+            Message::SetZoom(..) => {}
         }
     }
 
@@ -33,7 +95,7 @@ impl TileMapPane {
         ppu: &Ppu<P>,
         vram: &P::Vram,
         colors: &ConsoleColors,
-    ) -> pane_grid::Content<'_, Message> {
+    ) -> pane_grid::Content<'_, app::Message> {
         let control = ppu.control();
         let tile_map_id = self.tile_map;
 
@@ -54,7 +116,7 @@ impl TileMapPane {
         };
 
         // Pre-render tile map pixels so the closure doesn't need VramBank
-        let pixels: std::sync::Arc<[u8]> = match colors {
+        let pixels: Arc<[u8]> = match colors {
             ConsoleColors::Dmg { palette } => {
                 tile_map_rgba(vram.bank(0), tile_map_id, control, palette)
             }
@@ -69,31 +131,65 @@ impl TileMapPane {
             win_viewport,
         };
 
-        pane(
-            title_bar(&self.title),
-            responsive(move |size| {
-                let fit = size.width.min(size.height);
+        let mut detail = control.tile_address_mode().to_string();
+        if bg_viewport.is_some() {
+            detail.push_str(&format!(" · {scx},{scy}"));
+        }
+        let detail = row![
+            text(detail)
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(palette::MUTED),
+            button(
+                text(self.zoom.to_string())
+                    .font(fonts::monospace())
+                    .size(11.0)
+            )
+            .on_press(Message::SetZoom(tile_map_id, self.zoom.next()).into())
+            .style(button::text)
+            .padding(0),
+        ]
+        .spacing(s());
 
-                let renderer = TextureRenderer::with_pixels(256, 256, pixels.clone());
-                let overlay = overlay.clone();
-
-                container(
-                    Stack::new()
-                        .push(
-                            shader(renderer)
-                                .width(Length::Fixed(fit))
-                                .height(Length::Fixed(fit)),
-                        )
-                        .push(
-                            iced::widget::canvas(overlay)
-                                .width(Length::Fixed(fit))
-                                .height(Length::Fixed(fit)),
-                        ),
-                )
-                .center(Fill)
-                .into()
+        let body: Element<'_, app::Message> = match self.zoom {
+            Zoom::Fit => responsive(move |size| {
+                let fit = size.width.min(size.height);
+                container(map_view(pixels.clone(), overlay.clone(), fit))
+                    .center(Fill)
+                    .into()
             })
             .into(),
-        )
+            Zoom::Scale(scale) => scrollable(map_view(pixels, overlay, MAP_SIZE * scale as f32))
+                .direction(Direction::Both {
+                    vertical: Scrollbar::default(),
+                    horizontal: Scrollbar::default(),
+                })
+                .width(Fill)
+                .height(Fill)
+                .into(),
+        };
+
+        pane(title_bar_with_detail(&self.title, detail), body)
     }
 }
+
+fn map_view(
+    pixels: Arc<[u8]>,
+    overlay: viewport_overlay::ViewportOverlay,
+    size: f32,
+) -> Element<'static, app::Message> {
+    let renderer = TextureRenderer::with_pixels(256, 256, pixels);
+
+    Stack::new()
+        .push(
+            shader(renderer)
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size)),
+        )
+        .push(
+            iced::widget::canvas(overlay)
+                .width(Length::Fixed(size))
+                .height(Length::Fixed(size)),
+        )
+        .into()
+}
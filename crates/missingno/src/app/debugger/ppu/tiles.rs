@@ -1,31 +1,49 @@
 use iced::{
     Element,
     Length::Fill,
-    widget::{column, pane_grid, row, scrollable, text, toggler},
+    widget::{button, column, mouse_area, pane_grid, row, scrollable, text, toggler},
 };
 
 use crate::app::{
     self,
     console::ConsoleColors,
     debugger::{
-        panes::{self, pane, title_bar, title_bar_with_detail},
-        ppu::tile_atlas::tile_block_atlas,
+        self,
+        panes::{self, pane, title_bar_with_detail},
+        ppu::{
+            tile_atlas::tile_block_atlas,
+            tile_files::{TileFormat, TileSelection},
+        },
+    },
+    ui::{
+        fonts, palette,
+        sizes::{m, s},
     },
-    ui::sizes::m,
 };
-use missingno_gb::ppu::{
-    memory::{Vram, VramBank},
-    types::palette::Palette,
-    types::tiles::TileBlockId,
+use missingno_gb::{
+    debugger::tile_changes::TileChanges,
+    ppu::{
+        memory::{Vram, VramBank},
+        types::palette::Palette,
+        types::tiles::TileBlockId,
+    },
 };
 
 pub struct TilesPane {
     selected_bank: u8,
+    highlight_changes: bool,
+    /// Block and tile index under the mouse.
+    hovered: Option<(u8, u8)>,
+    selection: Option<TileSelection>,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum Message {
     SelectBank(u8),
+    HighlightChanges(bool),
+    Hover(u8, Option<u8>),
+    SelectHovered,
+    Select(TileSelection),
 }
 
 impl Into<app::Message> for Message {
@@ -36,43 +54,77 @@ impl Into<app::Message> for Message {
 
 impl TilesPane {
     pub fn new() -> Self {
-        Self { selected_bank: 0 }
+        Self {
+            selected_bank: 0,
+            highlight_changes: false,
+            hovered: None,
+            selection: None,
+        }
     }
 
     pub fn update(&mut self, message: Message) {
         match message {
             Message::SelectBank(bank) => self.selected_bank = bank,
+            Message::HighlightChanges(on) => self.highlight_changes = on,
+            Message::Hover(block, index) => {
+                self.hovered = index.map(|index| (block, index));
+            }
+            Message::SelectHovered => {
+                if let Some((block, index)) = self.hovered {
+                    self.selection = Some(TileSelection::Tile { block, index });
+                }
+            }
+            Message::Select(selection) => self.selection = Some(selection),
         }
     }
 
     pub fn content(
         &self,
         vram: &impl Vram,
+        changes: &TileChanges,
         colors: &ConsoleColors,
     ) -> pane_grid::Content<'_, app::Message> {
         let palette = colors.tiles_palette();
         let bank = vram.bank(self.selected_bank);
+        let highlighted = |block| {
+            if self.highlight_changes {
+                changes.block(self.selected_bank, block)
+            } else {
+                0
+            }
+        };
 
-        let title = if colors.is_cgb() {
-            title_bar_with_detail(
-                "Tiles",
+        let changed_toggle = toggler(self.highlight_changes)
+            .label("changed")
+            .size(14.0)
+            .on_toggle(|on| Message::HighlightChanges(on).into());
+        let mut detail = row![].spacing(m());
+        if let Some(selection) = self.selection {
+            detail = detail.push(self.selection_actions(selection));
+        }
+        detail = detail.push(changed_toggle);
+        if colors.is_cgb() {
+            detail = detail.push(
                 toggler(self.selected_bank == 1)
                     .label("bank 1")
                     .size(14.0)
                     .on_toggle(|on| Message::SelectBank(on as u8).into()),
-            )
-        } else {
-            title_bar("Tiles")
-        };
+            );
+        }
+        let title = title_bar_with_detail("Tiles", detail);
 
         pane(
             title,
             scrollable(
-                row![
-                    tile_block(bank, TileBlockId(0), palette),
-                    tile_block(bank, TileBlockId(1), palette),
-                    tile_block(bank, TileBlockId(2), palette)
-                ]
+                row((0..3).map(|block| {
+                    tile_block(
+                        bank,
+                        TileBlockId(block),
+                        palette,
+                        highlighted(TileBlockId(block)),
+                        self.selection,
+                    )
+                }))
                 .spacing(m())
                 .padding(m())
                 .width(Fill)
@@ -81,16 +133,73 @@ impl TilesPane {
             .into(),
         )
     }
+
+    /// The selection's address and its export and import buttons.
+    fn selection_actions(&self, selection: TileSelection) -> Element<'static, app::Message> {
+        let bank = self.selected_bank;
+        let action = |label: &'static str, message: debugger::Message| {
+            button(text(label).font(fonts::monospace()).size(11.0))
+                .on_press(message.into())
+                .style(button::text)
+                .padding(0)
+        };
+        let export = |format| debugger::Message::ExportTiles {
+            bank,
+            selection,
+            format,
+        };
+
+        row![
+            text(selection.to_string())
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(palette::MUTED),
+            action("Export 2bpp", export(TileFormat::Bin)),
+            action("Export PNG", export(TileFormat::Png)),
+            action("Import", debugger::Message::ImportTiles { bank, selection }),
+        ]
+        .spacing(s())
+        .into()
+    }
 }
 
+/// A block's atlas under its name. Clicking the name selects the block and
+/// clicking a tile selects that tile.
 fn tile_block(
     vram: &VramBank,
     block: TileBlockId,
     palette: &Palette,
+    highlighted: u128,
+    selection: Option<TileSelection>,
 ) -> Element<'static, app::Message> {
+    let id = block.0;
+    let selected = match selection {
+        Some(TileSelection::Tile { block, index }) if block == id => Some(index),
+        _ => None,
+    };
+    let block_selected = selection == Some(TileSelection::Block(id));
+
     column![
-        text(block.to_string()),
-        tile_block_atlas(vram.tile_block(block), palette)
+        button(text(block.to_string()).color_maybe(block_selected.then_some(palette::BLUE)))
+            .on_press(Message::Select(TileSelection::Block(id)).into())
+            .style(button::text)
+            .padding(0),
+        mouse_area(tile_block_atlas(
+            vram.tile_block(block),
+            palette,
+            highlighted,
+            selected
+        ))
+        .on_move(move |point| Message::Hover(id, tile_at(point)).into())
+        .on_exit(Message::Hover(id, None).into())
+        .on_press(Message::SelectHovered.into())
+        .interaction(iced::mouse::Interaction::Pointer),
     ]
     .into()
 }
+
+/// The tile under `point` in an atlas drawn at twice size, 16 tiles across.
+fn tile_at(point: iced::Point) -> Option<u8> {
+    let (column, row) = ((point.x / 16.0) as i32, (point.y / 16.0) as i32);
+    ((0..16).contains(&column) && (0..8).contains(&row)).then(|| (row * 16 + column) as u8)
+}
@@ -0,0 +1,124 @@
+use iced::{
+    Element, Length,
+    widget::{button, column, pane_grid, row, scrollable, text, toggler},
+};
+
+use crate::app::{
+    self,
+    debugger::{
+        self,
+        panes::{pane, title_bar_with_detail},
+    },
+    ui::{
+        fonts, palette,
+        sizes::{l, m, s},
+    },
+};
+use missingno_gb::{
+    Console, Model, cpu::instructions::Instruction, debugger::instructions::InstructionsIterator,
+    profiler::Profile,
+};
+
+/// Rows shown in each list.
+const HOTTEST: usize = 64;
+
+/// The hottest code since counting was turned on: the addresses and
+/// opcodes executed most often.
+pub struct ProfilerPane;
+
+impl ProfilerPane {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn content<'a, M: Model>(
+        &self,
+        console: &'a Console<M>,
+    ) -> pane_grid::Content<'a, app::Message> {
+        let profile = console.profile();
+        let detail = row![
+            toggler(profile.is_some())
+                .label("count")
+                .size(14.0)
+                .on_toggle(|on| debugger::Message::SetProfiling(on).into()),
+            button(text("Clear").font(fonts::monospace()).size(11.0))
+                .on_press(debugger::Message::ClearProfile.into())
+                .style(button::text)
+                .padding(0),
+        ]
+        .spacing(m());
+
+        let body: Element<'a, app::Message> = match profile {
+            Some(profile) => {
+                scrollable(row![hot_addresses(console, profile), hot_opcodes(profile)].spacing(l()))
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into()
+            }
+            None => text("Turn on counting to find the hottest code.")
+                .size(13.0)
+                .color(palette::MUTED)
+                .into(),
+        };
+
+        pane(title_bar_with_detail("Profiler", detail), body)
+    }
+}
+
+fn hot_addresses<'a, M: Model>(
+    console: &Console<M>,
+    profile: &Profile,
+) -> Element<'a, app::Message> {
+    let rows = profile
+        .hottest_addresses(HOTTEST)
+        .into_iter()
+        .map(|(address, count)| {
+            let mut iterator = InstructionsIterator::new(address, console);
+            let instruction = Instruction::decode(&mut iterator)
+                .map(|instruction| instruction.to_string())
+                .unwrap_or_default();
+            hot_row(
+                format!("${address:04X}"),
+                count,
+                profile.total(),
+                instruction,
+            )
+        });
+    column![heading("Address")].extend(rows).spacing(s()).into()
+}
+
+fn hot_opcodes<'a>(profile: &Profile) -> Element<'a, app::Message> {
+    let rows = profile
+        .hottest_opcodes(HOTTEST)
+        .into_iter()
+        .map(|(opcode, count)| {
+            hot_row(
+                format!("${opcode:02X}"),
+                count,
+                profile.total(),
+                String::new(),
+            )
+        });
+    column![heading("Opcode")].extend(rows).spacing(s()).into()
+}
+
+fn heading<'a>(label: &'a str) -> Element<'a, app::Message> {
+    text(label).size(11.0).color(palette::MUTED).into()
+}
+
+fn hot_row<'a>(key: String, count: u64, total: u64, detail: String) -> Element<'a, app::Message> {
+    let share = count as f64 * 100.0 / total.max(1) as f64;
+    row![
+        text(key).font(fonts::monospace()).size(13.0),
+        text(format!("{count:>10}"))
+            .font(fonts::monospace())
+            .size(13.0),
+        text(format!("{share:5.1}%"))
+            .font(fonts::monospace())
+            .size(13.0)
+            .color(palette::MUTED),
+        text(detail).font(fonts::monospace()).size(13.0),
+    ]
+    .spacing(m())
+    .into()
+}
@@ -1,6 +1,6 @@
 use iced::{
-    Length::{self, Fill},
-    widget::{container, pane_grid, responsive, shader},
+    Length::Fill,
+    widget::{pane_grid, shader},
 };
 
 use crate::app::{
@@ -10,6 +10,7 @@ use crate::app::{
         panes::{self, pane, title_bar},
     },
     screen::{ScreenDisplay, ScreenView},
+    settings::{ScreenFilter, ScreenScaling},
 };
 use missingno_gb::ppu::types::palette::PaletteChoice;
 
@@ -57,21 +58,21 @@ impl ScreenPane {
         self.screen_view.palette = palette;
     }
 
+    pub fn set_presentation(
+        &mut self,
+        scaling: ScreenScaling,
+        grid: bool,
+        filter: ScreenFilter,
+        frame_blending: bool,
+    ) {
+        self.screen_view
+            .set_presentation(scaling, grid, filter, frame_blending);
+    }
+
     pub fn content(&self) -> pane_grid::Content<'_, app::Message> {
         pane(
             title_bar("Screen"),
-            responsive(|size| {
-                let shortest = size.width.min(size.height);
-
-                container(
-                    shader(&self.screen_view)
-                        .width(Length::Fixed(shortest))
-                        .height(Length::Fixed(shortest)),
-                )
-                .center(Fill)
-                .into()
-            })
-            .into(),
+            shader(&self.screen_view).width(Fill).height(Fill).into(),
         )
     }
 }
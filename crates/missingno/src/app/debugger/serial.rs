@@ -0,0 +1,115 @@
+use iced::{
+    Length,
+    widget::{button, pane_grid, row, scrollable, text},
+};
+
+use crate::app::{
+    self,
+    debugger::{
+        self,
+        panes::{pane, title_bar_with_detail},
+    },
+    ui::{fonts, palette, sizes::s},
+};
+
+/// Scrollback cap. Once exceeded, the oldest output is dropped.
+const MAX_SCROLLBACK: usize = 64 * 1024;
+
+/// Bytes the game has shifted out of the serial port, decoded as ASCII.
+/// Test ROMs (blargg's in particular) report their results this way.
+pub struct SerialLog {
+    text: String,
+    bytes: usize,
+}
+
+impl SerialLog {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            bytes: 0,
+        }
+    }
+
+    pub fn append(&mut self, bytes: &[u8]) {
+        self.bytes += bytes.len();
+        self.text.extend(bytes.iter().map(|&byte| decode(byte)));
+        self.trim();
+    }
+
+    /// Log a line that didn't come over the link, such as an `LD D,D`
+    /// debug message.
+    pub fn log_message(&mut self, message: &str) {
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+        }
+        self.text.extend(message.bytes().map(decode));
+        self.text.push('\n');
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        if self.text.len() > MAX_SCROLLBACK {
+            let mut cut = self.text.len() - MAX_SCROLLBACK;
+            while !self.text.is_char_boundary(cut) {
+                cut += 1;
+            }
+            self.text.drain(..cut);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.bytes = 0;
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+fn decode(byte: u8) -> char {
+    match byte {
+        b'\n' | b'\t' | 0x20..=0x7E => byte as char,
+        _ => '\u{00B7}',
+    }
+}
+
+pub struct SerialPane;
+
+impl SerialPane {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn content<'a>(&self, log: &'a SerialLog) -> pane_grid::Content<'a, app::Message> {
+        let detail = row![
+            text(format!("{} bytes", log.bytes()))
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(palette::MUTED),
+            button(text("Clear").font(fonts::monospace()).size(11.0))
+                .on_press(debugger::Message::ClearSerialLog.into())
+                .style(button::text)
+                .padding(0),
+        ]
+        .spacing(s());
+
+        pane(
+            title_bar_with_detail("Serial", detail),
+            scrollable(
+                text(log.text())
+                    .font(fonts::monospace())
+                    .size(13.0)
+                    .width(Length::Fill),
+            )
+            .anchor_bottom()
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+        )
+    }
+}
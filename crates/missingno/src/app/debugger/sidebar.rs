@@ -105,11 +105,14 @@ impl Sidebar {
         cpu: &'a Cpu,
         game_boy: &'a Console<M>,
     ) -> Element<'a, app::Message> {
-        let summary = format!("pc {:04X} · sp {:04X}", cpu.ir_address, cpu.stack_pointer,);
+        let mut summary = format!("pc {:04X} · sp {:04X}", cpu.ir_address, cpu.stack_pointer,);
+        if game_boy.double_speed() {
+            summary.push_str(" · 2×");
+        }
         let collapsed = self.is_collapsed(Section::Cpu);
 
         let body = column![
-            pointers(cpu),
+            pointers(cpu, game_boy),
             rule::horizontal(1),
             register_a_row(cpu),
             register_pair_row(cpu, Register8::B, Register8::C, Register16::Bc),
@@ -117,6 +120,8 @@ impl Sidebar {
             register_pair_row(cpu, Register8::H, Register8::L, Register16::Hl),
             rule::horizontal(1),
             interrupts(game_boy),
+            rule::horizontal(1),
+            dma_row(game_boy),
         ]
         .padding(s())
         .spacing(s())
@@ -273,7 +278,7 @@ pub fn tooltip_style(theme: &iced::Theme) -> container::Style {
 
 // --- Pointers + halt ---
 
-fn pointers(cpu: &Cpu) -> Element<'_, app::Message> {
+fn pointers<'a, M: Model>(cpu: &'a Cpu, game_boy: &Console<M>) -> Element<'a, app::Message> {
     let halted = cpu.halt.state == HaltState::Halted;
     let pc_color = if halted {
         palette::OVERLAY0
@@ -307,13 +312,35 @@ fn pointers(cpu: &Cpu) -> Element<'_, app::Message> {
         pc_display
     };
 
-    row![
+    let mut row = row![
         pc_element,
         pointer("sp", format!("{:04X}", cpu.stack_pointer)),
     ]
     .spacing(s())
-    .align_y(Vertical::Center)
-    .into()
+    .align_y(Vertical::Center);
+    if let Some(speed) = speed_label(game_boy) {
+        row = row.push(Space::new().width(Length::Fill)).push(speed);
+    }
+    row.into()
+}
+
+/// CGB CPU speed, shown only once it differs from the DMG's: "2×" at
+/// double speed, "switching" while a STOP holds the CPU for the change.
+fn speed_label<M: Model>(game_boy: &Console<M>) -> Option<Element<'static, app::Message>> {
+    let label = if game_boy.speed_switch_in_progress() {
+        "switching"
+    } else if game_boy.double_speed() {
+        "2×"
+    } else {
+        return None;
+    };
+    Some(
+        text(label)
+            .font(fonts::monospace())
+            .size(DETAIL)
+            .color(palette::PEACH)
+            .into(),
+    )
 }
 
 fn pointer(label: &str, value: String) -> Element<'_, app::Message> {
@@ -332,6 +359,42 @@ fn pointer(label: &str, value: String) -> Element<'_, app::Message> {
     .into()
 }
 
+/// OAM DMA progress: the source page and the bytes still to copy, so a
+/// transfer can be watched while single-stepping.
+fn dma_row<M: Model>(game_boy: &Console<M>) -> Element<'static, app::Message> {
+    use super::interrupts::pip;
+
+    let status = game_boy.dma_status();
+    let source = status.map_or("----".to_string(), |status| {
+        format!("{:04X}", status.source)
+    });
+    let progress = match status {
+        None => "idle".to_string(),
+        Some(status) if status.starting => "starting".to_string(),
+        Some(status) => format!("{}/160 left", status.remaining),
+    };
+
+    row![
+        pip(status.is_some(), palette::PEACH),
+        text("dma")
+            .font(fonts::monospace())
+            .size(REG)
+            .color(palette::MUTED),
+        text(source)
+            .font(fonts::monospace())
+            .size(REG)
+            .color(palette::PURPLE),
+        Space::new().width(Length::Fill),
+        text(progress)
+            .font(fonts::monospace())
+            .size(DETAIL)
+            .color(palette::OVERLAY0),
+    ]
+    .spacing(s())
+    .align_y(Vertical::Center)
+    .into()
+}
+
 // --- Registers ---
 
 /// Fixed width for one 8-bit register display ("b 04"), so columns align.
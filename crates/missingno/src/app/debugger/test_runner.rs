@@ -0,0 +1,305 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use iced::{
+    Element, Length, Task,
+    alignment::Vertical,
+    widget::{Column, button, container, pane_grid, row, scrollable, text},
+};
+
+use crate::app::{
+    self,
+    debugger::{
+        self,
+        interrupts::pip,
+        panes::{pane, title_bar_with_detail},
+    },
+    load,
+    ui::{fonts, palette, sizes::s},
+};
+use missingno_gb::{Console, GameBoy, Model, cartridge::Cartridge, diagnostics};
+use missingno_gbc::GameBoyColor;
+
+/// Per-ROM budget in emulated frames (two minutes at ~60fps).
+const TIMEOUT_FRAMES: u32 = 7200;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    PickFolder,
+    FolderPicked(PathBuf),
+    Finished {
+        run: u64,
+        index: usize,
+        outcome: Outcome,
+    },
+}
+
+impl From<Message> for app::Message {
+    fn from(message: Message) -> Self {
+        debugger::Message::TestRunner(message).into()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    TimedOut,
+}
+
+struct TestResult {
+    path: PathBuf,
+    outcome: Option<Outcome>,
+}
+
+/// Runs every ROM in a folder headlessly and records a pass/fail verdict
+/// from its serial output (blargg) or its final register file (mooneye).
+pub struct TestRunner {
+    folder: Option<PathBuf>,
+    results: Vec<TestResult>,
+    /// Bumped for each folder run so verdicts from an abandoned run are dropped.
+    run: u64,
+}
+
+impl TestRunner {
+    pub fn new() -> Self {
+        Self {
+            folder: None,
+            results: Vec::new(),
+            run: 0,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<app::Message> {
+        match message {
+            Message::PickFolder => {
+                let dialog = rfd::AsyncFileDialog::new();
+                Task::perform(dialog.pick_folder(), |folder| match folder {
+                    Some(handle) => Message::FolderPicked(handle.path().to_path_buf()).into(),
+                    None => app::Message::None,
+                })
+            }
+            Message::FolderPicked(folder) => {
+                let mut roms = Vec::new();
+                collect_roms(&folder, &mut roms);
+                roms.sort();
+
+                self.results = roms
+                    .into_iter()
+                    .map(|path| TestResult {
+                        path,
+                        outcome: None,
+                    })
+                    .collect();
+                self.folder = Some(folder);
+                self.run += 1;
+                self.run_next()
+            }
+            Message::Finished {
+                run,
+                index,
+                outcome,
+            } => {
+                if run != self.run {
+                    return Task::none();
+                }
+                if let Some(result) = self.results.get_mut(index) {
+                    result.outcome = Some(outcome);
+                }
+                self.run_next()
+            }
+        }
+    }
+
+    fn run_next(&self) -> Task<app::Message> {
+        let Some(index) = self.results.iter().position(|r| r.outcome.is_none()) else {
+            return Task::none();
+        };
+
+        let run = self.run;
+        let path = self.results[index].path.clone();
+        Task::perform(smol::unblock(move || run_rom(&path)), move |outcome| {
+            Message::Finished {
+                run,
+                index,
+                outcome,
+            }
+            .into()
+        })
+    }
+
+    pub fn has_results(&self) -> bool {
+        !self.results.is_empty()
+    }
+
+    fn passed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, Some(Outcome::Passed)))
+            .count()
+    }
+
+    fn finished(&self) -> usize {
+        self.results.iter().filter(|r| r.outcome.is_some()).count()
+    }
+
+    fn display_name<'a>(&self, path: &'a Path) -> std::borrow::Cow<'a, str> {
+        self.folder
+            .as_deref()
+            .and_then(|folder| path.strip_prefix(folder).ok())
+            .unwrap_or(path)
+            .to_string_lossy()
+    }
+}
+
+pub struct TestRunnerPane;
+
+impl TestRunnerPane {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn content<'a>(&self, runner: &'a TestRunner) -> pane_grid::Content<'a, app::Message> {
+        let summary = if runner.results.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "{}/{} passed, {}/{} run",
+                runner.passed(),
+                runner.results.len(),
+                runner.finished(),
+                runner.results.len()
+            )
+        };
+
+        let detail = row![
+            text(summary)
+                .font(fonts::monospace())
+                .size(11.0)
+                .color(palette::MUTED),
+            button(text("Run folder…").font(fonts::monospace()).size(11.0))
+                .on_press(Message::PickFolder.into())
+                .style(button::text)
+                .padding(0),
+        ]
+        .spacing(s());
+
+        let rows = runner
+            .results
+            .iter()
+            .map(|result| result_row(runner.display_name(&result.path), result));
+
+        pane(
+            title_bar_with_detail("Test Runner", detail),
+            scrollable(Column::from_iter(rows).spacing(2.0).width(Length::Fill))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into(),
+        )
+    }
+}
+
+fn result_row<'a>(
+    name: std::borrow::Cow<'a, str>,
+    result: &'a TestResult,
+) -> Element<'a, app::Message> {
+    let (color, verdict) = match &result.outcome {
+        None => (palette::SURFACE2, ""),
+        Some(Outcome::Passed) => (palette::GREEN, "passed"),
+        Some(Outcome::Failed(detail)) => (palette::RED, detail.as_str()),
+        Some(Outcome::TimedOut) => (palette::YELLOW, "timed out"),
+    };
+
+    let debug: Element<'a, app::Message> = match result.outcome {
+        Some(Outcome::Failed(_) | Outcome::TimedOut) => {
+            button(text("Debug").font(fonts::monospace()).size(11.0))
+                .on_press(load::Message::LoadPath(result.path.clone()).into())
+                .style(button::text)
+                .padding(0)
+                .into()
+        }
+        _ => iced::widget::Space::new().into(),
+    };
+
+    container(
+        row![
+            pip(result.outcome.is_some(), color),
+            text(name)
+                .font(fonts::monospace())
+                .size(13.0)
+                .width(Length::FillPortion(2)),
+            text(verdict)
+                .font(fonts::monospace())
+                .size(13.0)
+                .color(palette::MUTED)
+                .width(Length::FillPortion(3)),
+            debug,
+        ]
+        .spacing(s())
+        .align_y(Vertical::Center),
+    )
+    .padding([0.0, s()])
+    .into()
+}
+
+fn collect_roms(dir: &Path, roms: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_roms(&path, roms);
+        } else if matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("gb" | "gbc")
+        ) {
+            roms.push(path);
+        }
+    }
+}
+
+fn run_rom(path: &Path) -> Outcome {
+    let rom = match fs::read(path) {
+        Ok(rom) => rom,
+        Err(e) => return Outcome::Failed(e.to_string()),
+    };
+
+    let cartridge = match Cartridge::new(rom, None) {
+        Ok(cartridge) => cartridge,
+        Err(e) => return Outcome::Failed(e.to_string()),
+    };
+    if cartridge.supports_cgb() {
+        run_console(GameBoyColor::new(cartridge, None))
+    } else {
+        run_console(GameBoy::new(cartridge, None))
+    }
+}
+
+fn run_console<M: Model>(mut console: Console<M>) -> Outcome {
+    let serial = diagnostics::run_until_serial_match(&mut console, &["Passed"], TIMEOUT_FRAMES);
+
+    if serial.contains("Passed") {
+        Outcome::Passed
+    } else if serial.contains("Failed") {
+        Outcome::Failed(last_line(&serial))
+    } else if !diagnostics::is_infinite_loop(&console) {
+        Outcome::TimedOut
+    } else if diagnostics::check_mooneye_pass(console.cpu()) {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(diagnostics::format_registers(console.cpu()))
+    }
+}
+
+fn last_line(output: &str) -> String {
+    output
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
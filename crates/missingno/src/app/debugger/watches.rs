@@ -0,0 +1,230 @@
+use iced::{
+    Element,
+    alignment::Vertical,
+    widget::{Column, button, column, container, row, text, text_input},
+};
+
+use crate::app::{
+    self, debugger,
+    ui::{
+        fonts, icons, palette,
+        sizes::{s, xs},
+    },
+};
+use missingno_gb::{
+    Console, Model,
+    debugger::expression::{Expression, ParseError},
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    InputChanged(String),
+    Add,
+    Remove(usize),
+    ToggleFormat(usize),
+}
+
+impl From<Message> for app::Message {
+    fn from(message: Message) -> Self {
+        debugger::Message::Watches(message).into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Hex,
+    Decimal,
+}
+
+struct Watch {
+    source: String,
+    expression: Expression,
+    format: Format,
+    value: u16,
+    /// The value differs from the one before the last step or frame.
+    changed: bool,
+}
+
+impl Watch {
+    fn formatted(&self) -> String {
+        match (self.format, self.expression.is_byte()) {
+            (Format::Hex, true) => format!("${:02X}", self.value),
+            (Format::Hex, false) => format!("${:04X}", self.value),
+            (Format::Decimal, _) => self.value.to_string(),
+        }
+    }
+}
+
+/// Expressions over registers and memory, re-evaluated whenever the
+/// emulator runs, with values that moved highlighted.
+pub struct Watches {
+    watches: Vec<Watch>,
+    input: String,
+    error: Option<ParseError>,
+}
+
+impl Watches {
+    pub fn new() -> Self {
+        Self {
+            watches: Vec::new(),
+            input: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn update<M: Model>(&mut self, message: Message, console: &Console<M>) {
+        match message {
+            Message::InputChanged(input) => {
+                self.input = input;
+                self.error = None;
+            }
+            Message::Add => match Expression::parse(&self.input) {
+                Ok(expression) => {
+                    self.watches.push(Watch {
+                        source: self.input.trim().to_string(),
+                        value: expression.evaluate(console),
+                        expression,
+                        format: Format::Hex,
+                        changed: false,
+                    });
+                    self.input.clear();
+                }
+                Err(error) => self.error = Some(error),
+            },
+            Message::Remove(index) => {
+                if index < self.watches.len() {
+                    self.watches.remove(index);
+                }
+            }
+            Message::ToggleFormat(index) => {
+                if let Some(watch) = self.watches.get_mut(index) {
+                    watch.format = match watch.format {
+                        Format::Hex => Format::Decimal,
+                        Format::Decimal => Format::Hex,
+                    };
+                }
+            }
+        }
+    }
+
+    /// Re-evaluate every watch after the emulator has run.
+    pub fn refresh<M: Model>(&mut self, console: &Console<M>) {
+        for watch in &mut self.watches {
+            let value = watch.expression.evaluate(console);
+            watch.changed = value != watch.value;
+            watch.value = value;
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, app::Message> {
+        let watch_list = Column::from_iter(
+            self.watches
+                .iter()
+                .enumerate()
+                .map(|(index, watch)| watch_row(index, watch)),
+        )
+        .spacing(xs());
+
+        let input = text_input("A, HL, [HL], [$C000]...", &self.input)
+            .font(fonts::monospace())
+            .on_input(|value| Message::InputChanged(value).into())
+            .on_submit(Message::Add.into());
+
+        let mut content = column![watch_list, input].spacing(s()).padding(s());
+        if let Some(error) = &self.error {
+            content = content.push(text(error.to_string()).size(12.0).color(palette::RED));
+        }
+        content.into()
+    }
+}
+
+fn watch_row(index: usize, watch: &Watch) -> Element<'_, app::Message> {
+    let value_color = if watch.changed {
+        palette::YELLOW
+    } else {
+        palette::TEXT
+    };
+    let format_label = match watch.format {
+        Format::Hex => "hex",
+        Format::Decimal => "dec",
+    };
+
+    container(
+        row![
+            button(icons::m_muted(icons::Icon::Close))
+                .on_press(Message::Remove(index).into())
+                .style(button::text),
+            text(&watch.source)
+                .font(fonts::monospace())
+                .color(palette::MUTED)
+                .width(96.0),
+            text(watch.formatted())
+                .font(fonts::monospace())
+                .color(value_color)
+                .width(64.0),
+            button(text(format_label).size(12.0))
+                .on_press(Message::ToggleFormat(index).into())
+                .style(button::text),
+        ]
+        .spacing(xs())
+        .align_y(Vertical::Center),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use missingno_gb::{GameBoy, cartridge::Cartridge};
+
+    use super::*;
+
+    fn game_boy() -> GameBoy {
+        GameBoy::new(Cartridge::new(vec![0; 0x8000], None).unwrap(), None)
+    }
+
+    fn watch(watches: &mut Watches, gb: &GameBoy, source: &str) {
+        watches.update(Message::InputChanged(source.to_string()), gb);
+        watches.update(Message::Add, gb);
+    }
+
+    #[test]
+    fn refresh_highlights_only_values_that_moved() {
+        let mut gb = game_boy();
+        let mut watches = Watches::new();
+        watch(&mut watches, &gb, "A");
+        watch(&mut watches, &gb, "B");
+        assert!(watches.watches.iter().all(|watch| !watch.changed));
+
+        gb.cpu_mut().a = gb.cpu().a.wrapping_add(1);
+        watches.refresh(&gb);
+        assert!(watches.watches[0].changed);
+        assert!(!watches.watches[1].changed);
+
+        watches.refresh(&gb);
+        assert!(!watches.watches[0].changed, "unchanged since the last step");
+    }
+
+    #[test]
+    fn formats_by_width_and_choice() {
+        let gb = game_boy();
+        let mut watches = Watches::new();
+        watch(&mut watches, &gb, "[$C000+1]");
+        watch(&mut watches, &gb, "$C000 + 1");
+        watches.watches[0].value = 0x2A;
+        assert_eq!(watches.watches[0].formatted(), "$2A");
+        assert_eq!(watches.watches[1].formatted(), "$C001");
+
+        watches.update(Message::ToggleFormat(1), &gb);
+        assert_eq!(watches.watches[1].formatted(), "49153");
+    }
+
+    #[test]
+    fn bad_input_is_kept_with_an_error() {
+        let gb = game_boy();
+        let mut watches = Watches::new();
+        watch(&mut watches, &gb, "[HL");
+        assert!(watches.watches.is_empty());
+        assert_eq!(watches.input, "[HL");
+        assert!(watches.error.is_some());
+    }
+}
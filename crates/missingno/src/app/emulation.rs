@@ -1,11 +1,14 @@
-use std::time::Instant;
-
 use iced::Task;
 use replace_with::replace_with_or_abort;
 
-use missingno_gb::joypad::Button;
+use missingno_gb::{audio::DEFAULT_OUTPUT_RATE, joypad::Button};
 
-use super::{App, Game, LoadedGame, Message, PendingAction, library};
+use super::{
+    App, AudioRecorder, Game, LoadedGame, Message, PendingAction, Toast, audio_output, library,
+    load,
+    save_states::{LoadStateError, Slot},
+    ui::icons::Icon,
+};
 
 impl App {
     pub(super) fn handle_emulation_message(&mut self, message: Message) -> Task<Message> {
@@ -49,11 +52,82 @@ impl App {
                             self.store.update_live_screenshots(session);
                         }
                     }
-                    self.screenshot_toast = Some(Instant::now());
+                    self.toast = Some(Toast::new(Icon::Camera, "Screenshot saved"));
+                }
+            }
+            Message::ToggleAudioRecording => {
+                if self.audio_recorder.is_some() {
+                    self.stop_audio_recording();
+                } else {
+                    let name = self
+                        .current_game
+                        .as_ref()
+                        .map(|current| current.entry.display_title())
+                        .unwrap_or_else(|| "recording".to_string());
+                    let dialog = rfd::AsyncFileDialog::new()
+                        .set_file_name(format!("{name}.wav"))
+                        .add_filter("WAV Audio", &["wav"]);
+                    return Task::perform(dialog.save_file(), Message::StartAudioRecording);
+                }
+            }
+            Message::StartAudioRecording(Some(handle)) => {
+                let sample_rate = self
+                    .audio_output
+                    .as_ref()
+                    .map_or(DEFAULT_OUTPUT_RATE, |audio| audio.sample_rate());
+                match AudioRecorder::create(
+                    handle.path(),
+                    sample_rate,
+                    self.settings.recording_format,
+                ) {
+                    Ok(recorder) => self.audio_recorder = Some(recorder),
+                    Err(error) => eprintln!("couldn't start recording: {error}"),
                 }
             }
-            Message::DismissScreenshotToast => {
-                self.screenshot_toast = None;
+            Message::StartAudioRecording(None) => {}
+            Message::SaveState(slot) => {
+                let console = match &self.game {
+                    Game::Loaded(LoadedGame::Emulator(emulator)) => emulator.console().clone(),
+                    Game::Loaded(LoadedGame::Debugger(debugger)) => debugger.console(),
+                    _ => return Task::none(),
+                };
+                self.toast = Some(match (self.save_states.save(slot, &console), slot) {
+                    (Ok(()), Slot::Quick) => Toast::new(Icon::Download, "Quicksaved".to_string()),
+                    (Ok(()), Slot::Numbered(slot)) => {
+                        Toast::new(Icon::Download, format!("Saved to slot {}", slot + 1))
+                    }
+                    (Err(error), slot) => {
+                        Toast::new(Icon::Warning, format!("{slot} couldn't be saved: {error}"))
+                    }
+                });
+            }
+            Message::LoadState(slot) => {
+                let loaded = match &mut self.game {
+                    Game::Loaded(LoadedGame::Emulator(emulator)) => {
+                        let current = emulator.console();
+                        self.save_states
+                            .load(slot, current.cartridge(), current.is_cgb())
+                            .map(|console| emulator.restore_console(console))
+                    }
+                    Game::Loaded(LoadedGame::Debugger(debugger)) => self
+                        .save_states
+                        .load(slot, debugger.cartridge(), debugger.is_cgb())
+                        .map(|console| debugger.restore_console(console)),
+                    _ => return Task::none(),
+                };
+                self.toast = Some(match (loaded, slot) {
+                    (Ok(()), Slot::Quick) => Toast::new(Icon::Clock, "Quickloaded".to_string()),
+                    (Ok(()), Slot::Numbered(slot)) => {
+                        Toast::new(Icon::Clock, format!("Loaded slot {}", slot + 1))
+                    }
+                    (Err(LoadStateError::Empty), Slot::Quick) => {
+                        Toast::new(Icon::Warning, "No quicksave yet".to_string())
+                    }
+                    (Err(error), slot) => Toast::new(Icon::Warning, format!("{slot} {error}")),
+                });
+            }
+            Message::DismissToast => {
+                self.toast = None;
             }
             Message::PressButton(button) => self.press_button(button),
             Message::ReleaseButton(button) => self.release_button(button),
@@ -70,12 +144,27 @@ impl App {
                                 let mut emu =
                                     debugger.disable_debugger(self.settings.use_sgb_colors);
                                 emu.set_palette(palette);
+                                emu.set_turbo(
+                                    &self.settings.turbo_buttons,
+                                    self.settings.turbo_frames,
+                                );
+                                emu.set_frame_skip(
+                                    self.settings.frame_skip,
+                                    self.settings.auto_frame_skip,
+                                );
+                                emu.set_speed(self.settings.speed);
+                                if let Some(audio) = &self.audio_output {
+                                    emu.set_audio_output_rate(audio.sample_rate());
+                                }
                                 LoadedGame::Emulator(emu)
                             }
                         }
                         LoadedGame::Emulator(emulator) => {
                             if debugger_enabled {
                                 let mut dbg = emulator.enable_debugger();
+                                if let Some(layout) = &self.settings.debugger_layout {
+                                    dbg.restore_pane_layout(layout);
+                                }
                                 dbg.set_palette(palette);
                                 LoadedGame::Debugger(dbg)
                             } else {
@@ -84,6 +173,9 @@ impl App {
                         }
                     });
                 }
+                if debugger_enabled && let Some(rom_path) = load::current_rom_path(self) {
+                    load::load_symbols(self, &rom_path);
+                }
             }
             _ => {}
         }
@@ -151,43 +243,33 @@ impl App {
         }
     }
 
-    /// Flush any debounced SRAM save from the emulator.
+    /// Save battery RAM now instead of waiting out the emulator's debounce.
+    /// Does nothing if the RAM hasn't changed since the last save.
     pub(super) fn flush_pending_save(&mut self) {
-        let flushed = match &mut self.game {
-            Game::Loaded(LoadedGame::Emulator(emu)) => emu.flush_pending_save(),
-            _ => false,
-        };
-        if flushed {
-            self.save();
+        if let Game::Loaded(LoadedGame::Emulator(emu)) = &mut self.game {
+            emu.flush_pending_save();
         }
+        self.save();
     }
 
     pub(super) fn save(&mut self) {
-        let (ram, cartridge_title) = match &self.game {
-            Game::Loaded(LoadedGame::Debugger(debugger)) => {
-                if !debugger.cartridge().has_battery() {
-                    return;
-                }
-                (
-                    debugger.cartridge().ram(),
-                    debugger.cartridge().title().to_string(),
-                )
-            }
-            Game::Loaded(LoadedGame::Emulator(emulator)) => {
-                if !emulator.console().cartridge().has_battery() {
-                    return;
-                }
-                (
-                    emulator.console().cartridge().ram(),
-                    emulator.console().cartridge().title().to_string(),
-                )
-            }
+        let cartridge = match &self.game {
+            Game::Loaded(LoadedGame::Debugger(debugger)) => debugger.cartridge(),
+            Game::Loaded(LoadedGame::Emulator(emulator)) => emulator.console().cartridge(),
             _ => return,
         };
-        let Some(ram) = ram else { return };
         let Some(current) = &mut self.current_game else {
             return;
         };
+        if !cartridge.has_battery() || cartridge.ram_generation() == current.saved_ram_generation
+        {
+            return;
+        }
+        current.saved_ram_generation = cartridge.ram_generation();
+        let Some(ram) = cartridge.save_data() else {
+            return;
+        };
+        let cartridge_title = cartridge.title().to_string();
 
         if let Some(session) = &mut current.session {
             // Check if SRAM has meaningfully changed, ignoring scratch regions
@@ -216,8 +298,43 @@ impl App {
             Game::Loaded(LoadedGame::Debugger(debugger)) => debugger.drain_audio_samples(),
             _ => return,
         };
-        if let Some(audio) = &mut self.audio_output {
+        if let Some(recorder) = &mut self.audio_recorder
+            && let Err(error) = recorder.push(&samples)
+        {
+            eprintln!("couldn't record audio: {error}");
+            self.stop_audio_recording();
+        }
+        // Ahead of the device, this frame's audio is skipped whole.
+        if let Some(audio) = &mut self.audio_output
+            && audio.buffer_fill() <= audio_output::BACKLOG_LIMIT
+        {
             audio.push_samples(&samples);
         }
     }
+
+    pub(super) fn recording_audio(&self) -> bool {
+        self.audio_recorder.is_some()
+    }
+
+    /// Complete the recording's file, if there is one.
+    pub(super) fn stop_audio_recording(&mut self) {
+        if let Some(recorder) = self.audio_recorder.take()
+            && let Err(error) = recorder.finish()
+        {
+            eprintln!("couldn't finish recording: {error}");
+        }
+    }
+
+    /// Print anything the core couldn't emulate, to help make sense of
+    /// compatibility reports.
+    pub(super) fn report_warnings(&mut self) {
+        let warnings = match &mut self.game {
+            Game::Loaded(LoadedGame::Emulator(emulator)) => emulator.console_mut().take_warnings(),
+            Game::Loaded(LoadedGame::Debugger(debugger)) => debugger.take_warnings(),
+            _ => return,
+        };
+        for warning in warnings {
+            eprintln!("warning: {warning}");
+        }
+    }
 }
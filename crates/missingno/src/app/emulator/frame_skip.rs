@@ -0,0 +1,124 @@
+use std::time::{Duration, Instant};
+
+/// Most frames auto frame-skip drops in a row, so a machine that can never
+/// keep up still sees the picture move.
+const MAX_AUTO_SKIPPED: u32 = 3;
+
+/// Chooses which emulated frames update the screen. Every frame is still
+/// emulated, so audio and game speed are unaffected; a skipped frame just
+/// leaves the previous picture up. Shows every `every`th frame, and with
+/// `auto` also drops frames whose tick arrived late.
+pub struct FrameSkip {
+    every: u32,
+    auto: bool,
+    /// Frames since the last one shown.
+    elapsed: u32,
+    /// Frames auto frame-skip has dropped since the last one shown.
+    skipped: u32,
+    last_tick: Option<Instant>,
+}
+
+impl FrameSkip {
+    pub fn new(every: u32, auto: bool) -> Self {
+        Self {
+            every: every.max(1),
+            auto,
+            elapsed: 0,
+            skipped: 0,
+            last_tick: None,
+        }
+    }
+
+    /// Forget the last tick, so the first frame after a pause isn't taken
+    /// for a late one.
+    pub fn resume(&mut self) {
+        self.last_tick = None;
+    }
+
+    /// Count a frame emulated at `now`, which ticks `interval` apart when
+    /// keeping up. Returns whether to show it.
+    pub fn present(&mut self, now: Instant, interval: Duration) -> bool {
+        let late = self
+            .last_tick
+            .is_some_and(|last| now.duration_since(last) > interval * 3 / 2);
+        self.last_tick = Some(now);
+
+        self.elapsed += 1;
+        if self.elapsed < self.every {
+            return false;
+        }
+        if self.auto && late && self.skipped < MAX_AUTO_SKIPPED {
+            self.skipped += 1;
+            return false;
+        }
+        self.elapsed = 0;
+        self.skipped = 0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_micros(16740);
+
+    /// Which of `ticks` (offsets from the first, in intervals) are shown.
+    fn shown(frame_skip: &mut FrameSkip, ticks: &[f64]) -> Vec<bool> {
+        let start = Instant::now();
+        ticks
+            .iter()
+            .map(|&tick| frame_skip.present(start + INTERVAL.mul_f64(tick), INTERVAL))
+            .collect()
+    }
+
+    #[test]
+    fn shows_every_frame_by_default() {
+        let mut frame_skip = FrameSkip::new(1, false);
+        assert_eq!(shown(&mut frame_skip, &[0.0, 1.0, 2.0]), [true; 3]);
+    }
+
+    #[test]
+    fn frame_skip_2_shows_every_other_frame() {
+        let mut frame_skip = FrameSkip::new(2, false);
+        assert_eq!(
+            shown(&mut frame_skip, &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]),
+            [false, true, false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn late_frames_show_without_auto() {
+        let mut frame_skip = FrameSkip::new(1, false);
+        assert_eq!(shown(&mut frame_skip, &[0.0, 2.0, 4.0]), [true; 3]);
+    }
+
+    #[test]
+    fn auto_drops_late_frames_only() {
+        let mut frame_skip = FrameSkip::new(1, true);
+        assert_eq!(
+            shown(&mut frame_skip, &[0.0, 1.0, 3.0, 4.0, 5.0]),
+            [true, true, false, true, true]
+        );
+    }
+
+    #[test]
+    fn resuming_after_a_pause_is_not_late() {
+        let mut frame_skip = FrameSkip::new(1, true);
+        assert_eq!(shown(&mut frame_skip, &[0.0]), [true]);
+        frame_skip.resume();
+        assert_eq!(shown(&mut frame_skip, &[100.0]), [true]);
+    }
+
+    #[test]
+    fn auto_still_shows_a_frame_when_always_behind() {
+        let mut frame_skip = FrameSkip::new(1, true);
+        let ticks: Vec<f64> = (0..10).map(|tick| tick as f64 * 2.0).collect();
+        assert_eq!(
+            shown(&mut frame_skip, &ticks),
+            [
+                true, false, false, false, true, false, false, false, true, false
+            ]
+        );
+    }
+}
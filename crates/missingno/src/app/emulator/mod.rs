@@ -1,28 +1,41 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use iced::{
     Element,
-    Length::{self, Fill},
+    Length::Fill,
     Subscription, Task, time,
-    widget::{button, container, mouse_area, responsive, shader, stack, svg},
+    widget::{button, container, mouse_area, row, shader, stack, svg},
 };
 
 use crate::app::{
     self,
     console::AnyConsole,
+    controls,
     screen::ScreenView,
+    settings::{Action, MAX_SPEED, MIN_SPEED, ScreenFilter, ScreenScaling},
     ui::{
         icons::{self, Icon},
         sizes::border_s,
     },
 };
+use frame_skip::FrameSkip;
 use missingno_gb::{joypad::Button, ppu::types::palette::PaletteChoice};
+use turbo::Turbo;
+
+mod frame_skip;
+mod turbo;
 
 /// Frames of silence before we flush an SRAM save.
 /// Games often write SRAM across several consecutive frames during a save
 /// operation. We wait for writes to stop before persisting.
 const SRAM_DEBOUNCE_FRAMES: u32 = 30; // ~0.5 seconds at 60fps
 
+/// Time between emulated frames, matching the DMG's ~59.7 Hz refresh.
+const FRAME_INTERVAL: Duration = Duration::from_micros(16740);
+
+/// Frames emulated per tick while fast-forward is held.
+const FAST_FORWARD_FRAMES: u32 = 4;
+
 pub struct Emulator {
     console: AnyConsole,
     screen_view: ScreenView,
@@ -32,13 +45,36 @@ pub struct Emulator {
     /// Countdown: frames since last SRAM write. When this reaches
     /// SRAM_DEBOUNCE_FRAMES, we fire SaveBattery. None = no pending save.
     sram_save_countdown: Option<u32>,
+    /// The cartridge's RAM generation as of the last frame, to spot writes.
+    ram_generation: u64,
+    turbo: Turbo,
+    frame_skip: FrameSkip,
+    /// Multiple of the Game Boy's own pace to run at.
+    speed: f32,
+    /// The audio device's sample rate, once known. The console resamples to
+    /// this divided by `speed`, so the device plays sound as fast as it's made.
+    audio_output_rate: Option<u32>,
+    /// Held down: run [`FAST_FORWARD_FRAMES`] frames per tick.
+    fast_forward: bool,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     EmulateFrame,
+    /// The fast-forward key went down or up.
+    SetFastForward(bool),
     ScreenHovered,
     ScreenUnhovered,
+    /// Write the displayed frame to a timestamped PNG.
+    SavePng,
+}
+
+/// Write `png` to `missingno-<date>-<time>.png` in the pictures folder,
+/// falling back to the working directory.
+fn save_png(png: &[u8]) -> std::io::Result<()> {
+    let dir = dirs::picture_dir().unwrap_or_default();
+    let name = jiff::Zoned::now().strftime("missingno-%Y%m%d-%H%M%S.png");
+    std::fs::write(dir.join(name.to_string()), png)
 }
 
 impl Into<app::Message> for Message {
@@ -50,12 +86,18 @@ impl Into<app::Message> for Message {
 impl Emulator {
     pub fn new(console: AnyConsole, use_sgb_colors: bool) -> Self {
         Self {
+            ram_generation: console.cartridge().ram_generation(),
             console,
             screen_view: ScreenView::new(),
             running: false,
             screen_hovered: false,
             use_sgb_colors,
             sram_save_countdown: None,
+            turbo: Turbo::new(Vec::new(), 1),
+            frame_skip: FrameSkip::new(1, false),
+            speed: 1.0,
+            audio_output_rate: None,
+            fast_forward: false,
         }
     }
 
@@ -65,12 +107,18 @@ impl Emulator {
         use_sgb_colors: bool,
     ) -> Self {
         Self {
+            ram_generation: console.cartridge().ram_generation(),
             console,
             screen_view,
             running: false,
             screen_hovered: false,
             use_sgb_colors,
             sram_save_countdown: None,
+            turbo: Turbo::new(Vec::new(), 1),
+            frame_skip: FrameSkip::new(1, false),
+            speed: 1.0,
+            audio_output_rate: None,
+            fast_forward: false,
         }
     }
 
@@ -86,33 +134,97 @@ impl Emulator {
         &mut self.console
     }
 
-    pub fn enable_debugger(self) -> app::debugger::AnyDebugger {
+    /// Carry on from a loaded save state at the current speed and output rate.
+    pub fn restore_console(&mut self, state: AnyConsole) {
+        self.console.restore(state);
+    }
+
+    /// Choose which buttons auto-fire while held, and how many frames they
+    /// spend in each of the pressed and released states.
+    pub fn set_turbo(&mut self, actions: &[Action], frames: u32) {
+        self.release_turbo_buttons();
+        let buttons = actions
+            .iter()
+            .filter_map(|&action| controls::action_to_joypad(action))
+            .collect();
+        self.turbo = Turbo::new(buttons, frames);
+    }
+
+    /// Show only every `every`th frame, and with `auto` also drop frames
+    /// that fall behind schedule. All frames are still emulated, so audio
+    /// keeps playing.
+    pub fn set_frame_skip(&mut self, every: u32, auto: bool) {
+        self.frame_skip = FrameSkip::new(every, auto);
+    }
+
+    /// Run at `speed` times the Game Boy's own pace, from [`MIN_SPEED`] to
+    /// [`MAX_SPEED`]. Sound is resampled to keep up rather than time-stretched,
+    /// so its pitch rises and falls with the speed.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self.apply_audio_output_rate();
+    }
+
+    /// The audio device's sample rate, for resampling at the current speed.
+    pub fn set_audio_output_rate(&mut self, hz: u32) {
+        self.audio_output_rate = Some(hz);
+        self.apply_audio_output_rate();
+    }
+
+    fn apply_audio_output_rate(&mut self) {
+        if let Some(hz) = self.audio_output_rate {
+            self.console
+                .set_audio_output_rate((hz as f32 / self.speed).round() as u32);
+        }
+    }
+
+    /// Time between emulated frames at the current speed.
+    fn frame_interval(&self) -> Duration {
+        FRAME_INTERVAL.div_f32(self.speed)
+    }
+
+    /// Hand the console to the debugger as it stands. Frames can stop
+    /// short (LCD off), so this may be mid-frame; stepping resumes from the
+    /// current instruction either way.
+    pub fn enable_debugger(mut self) -> app::debugger::AnyDebugger {
+        self.release_turbo_buttons();
+        // The debugger runs at normal speed.
+        if let Some(hz) = self.audio_output_rate {
+            self.console.set_audio_output_rate(hz);
+        }
+        self.console.skip_logo_boot();
         app::debugger::AnyDebugger::from_emulator(self.console, self.screen_view)
     }
 
     pub fn update(&mut self, message: Message) -> Task<app::Message> {
         match message {
             Message::EmulateFrame => {
-                // A frame is ~70224 dots; the CPU runs 1 or 2 T-cycles per dot
-                // (CGB double speed). Allow 2x a frame to avoid hanging the UI
-                // if the PPU never produces a frame (e.g. LCD off).
-                let max_tcycles_per_frame = 70224 * 2 * self.console.cpu_tcycles_per_dot() as u32;
-                let mut tcycles = 0;
-                let mut sram_dirty = false;
-                loop {
-                    let result = self.console.step();
-                    tcycles += result.tcycles;
-                    sram_dirty |= result.sram_dirty;
-                    if result.new_screen || tcycles >= max_tcycles_per_frame {
-                        break;
+                // Fast-forward only sounds the last frame of each tick, so
+                // audio keeps its real-time pace rather than piling up.
+                let frames = if self.fast_forward {
+                    FAST_FORWARD_FRAMES
+                } else {
+                    1
+                };
+                for frame in 0..frames {
+                    if frame > 0 {
+                        self.console.drain_audio_samples();
                     }
+                    self.emulate_frame();
+                }
+                if self
+                    .frame_skip
+                    .present(Instant::now(), self.frame_interval())
+                {
+                    self.screen_view.use_sgb_colors = self.use_sgb_colors;
+                    self.screen_view.apply(self.console.screen_display());
                 }
-                self.screen_view.use_sgb_colors = self.use_sgb_colors;
-                self.screen_view.apply(self.console.screen_display());
 
                 // Debounce SRAM saves: reset countdown on each dirty frame,
                 // fire SaveBattery after SRAM_DEBOUNCE_FRAMES of quiet.
-                if sram_dirty {
+                let ram_generation = self.console.cartridge().ram_generation();
+                if ram_generation != self.ram_generation {
+                    self.ram_generation = ram_generation;
                     self.sram_save_countdown = Some(0);
                 } else if let Some(count) = &mut self.sram_save_countdown {
                     *count += 1;
@@ -122,36 +234,63 @@ impl Emulator {
                     }
                 }
             }
+            Message::SetFastForward(held) => self.fast_forward = held,
             Message::ScreenHovered => self.screen_hovered = true,
             Message::ScreenUnhovered => self.screen_hovered = false,
+            Message::SavePng => {
+                if let AnyConsole::Dmg(console) = &self.console
+                    && let Err(error) = save_png(&console.capture_png())
+                {
+                    eprintln!("couldn't save screenshot: {error}");
+                }
+            }
         }
 
         Task::none()
     }
 
-    /// Force-flush any pending SRAM save. Call when pausing or closing.
-    /// Returns true if there was a pending save.
-    pub fn flush_pending_save(&mut self) -> bool {
-        self.sram_save_countdown.take().is_some()
+    /// Run one frame of the console.
+    fn emulate_frame(&mut self) {
+        for (button, pressed) in self.turbo.tick() {
+            if pressed {
+                self.console.press_button(button);
+            } else {
+                self.console.release_button(button);
+            }
+        }
+
+        // A frame is ~70224 dots; the CPU runs 1 or 2 T-cycles per dot
+        // (CGB double speed). Allow 2x a frame to avoid hanging the UI
+        // if the PPU never produces a frame (e.g. LCD off).
+        let max_tcycles_per_frame = 70224 * 2 * self.console.cpu_tcycles_per_dot() as u32;
+        self.console.run_cycles(max_tcycles_per_frame / 4);
+        self.console.finish_logo_boot();
+    }
+
+    /// Cancel the debounce countdown, for when the caller is about to save
+    /// straight away (pausing or closing).
+    pub fn flush_pending_save(&mut self) {
+        self.sram_save_countdown = None;
     }
 
     pub fn set_palette(&mut self, palette: PaletteChoice) {
         self.screen_view.palette = palette;
     }
 
+    pub fn set_presentation(
+        &mut self,
+        scaling: ScreenScaling,
+        grid: bool,
+        filter: ScreenFilter,
+        frame_blending: bool,
+    ) {
+        self.screen_view
+            .set_presentation(scaling, grid, filter, frame_blending);
+    }
+
     pub fn view(&self, fullscreen: bool) -> Element<'_, app::Message> {
-        let screen: Element<'_, app::Message> = responsive(|size| {
-            let shortest = size.width.min(size.height);
-
-            container(
-                shader(&self.screen_view)
-                    .width(Length::Fixed(shortest))
-                    .height(Length::Fixed(shortest)),
-            )
-            .center(Fill)
-            .into()
-        })
-        .into();
+        let screen: Element<'_, app::Message> =
+            shader(&self.screen_view).width(Fill).height(Fill).into();
 
         if fullscreen {
             screen
@@ -175,14 +314,28 @@ impl Emulator {
                     }
                 }
 
+                fn overlay_button(
+                    icon: Icon,
+                    message: app::Message,
+                ) -> button::Button<'static, app::Message> {
+                    button(icons::m(icon).style(|_, _| svg::Style {
+                        color: Some(iced::Color::WHITE),
+                    }))
+                    .style(overlay_button_style)
+                    .on_press(message)
+                }
+
+                // Only DMG frames map straight onto the greyscale PNG shades.
+                let save_png = matches!(self.console, AnyConsole::Dmg(_))
+                    .then(|| overlay_button(Icon::Camera, Message::SavePng.into()));
+
                 stack![
                     screen,
                     container(
-                        button(icons::m(Icon::Expand).style(|_, _| svg::Style {
-                            color: Some(iced::Color::WHITE),
-                        }))
-                        .style(overlay_button_style)
-                        .on_press(app::Message::ToggleFullscreen)
+                        row![]
+                            .push(save_png)
+                            .push(overlay_button(Icon::Expand, app::Message::ToggleFullscreen))
+                            .spacing(8)
                     )
                     .align_right(Fill)
                     .padding(8)
@@ -206,10 +359,14 @@ impl Emulator {
 
     pub fn run(&mut self) {
         self.running = true;
+        self.frame_skip.resume();
     }
 
     pub fn pause(&mut self) {
         self.running = false;
+        // A key released while paused never reaches the turbo timer, so
+        // drop auto-fire now rather than leave a button stuck down.
+        self.release_turbo_buttons();
     }
 
     pub fn reset(&mut self) {
@@ -217,20 +374,149 @@ impl Emulator {
     }
 
     pub fn press_button(&mut self, button: Button) {
+        // Any button skips the boot logo, as the game would start anyway.
+        self.console.skip_logo_boot();
+        self.turbo.press(button);
         self.console.press_button(button);
     }
 
     pub fn release_button(&mut self, button: Button) {
+        self.turbo.release(button);
         self.console.release_button(button);
     }
 
+    fn release_turbo_buttons(&mut self) {
+        for button in self.turbo.clear() {
+            self.console.release_button(button);
+        }
+    }
+
     pub fn subscription(&self) -> Subscription<app::Message> {
         if self.running {
             Subscription::batch([
-                time::every(Duration::from_micros(16740)).map(|_| Message::EmulateFrame.into())
+                time::every(self.frame_interval()).map(|_| Message::EmulateFrame.into())
             ])
         } else {
             Subscription::none()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use missingno_gb::{GameBoy, cartridge::Cartridge};
+
+    use super::*;
+
+    fn emulator(frame_skip: u32) -> Emulator {
+        let console = GameBoy::new(Cartridge::new(vec![0; 0x8000], None).unwrap(), None);
+        let mut emulator = Emulator::new(AnyConsole::Dmg(console), false);
+        emulator.set_frame_skip(frame_skip, false);
+        emulator
+    }
+
+    /// Counts in BC as it runs, B mirrored to $C000: about 7 a frame.
+    fn counting_emulator() -> Emulator {
+        let main = [
+            0x03, // INC BC
+            0x78, // LD A,B
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x18, 0xF9, // JR -7
+        ];
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+        rom[0x0150..0x0150 + main.len()].copy_from_slice(&main);
+        let console = GameBoy::new(Cartridge::new(rom, None).unwrap(), None);
+        let mut emulator = Emulator::new(AnyConsole::Dmg(console), false);
+        emulator.set_audio_output_rate(48000);
+        emulator
+    }
+
+    /// How far the counter gets in one tick, and the samples it makes.
+    fn tick(emulator: &mut Emulator) -> (u8, usize) {
+        let count = |emulator: &Emulator| match emulator.console() {
+            AnyConsole::Dmg(console) => console.peek(0xC000),
+            AnyConsole::Cgb(console) => console.peek(0xC000),
+        };
+        let before = count(emulator);
+        let _ = emulator.update(Message::EmulateFrame);
+        let samples = emulator.console_mut().drain_audio_samples().len();
+        (count(emulator).wrapping_sub(before), samples)
+    }
+
+    #[test]
+    fn fast_forward_runs_several_frames_per_tick() {
+        let mut emulator = counting_emulator();
+        tick(&mut emulator);
+        let (normal, normal_samples) = tick(&mut emulator);
+
+        let _ = emulator.update(Message::SetFastForward(true));
+        let (fast, fast_samples) = tick(&mut emulator);
+        assert!(
+            fast.abs_diff(normal * FAST_FORWARD_FRAMES as u8) <= 1,
+            "{fast} vs {normal}"
+        );
+        assert!(
+            fast_samples.abs_diff(normal_samples) <= 4,
+            "only one frame is heard: {fast_samples} vs {normal_samples}"
+        );
+
+        let _ = emulator.update(Message::SetFastForward(false));
+        let (released, released_samples) = tick(&mut emulator);
+        assert!(released.abs_diff(normal) <= 1, "{released} vs {normal}");
+        assert!(released_samples.abs_diff(normal_samples) <= 4);
+    }
+
+    #[test]
+    fn frame_skip_leaves_audio_untouched() {
+        let mut every_frame = emulator(1);
+        let mut every_other = emulator(2);
+        for frame in 0..4 {
+            let _ = every_frame.update(Message::EmulateFrame);
+            let _ = every_other.update(Message::EmulateFrame);
+            let samples = every_other.console_mut().drain_audio_samples();
+            assert!(!samples.is_empty(), "frame {frame} made no sound");
+            assert_eq!(samples, every_frame.console_mut().drain_audio_samples());
+        }
+    }
+
+    #[test]
+    fn half_speed_paces_frames_twice_as_far_apart() {
+        let mut emulator = emulator(1);
+        assert_eq!(emulator.frame_interval(), FRAME_INTERVAL);
+        emulator.set_speed(0.5);
+        assert_eq!(emulator.frame_interval(), FRAME_INTERVAL * 2);
+        emulator.set_speed(100.0);
+        assert_eq!(emulator.frame_interval(), FRAME_INTERVAL.div_f32(MAX_SPEED));
+    }
+
+    #[test]
+    fn speed_scales_samples_per_frame_to_match_playback() {
+        // At half speed each frame lasts twice as long on the wall clock, so
+        // it must make twice as many samples to keep the device fed.
+        let mut normal = emulator(1);
+        let mut half = emulator(1);
+        normal.set_audio_output_rate(48000);
+        half.set_audio_output_rate(48000);
+        half.set_speed(0.5);
+        for _ in 0..4 {
+            let _ = normal.update(Message::EmulateFrame);
+            let _ = half.update(Message::EmulateFrame);
+        }
+        let normal = normal.console_mut().drain_audio_samples().len();
+        let half = half.console_mut().drain_audio_samples().len();
+        assert!(normal > 0);
+        assert!(half.abs_diff(normal * 2) <= 4, "{half} vs {normal}");
+    }
+
+    #[test]
+    fn debugger_gets_the_device_rate_back() {
+        let mut emulator = emulator(1);
+        emulator.set_audio_output_rate(48000);
+        emulator.set_speed(2.0);
+        let app::debugger::AnyDebugger::Dmg(debugger) = emulator.enable_debugger() else {
+            panic!("not a DMG");
+        };
+        assert_eq!(debugger.game_boy().audio().output_rate(), 48000);
+    }
+}
@@ -0,0 +1,119 @@
+use missingno_gb::joypad::Button;
+
+/// Auto-fire for held buttons. While a turbo button is held it alternates
+/// between pressed and released, spending `frames` frames in each state.
+pub struct Turbo {
+    buttons: Vec<Button>,
+    frames: u32,
+    held: Vec<Held>,
+}
+
+struct Held {
+    button: Button,
+    pressed: bool,
+    elapsed: u32,
+}
+
+impl Turbo {
+    pub fn new(buttons: Vec<Button>, frames: u32) -> Self {
+        Self {
+            buttons,
+            frames: frames.max(1),
+            held: Vec::new(),
+        }
+    }
+
+    pub fn is_turbo(&self, button: Button) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    /// Start auto-firing `button` if it is a turbo button. It begins in the
+    /// pressed state, so the first press lands immediately.
+    pub fn press(&mut self, button: Button) {
+        if self.is_turbo(button) && !self.held.iter().any(|h| h.button == button) {
+            self.held.push(Held {
+                button,
+                pressed: true,
+                elapsed: 0,
+            });
+        }
+    }
+
+    pub fn release(&mut self, button: Button) {
+        self.held.retain(|h| h.button != button);
+    }
+
+    /// Advance one frame, yielding each held button whose state flips along
+    /// with whether it should now be pressed.
+    pub fn tick(&mut self) -> impl Iterator<Item = (Button, bool)> + '_ {
+        let frames = self.frames;
+        self.held.iter_mut().filter_map(move |held| {
+            held.elapsed += 1;
+            if held.elapsed < frames {
+                return None;
+            }
+            held.elapsed = 0;
+            held.pressed = !held.pressed;
+            Some((held.button, held.pressed))
+        })
+    }
+
+    /// Stop auto-firing everything, returning the buttons that were held so
+    /// the caller can release them.
+    pub fn clear(&mut self) -> impl Iterator<Item = Button> + '_ {
+        self.held.drain(..).map(|held| held.button)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_turbo_button_alternates() {
+        let mut turbo = Turbo::new(vec![Button::A], 3);
+        turbo.press(Button::A);
+
+        let mut pressed = true;
+        let mut history = Vec::new();
+        for _ in 0..12 {
+            for (button, state) in turbo.tick() {
+                assert_eq!(button, Button::A);
+                pressed = state;
+            }
+            history.push(pressed);
+        }
+
+        assert_eq!(
+            history,
+            [
+                true, true, false, false, false, true, true, true, false, false, false, true
+            ]
+        );
+    }
+
+    #[test]
+    fn non_turbo_buttons_are_not_auto_fired() {
+        let mut turbo = Turbo::new(vec![Button::A], 1);
+        turbo.press(Button::B);
+        assert_eq!(turbo.tick().count(), 0);
+    }
+
+    #[test]
+    fn release_stops_auto_fire() {
+        let mut turbo = Turbo::new(vec![Button::A], 1);
+        turbo.press(Button::A);
+        turbo.release(Button::A);
+        assert_eq!(turbo.tick().count(), 0);
+        assert_eq!(turbo.clear().count(), 0);
+    }
+
+    #[test]
+    fn clear_returns_held_buttons() {
+        let mut turbo = Turbo::new(vec![Button::A, Button::B], 1);
+        turbo.press(Button::A);
+        turbo.press(Button::B);
+        assert_eq!(turbo.clear().collect::<Vec<_>>(), [Button::A, Button::B]);
+        assert_eq!(turbo.tick().count(), 0);
+    }
+}
@@ -13,6 +13,7 @@ use crate::app::{
         activity::{self, ActivityKind, SessionFile},
         store::{ActivityState, SessionSummary},
     },
+    rom_file,
     ui::{
         buttons, containers, fonts, horizontal_rule,
         icons::{self, Icon},
@@ -60,7 +61,7 @@ pub(crate) fn view(data: DetailData<'_>) -> Element<'_, app::Message> {
 fn game_header<'a>(data: &DetailData<'a>) -> Element<'a, app::Message> {
     use iced::widget::stack;
 
-    let has_rom = data.entry.rom_paths.iter().any(|p| p.exists());
+    let has_rom = data.entry.rom_paths.iter().any(|p| rom_file::exists(p));
 
     // Cover thumbnail with back button overlay — clickable to play if ROM exists
     let cover: Element<'_, app::Message> = if let Some(handle) = data.cover {
@@ -2,7 +2,7 @@ use iced::Task;
 
 use missingno_gb::cartridge::Cartridge;
 
-use crate::app::{self, DetailSubScreen, FlashState, Game, Screen, load};
+use crate::app::{self, DetailSubScreen, FlashState, Game, Screen, load, rom_file};
 use crate::cartridge_rw;
 
 use super::{homebrew_browser, screenshot_gallery};
@@ -371,7 +371,7 @@ pub(in crate::app) fn handle(app: &mut app::App, message: app::Message) -> Task<
                             Some(p) => p.clone(),
                             None => return Task::none(),
                         };
-                        let rom_data = match std::fs::read(&rom_path) {
+                        let rom_data = match rom_file::read(&rom_path) {
                             Ok(data) => data,
                             Err(e) => {
                                 app.screen = Screen::ViewingGame {
@@ -1,11 +1,17 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use iced::Task;
 use jiff::Timestamp;
+use replace_with::replace_with_or_abort;
 use rfd::{AsyncFileDialog, FileHandle};
 
-use crate::app::{self, App, CurrentGame, Game, LoadedGame, Screen, console::AnyConsole, library};
-use missingno_gb::cartridge::Cartridge;
+use crate::app::{
+    self, App, CurrentGame, Game, LoadedGame, Screen,
+    console::{AnyConsole, RequiresCgb},
+    library, rom_file,
+    settings::ConsoleModel,
+};
+use missingno_gb::{BootRom, cartridge::Cartridge};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -14,6 +20,16 @@ pub enum Message {
     Picked(Option<FileHandle>),
     LoadPath(PathBuf),
     Loaded(PathBuf, Vec<u8>),
+    /// Back out of choosing a ROM from an archive.
+    CancelArchiveChoice,
+    /// Re-read the current game's ROM into the running debugger.
+    Reload,
+}
+
+/// An archive holding several ROMs, waiting for the user to pick one.
+pub struct ArchiveChoice {
+    pub archive: PathBuf,
+    pub roms: Vec<String>,
 }
 
 impl From<Message> for app::Message {
@@ -26,7 +42,8 @@ pub fn update(message: Message, app: &mut App) -> Task<app::Message> {
     match message {
         Message::Pick => {
             app.game = Game::Loading;
-            let mut dialog = AsyncFileDialog::new().add_filter("Game Boy ROM", &["gb", "gbc"]);
+            let mut dialog =
+                AsyncFileDialog::new().add_filter("Game Boy ROM", &["gb", "gbc", "zip"]);
             if let Some(dir) = app.recent_games.most_recent_dir() {
                 dialog = dialog.set_directory(dir);
             }
@@ -35,11 +52,15 @@ pub fn update(message: Message, app: &mut App) -> Task<app::Message> {
             });
         }
 
-        Message::LoadPath(rom_path) => match std::fs::read(&rom_path) {
-            Ok(rom) => return Task::done(Message::Loaded(rom_path, rom).into()),
-            Err(_) => {
-                app.recent_games.remove_path(&rom_path);
-                app.recent_games.save();
+        Message::LoadPath(rom_path) => match rom_file::read(&rom_path) {
+            Ok(rom) => {
+                app.archive_choice = None;
+                return Task::done(Message::Loaded(rom_path, rom).into());
+            }
+            // Keep it in the recent list: the file may come back (e.g. a
+            // drive that isn't mounted), and the menu greys it out meanwhile.
+            Err(error) => {
+                app.load_error = Some(format!("Couldn't open {}: {error}", file_name(&rom_path)));
             }
         },
 
@@ -55,17 +76,168 @@ pub fn update(message: Message, app: &mut App) -> Task<app::Message> {
         }
 
         Message::Loaded(rom_path, rom) => {
+            if rom_file::is_archive(&rom_path) {
+                return open_archive(app, rom_path, &rom);
+            }
             return setup_game(app, rom_path, rom);
         }
+
+        Message::CancelArchiveChoice => {
+            app.archive_choice = None;
+            app.game = Game::Unloaded;
+        }
+
+        Message::Reload => reload_game(app),
     }
 
     Task::none()
 }
 
+/// Load the only ROM in an archive, or ask which one to load.
+fn open_archive(app: &mut App, archive: PathBuf, bytes: &[u8]) -> Task<app::Message> {
+    let roms = match rom_file::archive_roms(bytes) {
+        Ok(roms) => roms,
+        Err(error) => {
+            report_load_error(app, &archive, error);
+            return Task::none();
+        }
+    };
+    if let [name] = roms.as_slice() {
+        return match rom_file::archive_entry(bytes, name) {
+            Ok(rom) => setup_game(app, archive.join(name), rom),
+            Err(error) => {
+                report_load_error(app, &archive, error);
+                Task::none()
+            }
+        };
+    }
+    app.archive_choice = Some(ArchiveChoice { archive, roms });
+    Task::none()
+}
+
+/// Surface a ROM that can't be loaded instead of starting it.
+fn report_load_error(app: &mut App, rom_path: &Path, error: impl std::fmt::Display) {
+    app.load_error = Some(format!("Couldn't load {}: {error}", file_name(rom_path)));
+    app.game = Game::Unloaded;
+}
+
+fn file_name(path: &Path) -> std::borrow::Cow<'_, str> {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+}
+
+/// Swap a fresh build of the current ROM into the debugger, for iterating on
+/// homebrew. Breakpoints and layout carry over; the cartridge RAM is copied
+/// from the old console. A ROM that fails to load leaves the old one running.
+fn reload_game(app: &mut App) {
+    let Some(rom_path) = current_rom_path(app) else {
+        return;
+    };
+    let Game::Loaded(LoadedGame::Debugger(debugger)) = &mut app.game else {
+        return;
+    };
+
+    let previous = debugger.cartridge();
+    let cartridge = match rebuilt_cartridge(&rom_path, previous) {
+        Ok(cartridge) => cartridge,
+        Err(error) => {
+            app.load_error = Some(format!("Couldn't reload {}: {error}", file_name(&rom_path)));
+            return;
+        }
+    };
+
+    // A different header usually means the path now holds another game.
+    let header_changed = cartridge.title() != previous.title()
+        || cartridge.header_checksum() != previous.header_checksum();
+    app.load_error = header_changed.then(|| {
+        format!(
+            "Reloaded {}, but its header changed: \"{}\" is now \"{}\"",
+            file_name(&rom_path),
+            previous.title(),
+            cartridge.title()
+        )
+    });
+
+    let mut console = match AnyConsole::new(cartridge, None, app.settings.console_model) {
+        Ok(console) => console,
+        Err(error) => {
+            app.load_error = Some(format!("Couldn't reload {}: {error}", file_name(&rom_path)));
+            return;
+        }
+    };
+    if let Some(audio) = &app.audio_output {
+        console.set_audio_output_rate(audio.sample_rate());
+    }
+    replace_with_or_abort(debugger, |debugger| debugger.reload(console));
+    debugger.set_palette(app.settings.palette);
+    debugger.set_presentation(
+        app.settings.screen_scaling,
+        app.settings.screen_grid,
+        app.settings.screen_filter,
+        app.settings.frame_blending,
+    );
+    load_symbols(app, &rom_path);
+}
+
+/// The ROM at `rom_path` with `previous`'s cartridge RAM. Fails on a file
+/// the assembler hasn't finished writing rather than running it.
+fn rebuilt_cartridge(rom_path: &Path, previous: &Cartridge) -> Result<Cartridge, String> {
+    let rom = rom_file::read(rom_path).map_err(|error| error.to_string())?;
+    Cartridge::new(rom, previous.save_data()).map_err(|error| error.to_string())
+}
+
+/// The current game's ROM, from the first of its paths that still exists.
+pub(super) fn current_rom_path(app: &App) -> Option<PathBuf> {
+    app.current_game.as_ref().and_then(|current| {
+        current
+            .entry
+            .rom_paths
+            .iter()
+            .find(|p| rom_file::exists(p))
+            .cloned()
+    })
+}
+
+/// Give the debugger the labels from the `.sym` file beside the ROM, if
+/// there is one, as RGBDS and no$gmb write it.
+pub(super) fn load_symbols(app: &mut App, rom_path: &Path) {
+    let Game::Loaded(LoadedGame::Debugger(debugger)) = &mut app.game else {
+        return;
+    };
+    let path = rom_path.with_extension("sym");
+    if !path.is_file() {
+        return;
+    }
+    if let Err(error) = debugger.load_symbols(&path) {
+        app.load_error = Some(format!(
+            "Couldn't load symbols from {}: {error}",
+            file_name(&path)
+        ));
+    }
+}
+
 /// Build the console for a ROM and wrap it for the active mode (debugger or
 /// emulator), storing it in `app.game`.
-fn start_console(app: &mut App, cartridge: Cartridge) {
-    let mut console = AnyConsole::new(cartridge, None);
+fn start_console(app: &mut App, rom_path: &Path, cartridge: Cartridge) -> Result<(), RequiresCgb> {
+    app.load_error = None;
+    // The logo is DMG-only, and the debugger should start at the game.
+    let boot_logo = app.settings.boot_logo
+        && !app.debugger_enabled
+        && match app.settings.console_model {
+            ConsoleModel::Auto => !cartridge.supports_cgb(),
+            ConsoleModel::GameBoy => true,
+            ConsoleModel::GameBoyColor => false,
+        };
+    let mut console = AnyConsole::new(
+        cartridge,
+        boot_logo.then_some(BootRom::Logo),
+        app.settings.console_model,
+    )?;
+    app.save_states.set_rom(rom_path);
+    if let Some(audio) = &app.audio_output {
+        console.set_audio_output_rate(audio.sample_rate());
+    }
     if let Some(link) = app.serial_link.take() {
         console.set_link(link);
     }
@@ -73,14 +245,40 @@ fn start_console(app: &mut App, cartridge: Cartridge) {
 
     if app.debugger_enabled {
         let mut debugger = app::debugger::AnyDebugger::new(console);
+        if let Some(layout) = &app.settings.debugger_layout {
+            debugger.restore_pane_layout(layout);
+        }
         debugger.set_palette(palette);
+        debugger.set_presentation(
+            app.settings.screen_scaling,
+            app.settings.screen_grid,
+            app.settings.screen_filter,
+            app.settings.frame_blending,
+        );
+        if let Game::Loaded(LoadedGame::Debugger(previous)) = &mut app.game {
+            debugger.restore_test_runner(previous.take_test_runner());
+        }
         app.game = Game::Loaded(LoadedGame::Debugger(debugger));
+        load_symbols(app, rom_path);
     } else {
         let mut emu = app::emulator::Emulator::new(console, app.settings.use_sgb_colors);
         emu.set_palette(palette);
+        emu.set_presentation(
+            app.settings.screen_scaling,
+            app.settings.screen_grid,
+            app.settings.screen_filter,
+            app.settings.frame_blending,
+        );
+        emu.set_turbo(&app.settings.turbo_buttons, app.settings.turbo_frames);
+        emu.set_frame_skip(app.settings.frame_skip, app.settings.auto_frame_skip);
+        emu.set_speed(app.settings.speed);
+        if let Some(audio) = &app.audio_output {
+            emu.set_audio_output_rate(audio.sample_rate());
+        }
         emu.run();
         app.game = Game::Loaded(LoadedGame::Emulator(emu));
     }
+    Ok(())
 }
 
 /// Select a game from the library by SHA1 and populate CurrentGame.
@@ -100,6 +298,7 @@ pub fn select_game(app: &mut App, sha1: &str) -> bool {
         session: None,
         started_from: None,
         initial_sram: None,
+        saved_ram_generation: 0,
     });
     true
 }
@@ -111,19 +310,35 @@ pub fn play_current_game(app: &mut App) -> Task<app::Message> {
         let Some(current) = &app.current_game else {
             return Task::none();
         };
-        let Some(rom_path) = current.entry.rom_paths.iter().find(|p| p.exists()).cloned() else {
+        let Some(rom_path) = current
+            .entry
+            .rom_paths
+            .iter()
+            .find(|p| rom_file::exists(p))
+            .cloned()
+        else {
             return Task::none();
         };
         (rom_path, current.game_dir.clone())
     };
 
-    let Ok(rom) = std::fs::read(&rom_path) else {
+    let Ok(rom) = rom_file::read(&rom_path) else {
         return Task::none();
     };
 
     let save_data = library::activity::load_current_sram(&game_dir);
     let initial_sram = save_data.clone();
-    start_console(app, Cartridge::new(rom, save_data));
+    let cartridge = match Cartridge::new(rom, save_data) {
+        Ok(cartridge) => cartridge,
+        Err(error) => {
+            report_load_error(app, &rom_path, error);
+            return Task::none();
+        }
+    };
+    if let Err(error) = start_console(app, &rom_path, cartridge) {
+        report_load_error(app, &rom_path, error);
+        return Task::none();
+    }
 
     // Start play session
     if let Some(current) = &mut app.current_game {
@@ -157,19 +372,35 @@ pub fn play_with_save(app: &mut App, activity_filename: &str) -> Task<app::Messa
         let Some(current) = &app.current_game else {
             return Task::none();
         };
-        let Some(rom_path) = current.entry.rom_paths.iter().find(|p| p.exists()).cloned() else {
+        let Some(rom_path) = current
+            .entry
+            .rom_paths
+            .iter()
+            .find(|p| rom_file::exists(p))
+            .cloned()
+        else {
             return Task::none();
         };
         (rom_path, current.game_dir.clone())
     };
 
-    let Ok(rom) = std::fs::read(&rom_path) else {
+    let Ok(rom) = rom_file::read(&rom_path) else {
         return Task::none();
     };
 
     let save_data = library::activity::load_sram_from(&game_dir, activity_filename);
     let initial_sram = save_data.clone();
-    start_console(app, Cartridge::new(rom, save_data));
+    let cartridge = match Cartridge::new(rom, save_data) {
+        Ok(cartridge) => cartridge,
+        Err(error) => {
+            report_load_error(app, &rom_path, error);
+            return Task::none();
+        }
+    };
+    if let Err(error) = start_console(app, &rom_path, cartridge) {
+        report_load_error(app, &rom_path, error);
+        return Task::none();
+    }
 
     if let Some(current) = &mut app.current_game {
         let session = library::activity::SessionFile::new(
@@ -199,6 +430,13 @@ pub fn play_with_save(app: &mut App, activity_filename: &str) -> Task<app::Messa
 
 /// Full pipeline for loading a ROM from a file path: create library entry + start emulation.
 pub fn setup_game(app: &mut App, rom_path: PathBuf, rom: Vec<u8>) -> Task<app::Message> {
+    // Homebrew often leaves the header checksum unset; only reject files
+    // that can't run at all.
+    if let Err(error) = Cartridge::validate_lenient(&rom) {
+        report_load_error(app, &rom_path, error);
+        return Task::none();
+    }
+
     let sha1 = library::hasheous::rom_sha1(&rom);
 
     // Check library for existing game
@@ -222,7 +460,7 @@ pub fn setup_game(app: &mut App, rom_path: PathBuf, rom: Vec<u8>) -> Task<app::M
             .expect("Could not determine library directory");
 
         // Import .sav from next to ROM if no activity exists yet
-        let legacy_sav = rom_path.with_extension("sav");
+        let legacy_sav = rom_file::legacy_save_path(&rom_path);
         if legacy_sav.exists() {
             library::activity::import_legacy_sav(&game_dir, &legacy_sav);
         }
@@ -242,7 +480,17 @@ pub fn setup_game(app: &mut App, rom_path: PathBuf, rom: Vec<u8>) -> Task<app::M
         library::load_cover(&game_dir).map(|bytes| iced::widget::image::Handle::from_bytes(bytes));
 
     // Create cartridge and start emulation
-    start_console(app, Cartridge::new(rom, save_data));
+    let cartridge = match Cartridge::new(rom, save_data) {
+        Ok(cartridge) => cartridge,
+        Err(error) => {
+            report_load_error(app, &rom_path, error);
+            return Task::none();
+        }
+    };
+    if let Err(error) = start_console(app, &rom_path, cartridge) {
+        report_load_error(app, &rom_path, error);
+        return Task::none();
+    }
 
     let session = library::activity::SessionFile::new(Timestamp::now(), None);
     library::activity::write_session(&game_dir, &session);
@@ -255,6 +503,7 @@ pub fn setup_game(app: &mut App, rom_path: PathBuf, rom: Vec<u8>) -> Task<app::M
         session: Some(session),
         started_from: None,
         initial_sram,
+        saved_ram_generation: 0,
     });
     app.screen = Screen::Emulator;
 
@@ -265,3 +514,23 @@ pub fn setup_game(app: &mut App, rom_path: PathBuf, rom: Vec<u8>) -> Task<app::M
 
     Task::none()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_half_written_rebuild_is_refused() {
+        let previous = Cartridge::new(vec![0; 0x8000], None).unwrap();
+        let path = std::env::temp_dir().join(format!("missingno_reload_{}.gb", std::process::id()));
+
+        std::fs::write(&path, vec![0; 0x150]).unwrap();
+        let half_written = rebuilt_cartridge(&path, &previous);
+        std::fs::write(&path, vec![0; 0x8000]).unwrap();
+        let finished = rebuilt_cartridge(&path, &previous);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(half_written.is_err());
+        assert!(finished.is_ok());
+    }
+}
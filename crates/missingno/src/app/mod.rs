@@ -1,13 +1,15 @@
-use std::{fs, path::PathBuf, time::Instant};
+use std::{path::PathBuf, time::Instant};
 
 use action_bar::ActionBar;
 use audio_output::AudioOutput;
+use audio_recorder::AudioRecorder;
 use iced::{Task, Theme, window};
 use missingno_gb::joypad;
 use ui::fonts;
 
 mod action_bar;
 mod audio_output;
+mod audio_recorder;
 mod console;
 mod controls;
 mod debugger;
@@ -16,6 +18,8 @@ mod emulator;
 pub mod library;
 mod load;
 mod recent;
+mod rom_file;
+mod save_states;
 mod screen;
 pub mod settings;
 mod texture_renderer;
@@ -74,6 +78,9 @@ struct App {
     fullscreen: Fullscreen,
     action_bar: ActionBar,
     audio_output: Option<AudioOutput>,
+    /// Where the game's audio is going as well as the speakers, while
+    /// recording.
+    audio_recorder: Option<AudioRecorder>,
     recent_games: recent::RecentGames,
     settings: settings::Settings,
     /// The running emulation session. Only set when a game is actually loaded.
@@ -81,8 +88,14 @@ struct App {
     store: library::store::GameStore,
     /// Action waiting for user confirmation (e.g. close game before launching another).
     pending_action: Option<PendingAction>,
-    /// When set, shows a brief "Screenshot saved" toast overlay.
-    screenshot_toast: Option<Instant>,
+    /// A brief note shown over the game, like "Screenshot saved".
+    toast: Option<Toast>,
+    /// Consoles saved to the numbered save state slots.
+    save_states: save_states::SaveStates,
+    /// Why the last ROM couldn't be loaded, shown until dismissed.
+    load_error: Option<String>,
+    /// An opened archive holding several ROMs, until one is picked.
+    archive_choice: Option<load::ArchiveChoice>,
     /// Serial link cable connection (BGB link protocol), injected into GameBoy on load.
     serial_link: Option<Box<dyn missingno_gb::serial_transfer::SerialLink>>,
     /// Homebrew Hub API client (shared, thread-safe).
@@ -215,6 +228,21 @@ enum CartridgeMessage {
     FlashComplete(Result<Option<Vec<u8>>, String>),
 }
 
+/// A brief note over the game, dismissed after a moment.
+struct Toast {
+    icon: ui::icons::Icon,
+    text: String,
+}
+
+impl Toast {
+    fn new(icon: ui::icons::Icon, text: impl Into<String>) -> Self {
+        Self {
+            icon,
+            text: text.into(),
+        }
+    }
+}
+
 enum Fullscreen {
     Windowed,
     Active {
@@ -244,6 +272,8 @@ struct CurrentGame {
     started_from: Option<String>,
     /// SRAM snapshot at session start, for detecting meaningful changes.
     initial_sram: Option<Vec<u8>>,
+    /// The cartridge's RAM generation when it was last saved.
+    saved_ram_generation: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -274,6 +304,10 @@ enum Message {
     Reset,
     SaveBattery,
     TakeScreenshot,
+    SaveState(save_states::Slot),
+    LoadState(save_states::Slot),
+    ToggleAudioRecording,
+    StartAudioRecording(Option<rfd::FileHandle>),
 
     PressButton(joypad::Button),
     ReleaseButton(joypad::Button),
@@ -296,7 +330,8 @@ enum Message {
     HideCursorTick,
     CloseRequested,
 
-    DismissScreenshotToast,
+    DismissToast,
+    DismissLoadError,
 
     // Cartridge reader/writer (device-level, not screen-specific)
     CartridgeRwPoll,
@@ -326,19 +361,23 @@ impl App {
 
         let store = library::store::GameStore::new();
 
-        let mut app = Self {
+        let app = Self {
             screen: Screen::Library { hovered_game: None },
             game: Game::Unloaded,
             debugger_enabled: debugger,
             fullscreen: Fullscreen::Windowed,
             action_bar: ActionBar::new(),
             audio_output: AudioOutput::new(),
+            audio_recorder: None,
             recent_games,
             settings,
             current_game: None,
             store,
             pending_action: None,
-            screenshot_toast: None,
+            toast: None,
+            save_states: save_states::SaveStates::new(),
+            load_error: None,
+            archive_choice: None,
             serial_link,
             homebrew_client: std::sync::Arc::new(library::homebrew_hub::HomebrewHubClient::new()),
             catalogue: std::sync::Arc::new(library::catalogue::Catalogue::load()),
@@ -356,9 +395,7 @@ impl App {
         let mut tasks = Vec::new();
 
         if let Some(rom_path) = rom_path {
-            if let Ok(rom) = fs::read(&rom_path) {
-                tasks.push(load::setup_game(&mut app, rom_path, rom));
-            }
+            tasks.push(Task::done(load::Message::LoadPath(rom_path).into()));
         }
 
         // Scan configured ROM directories on startup
@@ -403,7 +440,11 @@ impl App {
             | Message::Reset
             | Message::SaveBattery
             | Message::TakeScreenshot
-            | Message::DismissScreenshotToast
+            | Message::ToggleAudioRecording
+            | Message::StartAudioRecording(_)
+            | Message::SaveState(_)
+            | Message::LoadState(_)
+            | Message::DismissToast
             | Message::PressButton(_)
             | Message::ReleaseButton(_)
             | Message::ToggleDebugger(_) => return self.handle_emulation_message(message),
@@ -451,6 +492,8 @@ impl App {
                     }
                     Some(PendingAction::SwitchGame(sha1)) => {
                         // Close current game
+                        self.flush_pending_save();
+                        self.stop_audio_recording();
                         if let Some(current) = &mut self.current_game {
                             if let Some(session) = &mut current.session {
                                 session.end = Some(jiff::Timestamp::now());
@@ -467,6 +510,8 @@ impl App {
                         }
                     }
                     Some(PendingAction::StopGame) => {
+                        self.flush_pending_save();
+                        self.stop_audio_recording();
                         let sha1 = if let Some(current) = &mut self.current_game {
                             if let Some(session) = &mut current.session {
                                 session.end = Some(jiff::Timestamp::now());
@@ -493,6 +538,8 @@ impl App {
                         self.screen = Screen::Library { hovered_game: None };
                     }
                     Some(PendingAction::CloseApp) => {
+                        self.flush_pending_save();
+                        self.stop_audio_recording();
                         if let Some(current) = &mut self.current_game {
                             if let Some(session) = &mut current.session {
                                 session.end = Some(jiff::Timestamp::now());
@@ -507,6 +554,9 @@ impl App {
             Message::DismissConfirm => {
                 self.pending_action = None;
             }
+            Message::DismissLoadError => {
+                self.load_error = None;
+            }
             Message::PlayFromDetail => {
                 self.menu_open = false;
                 let viewing = self.viewing_sha1().map(|s| s.to_string());
@@ -670,14 +720,21 @@ impl App {
                 if let Game::Loaded(LoadedGame::Emulator(emulator)) = &mut self.game {
                     let task = emulator.update(message);
                     self.drain_audio();
+                    self.report_warnings();
                     return task;
                 }
             }
 
             Message::Debugger(message) => {
                 if let Game::Loaded(LoadedGame::Debugger(debugger)) = &mut self.game {
+                    let rearranged = matches!(message, debugger::Message::Pane(_));
                     let task = debugger.update(message);
+                    if rearranged {
+                        // Saved with the window size when the app closes.
+                        self.settings.debugger_layout = debugger.pane_layout();
+                    }
                     self.drain_audio();
+                    self.report_warnings();
                     return task;
                 }
             }
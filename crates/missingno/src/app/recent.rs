@@ -3,15 +3,33 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use jiff::Timestamp;
 use serde::{Deserialize, Serialize};
 
 const MAX_RECENT: usize = 10;
 
 #[derive(Serialize, Deserialize, Clone)]
-struct RecentGame {
+pub struct RecentGame {
     sha1: String,
     title: String,
     rom_path: PathBuf,
+    /// Missing from lists saved before timestamps were recorded.
+    #[serde(default)]
+    last_played: Option<Timestamp>,
+}
+
+impl RecentGame {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn rom_path(&self) -> &Path {
+        &self.rom_path
+    }
+
+    pub fn last_played(&self) -> Option<Timestamp> {
+        self.last_played
+    }
 }
 
 pub struct RecentGames {
@@ -49,6 +67,7 @@ impl RecentGames {
                 sha1: sha1.to_string(),
                 title: title.to_string(),
                 rom_path: rom_path.to_path_buf(),
+                last_played: Some(Timestamp::now()),
             },
         );
         self.games.truncate(MAX_RECENT);
@@ -62,10 +81,9 @@ impl RecentGames {
         }
     }
 
-    pub fn remove_path(&mut self, path: &Path) {
-        let path_str = path.to_string_lossy();
-        self.games
-            .retain(|g| g.rom_path.to_string_lossy() != path_str);
+    /// Most recently played first.
+    pub fn iter(&self) -> impl Iterator<Item = &RecentGame> {
+        self.games.iter()
     }
 
     pub fn most_recent_dir(&self) -> Option<PathBuf> {
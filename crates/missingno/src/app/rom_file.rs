@@ -0,0 +1,207 @@
+//! Reading ROMs from disk, including from inside zip archives. A ROM in an
+//! archive is addressed by a path through it, such as `games.zip/tetris.gb`,
+//! so the library and the recent games list can track it like a plain file.
+
+use std::{
+    fmt, fs,
+    io::{self, Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use zip::{ZipArchive, result::ZipError};
+
+#[derive(Debug)]
+pub enum RomFileError {
+    Io(io::Error),
+    Zip(ZipError),
+    /// The archive holds no `.gb` or `.gbc` files.
+    NoRoms,
+}
+
+impl fmt::Display for RomFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RomFileError::Io(error) => error.fmt(f),
+            RomFileError::Zip(error) => error.fmt(f),
+            RomFileError::NoRoms => write!(f, "the archive doesn't contain a Game Boy ROM"),
+        }
+    }
+}
+
+impl From<io::Error> for RomFileError {
+    fn from(error: io::Error) -> Self {
+        RomFileError::Io(error)
+    }
+}
+
+impl From<ZipError> for RomFileError {
+    fn from(error: ZipError) -> Self {
+        RomFileError::Zip(error)
+    }
+}
+
+pub fn is_archive(path: &Path) -> bool {
+    has_extension(path, &["zip"])
+}
+
+fn is_rom(path: &Path) -> bool {
+    has_extension(path, &["gb", "gbc"])
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extensions
+                .iter()
+                .any(|candidate| extension.eq_ignore_ascii_case(candidate))
+        })
+}
+
+/// The names of the ROMs in a zip archive, in archive order.
+pub fn archive_roms(archive: &[u8]) -> Result<Vec<String>, RomFileError> {
+    let archive = ZipArchive::new(Cursor::new(archive))?;
+    let roms: Vec<String> = archive
+        .file_names()
+        .filter(|name| !name.ends_with('/') && is_rom(Path::new(name)))
+        .map(str::to_owned)
+        .collect();
+    if roms.is_empty() {
+        return Err(RomFileError::NoRoms);
+    }
+    Ok(roms)
+}
+
+pub fn archive_entry(archive: &[u8], name: &str) -> Result<Vec<u8>, RomFileError> {
+    let mut archive = ZipArchive::new(Cursor::new(archive))?;
+    let mut file = archive.by_name(name)?;
+    let mut rom = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut rom)?;
+    Ok(rom)
+}
+
+/// Where the ROM at `path` lives if it's inside an archive: the archive's
+/// path and the entry's name within it.
+fn archive_path(path: &Path) -> Option<(&Path, String)> {
+    let archive = path
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| is_archive(ancestor) && ancestor.is_file())?;
+    let entry = path.strip_prefix(archive).ok()?;
+    let name: Vec<_> = entry
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect();
+    Some((archive, name.join("/")))
+}
+
+/// Read the ROM at `path`, which may lead into an archive.
+pub fn read(path: &Path) -> Result<Vec<u8>, RomFileError> {
+    match archive_path(path) {
+        Some((archive, name)) => archive_entry(&fs::read(archive)?, &name),
+        None => Ok(fs::read(path)?),
+    }
+}
+
+/// Whether there's a ROM to load at `path`, which may lead into an archive.
+pub fn exists(path: &Path) -> bool {
+    path.exists() || archive_path(path).is_some()
+}
+
+/// A file beside the ROM at `path`, named after it with `extension`. For a
+/// ROM inside an archive it goes beside the archive, as nothing can be
+/// written inside one.
+pub fn sidecar_path(path: &Path, extension: &str) -> PathBuf {
+    match archive_path(path) {
+        Some((archive, _)) => {
+            let stem = path.file_stem().unwrap_or_default();
+            archive.with_file_name(stem).with_extension(extension)
+        }
+        None => path.with_extension(extension),
+    }
+}
+
+/// Where an emulator that kept saves beside the ROM would have put this
+/// one's: named after the ROM, even when it's inside an archive.
+pub fn legacy_save_path(path: &Path) -> PathBuf {
+    sidecar_path(path, "sav")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use missingno_gb::cartridge::Cartridge;
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    use super::*;
+    use crate::app::{console::AnyConsole, settings::ConsoleModel};
+
+    /// Writes $42 to $C000, then spins.
+    fn rom() -> Vec<u8> {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+        rom[0x0150..0x0157].copy_from_slice(&[
+            0x3E, 0x42, // LD A,$42
+            0xEA, 0x00, 0xC0, // LD ($C000),A
+            0x18, 0xFE, // JR -2
+        ]);
+        rom
+    }
+
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn lists_only_roms() {
+        let archive = zip(&[
+            ("readme.txt", b"hello"),
+            ("Game.GB", &rom()),
+            ("extras/game (color).gbc", &rom()),
+        ]);
+        assert_eq!(
+            archive_roms(&archive).unwrap(),
+            ["Game.GB", "extras/game (color).gbc"]
+        );
+    }
+
+    #[test]
+    fn archive_without_roms_is_rejected() {
+        let archive = zip(&[("readme.txt", b"hello")]);
+        assert!(matches!(archive_roms(&archive), Err(RomFileError::NoRoms)));
+        assert!(matches!(
+            archive_roms(b"not a zip"),
+            Err(RomFileError::Zip(_))
+        ));
+    }
+
+    #[test]
+    fn rom_read_through_an_archive_boots() {
+        let dir = std::env::temp_dir().join(format!("missingno-rom-file-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("game.zip");
+        fs::write(&archive, zip(&[("game.gb", &rom())])).unwrap();
+
+        let path = archive.join("game.gb");
+        assert!(exists(&path));
+        assert_eq!(legacy_save_path(&path), dir.join("game.sav"));
+        let rom = read(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let mut console =
+            AnyConsole::new(Cartridge::new(rom, None).unwrap(), None, ConsoleModel::Auto).unwrap();
+        console.run_cycles(70224);
+        let AnyConsole::Dmg(game_boy) = &console else {
+            panic!("a DMG ROM started on a CGB");
+        };
+        assert_eq!(game_boy.peek(0xC000), 0x42);
+    }
+}
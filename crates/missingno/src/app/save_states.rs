@@ -0,0 +1,232 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use missingno_gb::cartridge::Cartridge;
+use missingno_gb::save_state::{SaveState, SaveStateError};
+use missingno_gbc::Cgb;
+
+use super::{console::AnyConsole, rom_file};
+
+/// Numbered slots, saved to with Ctrl+F1–F8 and loaded with Shift+F1–F8.
+pub const SLOTS: usize = 8;
+
+/// Where a state is saved: the quicksave on F5/F9, or a numbered slot
+/// counted from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Quick,
+    Numbered(usize),
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Slot::Quick => write!(f, "Quicksave"),
+            Slot::Numbered(slot) => write!(f, "Slot {}", slot + 1),
+        }
+    }
+}
+
+/// The running game's save states, kept in files beside the ROM (beside the
+/// archive for a zipped ROM): `<rom>.qsave` for the quicksave and
+/// `<rom>.state1`–`<rom>.state8` for the numbered slots. A state only loads
+/// back into the game it was saved from.
+pub struct SaveStates {
+    /// The ROM the slot files are named after.
+    rom_path: Option<PathBuf>,
+}
+
+/// Why a slot can't be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadStateError {
+    Empty,
+    /// Saved from a different ROM, or the same ROM on the other console.
+    OtherGame,
+    /// The file couldn't be read back.
+    Unreadable(String),
+}
+
+impl fmt::Display for LoadStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadStateError::Empty => write!(f, "is empty"),
+            LoadStateError::OtherGame => write!(f, "holds a different game"),
+            LoadStateError::Unreadable(error) => write!(f, "couldn't be read: {error}"),
+        }
+    }
+}
+
+impl From<SaveStateError> for LoadStateError {
+    fn from(error: SaveStateError) -> Self {
+        match error {
+            SaveStateError::Io(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                LoadStateError::Empty
+            }
+            SaveStateError::ModelMismatch | SaveStateError::ChecksumMismatch { .. } => {
+                LoadStateError::OtherGame
+            }
+            error => LoadStateError::Unreadable(error.to_string()),
+        }
+    }
+}
+
+impl SaveStates {
+    pub fn new() -> Self {
+        Self { rom_path: None }
+    }
+
+    /// Switch to the slots of the ROM at `rom_path`.
+    pub fn set_rom(&mut self, rom_path: &Path) {
+        self.rom_path = Some(rom_path.to_path_buf());
+    }
+
+    /// `<rom>.qsave` for the quicksave, `<rom>.state<N>` for a numbered slot.
+    fn path(&self, slot: Slot) -> Option<PathBuf> {
+        let rom_path = self.rom_path.as_ref()?;
+        let extension = match slot {
+            Slot::Quick => "qsave".to_string(),
+            Slot::Numbered(slot) => format!("state{}", slot + 1),
+        };
+        Some(rom_file::sidecar_path(rom_path, &extension))
+    }
+
+    pub fn save(&self, slot: Slot, console: &AnyConsole) -> Result<(), SaveStateError> {
+        let Some(path) = self.path(slot) else {
+            return Ok(());
+        };
+        match console {
+            AnyConsole::Dmg(console) => SaveState::capture(console).save_to_file(&path),
+            AnyConsole::Cgb(console) => SaveState::capture(console).save_to_file(&path),
+        }
+    }
+
+    /// The console in `slot`, if it was saved from the running `cartridge`
+    /// on the same kind of console.
+    pub fn load(
+        &self,
+        slot: Slot,
+        cartridge: &Cartridge,
+        cgb: bool,
+    ) -> Result<AnyConsole, LoadStateError> {
+        let path = self.path(slot).ok_or(LoadStateError::Empty)?;
+        Ok(if cgb {
+            AnyConsole::Cgb(SaveState::<Cgb>::load_from_file(&path)?.into_console(cartridge)?)
+        } else {
+            AnyConsole::Dmg(SaveState::load_from_file(&path)?.into_console(cartridge)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use missingno_gb::GameBoy;
+
+    use super::*;
+
+    fn console(title: &[u8]) -> AnyConsole {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0134..0x0134 + title.len()].copy_from_slice(title);
+        AnyConsole::Dmg(GameBoy::new(Cartridge::new(rom, None).unwrap(), None))
+    }
+
+    /// Slots for a ROM of its own in the temp directory, so tests running
+    /// alongside don't share files.
+    fn save_states(name: &str) -> SaveStates {
+        let mut states = SaveStates::new();
+        states.set_rom(&std::env::temp_dir().join(format!("missingno_save_states_{name}.gb")));
+        states
+    }
+
+    fn remove(states: &SaveStates, slot: Slot) {
+        std::fs::remove_file(states.path(slot).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn a_slot_is_a_file_named_after_the_rom() {
+        let mut states = SaveStates::new();
+        states.set_rom(Path::new("/games/Tetris.gb"));
+        assert_eq!(
+            states.path(Slot::Numbered(2)),
+            Some(PathBuf::from("/games/Tetris.state3"))
+        );
+        assert_eq!(
+            states.path(Slot::Quick),
+            Some(PathBuf::from("/games/Tetris.qsave"))
+        );
+    }
+
+    #[test]
+    fn a_zipped_roms_slots_go_beside_the_archive() {
+        let dir =
+            std::env::temp_dir().join(format!("missingno_save_states_zip_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("games.zip"), b"").unwrap();
+
+        let mut states = SaveStates::new();
+        states.set_rom(&dir.join("games.zip").join("tetris.gb"));
+        let path = states.path(Slot::Numbered(0));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(path, Some(dir.join("tetris.state1")));
+    }
+
+    #[test]
+    fn a_slot_loads_back_what_was_saved() {
+        let states = save_states("round_trip");
+        let mut saved = console(b"GAME");
+        saved.run_cycles(1000);
+        states.save(Slot::Numbered(2), &saved).unwrap();
+
+        let loaded = states.load(Slot::Numbered(2), console(b"GAME").cartridge(), false);
+        remove(&states, Slot::Numbered(2));
+        let AnyConsole::Dmg(loaded) = loaded.unwrap() else {
+            panic!("saved a DMG");
+        };
+        assert!(loaded.cpu().ir_address != 0x0100);
+    }
+
+    #[test]
+    fn an_empty_slot_is_refused() {
+        let states = save_states("empty");
+        assert_eq!(
+            states
+                .load(Slot::Numbered(0), console(b"GAME").cartridge(), false)
+                .err(),
+            Some(LoadStateError::Empty)
+        );
+    }
+
+    #[test]
+    fn another_games_state_is_refused() {
+        let states = save_states("other_game");
+        states.save(Slot::Numbered(0), &console(b"GAME")).unwrap();
+        let other_game = states
+            .load(Slot::Numbered(0), console(b"OTHER").cartridge(), false)
+            .err();
+        // The same game on the Game Boy Color.
+        let other_console = states
+            .load(Slot::Numbered(0), console(b"GAME").cartridge(), true)
+            .err();
+        remove(&states, Slot::Numbered(0));
+        assert_eq!(other_game, Some(LoadStateError::OtherGame));
+        assert_eq!(other_console, Some(LoadStateError::OtherGame));
+    }
+
+    #[test]
+    fn the_quicksave_is_kept_apart_from_the_slots() {
+        let states = save_states("quick");
+        let game = console(b"GAME");
+        let cartridge = game.cartridge();
+        assert_eq!(
+            states.load(Slot::Quick, cartridge, false).err(),
+            Some(LoadStateError::Empty),
+            "nothing to quickload yet"
+        );
+
+        states.save(Slot::Quick, &console(b"GAME")).unwrap();
+        let quick = states.load(Slot::Quick, cartridge, false);
+        let numbered = states.load(Slot::Numbered(0), cartridge, false);
+        remove(&states, Slot::Quick);
+        assert!(quick.is_ok());
+        assert!(numbered.is_err());
+    }
+}
@@ -9,7 +9,10 @@ use missingno_gb::{
     sgb::SgbRenderData,
 };
 
-use super::texture_renderer::TextureRenderer;
+use super::{
+    settings::{ScreenFilter, ScreenScaling},
+    texture_renderer::TextureRenderer,
+};
 
 #[derive(Clone, Debug)]
 pub enum ScreenDisplay {
@@ -49,6 +52,7 @@ impl From<SgbScreen> for ScreenDisplay {
     }
 }
 
+#[derive(Clone)]
 pub struct ScreenView {
     pub screen: Screen,
     pub palette: PaletteChoice,
@@ -56,6 +60,15 @@ pub struct ScreenView {
     pub use_sgb_colors: bool,
     /// Pre-corrected CGB RGBA frame; bypasses the palette paths when set.
     pub cgb_rgba: Option<std::sync::Arc<[u8]>>,
+    pub scaling: ScreenScaling,
+    /// Darken the gaps between pixels.
+    pub grid: bool,
+    pub filter: ScreenFilter,
+    /// Average each frame with the one before it, like the DMG's slow LCD,
+    /// so sprites flickered on alternate frames show as see-through.
+    pub frame_blending: bool,
+    /// The last frame drawn, unblended. Only kept while blending.
+    previous_rgba: Option<std::sync::Arc<[u8]>>,
 }
 
 impl ScreenView {
@@ -66,10 +79,32 @@ impl ScreenView {
             sgb_render_data: None,
             use_sgb_colors: true,
             cgb_rgba: None,
+            scaling: ScreenScaling::Stretch,
+            grid: false,
+            filter: ScreenFilter::Off,
+            frame_blending: false,
+            previous_rgba: None,
+        }
+    }
+
+    pub fn set_presentation(
+        &mut self,
+        scaling: ScreenScaling,
+        grid: bool,
+        filter: ScreenFilter,
+        frame_blending: bool,
+    ) {
+        self.scaling = scaling;
+        self.grid = grid;
+        self.filter = filter;
+        self.frame_blending = frame_blending;
+        if !frame_blending {
+            self.previous_rgba = None;
         }
     }
 
     pub fn apply(&mut self, display: ScreenDisplay) {
+        self.previous_rgba = self.frame_blending.then(|| self.frame_rgba());
         match display {
             ScreenDisplay::GameBoy(GameBoyScreen::Display(screen)) => {
                 self.screen = screen;
@@ -104,6 +139,36 @@ impl ScreenView {
     }
 }
 
+impl ScreenView {
+    /// The current frame as RGBA.
+    fn frame_rgba(&self) -> std::sync::Arc<[u8]> {
+        match &self.cgb_rgba {
+            Some(rgba) => rgba.clone(),
+            None => screen_to_pixels(
+                &self.screen,
+                self.palette.palette(),
+                self.sgb_render_data.as_ref(),
+                self.use_sgb_colors,
+            )
+            .into(),
+        }
+    }
+
+    /// What goes on screen: the current frame, blended with the previous
+    /// one if frame blending is on.
+    fn displayed_rgba(&self) -> std::sync::Arc<[u8]> {
+        let current = self.frame_rgba();
+        match &self.previous_rgba {
+            Some(previous) => current
+                .iter()
+                .zip(previous.iter())
+                .map(|(&current, &previous)| current.midpoint(previous))
+                .collect(),
+            None => current,
+        }
+    }
+}
+
 /// A powered-but-blank CGB LCD: all white.
 pub fn cgb_blank_rgba() -> Vec<u8> {
     vec![255; screen::PIXELS_PER_LINE as usize * screen::NUM_SCANLINES as usize * 4]
@@ -119,21 +184,12 @@ impl<Message> shader::Program<Message> for ScreenView {
         cursor: iced::mouse::Cursor,
         bounds: iced::Rectangle,
     ) -> Self::Primitive {
-        let pixels: std::sync::Arc<[u8]> = match &self.cgb_rgba {
-            Some(rgba) => rgba.clone(),
-            None => screen_to_pixels(
-                &self.screen,
-                self.palette.palette(),
-                self.sgb_render_data.as_ref(),
-                self.use_sgb_colors,
-            )
-            .into(),
-        };
         let renderer = TextureRenderer::with_pixels(
             screen::PIXELS_PER_LINE as u32,
             screen::NUM_SCANLINES as u32,
-            pixels,
-        );
+            self.displayed_rgba(),
+        )
+        .presented(self.scaling, self.grid, self.filter);
 
         <TextureRenderer as shader::Program<Message>>::draw(&renderer, &(), cursor, bounds)
     }
@@ -147,43 +203,43 @@ pub fn screen_to_pixels(
 ) -> Vec<u8> {
     use missingno_gb::sgb::MaskMode;
 
+    let Some(sgb_data) = sgb else {
+        return screen.to_rgba(palette);
+    };
+
     let mut pixels =
         Vec::with_capacity(screen::PIXELS_PER_LINE as usize * screen::NUM_SCANLINES as usize * 4);
 
     for y in 0..screen::NUM_SCANLINES {
         for x in 0..screen::PIXELS_PER_LINE {
             let palette_index = screen.pixel(x, y);
-            let color = if let Some(sgb_data) = sgb {
-                if !sgb_data.video_enabled {
-                    if use_sgb_colors {
-                        RGB8::new(255, 255, 255)
-                    } else {
-                        palette.color(PaletteIndex(0))
-                    }
+            let color = if !sgb_data.video_enabled {
+                if use_sgb_colors {
+                    RGB8::new(255, 255, 255)
                 } else {
-                    match sgb_data.mask_mode {
-                        MaskMode::Black => RGB8::new(0, 0, 0),
-                        MaskMode::BackdropColor => {
-                            if use_sgb_colors {
-                                sgb_data.palettes[0].colors[0].to_rgb8()
-                            } else {
-                                palette.color(palette_index)
-                            }
+                    palette.color(PaletteIndex(0))
+                }
+            } else {
+                match sgb_data.mask_mode {
+                    MaskMode::Black => RGB8::new(0, 0, 0),
+                    MaskMode::BackdropColor => {
+                        if use_sgb_colors {
+                            sgb_data.palettes[0].colors[0].to_rgb8()
+                        } else {
+                            palette.color(palette_index)
                         }
-                        MaskMode::Disabled | MaskMode::Freeze => {
-                            if use_sgb_colors {
-                                let cell_x = x as usize / 8;
-                                let cell_y = y as usize / 8;
-                                let pal_id = sgb_data.attribute_map.cells[cell_y][cell_x] as usize;
-                                sgb_data.palettes[pal_id].colors[palette_index.0 as usize].to_rgb8()
-                            } else {
-                                palette.color(palette_index)
-                            }
+                    }
+                    MaskMode::Disabled | MaskMode::Freeze => {
+                        if use_sgb_colors {
+                            let cell_x = x as usize / 8;
+                            let cell_y = y as usize / 8;
+                            let pal_id = sgb_data.attribute_map.cells[cell_y][cell_x] as usize;
+                            sgb_data.palettes[pal_id].colors[palette_index.0 as usize].to_rgb8()
+                        } else {
+                            palette.color(palette_index)
                         }
                     }
                 }
-            } else {
-                palette.color(palette_index)
             };
             pixels.extend_from_slice(&[color.r, color.g, color.b, 255]);
         }
@@ -195,3 +251,52 @@ pub fn screen_to_pixels(
 pub fn iced_color(color: RGB8) -> iced::Color {
     iced::Color::from_rgb8(color.r, color.g, color.b)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cgb_frame(value: u8) -> ScreenDisplay {
+        ScreenDisplay::Cgb(CgbScreen::Display(vec![
+            value;
+            screen::PIXELS_PER_LINE as usize
+                * screen::NUM_SCANLINES as usize
+                * 4
+        ]))
+    }
+
+    fn blending_view() -> ScreenView {
+        let mut view = ScreenView::new();
+        view.set_presentation(ScreenScaling::Stretch, false, ScreenFilter::Off, true);
+        view
+    }
+
+    #[test]
+    fn blending_averages_each_frame_with_the_previous_one() {
+        let mut view = blending_view();
+        view.apply(cgb_frame(200));
+        view.apply(cgb_frame(100));
+        assert!(view.displayed_rgba().iter().all(|&channel| channel == 150));
+
+        // Blends with the last frame as emulated, not as displayed.
+        view.apply(cgb_frame(100));
+        assert!(view.displayed_rgba().iter().all(|&channel| channel == 100));
+    }
+
+    #[test]
+    fn blending_leaves_the_emulated_frame_alone() {
+        let mut view = blending_view();
+        view.apply(cgb_frame(200));
+        view.apply(cgb_frame(100));
+        assert!(view.frame_rgba().iter().all(|&channel| channel == 100));
+    }
+
+    #[test]
+    fn turning_blending_off_shows_the_current_frame() {
+        let mut view = blending_view();
+        view.apply(cgb_frame(200));
+        view.apply(cgb_frame(100));
+        view.set_presentation(ScreenScaling::Stretch, false, ScreenFilter::Off, false);
+        assert!(view.displayed_rgba().iter().all(|&channel| channel == 100));
+    }
+}
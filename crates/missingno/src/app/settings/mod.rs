@@ -6,6 +6,8 @@ use std::{collections::HashMap, fmt, fs, path::PathBuf};
 use missingno_gb::ppu::types::palette::PaletteChoice;
 use serde::{Deserialize, Serialize};
 
+use crate::app::debugger::panes::PaneLayout;
+
 // ── Actions ───────────────────────────────────────────────────────────
 
 /// Every bindable action — game buttons and emulator controls.
@@ -24,6 +26,8 @@ pub enum Action {
     Screenshot,
     ToggleFullscreen,
     Pause,
+    // Emulator actions (held)
+    FastForward,
 }
 
 /// The 8 Game Boy buttons, for iteration and joypad mapping.
@@ -38,9 +42,28 @@ pub const GB_ACTIONS: [Action; 8] = [
     Action::GbSelect,
 ];
 
+/// Game Boy buttons that can be set to auto-fire.
+pub const TURBO_ACTIONS: [Action; 4] =
+    [Action::GbA, Action::GbB, Action::GbStart, Action::GbSelect];
+
+/// Turbo rates offered in settings, as frames spent in each of the pressed
+/// and released states.
+pub const TURBO_FRAMES: [u32; 4] = [1, 2, 3, 5];
+
+/// Frame skips offered in settings: show every frame, every 2nd or every 3rd.
+pub const FRAME_SKIPS: [u32; 3] = [1, 2, 3];
+
+/// Slowest and fastest emulation speeds, as multiples of the Game Boy's own.
+pub const MIN_SPEED: f32 = 0.25;
+pub const MAX_SPEED: f32 = 8.0;
+
 /// Emulator-level actions, for iteration.
-pub const EMULATOR_ACTIONS: [Action; 3] =
-    [Action::Screenshot, Action::ToggleFullscreen, Action::Pause];
+pub const EMULATOR_ACTIONS: [Action; 4] = [
+    Action::Screenshot,
+    Action::ToggleFullscreen,
+    Action::Pause,
+    Action::FastForward,
+];
 
 impl Action {
     /// True for Game Boy buttons that produce press/release events.
@@ -73,6 +96,120 @@ impl fmt::Display for Action {
             Action::Screenshot => write!(f, "Screenshot"),
             Action::ToggleFullscreen => write!(f, "Fullscreen"),
             Action::Pause => write!(f, "Pause"),
+            Action::FastForward => write!(f, "Fast forward"),
+        }
+    }
+}
+
+// ── Console ───────────────────────────────────────────────────────────
+
+/// Which console games run on. `Auto` follows the cartridge header: CGB
+/// games on the Game Boy Color, everything else on the Game Boy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ConsoleModel {
+    #[default]
+    Auto,
+    GameBoy,
+    GameBoyColor,
+}
+
+impl ConsoleModel {
+    pub const ALL: [ConsoleModel; 3] = [
+        ConsoleModel::Auto,
+        ConsoleModel::GameBoy,
+        ConsoleModel::GameBoyColor,
+    ];
+}
+
+impl fmt::Display for ConsoleModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsoleModel::Auto => write!(f, "Automatic"),
+            ConsoleModel::GameBoy => write!(f, "Game Boy"),
+            ConsoleModel::GameBoyColor => write!(f, "Game Boy Color"),
+        }
+    }
+}
+
+// ── Display ───────────────────────────────────────────────────────────
+
+/// How the game screen fills the space it's given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScreenScaling {
+    /// As large as fits, keeping the Game Boy's 10:9 shape.
+    #[default]
+    Fit,
+    /// The largest whole multiple of the Game Boy's resolution that fits,
+    /// so every pixel is the same size.
+    Integer,
+    /// Fill the space, whatever its shape.
+    Stretch,
+}
+
+impl ScreenScaling {
+    pub const ALL: [ScreenScaling; 3] = [
+        ScreenScaling::Fit,
+        ScreenScaling::Integer,
+        ScreenScaling::Stretch,
+    ];
+}
+
+impl fmt::Display for ScreenScaling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScreenScaling::Fit => write!(f, "Fit"),
+            ScreenScaling::Integer => write!(f, "Whole pixels"),
+            ScreenScaling::Stretch => write!(f, "Stretch"),
+        }
+    }
+}
+
+/// A look layered over the palette when the screen is drawn, imitating a
+/// real LCD. Cosmetic only: screenshots and the emulation don't see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScreenFilter {
+    /// The palette's colours as they are.
+    #[default]
+    Off,
+    /// The original Game Boy's washed-out, green-tinted screen.
+    Dmg,
+    /// The Game Boy Pocket's crisper, near-neutral greys.
+    Pocket,
+}
+
+impl ScreenFilter {
+    pub const ALL: [ScreenFilter; 3] = [ScreenFilter::Off, ScreenFilter::Dmg, ScreenFilter::Pocket];
+}
+
+impl fmt::Display for ScreenFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScreenFilter::Off => write!(f, "Off"),
+            ScreenFilter::Dmg => write!(f, "Original Game Boy"),
+            ScreenFilter::Pocket => write!(f, "Game Boy Pocket"),
+        }
+    }
+}
+
+/// How audio recordings store their samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RecordingFormat {
+    /// 16-bit integers, which anything can play.
+    #[default]
+    Pcm16,
+    /// 32-bit floats, exactly as the emulator mixed them.
+    Float32,
+}
+
+impl RecordingFormat {
+    pub const ALL: [RecordingFormat; 2] = [RecordingFormat::Pcm16, RecordingFormat::Float32];
+}
+
+impl fmt::Display for RecordingFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingFormat::Pcm16 => write!(f, "16-bit"),
+            RecordingFormat::Float32 => write!(f, "32-bit float"),
         }
     }
 }
@@ -105,7 +242,7 @@ impl Bindings {
             (Action::GbA, "x".to_string()),
             (Action::GbB, "z".to_string()),
             (Action::GbStart, "Enter".to_string()),
-            (Action::GbSelect, "Shift".to_string()),
+            (Action::GbSelect, "Backspace".to_string()),
             (Action::GbUp, "ArrowUp".to_string()),
             (Action::GbDown, "ArrowDown".to_string()),
             (Action::GbLeft, "ArrowLeft".to_string()),
@@ -113,6 +250,7 @@ impl Bindings {
             (Action::Screenshot, "F12".to_string()),
             (Action::ToggleFullscreen, "F11".to_string()),
             (Action::Pause, "Space".to_string()),
+            (Action::FastForward, "Tab".to_string()),
         ]))
     }
 
@@ -215,6 +353,15 @@ fn default_keyboard_bindings() -> Bindings {
 fn default_gamepad_bindings() -> Bindings {
     Bindings::default_gamepad()
 }
+fn default_turbo_frames() -> u32 {
+    2
+}
+fn default_frame_skip() -> u32 {
+    1
+}
+fn default_speed() -> f32 {
+    1.0
+}
 
 #[derive(Serialize, Deserialize)]
 struct SettingsFile {
@@ -232,6 +379,8 @@ struct SettingsFile {
     rom_directories: Vec<PathBuf>,
     #[serde(default = "default_true")]
     use_sgb_colors: bool,
+    #[serde(default)]
+    boot_logo: bool,
     #[serde(default = "default_true")]
     cartridge_rw_enabled: bool,
     #[serde(default)]
@@ -242,6 +391,30 @@ struct SettingsFile {
     keyboard_bindings: Bindings,
     #[serde(default = "default_gamepad_bindings")]
     gamepad_bindings: Bindings,
+    #[serde(default)]
+    turbo_buttons: Vec<Action>,
+    #[serde(default = "default_turbo_frames")]
+    turbo_frames: u32,
+    #[serde(default)]
+    console_model: ConsoleModel,
+    #[serde(default)]
+    debugger_layout: Option<PaneLayout>,
+    #[serde(default)]
+    screen_scaling: ScreenScaling,
+    #[serde(default)]
+    screen_grid: bool,
+    #[serde(default)]
+    screen_filter: ScreenFilter,
+    #[serde(default)]
+    frame_blending: bool,
+    #[serde(default = "default_frame_skip")]
+    frame_skip: u32,
+    #[serde(default)]
+    auto_frame_skip: bool,
+    #[serde(default = "default_speed")]
+    speed: f32,
+    #[serde(default)]
+    recording_format: RecordingFormat,
 }
 
 /// Legacy settings file format with flat KeyBindings structs.
@@ -277,11 +450,24 @@ impl Default for SettingsFile {
             palette: palette_to_string(PaletteChoice::default()),
             rom_directories: Vec::new(),
             use_sgb_colors: true,
+            boot_logo: false,
             cartridge_rw_enabled: true,
             window_width: None,
             window_height: None,
             keyboard_bindings: Bindings::default_keyboard(),
             gamepad_bindings: Bindings::default_gamepad(),
+            turbo_buttons: Vec::new(),
+            turbo_frames: default_turbo_frames(),
+            console_model: ConsoleModel::default(),
+            debugger_layout: None,
+            screen_scaling: ScreenScaling::default(),
+            screen_grid: false,
+            screen_filter: ScreenFilter::default(),
+            frame_blending: false,
+            frame_skip: default_frame_skip(),
+            auto_frame_skip: false,
+            speed: default_speed(),
+            recording_format: RecordingFormat::default(),
         }
     }
 }
@@ -298,11 +484,30 @@ pub struct Settings {
     pub palette: PaletteChoice,
     pub rom_directories: Vec<PathBuf>,
     pub use_sgb_colors: bool,
+    pub boot_logo: bool,
     pub cartridge_rw_enabled: bool,
     pub window_width: Option<f32>,
     pub window_height: Option<f32>,
     pub keyboard_bindings: Bindings,
     pub gamepad_bindings: Bindings,
+    pub turbo_buttons: Vec<Action>,
+    pub turbo_frames: u32,
+    pub console_model: ConsoleModel,
+    /// Debugger panes as last arranged; `None` for the default layout.
+    pub debugger_layout: Option<PaneLayout>,
+    pub screen_scaling: ScreenScaling,
+    /// Darken the gaps between pixels, like the DMG's LCD.
+    pub screen_grid: bool,
+    pub screen_filter: ScreenFilter,
+    /// Average each frame with the one before, like the DMG's slow LCD.
+    pub frame_blending: bool,
+    /// Show every `frame_skip`th frame; 1 shows them all.
+    pub frame_skip: u32,
+    /// Also drop frames that fall behind schedule.
+    pub auto_frame_skip: bool,
+    /// Emulation speed, from [`MIN_SPEED`] to [`MAX_SPEED`] times normal.
+    pub speed: f32,
+    pub recording_format: RecordingFormat,
 }
 
 impl Default for Settings {
@@ -315,11 +520,24 @@ impl Default for Settings {
             palette: PaletteChoice::default(),
             rom_directories: Vec::new(),
             use_sgb_colors: true,
+            boot_logo: false,
             cartridge_rw_enabled: true,
             window_width: None,
             window_height: None,
             keyboard_bindings: Bindings::default_keyboard(),
             gamepad_bindings: Bindings::default_gamepad(),
+            turbo_buttons: Vec::new(),
+            turbo_frames: default_turbo_frames(),
+            console_model: ConsoleModel::default(),
+            debugger_layout: None,
+            screen_scaling: ScreenScaling::default(),
+            screen_grid: false,
+            screen_filter: ScreenFilter::default(),
+            frame_blending: false,
+            frame_skip: default_frame_skip(),
+            auto_frame_skip: false,
+            speed: default_speed(),
+            recording_format: RecordingFormat::default(),
         }
     }
 }
@@ -345,11 +563,24 @@ impl Settings {
                 palette: parse_palette(&file.palette),
                 rom_directories: file.rom_directories,
                 use_sgb_colors: file.use_sgb_colors,
+                boot_logo: file.boot_logo,
                 cartridge_rw_enabled: file.cartridge_rw_enabled,
                 window_width: file.window_width,
                 window_height: file.window_height,
                 keyboard_bindings: file.keyboard_bindings,
                 gamepad_bindings: file.gamepad_bindings,
+                turbo_buttons: file.turbo_buttons,
+                turbo_frames: file.turbo_frames,
+                console_model: file.console_model,
+                debugger_layout: file.debugger_layout,
+                screen_scaling: file.screen_scaling,
+                screen_grid: file.screen_grid,
+                screen_filter: file.screen_filter,
+                frame_blending: file.frame_blending,
+                frame_skip: file.frame_skip,
+                auto_frame_skip: file.auto_frame_skip,
+                speed: file.speed.clamp(MIN_SPEED, MAX_SPEED),
+                recording_format: file.recording_format,
             };
         }
 
@@ -368,6 +599,9 @@ impl Settings {
             if keyboard.get(Action::Pause).is_none() {
                 keyboard.set(Action::Pause, "Space".to_string());
             }
+            if keyboard.get(Action::FastForward).is_none() {
+                keyboard.set(Action::FastForward, "Tab".to_string());
+            }
 
             let settings = Self {
                 setup_complete: file.setup_complete,
@@ -377,11 +611,24 @@ impl Settings {
                 palette: parse_palette(&file.palette),
                 rom_directories: file.rom_directories,
                 use_sgb_colors: file.use_sgb_colors,
+                boot_logo: false,
                 cartridge_rw_enabled: true,
                 window_width: file.window_width,
                 window_height: file.window_height,
                 keyboard_bindings: keyboard,
                 gamepad_bindings: gamepad,
+                turbo_buttons: Vec::new(),
+                turbo_frames: default_turbo_frames(),
+                console_model: ConsoleModel::default(),
+                debugger_layout: None,
+                screen_scaling: ScreenScaling::default(),
+                screen_grid: false,
+                screen_filter: ScreenFilter::default(),
+                frame_blending: false,
+                frame_skip: default_frame_skip(),
+                auto_frame_skip: false,
+                speed: default_speed(),
+                recording_format: RecordingFormat::default(),
             };
             // Re-save in new format so migration only happens once
             settings.save();
@@ -414,11 +661,24 @@ impl Settings {
             palette: palette_to_string(self.palette),
             rom_directories: self.rom_directories.clone(),
             use_sgb_colors: self.use_sgb_colors,
+            boot_logo: self.boot_logo,
             cartridge_rw_enabled: self.cartridge_rw_enabled,
             window_width: self.window_width,
             window_height: self.window_height,
             keyboard_bindings: self.keyboard_bindings.clone(),
             gamepad_bindings: self.gamepad_bindings.clone(),
+            turbo_buttons: self.turbo_buttons.clone(),
+            turbo_frames: self.turbo_frames,
+            console_model: self.console_model,
+            debugger_layout: self.debugger_layout.clone(),
+            screen_scaling: self.screen_scaling,
+            screen_grid: self.screen_grid,
+            screen_filter: self.screen_filter,
+            frame_blending: self.frame_blending,
+            frame_skip: self.frame_skip,
+            auto_frame_skip: self.auto_frame_skip,
+            speed: self.speed,
+            recording_format: self.recording_format,
         };
         if let Ok(data) = ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default()) {
             let _ = fs::write(path, data);
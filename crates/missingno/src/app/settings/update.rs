@@ -81,6 +81,47 @@ pub(in crate::app) fn handle(
                 _ => {}
             }
         }
+        super::view::Message::SelectScreenScaling(scaling) => {
+            app.settings.screen_scaling = scaling;
+            app.settings.save();
+            apply_presentation(app);
+        }
+        super::view::Message::SetScreenGrid(enabled) => {
+            app.settings.screen_grid = enabled;
+            app.settings.save();
+            apply_presentation(app);
+        }
+        super::view::Message::SetFrameBlending(enabled) => {
+            app.settings.frame_blending = enabled;
+            app.settings.save();
+            apply_presentation(app);
+        }
+        super::view::Message::SelectRecordingFormat(format) => {
+            app.settings.recording_format = format;
+            app.settings.save();
+        }
+        super::view::Message::SelectScreenFilter(filter) => {
+            app.settings.screen_filter = filter;
+            app.settings.save();
+            apply_presentation(app);
+        }
+        super::view::Message::SelectFrameSkip(every) => {
+            app.settings.frame_skip = every;
+            app.settings.save();
+            apply_frame_skip(app);
+        }
+        super::view::Message::SetAutoFrameSkip(enabled) => {
+            app.settings.auto_frame_skip = enabled;
+            app.settings.save();
+            apply_frame_skip(app);
+        }
+        super::view::Message::SetSpeed(speed) => {
+            app.settings.speed = speed;
+            app.settings.save();
+            if let Game::Loaded(LoadedGame::Emulator(emulator)) = &mut app.game {
+                emulator.set_speed(speed);
+            }
+        }
         super::view::Message::SetUseSgbColors(enabled) => {
             app.settings.use_sgb_colors = enabled;
             app.settings.save();
@@ -88,6 +129,10 @@ pub(in crate::app) fn handle(
                 emu.set_use_sgb_colors(enabled);
             }
         }
+        super::view::Message::SetBootLogo(enabled) => {
+            app.settings.boot_logo = enabled;
+            app.settings.save();
+        }
         super::view::Message::SetCartridgeRwEnabled(enabled) => {
             app.settings.cartridge_rw_enabled = enabled;
             app.settings.save();
@@ -96,6 +141,27 @@ pub(in crate::app) fn handle(
                 app.cartridge_rw_known_ports.clear();
             }
         }
+        super::view::Message::SetTurbo(action, enabled) => {
+            app.settings.turbo_buttons.retain(|&a| a != action);
+            if enabled {
+                app.settings.turbo_buttons.push(action);
+            }
+            app.settings.save();
+            if let Game::Loaded(LoadedGame::Emulator(emu)) = &mut app.game {
+                emu.set_turbo(&app.settings.turbo_buttons, app.settings.turbo_frames);
+            }
+        }
+        super::view::Message::SelectConsoleModel(model) => {
+            app.settings.console_model = model;
+            app.settings.save();
+        }
+        super::view::Message::SetTurboFrames(frames) => {
+            app.settings.turbo_frames = frames;
+            app.settings.save();
+            if let Game::Loaded(LoadedGame::Emulator(emu)) = &mut app.game {
+                emu.set_turbo(&app.settings.turbo_buttons, app.settings.turbo_frames);
+            }
+        }
         super::view::Message::StartListening(target) => {
             if let app::Screen::Settings {
                 ref mut listening_for,
@@ -186,3 +252,27 @@ pub(in crate::app) fn handle(
 
     Task::none()
 }
+
+fn apply_presentation(app: &mut app::App) {
+    let (scaling, grid, filter, frame_blending) = (
+        app.settings.screen_scaling,
+        app.settings.screen_grid,
+        app.settings.screen_filter,
+        app.settings.frame_blending,
+    );
+    match &mut app.game {
+        Game::Loaded(LoadedGame::Emulator(emulator)) => {
+            emulator.set_presentation(scaling, grid, filter, frame_blending)
+        }
+        Game::Loaded(LoadedGame::Debugger(debugger)) => {
+            debugger.set_presentation(scaling, grid, filter, frame_blending)
+        }
+        _ => {}
+    }
+}
+
+fn apply_frame_skip(app: &mut app::App) {
+    if let Game::Loaded(LoadedGame::Emulator(emulator)) = &mut app.game {
+        emulator.set_frame_skip(app.settings.frame_skip, app.settings.auto_frame_skip);
+    }
+}
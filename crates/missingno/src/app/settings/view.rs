@@ -4,12 +4,15 @@ use iced::{
     Alignment::Center,
     Element,
     Length::Fill,
-    widget::{column, container, row, svg, text, toggler},
+    widget::{column, container, row, slider, svg, text, toggler},
 };
 
 use crate::app::{
     self, controls,
-    settings::{Action, Bindings, EMULATOR_ACTIONS, GB_ACTIONS},
+    settings::{
+        Action, Bindings, EMULATOR_ACTIONS, FRAME_SKIPS, GB_ACTIONS, MAX_SPEED, MIN_SPEED,
+        TURBO_ACTIONS, TURBO_FRAMES,
+    },
     ui::{
         buttons, containers, horizontal_rule,
         icons::{self, Icon},
@@ -45,7 +48,16 @@ pub enum Message {
     AddRomDirectory(PathBuf),
     RemoveRomDirectory(usize),
     SelectPalette(missingno_gb::ppu::types::palette::PaletteChoice),
+    SelectScreenScaling(super::ScreenScaling),
+    SetScreenGrid(bool),
+    SetFrameBlending(bool),
+    SelectRecordingFormat(super::RecordingFormat),
+    SelectScreenFilter(super::ScreenFilter),
+    SelectFrameSkip(u32),
+    SetAutoFrameSkip(bool),
+    SetSpeed(f32),
     SetUseSgbColors(bool),
+    SetBootLogo(bool),
     SetHasheousEnabled(bool),
     SetHomebrewHubEnabled(bool),
     SetCartridgeRwEnabled(bool),
@@ -54,6 +66,9 @@ pub enum Message {
     ClearBinding,
     CancelCapture,
     ResetBindings,
+    SetTurbo(Action, bool),
+    SetTurboFrames(u32),
+    SelectConsoleModel(super::ConsoleModel),
     Back,
 }
 
@@ -148,6 +163,8 @@ fn controls_section(
         row![keyboard_col, gamepad_col].spacing(l()),
         horizontal_rule(),
         buttons::standard("Reset to defaults").on_press(Message::ResetBindings.into()),
+        horizontal_rule(),
+        turbo_settings(settings),
     ]
     .spacing(m())
     .max_width(600);
@@ -157,6 +174,40 @@ fn controls_section(
         .into()
 }
 
+fn turbo_settings(settings: &super::Settings) -> Element<'_, app::Message> {
+    let mut buttons_row = row![].spacing(l());
+    for action in TURBO_ACTIONS {
+        buttons_row = buttons_row.push(
+            toggler(settings.turbo_buttons.contains(&action))
+                .label(format!("{action}"))
+                .on_toggle(move |enabled| Message::SetTurbo(action, enabled).into())
+                .size(m()),
+        );
+    }
+
+    let mut rate_row = row![].spacing(s());
+    for frames in TURBO_FRAMES {
+        // Each press-release cycle takes two periods of `frames`.
+        let label = text(format!("{}/s", 60 / (frames * 2)));
+        rate_row = rate_row.push(if settings.turbo_frames == frames {
+            buttons::selected(label)
+        } else {
+            buttons::standard(label).on_press(Message::SetTurboFrames(frames).into())
+        });
+    }
+
+    column![
+        app_text::label("Turbo"),
+        text("Turbo buttons press and release repeatedly while held.").color(MUTED),
+        buttons_row,
+        row![text("Rate").width(120), rate_row]
+            .spacing(s())
+            .align_y(Center),
+    ]
+    .spacing(m())
+    .into()
+}
+
 fn binding_column<'a>(
     title: &'a str,
     bindings: &Bindings,
@@ -238,6 +289,16 @@ fn display_section(settings: &super::Settings) -> Element<'_, app::Message> {
             .size(m()),
         text("When disabled, the default palette is used for all games.").color(MUTED),
         horizontal_rule(),
+        toggler(settings.boot_logo)
+            .label("Play the start-up logo")
+            .on_toggle(|enabled| Message::SetBootLogo(enabled).into())
+            .size(m()),
+        text(
+            "An imitation of the Game Boy start-up screen, not the real boot ROM. \
+             Game Boy games only. Press any button to skip."
+        )
+        .color(MUTED),
+        horizontal_rule(),
         app_text::label("Palette"),
     ]
     .spacing(m());
@@ -270,6 +331,97 @@ fn display_section(settings: &super::Settings) -> Element<'_, app::Message> {
     }
 
     content = content.push(palette_row);
+
+    let mut scaling_row = row![].spacing(s());
+    for scaling in super::ScreenScaling::ALL {
+        let label = text(scaling.to_string());
+        scaling_row = scaling_row.push(if settings.screen_scaling == scaling {
+            buttons::selected(label)
+        } else {
+            buttons::standard(label).on_press(Message::SelectScreenScaling(scaling).into())
+        });
+    }
+    let mut filter_row = row![].spacing(s());
+    for filter in super::ScreenFilter::ALL {
+        let label = text(filter.to_string());
+        filter_row = filter_row.push(if settings.screen_filter == filter {
+            buttons::selected(label)
+        } else {
+            buttons::standard(label).on_press(Message::SelectScreenFilter(filter).into())
+        });
+    }
+    let mut frame_skip_row = row![].spacing(s());
+    for every in FRAME_SKIPS {
+        let label = text(match every {
+            1 => "Off".to_string(),
+            2 => "Every 2nd frame".to_string(),
+            3 => "Every 3rd frame".to_string(),
+            n => format!("Every {n}th frame"),
+        });
+        frame_skip_row = frame_skip_row.push(if settings.frame_skip == every {
+            buttons::selected(label)
+        } else {
+            buttons::standard(label).on_press(Message::SelectFrameSkip(every).into())
+        });
+    }
+    content = content.push(
+        column![
+            horizontal_rule(),
+            app_text::label("Screen size"),
+            scaling_row,
+            text(
+                "Whole pixels scales by the largest whole number that fits, so every pixel is \
+                 the same size and nothing shimmers as the picture scrolls."
+            )
+            .color(MUTED),
+            horizontal_rule(),
+            toggler(settings.screen_grid)
+                .label("Show the pixel grid")
+                .on_toggle(|enabled| Message::SetScreenGrid(enabled).into())
+                .size(m()),
+            text("Darkens the gaps between pixels, like the original Game Boy's screen.")
+                .color(MUTED),
+            horizontal_rule(),
+            toggler(settings.frame_blending)
+                .label("Blend frames")
+                .on_toggle(|enabled| Message::SetFrameBlending(enabled).into())
+                .size(m()),
+            text(
+                "Mixes each frame with the one before, like a real Game Boy's slow screen. \
+                 Some games flicker sprites on and off to make them see-through."
+            )
+            .color(MUTED),
+            horizontal_rule(),
+            app_text::label("Screen filter"),
+            filter_row,
+            text(
+                "Mimics the contrast and tint of a real Game Boy's LCD, on top of the palette. \
+                 Only changes how the screen is drawn; screenshots are unaffected."
+            )
+            .color(MUTED),
+            horizontal_rule(),
+            app_text::label("Frame skip"),
+            frame_skip_row,
+            toggler(settings.auto_frame_skip)
+                .label("Skip frames when falling behind")
+                .on_toggle(|enabled| Message::SetAutoFrameSkip(enabled).into())
+                .size(m()),
+            text(
+                "For slower computers. Games still run at full speed with unbroken sound; \
+                 skipped frames just aren't drawn."
+            )
+            .color(MUTED),
+            horizontal_rule(),
+            app_text::label("Speed"),
+            speed_row(settings.speed),
+            text(
+                "Slow motion for studying animations, fast for grinding. Sound speeds up and \
+                 slows down with the game, so its pitch changes too."
+            )
+            .color(MUTED),
+        ]
+        .spacing(m()),
+    );
     let content = content.max_width(600);
 
     iced::widget::scrollable(container(content).padding(l()).width(Fill))
@@ -277,6 +429,37 @@ fn display_section(settings: &super::Settings) -> Element<'_, app::Message> {
         .into()
 }
 
+/// A slider over the speed's base-2 logarithm, so halving and doubling are
+/// the same distance apart and 1× sits where a player would look for it.
+fn speed_row(speed: f32) -> Element<'static, app::Message> {
+    row![
+        slider(
+            MIN_SPEED.log2()..=MAX_SPEED.log2(),
+            speed.log2(),
+            |position| Message::SetSpeed(position.exp2()).into()
+        )
+        .step(0.25),
+        text(format!("{}×", format_speed(speed))).width(60),
+        if speed == 1.0 {
+            buttons::selected(text("Normal"))
+        } else {
+            buttons::standard(text("Normal")).on_press(Message::SetSpeed(1.0).into())
+        },
+    ]
+    .spacing(s())
+    .align_y(Center)
+    .into()
+}
+
+/// `speed` to two decimal places, without trailing zeros: "0.25", "1.5", "8".
+fn format_speed(speed: f32) -> String {
+    let formatted = format!("{speed:.2}");
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
 fn color_swatch(color: rgb::RGB8) -> Element<'static, app::Message> {
     let c = iced::Color::from_rgb8(color.r, color.g, color.b);
     container(iced::widget::Space::new().width(40).height(40))
@@ -390,6 +573,16 @@ fn general_section(settings: &super::Settings) -> Element<'_, app::Message> {
     directories = directories
         .push(buttons::standard("Add folder...").on_press(Message::PickRomDirectory.into()));
 
+    let mut recording_formats = row![].spacing(s());
+    for format in super::RecordingFormat::ALL {
+        let label = text(format.to_string());
+        recording_formats = recording_formats.push(if settings.recording_format == format {
+            buttons::selected(label)
+        } else {
+            buttons::standard(label).on_press(Message::SelectRecordingFormat(format).into())
+        });
+    }
+
     let content = column![
         about,
         horizontal_rule(),
@@ -398,6 +591,10 @@ fn general_section(settings: &super::Settings) -> Element<'_, app::Message> {
         horizontal_rule(),
         app_text::label("ROM Folders"),
         directories,
+        horizontal_rule(),
+        app_text::label("Audio recordings"),
+        recording_formats,
+        text("16-bit plays anywhere; 32-bit float keeps every detail of the mix.").color(MUTED),
     ]
     .spacing(m())
     .max_width(600);
@@ -411,7 +608,27 @@ fn hardware_section<'a>(
     settings: &'a super::Settings,
     detected_devices: &'a [crate::cartridge_rw::DetectedDevice],
 ) -> Element<'a, app::Message> {
+    let mut model_row = row![].spacing(s());
+    for model in super::ConsoleModel::ALL {
+        let label = text(model.to_string());
+        model_row = model_row.push(if settings.console_model == model {
+            buttons::selected(label)
+        } else {
+            buttons::standard(label).on_press(Message::SelectConsoleModel(model).into())
+        });
+    }
+
     let mut content = column![
+        app_text::label("Console"),
+        model_row,
+        text(
+            "Automatic runs Game Boy Color games on a Game Boy Color and everything else \
+             on a Game Boy. Choose Game Boy Color to play Game Boy games in colour, with \
+             the palette a Game Boy Color picks for each title. Colour-only games can't \
+             run on a Game Boy. Applies the next time a game starts."
+        )
+        .color(MUTED),
+        horizontal_rule(),
         app_text::label("Cartridge Reader/Writer"),
         toggler(settings.cartridge_rw_enabled)
             .label("Enable cartridge reader/writer support")
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
+use crate::app::settings::{ScreenFilter, ScreenScaling};
+
 static NEXT_TEXTURE_ID: AtomicU64 = AtomicU64::new(0);
 
 /// Reusable GPU texture renderer for pixel-based graphics
@@ -11,9 +13,13 @@ pub struct TextureRenderer {
     width: u32,
     height: u32,
     pixels: Arc<[u8]>,
+    scaling: ScreenScaling,
+    grid: bool,
+    filter: ScreenFilter,
 }
 
 impl TextureRenderer {
+    /// Stretched over the widget's bounds, with no grid.
     pub fn with_pixels(width: u32, height: u32, pixels: impl Into<Arc<[u8]>>) -> Self {
         let pixels = pixels.into();
         assert_eq!(pixels.len(), (width * height * 4) as usize);
@@ -22,8 +28,21 @@ impl TextureRenderer {
             width,
             height,
             pixels,
+            scaling: ScreenScaling::Stretch,
+            grid: false,
+            filter: ScreenFilter::Off,
         }
     }
+
+    /// Place the texture within the widget's bounds by `scaling`, darken
+    /// the gaps between texels if `grid` is set, and draw it through
+    /// `filter`.
+    pub fn presented(mut self, scaling: ScreenScaling, grid: bool, filter: ScreenFilter) -> Self {
+        self.scaling = scaling;
+        self.grid = grid;
+        self.filter = filter;
+        self
+    }
 }
 
 impl<Message> shader::Program<Message> for TextureRenderer {
@@ -38,6 +57,9 @@ impl<Message> shader::Program<Message> for TextureRenderer {
     ) -> Self::Primitive {
         TexturePrimitive {
             id: self.id,
+            scaling: self.scaling,
+            grid: self.grid,
+            filter: self.filter,
             state: Mutex::new(PrimitiveState::Pending {
                 width: self.width,
                 height: self.height,
@@ -50,6 +72,9 @@ impl<Message> shader::Program<Message> for TextureRenderer {
 #[derive(Debug)]
 pub struct TexturePrimitive {
     id: u64,
+    scaling: ScreenScaling,
+    grid: bool,
+    filter: ScreenFilter,
     state: Mutex<PrimitiveState>,
 }
 
@@ -122,7 +147,7 @@ impl shader::Primitive for TexturePrimitive {
                 // Use prepare()'s bounds (screen-space) not draw()'s bounds
                 // (content-space), so the texture renders at the correct
                 // position when inside a scrollable.
-                pipeline.update_vertices(queue, self.id, *bounds, viewport);
+                self.update_vertices(pipeline, queue, width, height, *bounds, viewport);
 
                 *state = PrimitiveState::Prepared {
                     width,
@@ -138,7 +163,7 @@ impl shader::Primitive for TexturePrimitive {
                 pipeline.ensure_texture(device, self.id, width, height);
 
                 if &old_bounds != bounds {
-                    pipeline.update_vertices(queue, self.id, *bounds, viewport);
+                    self.update_vertices(pipeline, queue, width, height, *bounds, viewport);
                 }
                 *state = PrimitiveState::Prepared {
                     width,
@@ -185,6 +210,104 @@ impl shader::Primitive for TexturePrimitive {
     }
 }
 
+impl TexturePrimitive {
+    fn update_vertices(
+        &self,
+        pipeline: &TexturePipeline,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        bounds: Rectangle,
+        viewport: &shader::Viewport,
+    ) {
+        let placed = fit(
+            bounds,
+            (width, height),
+            self.scaling,
+            viewport.scale_factor(),
+        );
+        pipeline.update_vertices(queue, self.id, placed, viewport, self.grid, self.filter);
+    }
+}
+
+/// The tone curve and tint a [`ScreenFilter`] puts on every colour
+/// channel: `black + (white - black) * value^gamma`, then times `tint`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FilterCurve {
+    black: f32,
+    white: f32,
+    gamma: f32,
+    tint: [f32; 3],
+}
+
+impl FilterCurve {
+    fn of(filter: ScreenFilter) -> Self {
+        match filter {
+            ScreenFilter::Off => Self {
+                black: 0.0,
+                white: 1.0,
+                gamma: 1.0,
+                tint: [1.0, 1.0, 1.0],
+            },
+            // The reflective STN panel never gets very dark or very light,
+            // and its polariser turns everything yellow-green.
+            ScreenFilter::Dmg => Self {
+                black: 0.12,
+                white: 0.88,
+                gamma: 1.3,
+                tint: [0.84, 1.0, 0.56],
+            },
+            // The Pocket's panel is higher contrast and close to neutral,
+            // with a slight warmth to its whites.
+            ScreenFilter::Pocket => Self {
+                black: 0.05,
+                white: 0.95,
+                gamma: 1.1,
+                tint: [1.0, 1.0, 0.95],
+            },
+        }
+    }
+}
+
+/// Where a `width` × `height` texture goes within `bounds`, centred.
+/// Integer scaling counts physical pixels, so each texel covers the same
+/// whole number of them at any `scale_factor`; if not even one multiple
+/// fits, it falls back to [`ScreenScaling::Fit`].
+fn fit(
+    bounds: Rectangle,
+    (width, height): (u32, u32),
+    scaling: ScreenScaling,
+    scale_factor: f32,
+) -> Rectangle {
+    let (width, height) = (width as f32, height as f32);
+    let (placed_width, placed_height) = match scaling {
+        ScreenScaling::Stretch => return bounds,
+        ScreenScaling::Integer
+            if bounds.width * scale_factor >= width && bounds.height * scale_factor >= height =>
+        {
+            let factor = ((bounds.width * scale_factor / width).floor())
+                .min((bounds.height * scale_factor / height).floor());
+            (
+                width * factor / scale_factor,
+                height * factor / scale_factor,
+            )
+        }
+        ScreenScaling::Fit | ScreenScaling::Integer => {
+            let factor = (bounds.width / width).min(bounds.height / height);
+            (width * factor, height * factor)
+        }
+    };
+    // Whole physical pixels for the offset too, or the texel edges land
+    // between them.
+    let snap = |offset: f32| (offset * scale_factor).floor() / scale_factor;
+    Rectangle {
+        x: bounds.x + snap((bounds.width - placed_width) / 2.0),
+        y: bounds.y + snap((bounds.height - placed_height) / 2.0),
+        width: placed_width,
+        height: placed_height,
+    }
+}
+
 struct TextureData {
     texture: wgpu::Texture,
     bind_group: wgpu::BindGroup,
@@ -258,6 +381,9 @@ impl shader::Pipeline for TexturePipeline {
                     attributes: &wgpu::vertex_attr_array![
                         0 => Float32x2,
                         1 => Float32x2,
+                        2 => Float32,
+                        3 => Float32x3,
+                        4 => Float32x3,
                     ],
                 }],
                 compilation_options: Default::default(),
@@ -358,7 +484,18 @@ impl TexturePipeline {
         id: u64,
         bounds: Rectangle,
         viewport: &shader::Viewport,
+        grid: bool,
+        filter: ScreenFilter,
     ) {
+        let grid = if grid { 1.0 } else { 0.0 };
+        let FilterCurve {
+            black,
+            white,
+            gamma,
+            tint,
+        } = FilterCurve::of(filter);
+        let curve = [black, white, gamma];
+
         // Transform bounds to NDC space based on viewport
         let scale = viewport.scale_factor();
         let viewport_width = viewport.physical_width() as f32 / scale;
@@ -376,26 +513,44 @@ impl TexturePipeline {
             Vertex {
                 position: [left, top],
                 tex_coords: [0.0, 0.0],
+                grid,
+                curve,
+                tint,
             },
             Vertex {
                 position: [right, top],
                 tex_coords: [1.0, 0.0],
+                grid,
+                curve,
+                tint,
             },
             Vertex {
                 position: [left, bottom],
                 tex_coords: [0.0, 1.0],
+                grid,
+                curve,
+                tint,
             },
             Vertex {
                 position: [left, bottom],
                 tex_coords: [0.0, 1.0],
+                grid,
+                curve,
+                tint,
             },
             Vertex {
                 position: [right, top],
                 tex_coords: [1.0, 0.0],
+                grid,
+                curve,
+                tint,
             },
             Vertex {
                 position: [right, bottom],
                 tex_coords: [1.0, 1.0],
+                grid,
+                curve,
+                tint,
             },
         ];
 
@@ -415,17 +570,28 @@ impl TexturePipeline {
 struct Vertex {
     position: [f32; 2],
     tex_coords: [f32; 2],
+    /// 1.0 to draw the pixel grid.
+    grid: f32,
+    /// [`FilterCurve`]'s black, white and gamma.
+    curve: [f32; 3],
+    tint: [f32; 3],
 }
 
 const SHADER_SOURCE: &str = r#"
 struct VertexInput {
     @location(0) position: vec2<f32>,
     @location(1) tex_coords: vec2<f32>,
+    @location(2) grid: f32,
+    @location(3) curve: vec3<f32>,
+    @location(4) tint: vec3<f32>,
 }
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) tex_coords: vec2<f32>,
+    @location(1) grid: f32,
+    @location(2) curve: vec3<f32>,
+    @location(3) tint: vec3<f32>,
 }
 
 @vertex
@@ -433,6 +599,9 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     var output: VertexOutput;
     output.position = vec4<f32>(input.position, 0.0, 1.0);
     output.tex_coords = input.tex_coords;
+    output.grid = input.grid;
+    output.curve = input.curve;
+    output.tint = input.tint;
     return output;
 }
 
@@ -453,7 +622,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     let texel_floor = floor(texel);
     let frac = texel - texel_floor;
 
-    // Screen pixels per texel in each axis
+    // Texels per screen pixel in each axis
     let scale = tex_size * fwidth(input.tex_coords);
 
     // Remap the fractional part: hold at 0 for most of the texel,
@@ -461,6 +630,125 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     let sharp = clamp((frac - (vec2(1.0) - scale)) / scale, vec2(0.0), vec2(1.0));
 
     let snapped = (texel_floor + vec2(0.5) + sharp) / tex_size;
-    return textureSample(texture, texture_sampler, snapped);
+    let sampled = textureSample(texture, texture_sampler, snapped);
+
+    // Screen filter: lift the blacks, dim the whites, bend the curve
+    // between them and tint. The identity when the filter is off.
+    let curved = pow(sampled.rgb, vec3(input.curve.z));
+    let color = vec4(mix(vec3(input.curve.x), vec3(input.curve.y), curved) * input.tint, sampled.a);
+
+    // Pixel grid: darken the screen pixel on each side of a texel edge,
+    // fading out below 3x where the lines would swamp the picture.
+    let cell = fract(input.tex_coords * tex_size);
+    let to_edge = min(cell, vec2(1.0) - cell) / scale;
+    let on_line = 1.0 - clamp(min(to_edge.x, to_edge.y), 0.0, 1.0);
+    let magnification = 1.0 / max(scale.x, scale.y);
+    let strength = 0.25 * input.grid * clamp(magnification - 2.0, 0.0, 1.0);
+    return vec4(color.rgb * (1.0 - strength * on_line), color.a);
 }
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: (u32, u32) = (160, 144);
+
+    fn bounds(width: f32, height: f32) -> Rectangle {
+        Rectangle {
+            x: 10.0,
+            y: 20.0,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn integer_scaling_picks_the_largest_factor_that_fits() {
+        // 3x (480 x 432) fits; 4x would be 576 tall.
+        let placed = fit(bounds(800.0, 500.0), SCREEN, ScreenScaling::Integer, 1.0);
+        assert_eq!((placed.width, placed.height), (480.0, 432.0));
+    }
+
+    #[test]
+    fn integer_scaling_centres_the_image() {
+        let placed = fit(bounds(800.0, 500.0), SCREEN, ScreenScaling::Integer, 1.0);
+        assert_eq!((placed.x, placed.y), (10.0 + 160.0, 20.0 + 34.0));
+    }
+
+    #[test]
+    fn integer_scaling_counts_physical_pixels() {
+        // 400 x 300 logical is 600 x 450 physical at 1.5x: 3x fits, so each
+        // texel is 3 physical pixels, 2 logical.
+        let placed = fit(bounds(400.0, 300.0), SCREEN, ScreenScaling::Integer, 1.5);
+        assert_eq!((placed.width, placed.height), (320.0, 288.0));
+        // The offset lands on a whole physical pixel.
+        assert_eq!(((placed.x - 10.0) * 1.5).fract(), 0.0);
+        assert_eq!(((placed.y - 20.0) * 1.5).fract(), 0.0);
+    }
+
+    #[test]
+    fn integer_scaling_too_small_for_1x_fits_instead() {
+        let placed = fit(bounds(80.0, 100.0), SCREEN, ScreenScaling::Integer, 1.0);
+        assert_eq!(
+            placed,
+            fit(bounds(80.0, 100.0), SCREEN, ScreenScaling::Fit, 1.0)
+        );
+        assert_eq!((placed.width, placed.height), (80.0, 72.0));
+    }
+
+    #[test]
+    fn fit_keeps_the_aspect_ratio() {
+        let placed = fit(bounds(500.0, 288.0), SCREEN, ScreenScaling::Fit, 1.0);
+        assert_eq!((placed.width, placed.height), (320.0, 288.0));
+        assert_eq!((placed.x, placed.y), (10.0 + 90.0, 20.0));
+    }
+
+    #[test]
+    fn stretch_fills_the_bounds() {
+        let area = bounds(500.0, 288.0);
+        assert_eq!(fit(area, SCREEN, ScreenScaling::Stretch, 1.0), area);
+    }
+
+    /// What the shader does to a colour.
+    fn filtered(filter: ScreenFilter, rgb: [f32; 3]) -> [f32; 3] {
+        let curve = FilterCurve::of(filter);
+        std::array::from_fn(|i| {
+            let value = curve.black + (curve.white - curve.black) * rgb[i].powf(curve.gamma);
+            value * curve.tint[i]
+        })
+    }
+
+    fn luma([r, g, b]: [f32; 3]) -> f32 {
+        0.299 * r + 0.587 * g + 0.114 * b
+    }
+
+    fn contrast(filter: ScreenFilter) -> f32 {
+        luma(filtered(filter, [1.0; 3])) - luma(filtered(filter, [0.0; 3]))
+    }
+
+    #[test]
+    fn no_filter_leaves_colours_alone() {
+        for rgb in [[0.0; 3], [1.0; 3], [0.2, 0.5, 0.8]] {
+            assert_eq!(filtered(ScreenFilter::Off, rgb), rgb);
+        }
+    }
+
+    #[test]
+    fn dmg_filter_is_low_contrast_and_green() {
+        assert!(contrast(ScreenFilter::Dmg) < contrast(ScreenFilter::Pocket));
+        let [r, g, b] = filtered(ScreenFilter::Dmg, [1.0; 3]);
+        assert!(g > r && r > b, "white comes out yellow-green: {r} {g} {b}");
+    }
+
+    #[test]
+    fn pocket_filter_is_crisper_but_not_full_range() {
+        assert!(contrast(ScreenFilter::Pocket) < contrast(ScreenFilter::Off));
+        assert!(contrast(ScreenFilter::Pocket) > 0.8);
+        let [r, g, b] = filtered(ScreenFilter::Pocket, [0.5; 3]);
+        assert!(
+            r.max(g).max(b) - r.min(g).min(b) < 0.05,
+            "near grey: {r} {g} {b}"
+        );
+    }
+}
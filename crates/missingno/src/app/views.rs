@@ -4,7 +4,7 @@ use iced::{
     Length::Fill,
     Padding, Subscription, Task, event, mouse, time,
     widget::{
-        Stack, center, column, container, mouse_area, opaque, row, scrollable, svg,
+        Column, Stack, center, column, container, mouse_area, opaque, row, scrollable, svg,
         text as iced_text,
     },
     window,
@@ -20,7 +20,8 @@ use super::ui::{
 };
 use super::{
     App, CartridgeMessage, DetailMessage, DetailSubScreen, FlashState, Fullscreen, Game,
-    LoadedGame, Message, PendingAction, Screen, controls, debugger, library, load, settings,
+    LoadedGame, Message, PendingAction, Screen, Toast, controls, debugger, library, load, recent,
+    rom_file, settings,
 };
 use crate::cartridge_rw;
 
@@ -91,8 +92,10 @@ impl App {
         };
 
         // 2. Shell overlays — applied once regardless of screen
+        let content = self.apply_load_error(content);
         let content = self.apply_toast(content);
         let content = self.apply_menu(content);
+        let content = self.apply_archive_choice(content);
         self.apply_confirmation_dialog(content)
     }
 
@@ -129,8 +132,8 @@ impl App {
     }
 
     fn apply_toast<'a>(&self, content: Element<'a, Message>) -> Element<'a, Message> {
-        if self.screenshot_toast.is_some() {
-            Stack::with_children(vec![content, screenshot_toast()]).into()
+        if let Some(toast) = &self.toast {
+            Stack::with_children(vec![content, toast_view(toast)]).into()
         } else {
             content
         }
@@ -152,6 +155,13 @@ impl App {
                     "Open ROM file...",
                     load::Message::Pick.into(),
                 ));
+                if self.recent_games.iter().next().is_some() {
+                    items = items.push(menu_divider());
+                    items = items.push(text::detail("Recent").color(MUTED));
+                    for game in self.recent_games.iter() {
+                        items = items.push(recent_menu_item(game));
+                    }
+                }
                 has_items = true;
             }
             Screen::ViewingGame {
@@ -202,6 +212,16 @@ impl App {
                         "Step Frame",
                         debugger::Message::StepFrame.into(),
                     ));
+                    items = items.push(menu_item(
+                        Icon::FileText,
+                        "Reload ROM",
+                        load::Message::Reload.into(),
+                    ));
+                    items = items.push(menu_item(
+                        Icon::Grid,
+                        "Reset Layout",
+                        debugger::panes::Message::ResetLayout.into(),
+                    ));
                 }
                 items = items.push(menu_item_danger(Icon::Close, "Reset", Message::Reset));
                 items = items.push(menu_divider());
@@ -313,6 +333,50 @@ impl App {
             .into()
     }
 
+    fn apply_archive_choice<'a>(&'a self, content: Element<'a, Message>) -> Element<'a, Message> {
+        let Some(choice) = &self.archive_choice else {
+            return content;
+        };
+
+        let roms = choice.roms.iter().map(|name| {
+            buttons::subtle_raw(iced_text(name.as_str()).width(Fill))
+                .width(Fill)
+                .on_press(load::Message::LoadPath(choice.archive.join(name)).into())
+                .into()
+        });
+
+        Stack::new()
+            .push(content)
+            .push(opaque(
+                mouse_area(
+                    center(
+                        container(
+                            column![
+                                iced_text(format!(
+                                    "{} holds several games. Which one?",
+                                    choice.archive.file_name().unwrap_or_default().display()
+                                )),
+                                scrollable(Column::with_children(roms).spacing(s())),
+                                buttons::standard("Cancel")
+                                    .on_press(load::Message::CancelArchiveChoice.into()),
+                            ]
+                            .spacing(l())
+                            .align_x(Center),
+                        )
+                        .max_width(480)
+                        .padding(l())
+                        .style(containers::menu),
+                    )
+                    .style(|_| container::Style {
+                        background: Some(iced::Color::from_rgba(0.0, 0.0, 0.0, 0.5).into()),
+                        ..Default::default()
+                    }),
+                )
+                .on_press(load::Message::CancelArchiveChoice.into()),
+            ))
+            .into()
+    }
+
     fn detail_view(&self) -> Element<'_, Message> {
         let (viewing_sha1, hovered_log_entry, header_hovered) = match &self.screen {
             Screen::ViewingGame {
@@ -759,6 +823,35 @@ impl App {
         self.library_view()
     }
 
+    fn apply_load_error<'a>(&self, content: Element<'a, Message>) -> Element<'a, Message> {
+        let Some(error) = self.load_error.clone() else {
+            return content;
+        };
+
+        column![
+            content,
+            mouse_area(
+                container(
+                    row![
+                        icons::m_colored(Icon::Warning, iced::Color::WHITE),
+                        iced_text(error).color(iced::Color::WHITE),
+                    ]
+                    .spacing(s())
+                    .align_y(Center),
+                )
+                .padding(m())
+                .width(Fill)
+                .style(|_: &iced::Theme| container::Style {
+                    background: Some(iced::Color::from_rgb(0.5, 0.15, 0.15).into()),
+                    ..Default::default()
+                }),
+            )
+            .on_press(Message::DismissLoadError)
+            .interaction(mouse::Interaction::Pointer),
+        ]
+        .into()
+    }
+
     fn missing_rom_dirs_bar(&self) -> Option<Element<'static, Message>> {
         let count = self
             .settings
@@ -889,9 +982,8 @@ impl App {
                 Game::Loaded(LoadedGame::Emulator(emulator)) => emulator.subscription(),
                 _ => Subscription::none(),
             },
-            if self.screenshot_toast.is_some() {
-                time::every(std::time::Duration::from_millis(1500))
-                    .map(|_| Message::DismissScreenshotToast)
+            if self.toast.is_some() {
+                time::every(std::time::Duration::from_millis(1500)).map(|_| Message::DismissToast)
             } else {
                 Subscription::none()
             },
@@ -937,6 +1029,25 @@ fn menu_item_danger<'a>(icon: Icon, label: &'a str, message: Message) -> Element
         .into()
 }
 
+/// A recent ROM with when it was last played. Greyed out and inert when the
+/// file has gone missing.
+fn recent_menu_item(game: &recent::RecentGame) -> Element<'static, Message> {
+    let mut label = column![iced_text(game.title().to_owned())].spacing(2);
+    if let Some(last_played) = game.last_played() {
+        label =
+            label.push(text::detail(format!("Played {}", friendly_ago(last_played))).color(MUTED));
+    }
+    let item = buttons::subtle_raw(label.padding([s() / 2.0, s()])).width(Fill);
+    if rom_file::exists(game.rom_path()) {
+        item.on_press(Message::MenuAction(Box::new(
+            load::Message::LoadPath(game.rom_path().to_path_buf()).into(),
+        )))
+        .into()
+    } else {
+        item.into()
+    }
+}
+
 /// Standard screen header: back button + title + horizontal rule.
 fn screen_header<'a>(title: &'a str, back_message: Message) -> Element<'a, Message> {
     column![
@@ -964,14 +1075,14 @@ fn screen_header_no_back<'a>(title: &'a str) -> Element<'a, Message> {
     .into()
 }
 
-fn screenshot_toast<'a>() -> Element<'a, Message> {
+fn toast_view<'a>(toast: &Toast) -> Element<'a, Message> {
     container(
         container(
             row![
-                icons::m(Icon::Camera).style(|_, _| svg::Style {
+                icons::m(toast.icon).style(|_, _| svg::Style {
                     color: Some(iced::Color::WHITE),
                 }),
-                iced_text("Screenshot saved").color(iced::Color::WHITE),
+                iced_text(toast.text.clone()).color(iced::Color::WHITE),
             ]
             .spacing(s())
             .align_y(Center),
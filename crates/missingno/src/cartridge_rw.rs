@@ -43,29 +43,6 @@ const OFW_DONE_LED_ON: u8 = 0x3D;
 const HEADER_SIZE: usize = 0x180;
 const CHUNK_SIZE: u16 = 64;
 
-/// ROM size table: index (byte 0x148) → size in bytes.
-const ROM_SIZES: &[(u8, u32)] = &[
-    (0x00, 32 * 1024),
-    (0x01, 64 * 1024),
-    (0x02, 128 * 1024),
-    (0x03, 256 * 1024),
-    (0x04, 512 * 1024),
-    (0x05, 1024 * 1024),
-    (0x06, 2 * 1024 * 1024),
-    (0x07, 4 * 1024 * 1024),
-    (0x08, 8 * 1024 * 1024),
-];
-
-/// RAM size table: index (byte 0x149) → size in bytes.
-const RAM_SIZES: &[(u8, u32)] = &[
-    (0x00, 0),
-    (0x01, 2 * 1024),
-    (0x02, 8 * 1024),
-    (0x03, 32 * 1024),
-    (0x04, 128 * 1024),
-    (0x05, 64 * 1024),
-];
-
 /// Known Nintendo logo bytes at 0x104-0x133.
 const NINTENDO_LOGO: [u8; 48] = [
     0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
@@ -376,46 +353,22 @@ fn parse_cartridge_header(header: &[u8]) -> Option<CartridgeHeader> {
         return None;
     }
 
-    let (title, sgb_flag, has_battery) = missingno_gb::cartridge::parse_header(header);
-    let mapper_byte = header[0x147];
-    let rom_size_index = header[0x148];
-    let ram_size_index = header[0x149];
-
-    // Validate header checksum
-    let mut checksum: u8 = 0;
-    for &byte in &header[0x134..0x14D] {
-        checksum = checksum.wrapping_sub(byte).wrapping_sub(1);
-    }
-    let header_checksum_valid = checksum == header[0x14D];
-
-    let rom_size = ROM_SIZES
-        .iter()
-        .find(|(i, _)| *i == rom_size_index)
-        .map(|(_, s)| *s)
-        .unwrap_or(0);
-
-    let ram_size = if mapper_byte == 0x05 || mapper_byte == 0x06 {
-        // MBC2: fixed 512 bytes
-        512
-    } else {
-        RAM_SIZES
-            .iter()
-            .find(|(i, _)| *i == ram_size_index)
-            .map(|(_, s)| *s)
-            .unwrap_or(0)
-    };
+    let parsed = missingno_gb::cartridge::parse_header(header);
+    let mapper_byte = parsed.cartridge_type;
+    let rom_size = parsed.rom_size.unwrap_or(0) as u32;
+    let ram_size = parsed.ram_size as u32;
 
     let mapper_name = mapper_name(mapper_byte);
 
     Some(CartridgeHeader {
-        title,
+        title: parsed.title,
         mapper_byte,
         mapper_name,
         rom_size,
         ram_size,
-        has_battery,
-        sgb_flag,
-        header_checksum_valid,
+        has_battery: parsed.has_battery,
+        sgb_flag: parsed.sgb,
+        header_checksum_valid: parsed.header_checksum_valid,
         flash: None, // Set by detect_flash() after header read
     })
 }
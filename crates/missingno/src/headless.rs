@@ -10,7 +10,7 @@ use missingno_gb::interrupts;
 use missingno_gb::ppu;
 use missingno_gb::ppu::memory::Vram;
 use missingno_gb::ppu::rendering::Mode;
-use missingno_gb::ppu::types::palette::Palette;
+use missingno_gb::ppu::types::palette::{Palette, PaletteChoice};
 use missingno_gb::ppu::types::sprites::{Attributes, SpriteId};
 use missingno_gb::{BootRom, ClockPhase, Console, Dmg, GameBoy, Model};
 use missingno_gbc::{Cgb, GameBoyColor};
@@ -37,10 +37,13 @@ pub fn run(
     let save_path = rom_path.with_extension("sav");
     let save_data = std::fs::read(&save_path).ok();
 
-    let cartridge = Cartridge::new(rom_data, save_data);
+    let cartridge = Cartridge::new(rom_data, save_data).unwrap_or_else(|e| {
+        eprintln!("error: can't load {}: {e}", rom_path.display());
+        process::exit(1);
+    });
     let title = cartridge.title().to_string();
 
-    if cartridge.is_cgb() {
+    if cartridge.supports_cgb() {
         let mut console = GameBoyColor::new(cartridge, boot_rom);
         if let Some(link) = link {
             console.set_link(link);
@@ -71,7 +74,7 @@ fn serve<M: HeadlessUi>(title: &str, mut debugger: Debugger<M>) {
 
 /// The model-specific views the HTTP endpoints expose: the screen's pixel
 /// format, colour sources for the tile-map render, and CGB palette RAM.
-trait HeadlessUi: Model {
+pub(crate) trait HeadlessUi: Model {
     /// What `screen_values` pixels hold, reported in the /screen JSON.
     const PIXEL_FORMAT: &'static str;
     /// Raw per-pixel values: 2-bit shades on DMG, RGB555 on CGB.
@@ -88,23 +91,15 @@ impl HeadlessUi for Dmg {
     const PIXEL_FORMAT: &'static str = "shade2";
 
     fn screen_values(console: &Console<Self>) -> Vec<Vec<u16>> {
-        let screen = console.screen();
-        (0..144u8)
-            .map(|y| (0..160u8).map(|x| screen.pixel(x, y).0 as u16).collect())
+        console
+            .screen()
+            .rows()
+            .map(|row| row.iter().map(|pixel| pixel.0 as u16).collect())
             .collect()
     }
 
     fn screen_rgb(console: &Console<Self>) -> Vec<u8> {
-        let screen = console.screen();
-        let greys: [u8; 4] = [0xFF, 0xAA, 0x55, 0x00];
-        let mut pixels = Vec::with_capacity(160 * 144 * 3);
-        for y in 0..144u8 {
-            for x in 0..160u8 {
-                let shade = greys[screen.pixel(x, y).0 as usize];
-                pixels.extend_from_slice(&[shade, shade, shade]);
-            }
-        }
-        pixels
+        console.screen().to_rgb8(&PaletteChoice::Classic)
     }
 
     fn tilemap_rgb(console: &Console<Self>, map_id: ppu::types::tiles::TileMapId) -> Vec<u8> {
@@ -323,6 +318,10 @@ fn handle_request<M: HeadlessUi>(mut request: tiny_http::Request, debugger: &mut
             debugger.step_over();
             respond_json(request, cpu_state(debugger.game_boy()));
         }
+        (&Method::Post, "/step-out") => {
+            debugger.step_out();
+            respond_json(request, cpu_state(debugger.game_boy()));
+        }
         (&Method::Post, "/reset") => {
             debugger.reset();
             respond_json(request, cpu_state(debugger.game_boy()));
@@ -480,6 +479,32 @@ fn handle_request<M: HeadlessUi>(mut request: tiny_http::Request, debugger: &mut
                 Err(_) => respond_error(request, 400, "invalid hex address"),
             }
         }
+        _ if path.starts_with("/watchpoints/bus-access/") => {
+            let addr_str = &path["/watchpoints/bus-access/".len()..];
+            match u16::from_str_radix(addr_str, 16) {
+                Ok(addr) => {
+                    let condition = WatchCondition::BusAccess { address: addr };
+                    match &method {
+                        &Method::Put => {
+                            debugger.add_watchpoint(condition.clone());
+                            respond_json(
+                                request,
+                                serde_json::json!({ "added": watchpoint_json(&condition) }),
+                            );
+                        }
+                        &Method::Delete => {
+                            debugger.remove_watchpoint(&condition);
+                            respond_json(
+                                request,
+                                serde_json::json!({ "removed": watchpoint_json(&condition) }),
+                            );
+                        }
+                        _ => respond_error(request, 405, "method not allowed"),
+                    }
+                }
+                Err(_) => respond_error(request, 400, "invalid hex address"),
+            }
+        }
         _ if path.starts_with("/watchpoints/dma-read/") => {
             let addr_str = &path["/watchpoints/dma-read/".len()..];
             match u16::from_str_radix(addr_str, 16) {
@@ -1168,6 +1193,10 @@ fn watchpoint_json(condition: &WatchCondition) -> serde_json::Value {
             "type": "bus_write",
             "address": format!("{address:04x}"),
         }),
+        WatchCondition::BusAccess { address } => serde_json::json!({
+            "type": "bus_access",
+            "address": format!("{address:04x}"),
+        }),
         WatchCondition::DmaRead { address } => serde_json::json!({
             "type": "dma_read",
             "address": format!("{address:04x}"),
@@ -1227,6 +1256,10 @@ fn parse_watchpoint_json(json: &serde_json::Value) -> Result<WatchCondition, Str
             let addr = parse_hex_field(json, "address")?;
             Ok(WatchCondition::BusWrite { address: addr })
         }
+        "bus_access" => {
+            let addr = parse_hex_field(json, "address")?;
+            Ok(WatchCondition::BusAccess { address: addr })
+        }
         "dma_read" => {
             let addr = parse_hex_field(json, "address")?;
             Ok(WatchCondition::DmaRead { address: addr })
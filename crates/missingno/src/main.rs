@@ -8,6 +8,7 @@ mod cartridge_rw;
 mod headless;
 mod link_cable;
 mod render;
+mod script;
 mod trace;
 
 #[derive(Parser)]
@@ -34,6 +35,10 @@ struct Args {
     /// Link cable: connect to a server at host:port (BGB link protocol).
     #[arg(long, value_name = "HOST:PORT", conflicts_with = "link_listen")]
     link_connect: Option<String>,
+
+    /// Link cable: plug the cable into itself, so every byte sent comes back.
+    #[arg(long, conflicts_with_all = ["link_listen", "link_connect"])]
+    link_loopback: bool,
 }
 
 #[derive(Subcommand)]
@@ -55,6 +60,16 @@ enum Command {
         #[arg(short, long, default_value = "70224")]
         cycles: u64,
 
+        /// Path to a boot ROM (DMG: 256 bytes, CGB: 2304 bytes).
+        #[arg(long)]
+        boot_rom: Option<PathBuf>,
+    },
+    /// Read debugger commands from stdin, one per line, and answer each on
+    /// stdout. The protocol is described in script.rs.
+    Script {
+        /// ROM to load before the first command.
+        rom: Option<PathBuf>,
+
         /// Path to a boot ROM (DMG: 256 bytes, CGB: 2304 bytes).
         #[arg(long)]
         boot_rom: Option<PathBuf>,
@@ -93,13 +108,18 @@ fn main() -> iced::Result {
             } => {
                 trace::run(rom, profile, output, cycles, load_boot_rom(boot_rom));
             }
+            Command::Script { rom, boot_rom } => script::run(rom, load_boot_rom(boot_rom)),
         }
         return Ok(());
     }
 
     let boot_rom = load_boot_rom(args.boot_rom);
 
-    let link = create_link(args.link_listen, args.link_connect);
+    let link = if args.link_loopback {
+        Some(Box::new(missingno_gb::serial_transfer::Loopback::new()) as Box<_>)
+    } else {
+        create_link(args.link_listen, args.link_connect)
+    };
 
     if args.headless {
         headless::run(args.rom_file, boot_rom, link);
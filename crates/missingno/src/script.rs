@@ -0,0 +1,490 @@
+//! A line-based command interface on stdin, for scripted tests and external
+//! tools: `missingno script [ROM] < commands.txt`.
+//!
+//! Each line is a command and its arguments, separated by spaces.
+//! Addresses and byte values are hex, with or without a `$` or `0x`
+//! prefix; counts are decimal. Blank lines and lines starting with `#` are
+//! skipped without a reply. Every other line gets zero or more lines of
+//! output followed by `ok`, or a single `error <message>` line.
+//!
+//! ```text
+//! load <path>               load a ROM (and its .sav, if there is one)
+//! reset                     restart the loaded ROM, keeping breakpoints
+//! step [n]                  run n instructions (default 1)
+//! run [frames]              run until a breakpoint or LD B,B, or for at most
+//!                           `frames` frames (default 3600); prints
+//!                           `break <pc>` or `frames <n>`
+//! break <addr> [condition]  set a breakpoint, stopping only when a
+//!                           condition such as `A == $90` holds if given
+//! unbreak <addr>            clear a breakpoint
+//! rewind                    go back to the last snapshot, taken every
+//!                           second of emulated time by `run`
+//! reg                       print the CPU registers
+//! read <addr> [len]         print len bytes (default 1, at most 65536),
+//!                           16 to a line
+//! write <addr> <byte>...    store bytes into VRAM, OAM, WRAM or HRAM
+//! screenshot <path>         save the screen as a PNG
+//! savestate <path>          write the console's state to a file
+//! loadstate <path>          go back to a state saved from the same game
+//! quit                      stop reading commands
+//! ```
+
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use missingno_gb::cartridge::Cartridge;
+use missingno_gb::cpu::HaltState;
+use missingno_gb::debugger::{Debugger, expression::BreakCondition};
+use missingno_gb::save_state::SaveState;
+use missingno_gb::{BootRom, Dmg, GameBoy};
+use missingno_gbc::{Cgb, GameBoyColor};
+
+use crate::headless::HeadlessUi;
+
+/// Frames `run` gives up after when nothing stops it: a minute of play.
+const DEFAULT_RUN_FRAMES: u32 = 3600;
+
+pub fn run(rom_path: Option<PathBuf>, boot_rom: Option<BootRom>) {
+    let mut session = Session::new(boot_rom);
+    if let Some(path) = rom_path
+        && let Err(message) = session.load(&path)
+    {
+        eprintln!("error: {message}");
+        process::exit(1);
+    }
+    let stdin = std::io::stdin().lock();
+    let stdout = std::io::stdout().lock();
+    if let Err(e) = session.serve(stdin, stdout) {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
+
+enum Loaded {
+    Dmg(Box<Debugger<Dmg>>),
+    Cgb(Box<Debugger<Cgb>>),
+}
+
+pub struct Session {
+    boot_rom: Option<BootRom>,
+    loaded: Option<Loaded>,
+}
+
+impl Session {
+    pub fn new(boot_rom: Option<BootRom>) -> Self {
+        Self {
+            boot_rom,
+            loaded: None,
+        }
+    }
+
+    /// Answer each line of `input` on `output` until `quit` or the end of
+    /// the input.
+    pub fn serve(&mut self, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == "quit" {
+                writeln!(output, "ok")?;
+                break;
+            }
+            match self.execute(line) {
+                Ok(lines) => {
+                    for line in lines {
+                        writeln!(output, "{line}")?;
+                    }
+                    writeln!(output, "ok")?;
+                }
+                Err(message) => writeln!(output, "error {message}")?,
+            }
+            output.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Run one command, returning its output lines.
+    pub fn execute(&mut self, line: &str) -> Result<Vec<String>, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+
+        match command {
+            "load" => {
+                let [path] = args[..] else {
+                    return Err("wrong arguments for load".to_string());
+                };
+                self.load(Path::new(path))?;
+                Ok(Vec::new())
+            }
+            _ => match self.loaded()? {
+                Loaded::Dmg(debugger) => console_command(debugger.as_mut(), command, &args),
+                Loaded::Cgb(debugger) => console_command(debugger.as_mut(), command, &args),
+            },
+        }
+    }
+
+    fn load(&mut self, path: &Path) -> Result<(), String> {
+        let rom = std::fs::read(path).map_err(|e| format!("can't read {}: {e}", path.display()))?;
+        let save = std::fs::read(path.with_extension("sav")).ok();
+        let cartridge =
+            Cartridge::new(rom, save).map_err(|e| format!("can't load {}: {e}", path.display()))?;
+        let boot_rom = self.boot_rom.clone();
+        self.loaded = Some(if cartridge.supports_cgb() {
+            Loaded::Cgb(Box::new(Debugger::new(GameBoyColor::new(
+                cartridge, boot_rom,
+            ))))
+        } else {
+            Loaded::Dmg(Box::new(Debugger::new(GameBoy::new(cartridge, boot_rom))))
+        });
+        Ok(())
+    }
+
+    fn loaded(&mut self) -> Result<&mut Loaded, String> {
+        self.loaded
+            .as_mut()
+            .ok_or_else(|| "no ROM loaded".to_string())
+    }
+}
+
+/// The commands that only need the running console.
+fn console_command<M: HeadlessUi>(
+    debugger: &mut Debugger<M>,
+    command: &str,
+    args: &[&str],
+) -> Result<Vec<String>, String> {
+    match (command, args) {
+        ("reset", []) => debugger.reset(),
+        ("step", [] | [_]) => {
+            let count = args.first().map_or(Ok(1), |count| decimal(count))?;
+            for _ in 0..count {
+                debugger.step();
+            }
+        }
+        ("run", [] | [_]) => {
+            let limit = args
+                .first()
+                .map_or(Ok(DEFAULT_RUN_FRAMES), |n| decimal(n))?;
+            for _ in 0..limit {
+                if debugger.step_frame().is_none() {
+                    let pc = debugger.game_boy().cpu().ir_address;
+                    return Ok(vec![format!("break {pc:04X}")]);
+                }
+            }
+            return Ok(vec![format!("frames {limit}")]);
+        }
+        ("break", [address]) => debugger.set_breakpoint(hex(address)?),
+        ("break", [address, condition @ ..]) => {
+            let address = hex(address)?;
+            let condition =
+                BreakCondition::parse(&condition.join(" ")).map_err(|e| e.to_string())?;
+            debugger.set_conditional_breakpoint(address, condition);
+        }
+        ("unbreak", [address]) => debugger.clear_breakpoint(hex(address)?),
+        ("rewind", []) => {
+            if !debugger.rewind() {
+                return Err("nothing to rewind to".to_string());
+            }
+        }
+        ("reg", []) => return Ok(vec![registers(debugger)]),
+        ("read", [address] | [address, _]) => {
+            let start = hex(address)?;
+            let len = args.get(1).map_or(Ok(1), |len| decimal(len))?;
+            if len > 0x10000 {
+                return Err(format!("can't read {len} bytes, only 65536"));
+            }
+            let bytes: Vec<u8> = (0..len)
+                .map(|offset| debugger.game_boy().peek(start.wrapping_add(offset as u16)))
+                .collect();
+            return Ok(bytes
+                .chunks(16)
+                .enumerate()
+                .map(|(row, bytes)| {
+                    let address = start.wrapping_add(row as u16 * 16);
+                    let bytes: Vec<String> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+                    format!("{address:04X} {}", bytes.join(" "))
+                })
+                .collect());
+        }
+        ("write", [address, values @ ..]) if !values.is_empty() => {
+            let start = hex(address)?;
+            for (offset, value) in values.iter().enumerate() {
+                let address = start.wrapping_add(offset as u16);
+                let value =
+                    u8::try_from(hex(value)?).map_err(|_| format!("{value} isn't a byte"))?;
+                if !debugger.game_boy_mut().poke_raw(address, value) {
+                    return Err(format!(
+                        "can't write {address:04X}: only VRAM, OAM, WRAM and HRAM"
+                    ));
+                }
+            }
+        }
+        ("screenshot", [path]) => {
+            let rgb = M::screen_rgb(debugger.game_boy());
+            image::RgbImage::from_raw(160, 144, rgb)
+                .expect("the screen is 160×144")
+                .save(path)
+                .map_err(|e| format!("can't save {path}: {e}"))?;
+        }
+        ("savestate", [path]) => SaveState::capture(debugger.game_boy())
+            .save_to_file(Path::new(path))
+            .map_err(|e| format!("can't save {path}: {e}"))?,
+        ("loadstate", [path]) => {
+            let console = SaveState::load_from_file(Path::new(path))
+                .and_then(|state| state.into_console(debugger.game_boy().cartridge()))
+                .map_err(|e| format!("can't load {path}: {e}"))?;
+            debugger.game_boy_mut().restore(console);
+        }
+        (
+            "reset" | "step" | "run" | "break" | "unbreak" | "rewind" | "reg" | "read" | "write"
+            | "screenshot" | "savestate" | "loadstate",
+            _,
+        ) => return Err(format!("wrong arguments for {command}")),
+        _ => return Err(format!("unknown command {command}")),
+    }
+    Ok(Vec::new())
+}
+
+fn registers<M: HeadlessUi>(debugger: &Debugger<M>) -> String {
+    let cpu = debugger.game_boy().cpu();
+    format!(
+        "AF={:02X}{:02X} BC={:02X}{:02X} DE={:02X}{:02X} HL={:02X}{:02X} SP={:04X} PC={:04X} \
+         IME={} HALT={}",
+        cpu.a,
+        cpu.flags.bits(),
+        cpu.b,
+        cpu.c,
+        cpu.d,
+        cpu.e,
+        cpu.h,
+        cpu.l,
+        cpu.stack_pointer,
+        cpu.ir_address,
+        cpu.interrupts_enabled() as u8,
+        (cpu.halt.state != HaltState::Running) as u8,
+    )
+}
+
+fn hex(word: &str) -> Result<u16, String> {
+    let digits = word
+        .strip_prefix('$')
+        .or_else(|| word.strip_prefix("0x"))
+        .unwrap_or(word);
+    u16::from_str_radix(digits, 16).map_err(|_| format!("{word} isn't a hex address or byte"))
+}
+
+fn decimal(word: &str) -> Result<u32, String> {
+    word.parse().map_err(|_| format!("{word} isn't a count"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Counts up in $C000 forever; the loop's increment is at $0153.
+    fn rom() -> Vec<u8> {
+        let mut rom = vec![0u8; 0x8000];
+        rom[0x0100..0x0104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]); // NOP; JP $0150
+        rom[0x0150..0x015C].copy_from_slice(&[
+            0x21, 0x00, 0xC0, // LD HL,$C000
+            0x34, // INC (HL)
+            0x3E, 0x42, // LD A,$42
+            0x18, 0xFB, // JR -5, to INC (HL)
+            0x00, 0x00, 0x00, 0x00,
+        ]);
+        rom
+    }
+
+    /// Feed `script` to a session with the test ROM saved at `rom.gb`.
+    fn transcript(script: &str) -> String {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "missingno-script-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = script.replace("$DIR", dir.to_str().unwrap());
+        std::fs::write(dir.join("rom.gb"), rom()).unwrap();
+
+        let mut output = Vec::new();
+        Session::new(None)
+            .serve(script.as_bytes(), &mut output)
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn breakpoint_stops_run_and_registers_show_where() {
+        let output = transcript(
+            "# stop the first time round the loop\n\
+             load $DIR/rom.gb\n\
+             break 0153\n\
+             run\n\
+             reg\n",
+        );
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[..4], ["ok", "ok", "break 0153", "ok"]);
+        assert!(lines[4].starts_with("AF="), "{}", lines[4]);
+        assert!(lines[4].contains(" HL=C000 "), "{}", lines[4]);
+        assert!(lines[4].contains(" PC=0153 "), "{}", lines[4]);
+        assert_eq!(lines[5], "ok");
+    }
+
+    #[test]
+    fn conditional_breakpoint_waits_for_its_condition() {
+        let output = transcript(
+            "load $DIR/rom.gb\n\
+             break 0153 [HL] == 5\n\
+             run\n\
+             read c000\n\
+             break 0153 HL\n",
+        );
+        assert_eq!(
+            output,
+            "ok\n\
+             ok\n\
+             break 0153\n\
+             ok\n\
+             C000 05\n\
+             ok\n\
+             error expected a comparison such as ==\n"
+        );
+    }
+
+    #[test]
+    fn run_without_a_breakpoint_stops_after_its_frames() {
+        let output = transcript("load $DIR/rom.gb\nrun 2\nread c000\n");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1..3], ["frames 2", "ok"]);
+        assert!(lines[3].starts_with("C000 "));
+    }
+
+    #[test]
+    fn reads_and_writes_memory() {
+        let output = transcript("load $DIR/rom.gb\nwrite $C100 12 ab 0x3\nread c0ff 4\n");
+        assert_eq!(output, "ok\nok\nC0FF 00 12 AB 03\nok\n");
+    }
+
+    #[test]
+    fn reads_wrap_onto_a_new_line_every_16_bytes() {
+        let output = transcript("load $DIR/rom.gb\nread 0150 20\n");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[1],
+            "0150 21 00 C0 34 3E 42 18 FB 00 00 00 00 00 00 00 00"
+        );
+        assert_eq!(lines[2], "0160 00 00 00 00");
+    }
+
+    #[test]
+    fn loadstate_goes_back_to_the_saved_state() {
+        let output = transcript(
+            "load $DIR/rom.gb\n\
+             step 3\n\
+             savestate $DIR/before.state\n\
+             step 30\n\
+             loadstate $DIR/before.state\n\
+             reg\n\
+             loadstate $DIR/missing.state\n",
+        );
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[5].contains(" PC=0153 "), "{}", lines[5]);
+        assert!(lines[7].starts_with("error can't load "), "{}", lines[7]);
+    }
+
+    #[test]
+    fn reads_no_more_than_the_whole_address_space() {
+        let output = transcript("load $DIR/rom.gb\nread 0000 65536\nread 0000 65537\n");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1 + 4096 + 1 + 1);
+        assert!(lines[4096].starts_with("FFF0 "), "{}", lines[4096]);
+        assert_eq!(lines[4096].split(' ').count(), 17);
+        assert_eq!(lines[4098], "error can't read 65537 bytes, only 65536");
+    }
+
+    #[test]
+    fn rewind_goes_back_to_the_last_second() {
+        let output = transcript(
+            "load $DIR/rom.gb\n\
+             rewind\n\
+             run 60\n\
+             reg\n\
+             step 30\n\
+             rewind\n\
+             reg\n",
+        );
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[..3],
+            ["ok", "error nothing to rewind to", "frames 60"]
+        );
+        assert_eq!(lines[3], lines[7]);
+    }
+
+    #[test]
+    fn screenshot_writes_a_png() {
+        let output = transcript("load $DIR/rom.gb\nrun 1\nscreenshot $DIR/screen.png\n");
+        assert!(output.ends_with("ok\n"), "{output}");
+    }
+
+    #[test]
+    fn an_unsupported_cartridge_is_reported_rather_than_loaded() {
+        let mut bad = rom();
+        bad[0x0147] = 0x04; // no cartridge has this type
+        let path =
+            std::env::temp_dir().join(format!("missingno-script-{}-mbc.gb", std::process::id()));
+        std::fs::write(&path, bad).unwrap();
+
+        let mut output = Vec::new();
+        Session::new(None)
+            .serve(
+                format!("load {}\nstep\n", path.display()).as_bytes(),
+                &mut output,
+            )
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "error can't load {}: unsupported cartridge type $04\n\
+                 error no ROM loaded\n",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn mistakes_are_reported_and_the_session_carries_on() {
+        let output = transcript(
+            "reg\n\
+             load $DIR/rom.gb\n\
+             write 0000 01\n\
+             frobnicate\n\
+             step many\n\
+             break\n\
+             loadstate\n\
+             step\n\
+             quit\n\
+             step\n",
+        );
+        assert_eq!(
+            output,
+            "error no ROM loaded\n\
+             ok\n\
+             error can't write 0000: only VRAM, OAM, WRAM and HRAM\n\
+             error unknown command frobnicate\n\
+             error many isn't a count\n\
+             error wrong arguments for break\n\
+             error wrong arguments for loadstate\n\
+             ok\n\
+             ok\n"
+        );
+    }
+}
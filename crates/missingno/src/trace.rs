@@ -28,7 +28,10 @@ pub fn run(
 
     let save_path = rom_path.with_extension("sav");
     let save_data = std::fs::read(&save_path).ok();
-    let cartridge = Cartridge::new(rom_data, save_data);
+    let cartridge = Cartridge::new(rom_data, save_data).unwrap_or_else(|e| {
+        eprintln!("error: can't load {}: {e}", rom_path.display());
+        process::exit(1);
+    });
 
     let output_path = output.unwrap_or_else(|| {
         let stem = rom_path.file_stem().unwrap().to_string_lossy();
@@ -39,7 +42,7 @@ pub fn run(
     eprintln!("output: {}", output_path.display());
     eprintln!("limit: {cycles} T-cycles");
 
-    if cartridge.is_cgb() {
+    if cartridge.supports_cgb() {
         trace_console(
             GameBoyColor::new(cartridge, boot_rom),
             &profile,